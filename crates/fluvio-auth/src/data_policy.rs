@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs::read;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use fluvio_protocol::derive::{Encoder, Decoder};
+
+use crate::x509::X509Identity;
+use super::{name_matches, DataAction};
+
+type Role = String;
+
+/// Topic names matched by permission.
+/// `*` matches any topic, a trailing `*` matches topics with the given prefix.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, Encoder, Decoder)]
+pub struct TopicPermission {
+    pub topic: String,
+    pub actions: Vec<DataAction>,
+}
+
+impl TopicPermission {
+    pub fn new(topic: impl Into<String>, actions: Vec<DataAction>) -> Self {
+        Self {
+            topic: topic.into(),
+            actions,
+        }
+    }
+
+    fn matches(&self, topic: &str) -> bool {
+        name_matches(&self.topic, topic)
+    }
+}
+
+/// Data plane policy evaluated by SPU for produce and consume.
+/// Distributed by SC to every SPU.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize, Encoder, Decoder)]
+pub struct DataPolicy(pub BTreeMap<Role, Vec<TopicPermission>>);
+
+impl From<BTreeMap<Role, Vec<TopicPermission>>> for DataPolicy {
+    fn from(map: BTreeMap<Role, Vec<TopicPermission>>) -> Self {
+        Self(map)
+    }
+}
+
+impl TryFrom<PathBuf> for DataPolicy {
+    type Error = std::io::Error;
+    fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+        debug!("reading data policy: {:#?}", path);
+        let file = read(path)?;
+        let policy: DataPolicy = serde_json::from_slice(&file)?;
+        Ok(policy)
+    }
+}
+
+impl DataPolicy {
+    /// only allows the `Root` role to produce and consume any topic
+    pub fn root() -> Self {
+        let mut policy = BTreeMap::new();
+        policy.insert(
+            String::from("Root"),
+            vec![TopicPermission::new(
+                "*",
+                vec![DataAction::Produce, DataAction::Consume],
+            )],
+        );
+        Self(policy)
+    }
+
+    /// check if any role of identity allows action on topic
+    pub fn evaluate(&self, action: DataAction, topic: &str, identity: &X509Identity) -> bool {
+        identity.roles().any(|role| {
+            self.0
+                .get(role)
+                .map(|permissions| {
+                    permissions.iter().any(|permission| {
+                        permission.matches(topic) && permission.actions.contains(&action)
+                    })
+                })
+                .unwrap_or(false)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::x509::X509Identity;
+    use crate::DataAction;
+
+    use super::{DataPolicy, TopicPermission};
+
+    #[test]
+    fn test_data_policy_evaluate() {
+        let mut policy = DataPolicy::root();
+        policy.0.insert(
+            "team-a".to_owned(),
+            vec![
+                TopicPermission::new("team-a.*", vec![DataAction::Produce, DataAction::Consume]),
+                TopicPermission::new("shared", vec![DataAction::Consume]),
+            ],
+        );
+
+        let root = X509Identity::new("admin".to_owned(), vec!["Root".to_owned()]);
+        let team_a = X509Identity::new("svc".to_owned(), vec!["team-a".to_owned()]);
+        let other = X509Identity::new("other".to_owned(), vec!["team-b".to_owned()]);
+        let member =
+            X509Identity::new("member".to_owned(), vec![]).with_groups(vec!["team-a".to_owned()]);
+
+        assert!(policy.evaluate(DataAction::Produce, "anything", &root));
+        assert!(policy.evaluate(DataAction::Produce, "team-a.events", &team_a));
+        assert!(policy.evaluate(DataAction::Consume, "shared", &team_a));
+        assert!(!policy.evaluate(DataAction::Produce, "shared", &team_a));
+        assert!(!policy.evaluate(DataAction::Consume, "team-b.events", &team_a));
+        assert!(!policy.evaluate(DataAction::Consume, "shared", &other));
+        assert!(policy.evaluate(DataAction::Consume, "team-a.events", &member));
+    }
+
+    #[test]
+    fn test_data_policy_serialization() {
+        let json = r#"{"team-a":[{"topic":"team-a.*","actions":["Produce","Consume"]}]}"#;
+        let policy: DataPolicy = serde_json::from_str(json).expect("parse");
+        assert_eq!(
+            policy.0.get("team-a"),
+            Some(&vec![TopicPermission::new(
+                "team-a.*",
+                vec![DataAction::Produce, DataAction::Consume]
+            )])
+        );
+    }
+}
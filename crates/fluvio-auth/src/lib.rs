@@ -1,7 +1,10 @@
 mod policy;
 mod error;
+mod data_policy;
 
 pub mod x509;
+pub mod sasl;
 
 pub use policy::*;
+pub use data_policy::{DataPolicy, TopicPermission};
 pub use error::AuthError;
@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_protocol::derive::{Encoder, Decoder};
 use fluvio_socket::FluvioSocket;
 
 use super::AuthError;
@@ -12,12 +13,38 @@ use super::AuthError;
 pub enum TypeAction {
     Create,
     Read,
+    Update,
 }
 
+#[derive(Debug, Clone, PartialEq, Hash, Eq, Deserialize, Serialize)]
 pub enum InstanceAction {
+    Create,
+    Read,
+    Update,
     Delete,
 }
 
+/// actions on records of topic, performed against SPU
+#[derive(
+    Debug, Clone, Copy, PartialEq, Hash, Eq, Deserialize, Serialize, Encoder, Decoder, Default,
+)]
+#[fluvio(encode_discriminant)]
+#[repr(u8)]
+pub enum DataAction {
+    #[default]
+    Produce = 0,
+    Consume = 1,
+}
+
+/// check if name matches pattern of permission.
+/// `*` matches any name, a trailing `*` matches names with the given prefix.
+pub fn name_matches(pattern: &str, name: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => name.starts_with(prefix),
+        None => pattern == name,
+    }
+}
+
 #[async_trait]
 pub trait AuthContext: Debug {
     /// check if any allow type specific action can be allowed
@@ -34,6 +61,9 @@ pub trait AuthContext: Debug {
         action: InstanceAction,
         key: &str,
     ) -> Result<bool, AuthError>;
+
+    /// check if records of topic can be produced or consumed
+    async fn allow_data_action(&self, action: DataAction, topic: &str) -> Result<bool, AuthError>;
 }
 
 #[async_trait]
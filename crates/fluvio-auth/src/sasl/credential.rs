@@ -0,0 +1,220 @@
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fs::read;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::debug;
+
+use fluvio_socket::scram::{ScramCredential, ScramServer, DEFAULT_ITERATIONS, SCRAM_SHA_256};
+
+use crate::x509::X509Identity;
+use super::{SaslMechanism, SaslSession, SaslStep};
+
+/// Salted credential of user with roles granted after authentication
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct UserCredential {
+    pub salt: String,
+    pub iterations: u32,
+    pub stored_key: String,
+    pub server_key: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+}
+
+impl UserCredential {
+    /// derive credential from password, password itself is not stored
+    pub fn new(password: &str, scopes: Vec<String>) -> Self {
+        let credential = ScramCredential::new(password, DEFAULT_ITERATIONS);
+        Self {
+            salt: STANDARD.encode(credential.salt),
+            iterations: credential.iterations,
+            stored_key: STANDARD.encode(credential.stored_key),
+            server_key: STANDARD.encode(credential.server_key),
+            scopes,
+        }
+    }
+
+    fn scram(&self) -> Result<ScramCredential, IoError> {
+        let decode = |value: &str| {
+            STANDARD
+                .decode(value)
+                .map_err(|err| IoError::new(ErrorKind::InvalidData, err))
+        };
+        Ok(ScramCredential {
+            salt: decode(&self.salt)?,
+            iterations: self.iterations,
+            stored_key: decode(&self.stored_key)?,
+            server_key: decode(&self.server_key)?,
+        })
+    }
+}
+
+/// Users allowed to authenticate with password
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CredentialStore(pub BTreeMap<String, UserCredential>);
+
+impl TryFrom<PathBuf> for CredentialStore {
+    type Error = IoError;
+    fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+        debug!("reading credentials: {:#?}", path);
+        let file = read(path)?;
+        let store: CredentialStore = serde_json::from_slice(&file)?;
+        Ok(store)
+    }
+}
+
+/// SCRAM-SHA-256 mechanism backed by credential store
+#[derive(Debug, Clone)]
+pub struct ScramMechanism {
+    store: Arc<CredentialStore>,
+    /// key for credentials of unknown users, derived from stored server keys so it can't be guessed
+    mock_key: Arc<Vec<u8>>,
+}
+
+impl ScramMechanism {
+    pub fn new(store: CredentialStore) -> Self {
+        let mut hasher = Sha256::new();
+        for user in store.0.values() {
+            hasher.update(user.server_key.as_bytes());
+        }
+        Self {
+            store: Arc::new(store),
+            mock_key: Arc::new(hasher.finalize().to_vec()),
+        }
+    }
+
+    /// Unknown user gets challenge with same salt and iterations every time,
+    /// so client can't tell it apart from existing user. Proof never matches it.
+    fn mock_credential(&self, username: &str) -> ScramCredential {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.mock_key).expect("hmac accepts any key length");
+        mac.update(username.as_bytes());
+        let key = mac.finalize().into_bytes().to_vec();
+        ScramCredential {
+            salt: key[..16].to_vec(),
+            iterations: DEFAULT_ITERATIONS,
+            stored_key: Sha256::digest(&key).to_vec(),
+            server_key: key,
+        }
+    }
+}
+
+impl SaslMechanism for ScramMechanism {
+    fn name(&self) -> &'static str {
+        SCRAM_SHA_256
+    }
+
+    fn session(&self) -> Box<dyn SaslSession> {
+        Box::new(ScramSession {
+            mechanism: self.clone(),
+            user: None,
+            server: ScramServer::default(),
+        })
+    }
+}
+
+struct ScramSession {
+    mechanism: ScramMechanism,
+    /// requested user, with stored credential if user exists
+    user: Option<(String, Option<UserCredential>, ScramCredential)>,
+    server: ScramServer,
+}
+
+impl SaslSession for ScramSession {
+    fn step(&mut self, input: &[u8]) -> Result<SaslStep, IoError> {
+        let message =
+            std::str::from_utf8(input).map_err(|err| IoError::new(ErrorKind::InvalidData, err))?;
+
+        match self.user.take() {
+            None => {
+                let username = ScramServer::username(message)?;
+                // unknown user is challenged as well and fails same way as wrong password
+                let user = self.mechanism.store.0.get(&username).cloned();
+                let credential = match &user {
+                    Some(user) => user.scram()?,
+                    None => self.mechanism.mock_credential(&username),
+                };
+                let server_first = self.server.server_first(message, &credential)?;
+                self.user = Some((username, user, credential));
+                Ok(SaslStep::Continue(server_first.into_bytes()))
+            }
+            Some((username, user, credential)) => {
+                let server_final = self.server.server_final(message, &credential)?;
+                let user = user.ok_or_else(|| {
+                    IoError::new(ErrorKind::PermissionDenied, "scram: invalid credentials")
+                })?;
+                let identity = X509Identity::new(username, user.scopes);
+                Ok(SaslStep::Complete(server_final.into_bytes(), identity))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_socket::scram::ScramClient;
+
+    use crate::sasl::{SaslMechanism, SaslStep};
+    use super::{CredentialStore, ScramMechanism, UserCredential};
+
+    #[test]
+    fn test_scram_mechanism() {
+        let mut store = CredentialStore::default();
+        store.0.insert(
+            "alice".to_owned(),
+            UserCredential::new("secret", vec!["team-a".to_owned()]),
+        );
+        let mechanism = ScramMechanism::new(store);
+
+        let mut client = ScramClient::new("alice", "secret");
+        let mut session = mechanism.session();
+        let server_first = match session.step(client.client_first().as_bytes()) {
+            Ok(SaslStep::Continue(bytes)) => String::from_utf8(bytes).expect("utf8"),
+            _ => panic!("expected challenge"),
+        };
+        let client_final = client.client_final(&server_first).expect("final");
+        match session.step(client_final.as_bytes()) {
+            Ok(SaslStep::Complete(server_final, identity)) => {
+                client
+                    .verify_server_final(std::str::from_utf8(&server_final).expect("utf8"))
+                    .expect("server verified");
+                assert_eq!(identity.principal, "alice");
+                assert_eq!(identity.scopes(), &vec!["team-a".to_owned()]);
+            }
+            _ => panic!("expected completion"),
+        }
+
+        // unknown user is challenged with same salt every time and fails at proof
+        let mut unknown = ScramClient::new("bob", "secret");
+        let mut session = mechanism.session();
+        let server_first = match session.step(unknown.client_first().as_bytes()) {
+            Ok(SaslStep::Continue(bytes)) => String::from_utf8(bytes).expect("utf8"),
+            _ => panic!("expected challenge"),
+        };
+        let salt = |server_first: &str| {
+            server_first
+                .split(',')
+                .find(|attr| attr.starts_with("s="))
+                .map(|attr| attr.to_owned())
+        };
+        let retry = match mechanism
+            .session()
+            .step(ScramClient::new("bob", "other").client_first().as_bytes())
+        {
+            Ok(SaslStep::Continue(bytes)) => String::from_utf8(bytes).expect("utf8"),
+            _ => panic!("expected challenge"),
+        };
+        assert!(salt(&server_first).is_some());
+        assert_eq!(salt(&server_first), salt(&retry));
+        let client_final = unknown.client_final(&server_first).expect("final");
+        assert!(session.step(client_final.as_bytes()).is_err());
+    }
+}
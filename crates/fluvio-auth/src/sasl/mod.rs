@@ -0,0 +1,249 @@
+//!
+//! # SASL Authentication
+//!
+//! Server side of handshake from `fluvio_socket::sasl`.
+//! Mechanisms are pluggable, password (SCRAM-SHA-256) and signed token are provided.
+//!
+
+mod credential;
+mod token;
+
+pub use credential::*;
+pub use token::*;
+
+use std::convert::TryFrom;
+use std::fmt::Debug;
+use std::io::{Error as IoError, ErrorKind};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use tracing::{debug, instrument};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::Encoder;
+use fluvio_socket::FluvioSocket;
+use fluvio_socket::sasl::{SaslAuthenticateRequest, SaslAuthenticateResponse, SaslHandshakeResponse};
+
+use crate::x509::X509Identity;
+use crate::x509::request::{AuthorizationApiRequest, AuthResponse};
+
+/// result of processing client message
+pub enum SaslStep {
+    /// more client messages are expected
+    Continue(Vec<u8>),
+    /// client is authenticated
+    Complete(Vec<u8>, X509Identity),
+}
+
+/// state of single authentication exchange
+pub trait SaslSession: Send {
+    fn step(&mut self, input: &[u8]) -> Result<SaslStep, IoError>;
+}
+
+/// authentication mechanism verified by server
+pub trait SaslMechanism: Debug + Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn session(&self) -> Box<dyn SaslSession>;
+}
+
+/// Creates identity of connection from SASL handshake.
+/// Identity sent by mTLS proxy is accepted too if proxy authenticates client certificates,
+/// otherwise identity claimed by connection is rejected.
+#[derive(Debug, Clone, Default)]
+pub struct SaslAuthenticator {
+    mechanisms: Vec<Arc<dyn SaslMechanism>>,
+    x509_proxy: bool,
+}
+
+impl SaslAuthenticator {
+    pub fn with_mechanism(mut self, mechanism: impl SaslMechanism + 'static) -> Self {
+        self.mechanisms.push(Arc::new(mechanism));
+        self
+    }
+
+    /// trust identity sent by mTLS proxy, set when proxy is configured with x509 auth scopes
+    pub fn with_x509_proxy(mut self, trusted: bool) -> Self {
+        self.x509_proxy = trusted;
+        self
+    }
+
+    /// authenticator from credential file and token secret file, none if neither is given
+    pub fn load(
+        credentials: Option<PathBuf>,
+        token_secret: Option<PathBuf>,
+    ) -> Result<Option<Self>, IoError> {
+        let mut authenticator = Self::default();
+        if let Some(path) = credentials {
+            authenticator =
+                authenticator.with_mechanism(ScramMechanism::new(CredentialStore::try_from(path)?));
+        }
+        if let Some(path) = token_secret {
+            let secret = std::fs::read_to_string(path)?;
+            authenticator = authenticator.with_mechanism(TokenMechanism::new(TokenSigner::new(
+                secret.trim().as_bytes(),
+            )));
+        }
+        Ok(if authenticator.mechanisms.is_empty() {
+            None
+        } else {
+            Some(authenticator)
+        })
+    }
+
+    pub fn mechanisms(&self) -> Vec<String> {
+        self.mechanisms
+            .iter()
+            .map(|mechanism| mechanism.name().to_owned())
+            .collect()
+    }
+
+    /// authenticate connection, identity is created only after client completes SASL exchange
+    #[instrument(skip(self, socket))]
+    pub async fn create_identity(
+        &self,
+        socket: &mut FluvioSocket,
+    ) -> Result<X509Identity, IoError> {
+        match next_request(socket).await? {
+            // client certificate was verified by proxy
+            AuthorizationApiRequest::AuthRequest(req_msg) if self.x509_proxy => {
+                let response = req_msg.new_response(AuthResponse { success: true });
+                send(socket, response, req_msg.header.api_version()).await?;
+                let request = req_msg.request;
+                debug!(principal = %request.principal, "x509 authenticated");
+                Ok(X509Identity {
+                    principal: request.principal,
+                    scopes: request.scopes,
+                    groups: request.groups,
+                })
+            }
+            // identity claimed by client itself can't be trusted
+            AuthorizationApiRequest::AuthRequest(req_msg) => {
+                let response = req_msg.new_response(AuthResponse { success: false });
+                send(socket, response, req_msg.header.api_version()).await?;
+                Err(IoError::new(
+                    ErrorKind::PermissionDenied,
+                    "sasl authentication is required",
+                ))
+            }
+            AuthorizationApiRequest::SaslHandshakeRequest(req_msg) => {
+                let mechanism = self
+                    .mechanisms
+                    .iter()
+                    .find(|mechanism| mechanism.name() == req_msg.request.mechanism);
+                let response = req_msg.new_response(SaslHandshakeResponse {
+                    success: mechanism.is_some(),
+                    mechanisms: self.mechanisms(),
+                });
+                send(socket, response, req_msg.header.api_version()).await?;
+                match mechanism {
+                    Some(mechanism) => authenticate(socket, mechanism.session()).await,
+                    None => Err(IoError::new(
+                        ErrorKind::PermissionDenied,
+                        format!("mechanism {} is not enabled", req_msg.request.mechanism),
+                    )),
+                }
+            }
+            AuthorizationApiRequest::SaslAuthenticateRequest(_) => Err(IoError::new(
+                ErrorKind::InvalidInput,
+                "sasl handshake is expected first",
+            )),
+        }
+    }
+}
+
+/// identity of connection, taken from mTLS proxy or negotiated by SASL if it is enabled
+pub async fn identity_from_connection(
+    socket: &mut FluvioSocket,
+    sasl: Option<&SaslAuthenticator>,
+) -> Result<X509Identity, IoError> {
+    match sasl {
+        Some(sasl) => sasl.create_identity(socket).await,
+        None => X509Identity::create_from_connection(socket).await,
+    }
+}
+
+async fn next_request(socket: &mut FluvioSocket) -> Result<AuthorizationApiRequest, IoError> {
+    match socket
+        .get_mut_stream()
+        .next_api_item::<AuthorizationApiRequest, _>()
+        .await
+    {
+        Some(Ok(request)) => Ok(request),
+        Some(Err(_)) | None => {
+            tracing::trace!("client connect terminated");
+            Err(IoError::new(ErrorKind::Interrupted, "connection closed"))
+        }
+    }
+}
+
+async fn send<R>(
+    socket: &mut FluvioSocket,
+    response: ResponseMessage<R>,
+    version: i16,
+) -> Result<(), IoError>
+where
+    ResponseMessage<R>: Encoder + Debug,
+{
+    socket
+        .get_mut_sink()
+        .send_response(&response, version)
+        .await
+        .map_err(|_| {
+            IoError::new(
+                ErrorKind::Interrupted,
+                "connection interrupted during response",
+            )
+        })
+}
+
+/// exchange authenticate messages until session completes or fails
+async fn authenticate(
+    socket: &mut FluvioSocket,
+    mut session: Box<dyn SaslSession>,
+) -> Result<X509Identity, IoError> {
+    loop {
+        let req_msg: RequestMessage<SaslAuthenticateRequest> = match next_request(socket).await? {
+            AuthorizationApiRequest::SaslAuthenticateRequest(req_msg) => req_msg,
+            _ => {
+                return Err(IoError::new(
+                    ErrorKind::InvalidInput,
+                    "sasl authenticate is expected",
+                ))
+            }
+        };
+        let version = req_msg.header.api_version();
+
+        match session.step(&req_msg.request.auth_bytes) {
+            Ok(SaslStep::Continue(auth_bytes)) => {
+                let response = req_msg.new_response(SaslAuthenticateResponse {
+                    success: true,
+                    auth_bytes,
+                    ..Default::default()
+                });
+                send(socket, response, version).await?;
+            }
+            Ok(SaslStep::Complete(auth_bytes, identity)) => {
+                debug!(principal = %identity.principal, "sasl authenticated");
+                let response = req_msg.new_response(SaslAuthenticateResponse {
+                    success: true,
+                    auth_bytes,
+                    complete: true,
+                    ..Default::default()
+                });
+                send(socket, response, version).await?;
+                return Ok(identity);
+            }
+            Err(err) => {
+                debug!(%err, "sasl authentication failed");
+                let response = req_msg.new_response(SaslAuthenticateResponse {
+                    error_message: Some("authentication failed".to_owned()),
+                    complete: true,
+                    ..Default::default()
+                });
+                send(socket, response, version).await?;
+                return Err(IoError::new(ErrorKind::PermissionDenied, err.to_string()));
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use std::io::{Error as IoError, ErrorKind};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+
+use fluvio_socket::sasl::TOKEN;
+
+use crate::x509::X509Identity;
+use super::{SaslMechanism, SaslSession, SaslStep};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Identity carried by token
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct TokenClaims {
+    pub principal: String,
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// seconds since unix epoch
+    pub expires_at: u64,
+}
+
+impl TokenClaims {
+    fn is_expired(&self) -> bool {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs())
+            .unwrap_or_default();
+        self.expires_at <= now
+    }
+}
+
+/// Signs and verifies tokens with shared secret.
+/// Token is `<claims>.<signature>`, both url safe base64.
+/// Rotating secret invalidates all tokens signed with previous one.
+#[derive(Clone)]
+pub struct TokenSigner {
+    secret: Vec<u8>,
+}
+
+impl std::fmt::Debug for TokenSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "TokenSigner")
+    }
+}
+
+impl TokenSigner {
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+
+    fn mac(&self) -> HmacSha256 {
+        HmacSha256::new_from_slice(&self.secret).expect("hmac accepts any key length")
+    }
+
+    pub fn sign(&self, claims: &TokenClaims) -> Result<String, IoError> {
+        let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(claims)?);
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+        Ok(format!("{payload}.{signature}"))
+    }
+
+    pub fn verify(&self, token: &str) -> Result<TokenClaims, IoError> {
+        let invalid = || IoError::new(ErrorKind::PermissionDenied, "invalid token");
+        let (payload, signature) = token.split_once('.').ok_or_else(invalid)?;
+        let signature = URL_SAFE_NO_PAD.decode(signature).map_err(|_| invalid())?;
+
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature).map_err(|_| invalid())?;
+
+        let claims: TokenClaims =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).map_err(|_| invalid())?)
+                .map_err(|_| invalid())?;
+        if claims.is_expired() {
+            return Err(IoError::new(ErrorKind::PermissionDenied, "token expired"));
+        }
+        Ok(claims)
+    }
+}
+
+/// Mechanism accepting tokens signed by cluster secret
+#[derive(Debug, Clone)]
+pub struct TokenMechanism {
+    signer: TokenSigner,
+}
+
+impl TokenMechanism {
+    pub fn new(signer: TokenSigner) -> Self {
+        Self { signer }
+    }
+}
+
+impl SaslMechanism for TokenMechanism {
+    fn name(&self) -> &'static str {
+        TOKEN
+    }
+
+    fn session(&self) -> Box<dyn SaslSession> {
+        Box::new(self.clone())
+    }
+}
+
+impl SaslSession for TokenMechanism {
+    fn step(&mut self, input: &[u8]) -> Result<SaslStep, IoError> {
+        let token =
+            std::str::from_utf8(input).map_err(|err| IoError::new(ErrorKind::InvalidData, err))?;
+        let claims = self.signer.verify(token)?;
+        Ok(SaslStep::Complete(
+            vec![],
+            X509Identity::new(claims.principal, claims.scopes),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{TokenClaims, TokenSigner};
+
+    #[test]
+    fn test_token_sign_verify() {
+        let signer = TokenSigner::new("secret");
+        let claims = TokenClaims {
+            principal: "job-1".to_owned(),
+            scopes: vec!["team-a".to_owned()],
+            expires_at: u64::MAX,
+        };
+        let token = signer.sign(&claims).expect("sign");
+        assert_eq!(signer.verify(&token).expect("verify"), claims);
+
+        // rotated secret
+        assert!(TokenSigner::new("other").verify(&token).is_err());
+
+        // tampered claims
+        let (_, signature) = token.split_once('.').expect("token");
+        let forged = TokenSigner::new("other")
+            .sign(&TokenClaims {
+                principal: "admin".to_owned(),
+                ..claims.clone()
+            })
+            .expect("sign");
+        let (payload, _) = forged.split_once('.').expect("token");
+        assert!(signer.verify(&format!("{payload}.{signature}")).is_err());
+
+        let expired = signer
+            .sign(&TokenClaims {
+                expires_at: 0,
+                ..claims
+            })
+            .expect("sign");
+        assert!(signer.verify(&expired).is_err());
+    }
+}
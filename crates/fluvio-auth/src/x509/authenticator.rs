@@ -76,7 +76,10 @@ impl X509Authenticator {
         Ok(response.success)
     }
 
-    fn principal_from_tls_stream(tls_stream: &DefaultServerTlsStream) -> Result<String, IoError> {
+    /// principal and groups of client certificate
+    fn principal_from_tls_stream(
+        tls_stream: &DefaultServerTlsStream,
+    ) -> Result<(String, Vec<String>), IoError> {
         trace!("tls_stream {:?}", tls_stream);
 
         let peer_certificate = tls_stream.peer_certificate();
@@ -87,13 +90,13 @@ impl X509Authenticator {
 
         trace!("client_certificate {:?}", tls_stream);
 
-        let principal = Self::principal_from_raw_certificate(
-            &client_certificate
-                .to_der()
-                .map_err(|err| err.into_io_error())?,
-        )?;
+        let der = client_certificate
+            .to_der()
+            .map_err(|err| err.into_io_error())?;
+        let principal = Self::principal_from_raw_certificate(&der)?;
+        let groups = Self::groups_from_raw_certificate(&der)?;
 
-        Ok(principal)
+        Ok((principal, groups))
     }
 
     fn principal_from_raw_certificate(certificate_bytes: &[u8]) -> Result<String, IoError> {
@@ -102,6 +105,19 @@ impl X509Authenticator {
             .and_then(|(_, parsed_cert)| Self::common_name_from_parsed_certificate(parsed_cert))
     }
 
+    /// groups are organizational units of certificate subject
+    fn groups_from_raw_certificate(certificate_bytes: &[u8]) -> Result<Vec<String>, IoError> {
+        let (_, certificate) = parse_x509_certificate(certificate_bytes)
+            .map_err(|err| IoError::new(IoErrorKind::InvalidData, err))?;
+        let groups = certificate
+            .subject()
+            .iter_organizational_unit()
+            .filter_map(|ou| ou.as_str().ok().map(|ou| ou.to_owned()))
+            .collect::<Vec<_>>();
+        debug!(?groups, "groups from cert");
+        Ok(groups)
+    }
+
     fn common_name_from_parsed_certificate(
         certificate: X509Certificate,
     ) -> Result<String, IoError> {
@@ -130,9 +146,9 @@ impl Authenticator for X509Authenticator {
         incoming_tls_stream: &DefaultServerTlsStream,
         target_tcp_stream: &TcpStream,
     ) -> Result<bool, IoError> {
-        let principal = Self::principal_from_tls_stream(incoming_tls_stream)?;
+        let (principal, groups) = Self::principal_from_tls_stream(incoming_tls_stream)?;
         let scopes = self.scope_bindings.get_scopes(&principal);
-        let authorization_request = AuthRequest::new(principal, scopes);
+        let authorization_request = AuthRequest::new(principal, scopes).with_groups(groups);
         let success =
             Self::send_authorization_request(target_tcp_stream, authorization_request).await?;
         Ok(success)
@@ -150,6 +166,13 @@ mod tests {
         assert_eq!(common_name, "root".to_owned());
     }
 
+    #[test]
+    fn test_groups_from_raw_certificate() {
+        let (_, pem) = x509_parser::prelude::parse_x509_pem(TEST_CERTIFICATE.as_bytes()).unwrap();
+        let groups = X509Authenticator::groups_from_raw_certificate(&pem.contents).unwrap();
+        assert!(groups.is_empty());
+    }
+
     const TEST_CERTIFICATE: &str = r#"-----BEGIN CERTIFICATE-----
 MIIG1jCCBL6gAwIBAgIUJA7m5OdyaHO9TosR3zZDH7kuP7AwDQYJKoZIhvcNAQEL
 BQAwgZMxCzAJBgNVBAYTAlVTMQswCQYDVQQIDAJDQTEUMBIGA1UEBwwLU2FudGEg
@@ -11,17 +11,37 @@ use super::request::{AuthorizationScopes, AuthorizationApiRequest, AuthResponse}
 pub struct X509Identity {
     pub principal: String,
     pub scopes: AuthorizationScopes,
+    #[serde(default)]
+    pub groups: Vec<String>,
 }
 
 impl X509Identity {
     pub fn new(principal: String, scopes: AuthorizationScopes) -> Self {
-        Self { principal, scopes }
+        Self {
+            principal,
+            scopes,
+            groups: vec![],
+        }
+    }
+
+    pub fn with_groups(mut self, groups: Vec<String>) -> Self {
+        self.groups = groups;
+        self
     }
 
     pub fn scopes(&self) -> &AuthorizationScopes {
         &self.scopes
     }
 
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// roles granted to identity, which are scopes and groups
+    pub fn roles(&self) -> impl Iterator<Item = &String> {
+        self.scopes.iter().chain(self.groups.iter())
+    }
+
     /// extract x509 identity from TCP Socket
     pub async fn create_from_connection(socket: &mut FluvioSocket) -> Result<Self, std::io::Error> {
         let identity = {
@@ -35,7 +55,14 @@ impl X509Identity {
                         AuthorizationApiRequest::AuthRequest(req_msg) => Self {
                             scopes: req_msg.request.scopes,
                             principal: req_msg.request.principal,
+                            groups: req_msg.request.groups,
                         },
+                        _ => {
+                            return Err(std::io::Error::new(
+                                std::io::ErrorKind::InvalidInput,
+                                "sasl authentication is not enabled",
+                            ))
+                        }
                     },
                     Err(_e) => {
                         return Err(std::io::Error::new(
@@ -5,6 +5,10 @@ use std::fmt::Debug;
 use fluvio_protocol::bytes::Buf;
 use fluvio_protocol::api::{api_decode, ApiMessage, Request, RequestHeader, RequestMessage};
 use fluvio_protocol::derive::{Encoder, Decoder};
+use fluvio_socket::sasl::{
+    SaslAuthenticateRequest, SaslHandshakeRequest, SASL_AUTHENTICATE_API_KEY,
+    SASL_HANDSHAKE_API_KEY,
+};
 
 pub type AuthorizationScopes = Vec<String>;
 
@@ -15,16 +19,29 @@ pub const AUTH_REQUEST_API_KEY: u16 = 8;
 pub struct AuthRequest {
     pub principal: String,
     pub scopes: AuthorizationScopes,
+    /// groups of principal, taken from certificate subject
+    #[fluvio(min_version = 1)]
+    pub groups: Vec<String>,
 }
 
 impl AuthRequest {
     pub fn new(principal: String, scopes: AuthorizationScopes) -> Self {
-        AuthRequest { principal, scopes }
+        AuthRequest {
+            principal,
+            scopes,
+            groups: vec![],
+        }
+    }
+
+    pub fn with_groups(mut self, groups: Vec<String>) -> Self {
+        self.groups = groups;
+        self
     }
 }
 
 impl Request for AuthRequest {
     const API_KEY: u16 = AUTH_REQUEST_API_KEY;
+    const DEFAULT_API_VERSION: i16 = 1;
     type Response = AuthResponse;
 }
 
@@ -36,6 +53,8 @@ pub struct AuthResponse {
 #[derive(Debug)]
 pub enum AuthorizationApiRequest {
     AuthRequest(RequestMessage<AuthRequest>),
+    SaslHandshakeRequest(RequestMessage<SaslHandshakeRequest>),
+    SaslAuthenticateRequest(RequestMessage<SaslAuthenticateRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -56,6 +75,17 @@ impl ApiMessage for AuthorizationApiRequest {
     {
         match header.api_key() {
             AUTH_REQUEST_API_KEY => api_decode!(AuthorizationApiRequest, AuthRequest, src, header),
+            SASL_HANDSHAKE_API_KEY => {
+                api_decode!(AuthorizationApiRequest, SaslHandshakeRequest, src, header)
+            }
+            SASL_AUTHENTICATE_API_KEY => {
+                api_decode!(
+                    AuthorizationApiRequest,
+                    SaslAuthenticateRequest,
+                    src,
+                    header
+                )
+            }
             _ => Err(std::io::Error::new(
                 std::io::ErrorKind::InvalidInput,
                 format!("api auth header key should be set to {AUTH_REQUEST_API_KEY:?}"),
@@ -35,8 +35,9 @@ mod cmd {
     use handlebars::{self, Handlebars};
     use anyhow::Result;
 
-    use fluvio_types::PartitionId;
+    use fluvio_types::{PartitionId, Timestamp};
     use fluvio_spu_schema::server::smartmodule::SmartModuleContextData;
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleWindowConfig;
     use fluvio_protocol::record::NO_TIMESTAMP;
     use fluvio::metadata::tableformat::TableFormatSpec;
     use fluvio_future::io::StreamExt;
@@ -48,7 +49,7 @@ mod cmd {
     use crate::render::ProgressRenderer;
     use crate::CliError;
     use crate::common::FluvioExtensionMetadata;
-    use crate::util::{parse_isolation, parse_key_val};
+    use crate::util::{parse_isolation, parse_key_val, parse_timestamp};
     use crate::common::Terminal;
     use crate::client::smartmodule_invocation::{
         create_smartmodule, create_smartmodule_from_path, create_smartmodule_list,
@@ -117,25 +118,35 @@ mod cmd {
         pub table_format: Option<String>,
 
         /// Consume records from the beginning of the log
-        #[arg(short = 'B', long,  conflicts_with_all = &["head","start", "tail"])]
+        #[arg(short = 'B', long,  conflicts_with_all = &["head","start", "tail", "since"])]
         pub beginning: bool,
 
         /// Consume records starting <integer> from the beginning of the log
-        #[arg(short = 'H', long, value_name = "integer", conflicts_with_all = &["beginning", "start", "tail"])]
+        #[arg(short = 'H', long, value_name = "integer", conflicts_with_all = &["beginning", "start", "tail", "since"])]
         pub head: Option<u32>,
 
         /// Consume records starting <integer> from the end of the log
-        #[arg(short = 'T', long,  value_name = "integer", conflicts_with_all = &["beginning","head", "start"])]
+        #[arg(short = 'T', long,  value_name = "integer", conflicts_with_all = &["beginning","head", "start", "since"])]
         pub tail: Option<u32>,
 
         /// The absolute offset of the first record to begin consuming from
-        #[arg(long, value_name = "integer", conflicts_with_all = &["beginning", "head", "tail"])]
+        #[arg(long, value_name = "integer", conflicts_with_all = &["beginning", "head", "tail", "since"])]
         pub start: Option<u32>,
 
         /// Consume records until end offset (inclusive)
         #[arg(long, value_name = "integer")]
         pub end: Option<u32>,
 
+        /// Consume records produced at or after given time.
+        /// Time is RFC3339, e.g. 2023-01-02T10:00:00Z, or duration before now, e.g. 1h
+        #[arg(long, value_name = "time", value_parser = parse_timestamp, conflicts_with_all = &["beginning", "head", "start", "tail"])]
+        pub since: Option<Timestamp>,
+
+        /// Consume records produced at or before given time.
+        /// Time is RFC3339, e.g. 2023-01-02T10:00:00Z, or duration before now, e.g. 1h
+        #[arg(long, value_name = "time", value_parser = parse_timestamp)]
+        pub until: Option<Timestamp>,
+
         /// Maximum number of bytes to be retrieved
         #[arg(short = 'b', long = "maxbytes", value_name = "integer")]
         pub max_bytes: Option<i32>,
@@ -146,6 +157,10 @@ mod cmd {
         #[arg(long, value_parser=parse_isolation)]
         pub isolation: Option<Isolation>,
 
+        /// Don't verify CRC of consumed batches
+        #[arg(long)]
+        pub disable_crc_check: bool,
+
         /// Suppress items items that have an unknown output type
         #[arg(long = "suppress-unknown")]
         pub suppress_unknown: bool,
@@ -182,6 +197,32 @@ mod cmd {
         #[arg(long, requires = "aggregate_group", alias = "a-init")]
         pub aggregate_initial: Option<String>,
 
+        /// (Optional) Name under which state of aggregate SmartModules is checkpointed.
+        /// Consumer started again with same name resumes from checkpointed state and offset
+        #[arg(long, value_name = "name")]
+        pub checkpoint: Option<String>,
+
+        /// (Optional) Size in milliseconds of windows for window SmartModules
+        #[arg(
+            long,
+            value_name = "ms",
+            requires = "smartmodule_group",
+            conflicts_with = "aggregate_initial"
+        )]
+        pub window_size: Option<i64>,
+
+        /// (Optional) Interval in milliseconds at which new window starts, same as size if not set
+        #[arg(long, value_name = "ms", requires = "window_size")]
+        pub window_slide: Option<i64>,
+
+        /// (Optional) How long in milliseconds window is kept open for late records
+        #[arg(long, value_name = "ms", requires = "window_size")]
+        pub window_lateness: Option<i64>,
+
+        /// (Optional) Aggregate records with different keys in separate windows
+        #[arg(long, requires = "window_size")]
+        pub window_by_key: bool,
+
         /// (Optional) Extra input parameters passed to the smartmodule.
         /// They should be passed using key=value format
         /// Eg. fluvio consume topic-name --smartmodule my_filter -e foo=bar -e key=value -e one=1
@@ -285,6 +326,14 @@ mod cmd {
                 SmartModuleContextData::Aggregate {
                     accumulator: agg_initial.clone().into_bytes(),
                 }
+            } else if let Some(size) = self.window_size {
+                let mut config =
+                    SmartModuleWindowConfig::sliding(size, self.window_slide.unwrap_or(size))
+                        .with_lateness(self.window_lateness.unwrap_or_default());
+                if self.window_by_key {
+                    config = config.with_key();
+                }
+                SmartModuleContextData::Window(config)
             } else {
                 SmartModuleContextData::None
             }
@@ -340,6 +389,10 @@ mod cmd {
 
             builder.smartmodule(smart_module);
 
+            if let Some(checkpoint) = &self.checkpoint {
+                builder.checkpoint(checkpoint.clone());
+            }
+
             if self.disable_continuous {
                 builder.disable_continuous(true);
             }
@@ -359,10 +412,23 @@ mod cmd {
                 }
             }
 
+            if let (Some(since), Some(until)) = (self.since, self.until) {
+                if until < since {
+                    return Err(CliError::InvalidArg(
+                        "Argument until must not be earlier than since".to_owned(),
+                    )
+                    .into());
+                }
+            }
+
             if let Some(isolation) = self.isolation {
                 builder.isolation(isolation);
             }
 
+            if self.disable_crc_check {
+                builder.disable_crc_check(true);
+            }
+
             let consume_config = builder.build()?;
             debug!("consume config: {:#?}", consume_config);
 
@@ -463,6 +529,14 @@ mod cmd {
                                     Err(other) => return Err(other.into()),
                                 };
 
+                                if self.is_after_until(&record) {
+                                    eprintln!("Until time has been reached; exiting");
+                                    break;
+                                }
+                                if self.is_before_since(&record) {
+                                    continue;
+                                }
+
                                 self.print_record(
                                     templates.as_ref(),
                                     &record,
@@ -516,6 +590,14 @@ mod cmd {
                         Err(other) => return Err(other.into()),
                     };
 
+                    if self.is_after_until(&record) {
+                        eprintln!("Until time has been reached; exiting");
+                        break;
+                    }
+                    if self.is_before_since(&record) {
+                        continue;
+                    }
+
                     self.print_record(
                         templates.as_ref(),
                         &record,
@@ -657,12 +739,16 @@ mod cmd {
                 format!(" starting at offset {offset}")
             } else if let Some(offset) = self.tail {
                 format!(" starting {offset} from the end of log")
+            } else if let Some(since) = self.since {
+                format!(" starting at time {}", format_timestamp(since))
             } else {
                 "".to_string()
             };
 
             let ending_description = if let Some(end) = self.end {
                 format!(" until offset {end} (inclusive)")
+            } else if let Some(until) = self.until {
+                format!(" until time {}", format_timestamp(until))
             } else {
                 "".to_string()
             };
@@ -706,12 +792,34 @@ mod cmd {
                 Offset::absolute(offset as i64).unwrap()
             } else if let Some(offset) = self.tail {
                 Offset::from_end(offset)
+            } else if let Some(since) = self.since {
+                Offset::from_timestamp(since)
             } else {
                 Offset::end()
             };
 
             Ok(offset)
         }
+
+        /// Seek by time starts at batch boundary, so earlier records of that batch are skipped
+        fn is_before_since(&self, record: &Record) -> bool {
+            match self.since {
+                Some(since) => record.timestamp() != NO_TIMESTAMP && record.timestamp() < since,
+                None => false,
+            }
+        }
+
+        fn is_after_until(&self, record: &Record) -> bool {
+            match self.until {
+                Some(until) => record.timestamp() != NO_TIMESTAMP && record.timestamp() > until,
+                None => false,
+            }
+        }
+    }
+
+    fn format_timestamp(timestamp: Timestamp) -> String {
+        humantime::format_rfc3339_millis(UNIX_EPOCH + Duration::from_millis(timestamp as u64))
+            .to_string()
     }
 
     // Uses clap::ArgEnum to choose possible variables
@@ -753,14 +861,22 @@ mod cmd {
                 head: Default::default(),
                 tail: Default::default(),
                 end: Default::default(),
+                since: Default::default(),
+                until: Default::default(),
                 max_bytes: Default::default(),
                 suppress_unknown: Default::default(),
                 output: Default::default(),
                 smartmodule: Default::default(),
                 smartmodule_path: Default::default(),
                 aggregate_initial: Default::default(),
+                checkpoint: Default::default(),
+                window_size: Default::default(),
+                window_slide: Default::default(),
+                window_lateness: Default::default(),
+                window_by_key: Default::default(),
                 params: Default::default(),
                 isolation: Default::default(),
+                disable_crc_check: Default::default(),
                 beginning: Default::default(),
                 transforms_file: Default::default(),
                 transform: Default::default(),
@@ -828,6 +944,15 @@ mod cmd {
                 "Consuming records from 'TOPIC_NAME' until offset 2 (inclusive)",
                 opt.format_status_string(),
             );
+
+            // --since --until
+            let mut opt = get_opt();
+            opt.since = Some(1_672_653_600_000);
+            opt.until = Some(1_672_657_200_000);
+            assert_eq!(
+                "Consuming records from 'TOPIC_NAME' starting at time 2023-01-02T10:00:00.000Z until time 2023-01-02T11:00:00.000Z",
+                opt.format_status_string(),
+            );
         }
 
         #[test]
@@ -860,6 +985,12 @@ mod cmd {
             opt.start = Some(1);
             let offset = opt.calculate_offset().unwrap();
             assert_eq!(offset, Offset::absolute(1).unwrap());
+
+            // --since
+            let mut opt = get_opt();
+            opt.since = Some(1_000);
+            let offset = opt.calculate_offset().unwrap();
+            assert_eq!(offset, Offset::from_timestamp(1_000));
         }
     }
 }
@@ -4,6 +4,8 @@ mod hub;
 mod produce;
 mod partition;
 mod tableformat;
+mod transform;
+mod quota;
 mod smartmodule;
 mod smartmodule_invocation;
 
@@ -51,6 +53,8 @@ mod cmd {
     use super::topic::TopicCmd;
     use super::partition::PartitionCmd;
     use super::tableformat::TableFormatCmd;
+    use super::transform::TransformCmd;
+    use super::quota::QuotaCmd;
     use super::hub::HubCmd;
 
     #[async_trait]
@@ -131,6 +135,20 @@ mod cmd {
         #[command(subcommand, name = "table-format", visible_alias = "tf")]
         TableFormat(TableFormatCmd),
 
+        /// Create and manage Transforms
+        ///
+        /// Transform continuously reads records of source topic, runs them through
+        /// SmartModules and produces results into target topic.
+        #[command(subcommand, name = "transform")]
+        Transform(TransformCmd),
+
+        /// Create and manage Quotas
+        ///
+        /// Quota limits produce and fetch rate of clients, matched by client id
+        /// or authenticated principal.
+        #[command(subcommand, name = "quota")]
+        Quota(QuotaCmd),
+
         /// Work with the SmartModule Hub
         #[command(subcommand, name = "hub")]
         Hub(HubCmd),
@@ -162,6 +180,12 @@ mod cmd {
                 Self::TableFormat(tableformat) => {
                     tableformat.process(out, target).await?;
                 }
+                Self::Transform(transform) => {
+                    transform.process(out, target).await?;
+                }
+                Self::Quota(quota) => {
+                    quota.process(out, target).await?;
+                }
                 Self::Hub(hub) => {
                     hub.process(out, target).await?;
                 }
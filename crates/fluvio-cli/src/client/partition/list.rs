@@ -97,6 +97,7 @@ mod display {
                 "HW",
                 "LEO",
                 "LRS",
+                "ISR",
                 "FOLLOWER OFFSETS",
             ])
         }
@@ -143,6 +144,7 @@ mod display {
                         Cell::new(status.leader.leo.to_string()),
                         Cell::new(status.leader.leo.to_string()),
                         Cell::new(status.lrs().to_string()),
+                        Cell::new(format!("{:?}", status.in_sync_replicas)),
                         Cell::new(format!("{:?}", status.replicas)),
                     ])
                 })
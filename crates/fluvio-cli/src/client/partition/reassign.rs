@@ -0,0 +1,51 @@
+//!
+//! # Reassign Partition
+//!
+//! CLI tree to move replicas of a Partition to other SPUs
+//!
+
+use tracing::debug;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_types::{PartitionId, SpuId};
+use fluvio::Fluvio;
+
+#[derive(Debug, Parser)]
+pub struct ReassignPartitionOpt {
+    /// The name of the Topic
+    #[arg(value_name = "topic")]
+    topic: String,
+
+    /// Partition to reassign
+    #[arg(short = 'p', long = "partition", value_name = "partition")]
+    partition: PartitionId,
+
+    /// Target replica SPU ids, first one becomes leader
+    ///
+    /// New replicas are added first, leadership and removal of old replicas
+    /// happen once new replicas are in sync.
+    #[arg(
+        long = "replicas",
+        value_name = "spu ids",
+        value_delimiter = ',',
+        required = true
+    )]
+    replicas: Vec<SpuId>,
+}
+
+impl ReassignPartitionOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        debug!(topic = %self.topic, partition = self.partition, replicas = ?self.replicas, "reassigning partition");
+        let admin = fluvio.admin().await;
+        admin
+            .reassign_partition(self.topic.clone(), self.partition, self.replicas)
+            .await?;
+        println!(
+            "partition \"{}-{}\" reassignment started",
+            self.topic, self.partition
+        );
+
+        Ok(())
+    }
+}
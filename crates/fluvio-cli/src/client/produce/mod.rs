@@ -122,6 +122,10 @@ mod cmd {
         #[arg(long, default_value = "at-least-once")]
         pub delivery_semantic: DeliverySemantic,
 
+        /// Enable idempotent producer, so batches retried by producer are not written twice
+        #[arg(long)]
+        pub idempotent: bool,
+
         /// Name of the smartmodule
         #[arg(
             long,
@@ -271,6 +275,7 @@ mod cmd {
 
             let config = config_builder
                 .delivery_semantic(self.delivery_semantic)
+                .idempotent(self.idempotent)
                 .build()
                 .map_err(FluvioError::from)?;
 
@@ -0,0 +1,73 @@
+//!
+//! # Create a Quota
+//!
+//! CLI tree to create Quota limiting produce and fetch rate of clients
+//!
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::quota::{QuotaSpec, QuotaTarget};
+
+use crate::CliError;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateQuotaOpt {
+    /// The name of the Quota to create
+    #[arg(value_name = "name")]
+    pub name: String,
+
+    /// Apply to clients sending this client id.
+    /// Without client id or principal, quota applies to every client without own quota
+    #[arg(long, conflicts_with = "principal")]
+    pub client_id: Option<String>,
+
+    /// Apply to clients authenticated as this principal
+    #[arg(long)]
+    pub principal: Option<String>,
+
+    /// Max bytes per second client can produce to each SPU
+    /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
+    #[arg(long, value_name = "bytes")]
+    pub produce_byte_rate: Option<bytesize::ByteSize>,
+
+    /// Max bytes per second client can fetch from each SPU
+    /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
+    #[arg(long, value_name = "bytes")]
+    pub fetch_byte_rate: Option<bytesize::ByteSize>,
+
+    /// Max produce and fetch requests per second client can send to each SPU
+    #[arg(long, value_name = "requests")]
+    pub request_rate: Option<u64>,
+}
+
+impl CreateQuotaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let target = match (self.client_id, self.principal) {
+            (Some(client_id), _) => QuotaTarget::ClientId { client_id },
+            (None, Some(principal)) => QuotaTarget::Principal { principal },
+            (None, None) => QuotaTarget::Default,
+        };
+        let spec = QuotaSpec {
+            target,
+            produce_byte_rate: self.produce_byte_rate.map(|rate| rate.as_u64()),
+            fetch_byte_rate: self.fetch_byte_rate.map(|rate| rate.as_u64()),
+            request_rate: self.request_rate,
+        };
+        spec.validate().map_err(CliError::InvalidArg)?;
+
+        debug!(name = %self.name, ?spec, "creating quota");
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("quota \"{}\" created", &self.name);
+
+        Ok(())
+    }
+}
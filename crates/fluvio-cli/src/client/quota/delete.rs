@@ -0,0 +1,29 @@
+//!
+//! # Delete Quota
+//!
+//! CLI tree to delete Quota, clients it applied to are no longer limited
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::quota::QuotaSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteQuotaOpt {
+    /// The name of the Quota to delete
+    name: String,
+}
+
+impl DeleteQuotaOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<QuotaSpec, _>(&self.name).await?;
+        println!("quota \"{}\" deleted", &self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,71 @@
+mod create;
+mod delete;
+mod list;
+
+pub use cmd::QuotaCmd;
+
+mod cmd {
+
+    use std::sync::Arc;
+    use std::fmt::Debug;
+
+    use async_trait::async_trait;
+    use clap::Parser;
+    use anyhow::Result;
+
+    use fluvio::Fluvio;
+    use fluvio_extension_common::Terminal;
+    use fluvio_extension_common::COMMAND_TEMPLATE;
+
+    use crate::client::cmd::ClientCmd;
+
+    use super::create::CreateQuotaOpt;
+    use super::delete::DeleteQuotaOpt;
+    use super::list::ListQuotasOpt;
+
+    #[derive(Debug, Parser)]
+    pub enum QuotaCmd {
+        /// Create a new Quota limiting produce and fetch rate of clients
+        #[command(
+            name = "create",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Create(CreateQuotaOpt),
+
+        /// Delete a Quota
+        #[command(
+            name = "delete",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Delete(DeleteQuotaOpt),
+
+        /// List all Quotas
+        #[command(
+            name = "list",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        List(ListQuotasOpt),
+    }
+
+    #[async_trait]
+    impl ClientCmd for QuotaCmd {
+        async fn process_client<O: Terminal + Debug + Send + Sync>(
+            self,
+            out: Arc<O>,
+            fluvio: &Fluvio,
+        ) -> Result<()> {
+            match self {
+                Self::Create(create) => {
+                    create.process(fluvio).await?;
+                }
+                Self::Delete(delete) => {
+                    delete.process(fluvio).await?;
+                }
+                Self::List(list) => {
+                    list.process(out, fluvio).await?;
+                }
+            }
+            Ok(())
+        }
+    }
+}
@@ -22,6 +22,7 @@ pub(crate) fn create_smartmodule(
         wasm: SmartModuleInvocationWasm::Predefined(name.to_string()),
         kind: SmartModuleKind::Generic(ctx),
         params: params.into(),
+        ..Default::default()
     }
 }
 
@@ -41,6 +42,7 @@ pub(crate) fn create_smartmodule_from_path(
         wasm: SmartModuleInvocationWasm::AdHoc(buffer),
         kind: SmartModuleKind::Generic(ctx),
         params: params.into(),
+        ..Default::default()
     })
 }
 
@@ -60,6 +62,7 @@ pub(crate) fn create_smartmodule_list(
                 .map(|(k, v)| (k, v.into()))
                 .collect::<std::collections::BTreeMap<String, String>>()
                 .into(),
+            error_policy: t.on_error.into(),
         })
         .collect())
 }
@@ -17,6 +17,7 @@ use anyhow::Result;
 use fluvio_types::PartitionCount;
 use fluvio_types::ReplicationFactor;
 use fluvio::metadata::topic::CleanupPolicy;
+use fluvio::metadata::topic::CompactPolicy;
 use fluvio::metadata::topic::ReplicaSpec;
 use fluvio::metadata::topic::SegmentBasedPolicy;
 use fluvio::metadata::topic::TopicStorageConfig;
@@ -138,30 +139,22 @@ impl CreateTopicOpt {
         }
 
         let mut topic_spec: TopicSpec = replica_spec.into();
-        if let Some(retention) = self.setting.retention_time {
-            topic_spec.set_cleanup_policy(CleanupPolicy::Segment(SegmentBasedPolicy {
-                time_in_seconds: retention.as_secs() as u32,
-            }));
+        if let Some(policy) = self.setting.cleanup_policy() {
+            topic_spec.set_cleanup_policy(policy);
         }
 
-        if let Some(compression_type) = self.setting.compression_type {
+        if let Some(compression_type) = self.setting.compression_type() {
             topic_spec.set_compression_type(compression_type);
         }
 
-        if self.setting.segment_size.is_some() || self.setting.max_partition_size.is_some() {
-            let mut storage = TopicStorageConfig::default();
-
-            if let Some(segment_size) = self.setting.segment_size {
-                storage.segment_size = Some(segment_size.as_u64() as u32);
-            }
-
-            if let Some(max_partition_size) = self.setting.max_partition_size {
-                storage.max_partition_size = Some(max_partition_size.as_u64());
-            }
-
+        if let Some(storage) = self.setting.storage() {
             topic_spec.set_storage(storage);
         }
 
+        if let Some(min_in_sync_replicas) = self.setting.min_in_sync_replicas() {
+            topic_spec.set_min_in_sync_replicas(min_in_sync_replicas);
+        }
+
         // return server separately from config
         Ok((self.topic, topic_spec))
     }
@@ -174,6 +167,16 @@ pub struct TopicConfigOpt {
     #[arg(long, value_name = "time",value_parser=parse_duration)]
     retention_time: Option<Duration>,
 
+    /// Compact topic by key, only latest record for each key is kept.
+    /// If retention time is also set, old segments are deleted as well
+    #[arg(long)]
+    compact: bool,
+
+    /// How long records with key and empty value (tombstones) are kept in compacted topic
+    /// Ex: '1h', '2d 10s', '1 day' (default)
+    #[arg(long, value_name = "time", value_parser=parse_duration, requires = "compact")]
+    tombstone_retention_time: Option<Duration>,
+
     /// Segment size (by default measured in bytes)
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
@@ -187,6 +190,55 @@ pub struct TopicConfigOpt {
     /// Ex: `2048`, '2 Ki', '10 MiB', `1 GB`
     #[arg(long, value_name = "bytes")]
     max_partition_size: Option<bytesize::ByteSize>,
+
+    /// Min number of replicas in sync with leader, for produce with committed isolation
+    /// to be accepted. Defaults to SPU configuration
+    #[arg(long, value_name = "integer")]
+    min_in_sync_replicas: Option<u16>,
+}
+
+impl TopicConfigOpt {
+    /// cleanup policy if any of retention options is set
+    pub(crate) fn cleanup_policy(&self) -> Option<CleanupPolicy> {
+        let retention = self.retention_time.map(|retention| SegmentBasedPolicy {
+            time_in_seconds: retention.as_secs() as u32,
+        });
+        if self.compact {
+            let mut compact = CompactPolicy {
+                delete: retention,
+                ..Default::default()
+            };
+            if let Some(tombstone_retention) = self.tombstone_retention_time {
+                compact.tombstone_retention_secs = tombstone_retention.as_secs() as u32;
+            }
+            Some(CleanupPolicy::Compact(compact))
+        } else {
+            retention.map(CleanupPolicy::Segment)
+        }
+    }
+
+    /// storage config if any of size options is set
+    pub(crate) fn storage(&self) -> Option<TopicStorageConfig> {
+        if self.segment_size.is_none() && self.max_partition_size.is_none() {
+            return None;
+        }
+        Some(TopicStorageConfig {
+            segment_size: self
+                .segment_size
+                .map(|segment_size| segment_size.as_u64() as u32),
+            max_partition_size: self
+                .max_partition_size
+                .map(|max_partition_size| max_partition_size.as_u64()),
+        })
+    }
+
+    pub(crate) fn compression_type(&self) -> Option<CompressionAlgorithm> {
+        self.compression_type.clone()
+    }
+
+    pub(crate) fn min_in_sync_replicas(&self) -> Option<u16> {
+        self.min_in_sync_replicas
+    }
 }
 
 /// module to load partitions maps from file
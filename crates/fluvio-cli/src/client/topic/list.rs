@@ -109,9 +109,13 @@ mod display {
                         Cell::new(topic.type_label()),
                         Cell::new(topic.partitions_display()).set_alignment(CellAlignment::Left),
                         Cell::new(topic.replication_factor_display()),
-                        Cell::new(format_duration(Duration::from_secs(
-                            topic.retention_secs() as u64
-                        ))),
+                        Cell::new(match topic.get_clean_policy() {
+                            Some(policy) if !policy.is_delete() => "compact".to_owned(),
+                            _ => {
+                                format_duration(Duration::from_secs(topic.retention_secs() as u64))
+                                    .to_string()
+                            }
+                        }),
                         Cell::new(topic.get_compression_type()),
                         Cell::new(metadata.status.resolution.to_string()),
                         Cell::new(metadata.status.reason.to_string()),
@@ -2,6 +2,7 @@ mod create;
 mod delete;
 mod describe;
 mod list;
+mod update;
 
 pub use cmd::TopicCmd;
 
@@ -25,6 +26,7 @@ mod cmd {
     use super::delete::DeleteTopicOpt;
     use super::describe::DescribeTopicsOpt;
     use super::list::ListTopicsOpt;
+    use super::update::UpdateTopicOpt;
 
     #[derive(Debug, Parser)]
     #[command(name = "topic", about = "Topic operations")]
@@ -56,6 +58,13 @@ mod cmd {
             help_template = COMMAND_TEMPLATE,
         )]
         List(ListTopicsOpt),
+
+        /// Change configuration of an existing Topic
+        #[command(
+            name = "update",
+            help_template = COMMAND_TEMPLATE,
+        )]
+        Update(UpdateTopicOpt),
     }
 
     #[async_trait]
@@ -78,6 +87,9 @@ mod cmd {
                 Self::List(list) => {
                     list.process(out, fluvio).await?;
                 }
+                Self::Update(update) => {
+                    update.process(fluvio).await?;
+                }
             }
 
             Ok(())
@@ -0,0 +1,59 @@
+//!
+//! # Update Topic
+//!
+//! CLI tree to change configuration of existing Topic
+//!
+
+use tracing::debug;
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio_types::PartitionCount;
+use fluvio::Fluvio;
+use fluvio::metadata::topic::{TopicSpec, TopicUpdate};
+
+use crate::CliError;
+use super::create::TopicConfigOpt;
+
+#[derive(Debug, Parser)]
+pub struct UpdateTopicOpt {
+    /// The name of the Topic to update
+    #[arg(value_name = "name")]
+    topic: String,
+
+    /// New number of Partitions of the Topic
+    ///
+    /// Partitions can only be added, existing partitions keep their data.
+    /// Producers start sending to new partitions on their next metadata update.
+    #[arg(short = 'p', long = "partitions", value_name = "partitions")]
+    partitions: Option<PartitionCount>,
+
+    // cleanup policy is replaced if any of retention options is set,
+    // storage sizes and compression are changed only if set
+    #[clap(flatten)]
+    setting: TopicConfigOpt,
+}
+
+impl UpdateTopicOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let update = TopicUpdate {
+            cleanup_policy: self.setting.cleanup_policy(),
+            storage: self.setting.storage(),
+            compression_type: self.setting.compression_type(),
+            partitions: self.partitions,
+            min_in_sync_replicas: self.setting.min_in_sync_replicas(),
+        };
+        if update.is_empty() {
+            return Err(CliError::InvalidArg("no topic configuration to update".to_owned()).into());
+        }
+
+        debug!(topic = %self.topic, ?update, "updating topic");
+        let admin = fluvio.admin().await;
+        admin
+            .update::<TopicSpec, _>(self.topic.clone(), update)
+            .await?;
+        println!("topic \"{}\" updated", self.topic);
+
+        Ok(())
+    }
+}
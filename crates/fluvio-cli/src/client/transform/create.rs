@@ -0,0 +1,86 @@
+//!
+//! # Create a Transform
+//!
+//! CLI tree to create Transform from source topic to target topic
+//!
+
+use std::path::PathBuf;
+
+use clap::Parser;
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::transform::{TransformSpec, TransformStep};
+use fluvio_smartengine::transformation::{TransformationConfig, TransformationStep};
+
+use crate::CliError;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct CreateTransformOpt {
+    /// The name of the Transform to create
+    #[arg(value_name = "name")]
+    pub name: String,
+
+    /// Topic records are read from
+    #[arg(long)]
+    pub source: String,
+
+    /// Topic transformed records are produced to
+    #[arg(long)]
+    pub target: String,
+
+    /// Path to a file with transformation specification.
+    #[arg(long, required_unless_present = "transform")]
+    pub transforms_file: Option<PathBuf>,
+
+    /// Transformation specification as JSON formatted string.
+    /// E.g. fluvio transform create uppercase --source in --target out --transform='{"uses":"infinyon/uppercase@0.1.0"}'
+    #[arg(long, short, conflicts_with = "transforms_file")]
+    pub transform: Vec<String>,
+}
+
+impl CreateTransformOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let config = if let Some(transforms_file) = &self.transforms_file {
+            TransformationConfig::from_file(transforms_file).map_err(|err| {
+                CliError::InvalidArg(format!(
+                    "unable to process `transforms_file` argument: {err}"
+                ))
+            })?
+        } else {
+            TransformationConfig::try_from(self.transform.clone()).map_err(|err| {
+                CliError::InvalidArg(format!("unable to parse `transform` argument: {err}"))
+            })?
+        };
+
+        let spec = config.transforms.into_iter().map(transform_step).fold(
+            TransformSpec::new(self.source, self.target),
+            |spec, step| spec.with_step(step),
+        );
+
+        debug!(name = %self.name, ?spec, "creating transform");
+
+        let admin = fluvio.admin().await;
+        admin.create(self.name.clone(), false, spec).await?;
+        println!("transform \"{}\" created", &self.name);
+
+        Ok(())
+    }
+}
+
+fn transform_step(step: TransformationStep) -> TransformStep {
+    TransformStep {
+        uses: step.uses,
+        with: step
+            .with
+            .into_iter()
+            .map(|(key, value)| (key, value.into()))
+            .collect(),
+        on_error: step.on_error,
+    }
+}
@@ -0,0 +1,29 @@
+//!
+//! # Delete Transform
+//!
+//! CLI tree to delete Transform, records already produced to target topic are kept
+//!
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::transform::TransformSpec;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DeleteTransformOpt {
+    /// The name of the Transform to delete
+    name: String,
+}
+
+impl DeleteTransformOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.delete::<TransformSpec, _>(&self.name).await?;
+        println!("transform \"{}\" deleted", &self.name);
+        Ok(())
+    }
+}
@@ -0,0 +1,111 @@
+//! # List Transforms CLI
+//!
+//! CLI tree and processing to list Transforms
+//!
+
+use std::sync::Arc;
+
+use clap::Parser;
+use anyhow::Result;
+
+use fluvio::Fluvio;
+use fluvio::metadata::transform::TransformSpec;
+
+use fluvio_extension_common::Terminal;
+use fluvio_extension_common::OutputFormat;
+
+#[derive(Debug, Parser)]
+pub struct ListTransformsOpt {
+    #[clap(flatten)]
+    output: OutputFormat,
+}
+
+impl ListTransformsOpt {
+    /// Process list transforms cli request
+    pub async fn process<O: Terminal>(self, out: Arc<O>, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        let lists = admin.all::<TransformSpec>().await?;
+
+        output::transforms_response_to_output(out, lists, self.output.format)
+    }
+}
+
+mod output {
+
+    //!
+    //! # Fluvio SC - output processing
+    //!
+
+    use comfy_table::{Row, Cell};
+    use comfy_table::CellAlignment;
+    use tracing::debug;
+    use serde::Serialize;
+    use anyhow::Result;
+
+    use fluvio_extension_common::output::OutputType;
+    use fluvio_extension_common::Terminal;
+    use fluvio::metadata::objects::Metadata;
+    use fluvio::metadata::transform::TransformSpec;
+    use fluvio_extension_common::output::TableOutputHandler;
+    use fluvio_extension_common::t_println;
+
+    #[derive(Serialize)]
+    struct ListTransforms(Vec<Metadata<TransformSpec>>);
+
+    // -----------------------------------
+    // Format Output
+    // -----------------------------------
+
+    /// Format Transform list
+    pub fn transforms_response_to_output<O: Terminal>(
+        out: std::sync::Arc<O>,
+        list_transforms: Vec<Metadata<TransformSpec>>,
+        output_type: OutputType,
+    ) -> Result<()> {
+        debug!("transforms: {:#?}", list_transforms);
+
+        if !list_transforms.is_empty() {
+            let transforms = ListTransforms(list_transforms);
+            out.render_list(&transforms, output_type)?;
+            Ok(())
+        } else {
+            t_println!(out, "no transforms");
+            Ok(())
+        }
+    }
+
+    // -----------------------------------
+    // Output Handlers
+    // -----------------------------------
+    impl TableOutputHandler for ListTransforms {
+        fn header(&self) -> Row {
+            Row::from(["NAME", "SOURCE", "TARGET", "SMARTMODULES"])
+        }
+
+        fn errors(&self) -> Vec<String> {
+            vec![]
+        }
+
+        fn content(&self) -> Vec<Row> {
+            self.0
+                .iter()
+                .map(|r| {
+                    let smartmodules = r
+                        .spec
+                        .transforms
+                        .iter()
+                        .map(|step| step.uses.as_str())
+                        .collect::<Vec<_>>()
+                        .join(",");
+
+                    Row::from([
+                        Cell::new(&r.name).set_alignment(CellAlignment::Left),
+                        Cell::new(&r.spec.source).set_alignment(CellAlignment::Left),
+                        Cell::new(&r.spec.target).set_alignment(CellAlignment::Left),
+                        Cell::new(smartmodules).set_alignment(CellAlignment::Left),
+                    ])
+                })
+                .collect()
+        }
+    }
+}
@@ -429,4 +429,26 @@ mod util {
         })?;
         Ok((s[..pos].parse()?, s[pos + 1..].parse()?))
     }
+
+    /// parse either RFC3339 time or duration relative to now, e.g. "10m", into unix time in millis
+    pub(crate) fn parse_timestamp(s: &str) -> Result<fluvio_types::Timestamp, String> {
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        let time = match humantime::parse_rfc3339_weak(s) {
+            Ok(time) => time,
+            Err(_) => {
+                let ago = humantime::parse_duration(s).map_err(|_| {
+                    format!("invalid time: {s}. Use RFC3339 time or duration, e.g. 2023-01-02T10:00:00Z or 10m")
+                })?;
+                SystemTime::now()
+                    .checked_sub(ago)
+                    .ok_or_else(|| format!("duration is too long: {s}"))?
+            }
+        };
+        let millis = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| format!("time is before unix epoch: {s}"))?
+            .as_millis();
+        Ok(millis as fluvio_types::Timestamp)
+    }
 }
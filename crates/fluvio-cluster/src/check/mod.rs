@@ -784,6 +784,18 @@ impl ClusterChecker {
         self
     }
 
+    /// Adds checks required for starting a local cluster
+    /// which stores metadata in local files instead of Kubernetes.
+    ///
+    /// Note that no checks are run until the [`run`] method is invoked.
+    ///
+    /// [`run`]: ClusterChecker::run
+    pub fn with_no_k8_checks(mut self) -> Self {
+        let checks: Vec<Box<(dyn ClusterCheck)>> = vec![Box::new(LocalClusterCheck)];
+        self.checks.extend(checks);
+        self
+    }
+
     /// Performs checks and fixes as required.
     pub async fn run(
         self,
@@ -0,0 +1,31 @@
+//!
+//! # Drain SPU
+//!
+//! CLI tree to move all replicas off an SPU before decommissioning it
+//!
+
+use anyhow::Result;
+use clap::Parser;
+
+use fluvio::Fluvio;
+use fluvio_types::SpuId;
+
+// -----------------------------------
+// CLI Options
+// -----------------------------------
+
+#[derive(Debug, Parser)]
+pub struct DrainSpuOpt {
+    /// SPU id
+    #[arg(short = 'i', long = "id")]
+    id: SpuId,
+}
+
+impl DrainSpuOpt {
+    pub async fn process(self, fluvio: &Fluvio) -> Result<()> {
+        let admin = fluvio.admin().await;
+        admin.drain_spu(self.id).await?;
+        println!("spu {} drain started", self.id);
+        Ok(())
+    }
+}
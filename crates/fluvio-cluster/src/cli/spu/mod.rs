@@ -5,6 +5,7 @@ mod list;
 mod display;
 mod register;
 mod unregister;
+mod drain;
 
 use anyhow::Result;
 
@@ -12,6 +13,7 @@ use fluvio::Fluvio;
 use list::ListSpusOpt;
 use register::RegisterCustomSpuOpt;
 use unregister::UnregisterCustomSpuOpt;
+use drain::DrainSpuOpt;
 
 use super::common::COMMAND_TEMPLATE;
 use super::common::output::Terminal;
@@ -38,6 +40,13 @@ pub enum SpuCmd {
         help_template = COMMAND_TEMPLATE,
     )]
     List(ListSpusOpt),
+
+    /// Move all replicas off an SPU so it can be decommissioned
+    #[command(
+        name = "drain",
+        help_template = COMMAND_TEMPLATE,
+    )]
+    Drain(DrainSpuOpt),
 }
 
 impl SpuCmd {
@@ -52,6 +61,9 @@ impl SpuCmd {
             Self::List(list) => {
                 list.process(out, fluvio).await?;
             }
+            Self::Drain(drain) => {
+                drain.process(fluvio).await?;
+            }
         }
         Ok(())
     }
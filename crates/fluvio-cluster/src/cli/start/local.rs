@@ -21,6 +21,7 @@ pub async fn process_local(
     builder
         .log_dir(opt.log_dir.to_string())
         .spu_replicas(opt.spu)
+        .k8_metadata(opt.local_k8)
         .hide_spinner(false);
 
     if let Some(chart_location) = opt.k8_config.chart_location {
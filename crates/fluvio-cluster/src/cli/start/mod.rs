@@ -149,6 +149,10 @@ pub struct StartOpt {
     #[arg(long)]
     local: bool,
 
+    /// install local spu/sc(custom) with metadata stored in Kubernetes
+    #[arg(long, conflicts_with = "local")]
+    local_k8: bool,
+
     #[clap(flatten)]
     pub tls: TlsOpt,
 
@@ -183,7 +187,7 @@ impl StartOpt {
 
         if self.sys_only {
             process_sys(&self, upgrade)?;
-        } else if self.local {
+        } else if self.local || self.local_k8 {
             process_local(self, platform_version).await?;
         } else {
             process_k8(self, platform_version, upgrade).await?;
@@ -221,6 +221,8 @@ impl ClusterUninstaller {
         let _ = self.remove_custom_objects("managedconnectors", ns, None, false, &pb);
         let _ = self.remove_custom_objects("derivedstreams", ns, None, false, &pb);
         let _ = self.remove_custom_objects("smartmodules", ns, None, false, &pb);
+        let _ = self.remove_custom_objects("transforms", ns, None, false, &pb);
+        let _ = self.remove_custom_objects("quotas", ns, None, false, &pb);
 
         // delete secrets
         let _ = self.remove_secrets("fluvio-ca");
@@ -15,6 +15,7 @@ pub struct ScProcess {
     pub launcher: Option<PathBuf>,
     pub tls_policy: TlsPolicy,
     pub rust_log: String,
+    pub metadata_dir: Option<PathBuf>,
 }
 
 impl FluvioLocalProcess for ScProcess {}
@@ -31,6 +32,9 @@ impl ScProcess {
             cmd.arg("run").arg("sc").arg("--local");
             cmd
         };
+        if let Some(metadata_dir) = &self.metadata_dir {
+            binary.arg("--metadata-dir").arg(metadata_dir);
+        }
         if let TlsPolicy::Verified(tls) = &self.tls_policy {
             self.set_server_tls(&mut binary, tls, 9005)?;
         }
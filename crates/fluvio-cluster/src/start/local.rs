@@ -11,7 +11,7 @@ use once_cell::sync::Lazy;
 
 use fluvio::{Fluvio, FluvioConfig};
 use fluvio::config::{TlsPolicy, ConfigFile, LOCAL_PROFILE};
-use fluvio_controlplane_metadata::spu::{SpuSpec};
+use fluvio_controlplane_metadata::spu::{CustomSpuSpec, SpuSpec};
 use fluvio_future::timer::sleep;
 use fluvio_command::CommandExt;
 use k8_types::{InputK8Obj, InputObjectMeta};
@@ -36,6 +36,7 @@ const DEFAULT_SPU_REPLICAS: u16 = 1;
 const DEFAULT_TLS_POLICY: TlsPolicy = TlsPolicy::Disabled;
 const LOCAL_SC_ADDRESS: &str = "localhost:9003";
 const LOCAL_SC_PORT: u16 = 9003;
+const LOCAL_METADATA_DIR: &str = "metadata";
 
 static DEFAULT_RUNNER_PATH: Lazy<Option<PathBuf>> = Lazy::new(|| std::env::current_exe().ok());
 
@@ -166,6 +167,24 @@ pub struct LocalConfig {
 
     #[builder(default = "true")]
     hide_spinner: bool,
+
+    /// Whether to store cluster metadata in Kubernetes instead of local files.
+    ///
+    /// Defaults to `false`, so local cluster doesn't need Kubernetes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio_cluster::{ClusterError, LocalConfigBuilder};
+    /// # fn example(builder: &mut LocalConfigBuilder) -> Result<(), ClusterError> {
+    /// let config = builder
+    ///     .k8_metadata(true)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[builder(default = "false")]
+    k8_metadata: bool,
 }
 
 impl LocalConfig {
@@ -192,6 +211,15 @@ impl LocalConfig {
         self.launcher.as_deref()
     }
 
+    /// directory of local metadata, none if metadata is stored in Kubernetes
+    pub fn metadata_dir(&self) -> Option<PathBuf> {
+        if self.k8_metadata {
+            None
+        } else {
+            Some(self.data_dir.join(LOCAL_METADATA_DIR))
+        }
+    }
+
     pub fn as_spu_cluster_manager(&self) -> LocalSpuProcessClusterManager {
         LocalSpuProcessClusterManager {
             log_dir: self.log_dir.to_owned(),
@@ -364,6 +392,15 @@ impl LocalInstaller {
 
         self.pb_factory
             .println(InstallProgressMessage::PreFlightCheck.msg());
+
+        if !self.config.k8_metadata {
+            ClusterChecker::empty()
+                .with_no_k8_checks()
+                .run(&self.pb_factory, fix)
+                .await?;
+            return Ok(());
+        }
+
         ClusterChecker::empty()
             .with_local_checks()
             .with_check(SysChartCheck::new(
@@ -396,12 +433,17 @@ impl LocalInstaller {
             })?;
         }
 
-        let client = load_and_share()?;
+        let client = if self.config.k8_metadata {
+            let client = load_and_share()?;
 
-        pb.set_message("Ensure CRDs are installed");
-        // before we do let's try make sure SPU are installed.
-        check_crd(client.clone()).await?;
-        pb.set_message("CRD Checked");
+            pb.set_message("Ensure CRDs are installed");
+            // before we do let's try make sure SPU are installed.
+            check_crd(client.clone()).await?;
+            pb.set_message("CRD Checked");
+            Some(client)
+        } else {
+            None
+        };
 
         pb.set_message("Sync files");
         // ensure we sync files before we launch servers
@@ -426,7 +468,7 @@ impl LocalInstaller {
         self.set_profile()?;
 
         let pb = self.pb_factory.create()?;
-        self.launch_spu_group(client.clone(), &pb).await?;
+        self.launch_spu_group(client, &fluvio, &pb).await?;
         self.confirm_spu(self.config.spu_replicas, &fluvio, &pb)
             .await?;
         pb.println(format!("✅ {} SPU launched", self.config.spu_replicas));
@@ -458,6 +500,7 @@ impl LocalInstaller {
             launcher: self.config.launcher.clone(),
             tls_policy: self.config.server_tls_policy.clone(),
             rust_log: self.config.rust_log.clone(),
+            metadata_dir: self.config.metadata_dir(),
         };
 
         sc_process.start()?;
@@ -498,10 +541,11 @@ impl LocalInstaller {
         Ok(())
     }
 
-    #[instrument(skip(self))]
+    #[instrument(skip(self, fluvio))]
     async fn launch_spu_group(
         &self,
-        client: SharedK8Client,
+        client: Option<SharedK8Client>,
+        fluvio: &Fluvio,
         pb: &ProgressRenderer,
     ) -> Result<(), LocalInstallError> {
         let count = self.config.spu_replicas;
@@ -509,7 +553,7 @@ impl LocalInstaller {
         let runtime = self.config.as_spu_cluster_manager();
         for i in 0..count {
             pb.set_message(InstallProgressMessage::StartSPU(i + 1, count).msg());
-            self.launch_spu(i, &runtime, client.clone()).await?;
+            self.launch_spu(i, &runtime, client.clone(), fluvio).await?;
         }
         debug!(
             "SC log generated at {}/flv_sc.log",
@@ -519,29 +563,38 @@ impl LocalInstaller {
         Ok(())
     }
 
-    #[instrument(skip(self, cluster_manager, client))]
+    #[instrument(skip(self, cluster_manager, client, fluvio))]
     async fn launch_spu(
         &self,
         spu_index: u16,
         cluster_manager: &LocalSpuProcessClusterManager,
-        client: SharedK8Client,
+        client: Option<SharedK8Client>,
+        fluvio: &Fluvio,
     ) -> Result<(), LocalInstallError> {
         use k8_client::meta_client::MetadataClient;
         use crate::runtime::spu::{SpuClusterManager};
 
         let spu_process = cluster_manager.create_spu_relative(spu_index);
-
-        let input = InputK8Obj::new(
-            spu_process.spec().clone(),
-            InputObjectMeta {
-                name: format!("custom-spu-{}", spu_process.id()),
-                namespace: "default".to_owned(),
-                ..Default::default()
-            },
-        );
-
-        debug!(input=?input,"creating spu");
-        client.create_item(input).await?;
+        let name = format!("custom-spu-{}", spu_process.id());
+
+        if let Some(client) = client {
+            let input = InputK8Obj::new(
+                spu_process.spec().clone(),
+                InputObjectMeta {
+                    name,
+                    namespace: "default".to_owned(),
+                    ..Default::default()
+                },
+            );
+
+            debug!(input=?input,"creating spu");
+            client.create_item(input).await?;
+        } else {
+            // SC owns local metadata, so register through SC
+            let spec = CustomSpuSpec::from(spu_process.spec().clone());
+            debug!(%name, ?spec, "registering custom spu");
+            fluvio.admin().await.create(name, false, spec).await?;
+        }
         debug!("sleeping 1 sec");
         // sleep 1 seconds for sc to connect
         sleep(Duration::from_millis(1000)).await;
@@ -366,6 +366,7 @@ mod tests {
                         ),
                         ("param".to_string(), "param_value".into()),
                     ]),
+                    ..Default::default()
                 }
                 .into(),
             ),
@@ -0,0 +1,38 @@
+//!
+//! # Cluster
+//!
+//! Interface to the ConsumerGroup metadata in K8 key value store
+//!
+
+use super::ConsumerGroupStatus;
+use super::ConsumerGroupSpec;
+use crate::k8_types::Status as K8Status;
+use crate::k8_types::{Crd, Spec, DefaultHeader};
+
+/// implement k8 status for consumer group status because they are same
+impl K8Status for ConsumerGroupStatus {}
+
+use crd::CONSUMER_GROUP_SPEC_API;
+mod crd {
+
+    use crate::k8_types::{Crd, CrdNames, GROUP, V1};
+
+    pub const CONSUMER_GROUP_SPEC_API: Crd = Crd {
+        group: GROUP,
+        version: V1,
+        names: CrdNames {
+            kind: "ConsumerGroup",
+            plural: "consumergroups",
+            singular: "consumergroup",
+        },
+    };
+}
+
+impl Spec for ConsumerGroupSpec {
+    type Status = ConsumerGroupStatus;
+    type Header = DefaultHeader;
+
+    fn metadata() -> &'static Crd {
+        &CONSUMER_GROUP_SPEC_API
+    }
+}
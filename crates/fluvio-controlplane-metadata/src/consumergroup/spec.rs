@@ -0,0 +1,82 @@
+#![allow(clippy::assign_op_pattern)]
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::record::Offset;
+use fluvio_types::PartitionId;
+
+/// Consumer group with offsets committed by its members
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConsumerGroupSpec {
+    /// topic consumed by group
+    pub topic: String,
+    /// committed offsets, sorted by partition
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub offsets: Vec<ConsumerOffset>,
+}
+
+impl ConsumerGroupSpec {
+    pub fn new(topic: impl Into<String>) -> Self {
+        Self {
+            topic: topic.into(),
+            offsets: vec![],
+        }
+    }
+
+    /// committed offset of partition
+    pub fn offset(&self, partition: PartitionId) -> Option<Offset> {
+        self.offsets
+            .iter()
+            .find(|committed| committed.partition == partition)
+            .map(|committed| committed.offset)
+    }
+
+    /// update committed offset of partition
+    pub fn commit(&mut self, partition: PartitionId, offset: Offset) {
+        match self
+            .offsets
+            .binary_search_by_key(&partition, |committed| committed.partition)
+        {
+            Ok(index) => self.offsets[index].offset = offset,
+            Err(index) => self
+                .offsets
+                .insert(index, ConsumerOffset { partition, offset }),
+        }
+    }
+}
+
+/// Offset of next record to be consumed in partition
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConsumerOffset {
+    pub partition: PartitionId,
+    pub offset: Offset,
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::ConsumerGroupSpec;
+
+    #[test]
+    fn test_commit_offsets() {
+        let mut spec = ConsumerGroupSpec::new("test");
+        spec.commit(2, 10);
+        spec.commit(0, 5);
+        spec.commit(2, 12);
+
+        assert_eq!(spec.offset(0), Some(5));
+        assert_eq!(spec.offset(1), None);
+        assert_eq!(spec.offset(2), Some(12));
+        assert_eq!(spec.offsets.len(), 2);
+        assert_eq!(spec.offsets[0].partition, 0);
+    }
+}
@@ -0,0 +1,28 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Membership of consumer group is managed by SC in memory, only number of
+/// members in current generation is reported
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct ConsumerGroupStatus {
+    pub generation: i32,
+    pub members: u32,
+}
+
+impl fmt::Display for ConsumerGroupStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "generation: {} members: {}",
+            self.generation, self.members
+        )
+    }
+}
@@ -5,6 +5,9 @@ pub mod spg;
 pub mod message;
 pub mod smartmodule;
 pub mod tableformat;
+pub mod consumergroup;
+pub mod transform;
+pub mod quota;
 
 pub use fluvio_stream_model::core;
 
@@ -31,6 +34,9 @@ pub mod extended {
         SmartModule,
         TableFormat,
         DerivedStream,
+        ConsumerGroup,
+        Transform,
+        Quota,
     }
 
     pub trait SpecExt: Spec {
@@ -11,6 +11,8 @@ pub use self::smartmodule_msg::{SmartModuleMsgs, SmartModuleMsg};
 
 pub use spu_msg::*;
 pub use smartmodule_msg::*;
+pub use transform_msg::*;
+pub use quota_msg::*;
 
 mod spu_msg {
 
@@ -21,6 +23,26 @@ mod spu_msg {
     pub type SpuMsg = Message<SpuSpec>;
 }
 
+mod transform_msg {
+
+    use crate::transform::Transform;
+
+    use super::{Message, Messages};
+
+    pub type TransformMsg = Message<Transform>;
+    pub type TransformMsgs = Messages<Transform>;
+}
+
+mod quota_msg {
+
+    use crate::quota::Quota;
+
+    use super::{Message, Messages};
+
+    pub type QuotaMsg = Message<Quota>;
+    pub type QuotaMsgs = Messages<Quota>;
+}
+
 mod smartmodule_msg {
 
     use crate::smartmodule::SmartModule;
@@ -22,6 +22,8 @@ pub struct Replica {
     pub cleanup_policy: Option<CleanupPolicy>,
     pub storage: Option<TopicStorageConfig>,
     pub compression_type: CompressionAlgorithm,
+    /// min in sync replicas of topic, SPU default is used if not set
+    pub min_in_sync_replicas: Option<u16>,
 }
 
 impl Replica {
@@ -64,6 +66,7 @@ where
             cleanup_policy: spec.cleanup_policy,
             storage: spec.storage,
             compression_type: spec.compression_type,
+            min_in_sync_replicas: spec.min_in_sync_replicas,
         }
     }
 }
@@ -9,6 +9,8 @@ use fluvio_protocol::{Encoder, Decoder};
 
 use crate::topic::{CleanupPolicy, TopicStorageConfig, TopicSpec, CompressionAlgorithm};
 
+use super::PartitionStatus;
+
 /// Spec for Partition
 /// Each partition has replicas spread among SPU
 /// one of replica is leader which is duplicated in the leader field
@@ -28,6 +30,19 @@ pub struct PartitionSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 6)]
     pub compression_type: CompressionAlgorithm,
+    /// target replicas of reassignment in progress
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 12)]
+    pub reassignment: Option<Vec<SpuId>>,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 12)]
+    pub min_in_sync_replicas: Option<u16>,
 }
 
 impl PartitionSpec {
@@ -49,7 +64,70 @@ impl PartitionSpec {
             cleanup_policy: topic.get_clean_policy().cloned(),
             storage: topic.get_storage().cloned(),
             compression_type: topic.get_compression_type().clone(),
+            reassignment: None,
+            min_in_sync_replicas: topic.get_min_in_sync_replicas(),
+        }
+    }
+
+    /// apply configuration of topic, return true if configuration has changed
+    pub fn update_config(&mut self, topic: &TopicSpec) -> bool {
+        let cleanup_policy = topic.get_clean_policy().cloned();
+        let storage = topic.get_storage().cloned();
+        let compression_type = topic.get_compression_type();
+        let min_in_sync_replicas = topic.get_min_in_sync_replicas();
+        if self.cleanup_policy == cleanup_policy
+            && self.storage == storage
+            && &self.compression_type == compression_type
+            && self.min_in_sync_replicas == min_in_sync_replicas
+        {
+            return false;
+        }
+        self.cleanup_policy = cleanup_policy;
+        self.storage = storage;
+        self.compression_type = compression_type.clone();
+        self.min_in_sync_replicas = min_in_sync_replicas;
+        true
+    }
+
+    /// start moving replicas to target spus.
+    /// target spus are added to replicas first, so they can catch up with leader
+    pub fn start_reassignment(&mut self, target: Vec<SpuId>) {
+        for spu in &target {
+            if !self.replicas.contains(spu) {
+                self.replicas.push(*spu);
+            }
         }
+        self.reassignment = Some(target);
+    }
+
+    /// next step of reassignment in progress, based on status reported by leader.
+    /// returns None if there is nothing to change yet.
+    ///  * once all target replicas are in sync, leadership moves to target if needed
+    ///  * then replicas not in target are removed and reassignment is complete
+    pub fn next_reassignment_step(&self, status: &PartitionStatus) -> Option<Self> {
+        let target = self.reassignment.as_ref()?;
+        if target.is_empty() || status.leader.spu != self.leader {
+            return None;
+        }
+
+        let in_sync = |spu: &SpuId| {
+            *spu == self.leader
+                || status.replica_iter().any(|replica| {
+                    replica.spu == *spu && replica.leo >= 0 && replica.leo >= status.leader.hw
+                })
+        };
+        if !target.iter().all(in_sync) {
+            return None;
+        }
+
+        let mut next = self.clone();
+        if target.contains(&self.leader) {
+            next.replicas = target.clone();
+            next.reassignment = None;
+        } else {
+            next.leader = target[0];
+        }
+        Some(next)
     }
 
     pub fn has_spu(&self, spu: &SpuId) -> bool {
@@ -80,3 +158,45 @@ impl From<Vec<SpuId>> for PartitionSpec {
 pub struct PartitionConfig {
     pub retention_time_seconds: Option<u32>,
 }
+
+#[cfg(test)]
+mod test {
+
+    use crate::partition::{PartitionStatus, ReplicaStatus};
+
+    use super::PartitionSpec;
+
+    #[test]
+    fn test_reassignment_steps() {
+        let mut spec = PartitionSpec::new(1, vec![1, 2]);
+        spec.start_reassignment(vec![3, 2]);
+        assert_eq!(spec.replicas, vec![1, 2, 3]);
+        assert_eq!(spec.reassignment, Some(vec![3, 2]));
+
+        // new replica is still behind
+        let mut status = PartitionStatus::new(
+            (1, 10, 10),
+            vec![ReplicaStatus::new(2, 10, 10), ReplicaStatus::new(3, -1, -1)],
+        );
+        assert!(spec.next_reassignment_step(&status).is_none());
+
+        // new replica caught up, leadership moves to target
+        status.replicas[1] = ReplicaStatus::new(3, 10, 10);
+        let spec = spec.next_reassignment_step(&status).expect("leader change");
+        assert_eq!(spec.leader, 3);
+        assert_eq!(spec.replicas, vec![1, 2, 3]);
+
+        // wait until new leader reports status
+        assert!(spec.next_reassignment_step(&status).is_none());
+
+        let status = PartitionStatus::new(
+            (3, 10, 10),
+            vec![ReplicaStatus::new(1, 10, 10), ReplicaStatus::new(2, 10, 10)],
+        );
+        let spec = spec.next_reassignment_step(&status).expect("shrink");
+        assert_eq!(spec.leader, 3);
+        assert_eq!(spec.replicas, vec![3, 2]);
+        assert!(spec.reassignment.is_none());
+        assert!(spec.next_reassignment_step(&status).is_none());
+    }
+}
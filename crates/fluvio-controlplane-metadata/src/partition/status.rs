@@ -41,6 +41,10 @@ pub struct PartitionStatus {
     #[fluvio(min_version = 5)]
     pub size: i64,
     pub is_being_deleted: bool,
+    /// leader and followers which are in sync with leader
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    #[fluvio(min_version = 12)]
+    pub in_sync_replicas: Vec<SpuId>,
 }
 
 impl Default for PartitionStatus {
@@ -52,6 +56,7 @@ impl Default for PartitionStatus {
             lsr: Default::default(),
             replicas: Default::default(),
             is_being_deleted: Default::default(),
+            in_sync_replicas: Default::default(),
         }
     }
 }
@@ -146,7 +151,14 @@ impl PartitionStatus {
         self
     }
 
-    /// Fnd best candidate from online replicas
+    /// true if replica was in sync with leader when leader last reported status,
+    /// leader which doesn't report in-sync replicas considers all replicas in sync
+    pub fn is_in_sync(&self, spu: SpuId) -> bool {
+        self.in_sync_replicas.is_empty() || self.in_sync_replicas.contains(&spu)
+    }
+
+    /// Fnd best candidate from online replicas in sync with leader,
+    /// replica out of sync may miss committed records.
     /// If there are multiple matches, find with best score (lowest lag)
     pub fn candidate_leader<P>(&self, online: &HashSet<SpuId>, policy: &P) -> Option<SpuId>
     where
@@ -157,7 +169,7 @@ impl PartitionStatus {
 
         for candidate in &self.replicas {
             // only do for live replicas
-            if online.contains(&candidate.spu) {
+            if online.contains(&candidate.spu) && self.is_in_sync(candidate.spu) {
                 if let ElectionScoring::Score(score) =
                     policy.potential_leader_score(candidate, &self.leader)
                 {
@@ -181,6 +193,7 @@ impl PartitionStatus {
     pub fn merge(&mut self, other: Self) {
         self.resolution = other.resolution;
         self.size = other.size;
+        self.in_sync_replicas = other.in_sync_replicas;
         if let Some(old) = self.leader.merge(&other.leader) {
             self.replicas.push(old); // move old leader to replicas
         }
@@ -410,6 +423,23 @@ mod test {
         assert!(status.candidate_leader(&online_spu, &policy).is_none());
     }
 
+    #[test]
+    fn test_candidate_spu_in_sync() {
+        let mut status = PartitionStatus::new(
+            (5000, 100, 110),
+            vec![(5001, 100, 110).into(), (5002, 100, 108).into()],
+        );
+        status.in_sync_replicas = vec![5000, 5002];
+        let online_spu: HashSet<_> = [5001, 5002].into_iter().collect();
+        let policy = SimplePolicy {};
+
+        // 5001 has least lag but was removed from in-sync replicas
+        assert_eq!(status.candidate_leader(&online_spu, &policy), Some(5002));
+
+        status.in_sync_replicas = vec![5000];
+        assert!(status.candidate_leader(&online_spu, &policy).is_none());
+    }
+
     #[test]
     fn test_merge_initial() {
         let mut target = PartitionStatus::default();
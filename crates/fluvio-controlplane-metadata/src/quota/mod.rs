@@ -0,0 +1,94 @@
+mod spec;
+mod status;
+
+pub use spec::*;
+pub use status::*;
+
+use std::fmt;
+
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_stream_model::store::MetadataStoreObject;
+use fluvio_protocol::{Encoder, Decoder};
+
+#[cfg(feature = "k8")]
+mod k8;
+#[cfg(feature = "k8")]
+pub use k8::*;
+
+/// Quota object that can be used to transport from SC to SPU
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+pub struct Quota {
+    pub name: String,
+    pub spec: QuotaSpec,
+}
+
+impl fmt::Display for Quota {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Quota({})", self.name)
+    }
+}
+
+impl<C> From<MetadataStoreObject<QuotaSpec, C>> for Quota
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<QuotaSpec, C>) -> Self {
+        let name = mso.key_owned();
+        let spec = mso.spec;
+        Self { name, spec }
+    }
+}
+
+mod convert {
+
+    use crate::core::{Spec, Status, Removable, Creatable};
+    use crate::extended::{ObjectType, SpecExt};
+    use super::*;
+
+    impl Spec for QuotaSpec {
+        const LABEL: &'static str = "Quota";
+
+        type Status = QuotaStatus;
+
+        type Owner = Self;
+        type IndexKey = String;
+    }
+
+    impl SpecExt for QuotaSpec {
+        const OBJECT_TYPE: ObjectType = ObjectType::Quota;
+    }
+
+    impl Removable for QuotaSpec {
+        type DeleteKey = String;
+    }
+
+    impl Creatable for QuotaSpec {}
+
+    impl Status for QuotaStatus {}
+
+    #[cfg(feature = "k8")]
+    mod extended {
+
+        use crate::store::k8::K8ExtendedSpec;
+        use crate::store::k8::K8ConvertError;
+        use crate::store::k8::K8MetaItem;
+        use crate::store::MetadataStoreObject;
+        use crate::k8_types::K8Obj;
+        use crate::store::k8::default_convert_from_k8;
+
+        use super::QuotaSpec;
+
+        impl K8ExtendedSpec for QuotaSpec {
+            type K8Spec = Self;
+            type K8Status = Self::Status;
+
+            fn convert_from_k8(
+                k8_obj: K8Obj<Self::K8Spec>,
+                multi_namespace_context: bool,
+            ) -> Result<MetadataStoreObject<Self, K8MetaItem>, K8ConvertError<Self::K8Spec>>
+            {
+                default_convert_from_k8(k8_obj, multi_namespace_context)
+            }
+        }
+    }
+}
@@ -0,0 +1,144 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+/// Limits rate of produce and fetch of matching clients.
+/// Rates are enforced by each SPU separately, so client can reach them on every SPU it talks to.
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct QuotaSpec {
+    /// clients quota applies to
+    pub target: QuotaTarget,
+    /// max bytes per second client can produce
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub produce_byte_rate: Option<u64>,
+    /// max bytes per second client can fetch
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub fetch_byte_rate: Option<u64>,
+    /// max produce and fetch requests per second
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub request_rate: Option<u64>,
+}
+
+impl QuotaSpec {
+    pub fn new(target: QuotaTarget) -> Self {
+        Self {
+            target,
+            ..Default::default()
+        }
+    }
+
+    pub fn with_produce_byte_rate(mut self, rate: u64) -> Self {
+        self.produce_byte_rate = Some(rate);
+        self
+    }
+
+    pub fn with_fetch_byte_rate(mut self, rate: u64) -> Self {
+        self.fetch_byte_rate = Some(rate);
+        self
+    }
+
+    pub fn with_request_rate(mut self, rate: u64) -> Self {
+        self.request_rate = Some(rate);
+        self
+    }
+
+    /// quota must limit at least one rate and rates must be positive
+    pub fn validate(&self) -> Result<(), String> {
+        let rates = [
+            self.produce_byte_rate,
+            self.fetch_byte_rate,
+            self.request_rate,
+        ];
+        if rates.iter().all(Option::is_none) {
+            return Err("quota must limit at least one rate".to_owned());
+        }
+        if rates.iter().flatten().any(|rate| *rate == 0) {
+            return Err("rate must be greater than 0".to_owned());
+        }
+        match &self.target {
+            QuotaTarget::ClientId { client_id } if client_id.is_empty() => {
+                Err("client id can't be empty".to_owned())
+            }
+            QuotaTarget::Principal { principal } if principal.is_empty() => {
+                Err("principal can't be empty".to_owned())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Clients quota applies to. Quota of authenticated principal takes precedence
+/// over quota of client id, default quota applies to clients without own quota.
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "type", rename_all = "camelCase")
+)]
+pub enum QuotaTarget {
+    /// every client without own quota, usage is still tracked per client
+    #[default]
+    #[fluvio(tag = 0)]
+    Default,
+    /// clients sending this client id in request header
+    #[fluvio(tag = 1)]
+    #[cfg_attr(feature = "use_serde", serde(rename_all = "camelCase"))]
+    ClientId { client_id: String },
+    /// clients authenticated as this principal
+    #[fluvio(tag = 2)]
+    Principal { principal: String },
+}
+
+impl fmt::Display for QuotaTarget {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Default => write!(f, "default"),
+            Self::ClientId { client_id } => write!(f, "client-id: {client_id}"),
+            Self::Principal { principal } => write!(f, "principal: {principal}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{QuotaSpec, QuotaTarget};
+
+    #[test]
+    fn test_validate_quota() {
+        assert!(QuotaSpec::new(QuotaTarget::Default).validate().is_err());
+        assert!(QuotaSpec::new(QuotaTarget::Default)
+            .with_request_rate(0)
+            .validate()
+            .is_err());
+        assert!(QuotaSpec::new(QuotaTarget::ClientId {
+            client_id: String::new()
+        })
+        .with_produce_byte_rate(1024)
+        .validate()
+        .is_err());
+        assert!(QuotaSpec::new(QuotaTarget::Principal {
+            principal: "svc".to_owned()
+        })
+        .with_produce_byte_rate(1024)
+        .with_fetch_byte_rate(2048)
+        .validate()
+        .is_ok());
+    }
+}
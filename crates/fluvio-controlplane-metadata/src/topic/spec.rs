@@ -13,7 +13,7 @@ use std::ops::Deref;
 
 use fluvio_types::defaults::{
     STORAGE_RETENTION_SECONDS, SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN, STORAGE_RETENTION_SECONDS_MIN,
-    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES,
+    SPU_PARTITION_MAX_BYTES_MIN, SPU_LOG_SEGMENT_MAX_BYTES, STORAGE_TOMBSTONE_RETENTION_SECONDS,
 };
 use fluvio_types::{ReplicaMap, SpuId};
 use fluvio_types::{PartitionId, PartitionCount, ReplicationFactor, IgnoreRackAssignment};
@@ -34,6 +34,13 @@ pub struct TopicSpec {
     #[cfg_attr(feature = "use_serde", serde(default))]
     #[fluvio(min_version = 6)]
     compression_type: CompressionAlgorithm,
+    /// produce with committed isolation is rejected if fewer replicas are in sync
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    #[fluvio(min_version = 12)]
+    min_in_sync_replicas: Option<u16>,
 }
 
 impl From<ReplicaSpec> for TopicSpec {
@@ -96,6 +103,31 @@ impl TopicSpec {
         &self.compression_type
     }
 
+    pub fn set_min_in_sync_replicas(&mut self, min_in_sync_replicas: u16) {
+        self.min_in_sync_replicas = Some(min_in_sync_replicas);
+    }
+
+    pub fn get_min_in_sync_replicas(&self) -> Option<u16> {
+        self.min_in_sync_replicas
+    }
+
+    /// increase partition count of computed topic, partitions can't be removed
+    pub fn set_partitions(&mut self, partitions: PartitionCount) -> Result<(), String> {
+        match &mut self.replicas {
+            ReplicaSpec::Computed(param) if partitions < param.partitions => Err(format!(
+                "partition count can't be decreased from {} to {}",
+                param.partitions, partitions
+            )),
+            ReplicaSpec::Computed(param) => {
+                param.partitions = partitions;
+                Ok(())
+            }
+            ReplicaSpec::Assigned(_) => {
+                Err("partitions of assigned topic can't be changed".to_owned())
+            }
+        }
+    }
+
     pub fn get_storage(&self) -> Option<&TopicStorageConfig> {
         self.storage.as_ref()
     }
@@ -127,6 +159,19 @@ impl TopicSpec {
             }
         }
 
+        if let Some(min_in_sync_replicas) = self.min_in_sync_replicas {
+            if min_in_sync_replicas == 0 {
+                return Some("min_in_sync_replicas must be at least 1".to_owned());
+            }
+            if let Some(replication) = self.replicas.replication_factor() {
+                if min_in_sync_replicas as ReplicationFactor > replication {
+                    return Some(format!(
+                        "min_in_sync_replicas {min_in_sync_replicas} is greater than replication factor {replication}"
+                    ));
+                }
+            }
+        }
+
         if let Some(storage) = self.get_storage() {
             if let Some(segment_size) = storage.segment_size {
                 if segment_size < SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN {
@@ -562,6 +607,9 @@ pub enum CleanupPolicy {
     #[cfg_attr(feature = "use_serde", serde(rename = "segment"))]
     #[fluvio(tag = 0)]
     Segment(SegmentBasedPolicy),
+    #[cfg_attr(feature = "use_serde", serde(rename = "compact"))]
+    #[fluvio(tag = 1)]
+    Compact(CompactPolicy),
 }
 
 impl Default for CleanupPolicy {
@@ -574,6 +622,20 @@ impl CleanupPolicy {
     pub fn retention_secs(&self) -> u32 {
         match self {
             CleanupPolicy::Segment(policy) => policy.retention_secs(),
+            CleanupPolicy::Compact(policy) => policy.retention_secs(),
+        }
+    }
+
+    /// true if only latest record per key should be kept
+    pub fn is_compact(&self) -> bool {
+        matches!(self, CleanupPolicy::Compact(_))
+    }
+
+    /// true if segments are deleted after retention time
+    pub fn is_delete(&self) -> bool {
+        match self {
+            CleanupPolicy::Segment(_) => true,
+            CleanupPolicy::Compact(policy) => policy.delete.is_some(),
         }
     }
 }
@@ -594,6 +656,57 @@ impl SegmentBasedPolicy {
     }
 }
 
+/// Key based compaction.
+/// Closed segments are rewritten so only latest record for each key is kept.
+/// Records with key and empty value (tombstones) are removed after `tombstone_retention_secs`.
+/// If `delete` is set, segments older than its retention are also removed.
+#[derive(Decoder, Encoder, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct CompactPolicy {
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default = "default_tombstone_retention_secs")
+    )]
+    pub tombstone_retention_secs: u32,
+    #[cfg_attr(
+        feature = "use_serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub delete: Option<SegmentBasedPolicy>,
+}
+
+impl Default for CompactPolicy {
+    fn default() -> Self {
+        Self {
+            tombstone_retention_secs: STORAGE_TOMBSTONE_RETENTION_SECONDS,
+            delete: None,
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn default_tombstone_retention_secs() -> u32 {
+    STORAGE_TOMBSTONE_RETENTION_SECONDS
+}
+
+impl CompactPolicy {
+    /// retention of segments, compact only policy never expires segments
+    pub fn retention_secs(&self) -> u32 {
+        self.delete
+            .as_ref()
+            .map(|policy| policy.retention_secs())
+            .unwrap_or(u32::MAX)
+    }
+
+    pub fn tombstone_retention_secs(&self) -> u32 {
+        self.tombstone_retention_secs
+    }
+}
+
 #[derive(Decoder, Encoder, Default, Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(
     feature = "use_serde",
@@ -889,6 +1002,29 @@ pub mod test {
         }
     }
 
+    #[test]
+    fn test_encode_decode_compact_cleanup_policy() {
+        let policy = CleanupPolicy::Compact(CompactPolicy {
+            tombstone_retention_secs: 3600,
+            delete: Some(SegmentBasedPolicy {
+                time_in_seconds: 7200,
+            }),
+        });
+        let mut dest = vec![];
+        policy.encode(&mut dest, 0).expect("encode");
+
+        let mut decoded = CleanupPolicy::default();
+        decoded.decode(&mut Cursor::new(&dest), 0).expect("decode");
+        assert_eq!(decoded, policy);
+        assert!(decoded.is_compact());
+        assert!(decoded.is_delete());
+        assert_eq!(decoded.retention_secs(), 7200);
+
+        let compact_only = CleanupPolicy::Compact(CompactPolicy::default());
+        assert!(!compact_only.is_delete());
+        assert_eq!(compact_only.retention_secs(), u32::MAX);
+    }
+
     #[test]
     fn test_partition_map_str() {
         // Test multiple
@@ -9,7 +9,7 @@ use std::collections::BTreeMap;
 use std::fmt;
 
 use fluvio_protocol::{Encoder, Decoder};
-use fluvio_types::{ReplicaMap, SpuId, PartitionId};
+use fluvio_types::{PartitionCount, ReplicaMap, SpuId, PartitionId};
 
 // -----------------------------------
 // Data Structures
@@ -150,6 +150,16 @@ impl TopicStatus {
         self.replica_map.len() as i32
     }
 
+    /// partitions available to clients. SC adds partitions to replica map of provisioned
+    /// topic only once their leaders are online, so partitions beyond it are not counted
+    pub fn available_partitions(&self, spec_partitions: PartitionCount) -> PartitionCount {
+        if self.replica_map.is_empty() {
+            spec_partitions
+        } else {
+            spec_partitions.min(self.replica_map.len() as PartitionCount)
+        }
+    }
+
     pub fn set_replica_map(&mut self, replica_map: ReplicaMap) {
         self.replica_map = replica_map;
     }
@@ -18,6 +18,11 @@ pub trait TopicMd<C: MetadataItem> {
         &self,
         partition_store: &PartitionLocalStore<C>,
     ) -> Vec<PartitionMetadata<C>>;
+
+    async fn update_partition_configs(
+        &self,
+        partition_store: &PartitionLocalStore<C>,
+    ) -> Vec<PartitionMetadata<C>>;
 }
 
 #[async_trait]
@@ -44,6 +49,29 @@ where
         }
         partitions
     }
+
+    /// existing partitions with configuration updated from my spec if it has changed
+    async fn update_partition_configs(
+        &self,
+        partition_store: &PartitionLocalStore<C>,
+    ) -> Vec<PartitionMetadata<C>> {
+        let mut partitions = vec![];
+        for idx in self.status.replica_map.keys() {
+            let replica_key = ReplicaKey::new(self.key(), *idx);
+            if let Some(partition) = partition_store.value(&replica_key).await {
+                let mut partition = partition.inner_owned();
+                if partition.spec.update_config(&self.spec) {
+                    debug!(
+                        "Topic: {} updating partition config: {}",
+                        self.key(),
+                        replica_key
+                    );
+                    partitions.push(partition);
+                }
+            }
+        }
+        partitions
+    }
 }
 
 #[async_trait]
@@ -150,6 +178,40 @@ mod test {
         assert_eq!(topic1, topic2);
     }
 
+    #[fluvio_future::test]
+    async fn test_update_partition_configs() {
+        use crate::partition::store::{DefaultPartitionStore, PartitionLocalStorePolicy};
+        use crate::topic::TopicStorageConfig;
+        use super::TopicMd;
+
+        let mut topic = DefaultTopicMd::new(
+            "topic1",
+            (2, 2, false).into(),
+            TopicStatus::new(
+                TopicResolution::Provisioned,
+                vec![vec![0, 1], vec![1, 2]],
+                "".to_owned(),
+            ),
+        );
+        let partitions = DefaultPartitionStore::bulk_load(vec![
+            (("topic1", 0), vec![0, 1]),
+            (("topic1", 1), vec![1, 2]),
+        ]);
+        assert!(topic.update_partition_configs(&partitions).await.is_empty());
+
+        topic.spec.set_storage(TopicStorageConfig {
+            segment_size: Some(2000),
+            max_partition_size: None,
+        });
+        let updated = topic.update_partition_configs(&partitions).await;
+        assert_eq!(updated.len(), 2);
+        for partition in updated {
+            assert_eq!(partition.spec.storage, topic.spec.get_storage().cloned());
+            // replica assignment is kept
+            assert_eq!(partition.spec.replicas.len(), 2);
+        }
+    }
+
     #[fluvio_future::test]
     async fn test_topics_in_pending_state() {
         use std::collections::HashSet;
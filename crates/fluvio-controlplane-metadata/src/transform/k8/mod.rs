@@ -0,0 +1,38 @@
+//!
+//! # Cluster
+//!
+//! Interface to the Transform metadata in K8 key value store
+//!
+
+use super::TransformStatus;
+use super::TransformSpec;
+use crate::k8_types::Status as K8Status;
+use crate::k8_types::{Crd, Spec, DefaultHeader};
+
+/// implement k8 status for transform status because they are same
+impl K8Status for TransformStatus {}
+
+use crd::TRANSFORM_SPEC_API;
+mod crd {
+
+    use crate::k8_types::{Crd, CrdNames, GROUP, V1};
+
+    pub const TRANSFORM_SPEC_API: Crd = Crd {
+        group: GROUP,
+        version: V1,
+        names: CrdNames {
+            kind: "Transform",
+            plural: "transforms",
+            singular: "transform",
+        },
+    };
+}
+
+impl Spec for TransformSpec {
+    type Status = TransformStatus;
+    type Header = DefaultHeader;
+
+    fn metadata() -> &'static Crd {
+        &TRANSFORM_SPEC_API
+    }
+}
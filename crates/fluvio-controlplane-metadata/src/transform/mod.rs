@@ -0,0 +1,96 @@
+mod spec;
+mod status;
+
+pub use spec::*;
+pub use status::*;
+
+use std::fmt;
+
+use fluvio_stream_model::core::MetadataItem;
+use fluvio_stream_model::store::MetadataStoreObject;
+use fluvio_protocol::{Encoder, Decoder};
+
+#[cfg(feature = "k8")]
+mod k8;
+#[cfg(feature = "k8")]
+pub use k8::*;
+
+/// Transform object that can be used to transport from SC to SPU
+#[derive(Debug, Default, Clone, Eq, PartialEq, Encoder, Decoder)]
+pub struct Transform {
+    pub name: String,
+    pub spec: TransformSpec,
+    pub status: TransformStatus,
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Transform({})", self.name)
+    }
+}
+
+impl<C> From<MetadataStoreObject<TransformSpec, C>> for Transform
+where
+    C: MetadataItem,
+{
+    fn from(mso: MetadataStoreObject<TransformSpec, C>) -> Self {
+        let name = mso.key_owned();
+        let spec = mso.spec;
+        let status = mso.status;
+        Self { name, spec, status }
+    }
+}
+
+mod convert {
+
+    use crate::core::{Spec, Status, Removable, Creatable};
+    use crate::extended::{ObjectType, SpecExt};
+    use super::*;
+
+    impl Spec for TransformSpec {
+        const LABEL: &'static str = "Transform";
+
+        type Status = TransformStatus;
+
+        type Owner = Self;
+        type IndexKey = String;
+    }
+
+    impl SpecExt for TransformSpec {
+        const OBJECT_TYPE: ObjectType = ObjectType::Transform;
+    }
+
+    impl Removable for TransformSpec {
+        type DeleteKey = String;
+    }
+
+    impl Creatable for TransformSpec {}
+
+    impl Status for TransformStatus {}
+
+    #[cfg(feature = "k8")]
+    mod extended {
+
+        use crate::store::k8::K8ExtendedSpec;
+        use crate::store::k8::K8ConvertError;
+        use crate::store::k8::K8MetaItem;
+        use crate::store::MetadataStoreObject;
+        use crate::k8_types::K8Obj;
+        use crate::store::k8::default_convert_from_k8;
+
+        use super::TransformSpec;
+
+        impl K8ExtendedSpec for TransformSpec {
+            type K8Spec = Self;
+            type K8Status = Self::Status;
+
+            fn convert_from_k8(
+                k8_obj: K8Obj<Self::K8Spec>,
+                multi_namespace_context: bool,
+            ) -> Result<MetadataStoreObject<Self, K8MetaItem>, K8ConvertError<Self::K8Spec>>
+            {
+                default_convert_from_k8(k8_obj, multi_namespace_context)
+            }
+        }
+    }
+}
@@ -0,0 +1,67 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::collections::BTreeMap;
+
+use fluvio_protocol::{Encoder, Decoder};
+
+pub use fluvio_smartmodule::dataplane::smartmodule::SmartModuleErrorPolicy;
+
+/// Records of source topic continuously transformed by SmartModule chain and produced
+/// into target topic. Leader of each source partition runs the chain.
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TransformSpec {
+    /// topic records are read from
+    pub source: String,
+    /// topic transformed records are produced to
+    pub target: String,
+    /// SmartModules applied to records in order
+    pub transforms: Vec<TransformStep>,
+}
+
+impl TransformSpec {
+    pub fn new(source: impl Into<String>, target: impl Into<String>) -> Self {
+        Self {
+            source: source.into(),
+            target: target.into(),
+            transforms: vec![],
+        }
+    }
+
+    pub fn with_step(mut self, step: TransformStep) -> Self {
+        self.transforms.push(step);
+        self
+    }
+}
+
+/// SmartModule in transform chain, same as step of transformation config
+#[derive(Encoder, Decoder, Default, Debug, Eq, PartialEq, Clone)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TransformStep {
+    /// name of SmartModule
+    pub uses: String,
+    /// parameters passed to SmartModule
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub with: BTreeMap<String, String>,
+    /// what happens with records SmartModule fails to process
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub on_error: SmartModuleErrorPolicy,
+}
+
+impl TransformStep {
+    pub fn new(uses: impl Into<String>) -> Self {
+        Self {
+            uses: uses.into(),
+            with: BTreeMap::new(),
+            on_error: SmartModuleErrorPolicy::default(),
+        }
+    }
+}
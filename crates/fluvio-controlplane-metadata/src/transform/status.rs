@@ -0,0 +1,63 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::record::Offset;
+
+use fluvio_types::PartitionId;
+
+/// Transform is run by SPUs leading source partitions.
+/// SC keeps the source offset committed by each partition so a new leader resumes from it.
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct TransformStatus {
+    /// next source offset to process, by source partition
+    #[cfg_attr(feature = "use_serde", serde(default))]
+    pub offsets: BTreeMap<PartitionId, Offset>,
+}
+
+impl TransformStatus {
+    /// committed offset of source partition
+    pub fn offset(&self, partition: PartitionId) -> Option<Offset> {
+        self.offsets.get(&partition).copied()
+    }
+
+    /// record committed offset, offsets never move backwards
+    pub fn commit(&mut self, partition: PartitionId, offset: Offset) {
+        let committed = self.offsets.entry(partition).or_insert(offset);
+        if offset > *committed {
+            *committed = offset;
+        }
+    }
+}
+
+impl fmt::Display for TransformStatus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "")
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::TransformStatus;
+
+    #[test]
+    fn test_commit_offset() {
+        let mut status = TransformStatus::default();
+        assert_eq!(status.offset(0), None);
+
+        status.commit(0, 10);
+        status.commit(1, 3);
+        // late report of previous leader doesn't move offset back
+        status.commit(0, 7);
+        assert_eq!(status.offset(0), Some(10));
+        assert_eq!(status.offset(1), Some(3));
+    }
+}
@@ -13,6 +13,10 @@ pub use self::requests::register_spu::*;
 pub use self::requests::update_lrs::*;
 pub use self::requests::remove::*;
 pub use self::requests::update_smartmodule::*;
+pub use self::requests::update_data_policy::*;
+pub use self::requests::update_transform::*;
+pub use self::requests::update_transform_offsets::*;
+pub use self::requests::update_quota::*;
 
 use fluvio_protocol::api::RequestMessage;
 
@@ -4,6 +4,10 @@ pub mod register_spu;
 pub mod update_lrs;
 pub mod remove;
 pub mod update_smartmodule;
+pub mod update_data_policy;
+pub mod update_transform;
+pub mod update_transform_offsets;
+pub mod update_quota;
 
 mod request;
 pub use self::request::ControlPlaneRequest;
@@ -0,0 +1,29 @@
+#![allow(clippy::assign_op_pattern)]
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+use fluvio_auth::DataPolicy;
+
+use crate::InternalSpuApi;
+
+/// produce/consume policy enforced by SPU.
+/// if policy is not set, SPU allows every connection
+#[derive(Decoder, Encoder, Default, Debug, Clone)]
+pub struct UpdateDataPolicyRequest {
+    pub policy: Option<DataPolicy>,
+}
+
+impl UpdateDataPolicyRequest {
+    pub fn new(policy: Option<DataPolicy>) -> Self {
+        Self { policy }
+    }
+}
+
+impl Request for UpdateDataPolicyRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateDataPolicy as u16;
+    type Response = UpdateDataPolicyResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateDataPolicyResponse {}
@@ -8,6 +8,7 @@ use fluvio_protocol::Decoder;
 use fluvio_protocol::Encoder;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_controlplane_metadata::partition::ReplicaStatus;
+use fluvio_types::SpuId;
 
 use crate::InternalScKey;
 
@@ -64,6 +65,8 @@ pub struct LrsRequest {
     pub leader: ReplicaStatus,
     pub replicas: Vec<ReplicaStatus>,
     pub size: i64,
+    /// leader and followers which are in sync with leader
+    pub in_sync_replicas: Vec<SpuId>,
 }
 
 impl PartialEq for LrsRequest {
@@ -99,6 +102,12 @@ impl LrsRequest {
             leader,
             replicas,
             size,
+            in_sync_replicas: vec![],
         }
     }
+
+    pub fn with_in_sync_replicas(mut self, in_sync_replicas: Vec<SpuId>) -> Self {
+        self.in_sync_replicas = in_sync_replicas;
+        self
+    }
 }
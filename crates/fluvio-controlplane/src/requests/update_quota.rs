@@ -0,0 +1,20 @@
+#![allow(clippy::assign_op_pattern)]
+
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+use fluvio_protocol::api::Request;
+
+use fluvio_controlplane_metadata::quota::Quota;
+
+use crate::InternalSpuApi;
+use super::ControlPlaneRequest;
+
+pub type UpdateQuotaRequest = ControlPlaneRequest<Quota>;
+
+impl Request for UpdateQuotaRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateQuota as u16;
+    type Response = UpdateQuotaResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateQuotaResponse {}
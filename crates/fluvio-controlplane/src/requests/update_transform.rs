@@ -0,0 +1,20 @@
+#![allow(clippy::assign_op_pattern)]
+
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+use fluvio_protocol::api::Request;
+
+use fluvio_controlplane_metadata::transform::Transform;
+
+use crate::InternalSpuApi;
+use super::ControlPlaneRequest;
+
+pub type UpdateTransformRequest = ControlPlaneRequest<Transform>;
+
+impl Request for UpdateTransformRequest {
+    const API_KEY: u16 = InternalSpuApi::UpdateTransform as u16;
+    type Response = UpdateTransformResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateTransformResponse {}
@@ -0,0 +1,60 @@
+#![allow(clippy::assign_op_pattern)]
+
+use std::fmt;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::Decoder;
+use fluvio_protocol::Encoder;
+use fluvio_protocol::record::Offset;
+use fluvio_types::PartitionId;
+
+use crate::InternalScKey;
+
+/// Source offsets committed by transforms running on SPU
+#[derive(Decoder, Encoder, Debug, Default, Clone)]
+pub struct UpdateTransformOffsetsRequest {
+    offsets: Vec<TransformOffset>,
+}
+
+impl UpdateTransformOffsetsRequest {
+    pub fn new(offsets: Vec<TransformOffset>) -> Self {
+        Self { offsets }
+    }
+
+    /// make into vec of offsets
+    pub fn into_offsets(self) -> Vec<TransformOffset> {
+        self.offsets
+    }
+}
+
+impl fmt::Display for UpdateTransformOffsetsRequest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "transform offsets {}", self.offsets.len())
+    }
+}
+
+impl Request for UpdateTransformOffsetsRequest {
+    const API_KEY: u16 = InternalScKey::UpdateTransformOffsets as u16;
+    type Response = UpdateTransformOffsetsResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct UpdateTransformOffsetsResponse {}
+
+/// next offset of source partition to be processed by transform
+#[derive(Decoder, Encoder, Debug, Default, Clone, Eq, PartialEq)]
+pub struct TransformOffset {
+    pub name: String,
+    pub partition: PartitionId,
+    pub offset: Offset,
+}
+
+impl TransformOffset {
+    pub fn new(name: impl Into<String>, partition: PartitionId, offset: Offset) -> Self {
+        Self {
+            name: name.into(),
+            partition,
+            offset,
+        }
+    }
+}
@@ -12,6 +12,7 @@ use fluvio_protocol::derive::Decoder;
 use super::RegisterSpuRequest;
 use super::UpdateLrsRequest;
 use super::ReplicaRemovedRequest;
+use super::UpdateTransformOffsetsRequest;
 
 /// API call from Spu to SC
 
@@ -24,6 +25,7 @@ pub enum InternalScKey {
     RegisterSpu = 2000,
     UpdateLrs = 2001,
     ReplicaRemoved = 2002,
+    UpdateTransformOffsets = 2003,
 }
 
 /// Request made to Spu from Sc
@@ -35,6 +37,8 @@ pub enum InternalScRequest {
     UpdateLrsRequest(RequestMessage<UpdateLrsRequest>),
     #[fluvio(tag = 2)]
     ReplicaRemovedRequest(RequestMessage<ReplicaRemovedRequest>),
+    #[fluvio(tag = 3)]
+    UpdateTransformOffsetsRequest(RequestMessage<UpdateTransformOffsetsRequest>),
 }
 
 impl Default for InternalScRequest {
@@ -62,6 +66,14 @@ impl ApiMessage for InternalScRequest {
             InternalScKey::ReplicaRemoved => {
                 api_decode!(InternalScRequest, ReplicaRemovedRequest, src, header)
             }
+            InternalScKey::UpdateTransformOffsets => {
+                api_decode!(
+                    InternalScRequest,
+                    UpdateTransformOffsetsRequest,
+                    src,
+                    header
+                )
+            }
         }
     }
 }
@@ -12,6 +12,9 @@ use fluvio_protocol::Decoder;
 use super::UpdateSpuRequest;
 use super::UpdateReplicaRequest;
 use super::UpdateSmartModuleRequest;
+use super::UpdateDataPolicyRequest;
+use super::UpdateTransformRequest;
+use super::UpdateQuotaRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
@@ -21,6 +24,9 @@ pub enum InternalSpuApi {
     UpdateReplica = 1002,
     UpdateSmartModule = 1003,
     // UpdateDerivedStream = 1004,
+    UpdateDataPolicy = 1005,
+    UpdateTransform = 1006,
+    UpdateQuota = 1007,
 }
 
 impl Default for InternalSpuApi {
@@ -37,6 +43,12 @@ pub enum InternalSpuRequest {
     UpdateReplicaRequest(RequestMessage<UpdateReplicaRequest>),
     #[fluvio(tag = 2)]
     UpdateSmartModuleRequest(RequestMessage<UpdateSmartModuleRequest>),
+    #[fluvio(tag = 3)]
+    UpdateDataPolicyRequest(RequestMessage<UpdateDataPolicyRequest>),
+    #[fluvio(tag = 4)]
+    UpdateTransformRequest(RequestMessage<UpdateTransformRequest>),
+    #[fluvio(tag = 5)]
+    UpdateQuotaRequest(RequestMessage<UpdateQuotaRequest>),
 }
 
 // Added to satisfy Encoder/Decoder traits
@@ -67,6 +79,15 @@ impl ApiMessage for InternalSpuRequest {
             InternalSpuApi::UpdateSmartModule => {
                 api_decode!(Self, UpdateSmartModuleRequest, src, header)
             }
+            InternalSpuApi::UpdateDataPolicy => {
+                api_decode!(Self, UpdateDataPolicyRequest, src, header)
+            }
+            InternalSpuApi::UpdateTransform => {
+                api_decode!(Self, UpdateTransformRequest, src, header)
+            }
+            InternalSpuApi::UpdateQuota => {
+                api_decode!(Self, UpdateQuotaRequest, src, header)
+            }
         }
     }
 }
@@ -156,11 +156,11 @@ pub mod target {
                         .config()
                         // NOTE: This will not fallback to current cluster like it did before
                         // Current cluster will be used when no profile is given.
-                        .cluster_with_profile(&profile)
+                        .cluster_config_with_profile(&profile)
                         .ok_or_else(|| {
                             IoError::new(ErrorKind::Other, "Cluster not found for profile")
                         })?;
-                    Ok(cluster.clone())
+                    Ok(cluster)
                 }
                 (None, Some(cluster)) => {
                     let cluster = FluvioConfig::new(cluster).with_tls(tls);
@@ -177,8 +177,8 @@ pub mod target {
 
                     // Try to use the default cluster from saved config
                     let config_file = ConfigFile::load(None)?;
-                    let cluster = config_file.config().current_cluster()?;
-                    Ok(cluster.clone())
+                    let cluster = config_file.config().current_cluster_config()?;
+                    Ok(cluster)
                 }
             }
         }
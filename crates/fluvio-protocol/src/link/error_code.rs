@@ -45,6 +45,18 @@ pub enum ErrorCode {
     #[fluvio(tag = 13)]
     #[error("permission denied")]
     PermissionDenied,
+    #[fluvio(tag = 19)]
+    #[error("not enough replicas in sync with leader, {in_sync} of required {min}")]
+    NotEnoughInSyncReplicas { in_sync: u16, min: u16 },
+    #[fluvio(tag = 45)]
+    #[error("the producer sent a batch out of sequence")]
+    OutOfOrderSequenceNumber,
+    #[fluvio(tag = 47)]
+    #[error("the producer epoch is older than the current epoch")]
+    InvalidProducerEpoch,
+    #[fluvio(tag = 48)]
+    #[error("the producer has no open transaction to commit")]
+    InvalidTxnState,
     #[fluvio(tag = 56)]
     #[error("a storage error occurred")]
     StorageError,
@@ -54,6 +66,9 @@ pub enum ErrorCode {
     #[fluvio(tag = 61)]
     #[error("invalid Delete request")]
     InvalidDeleteRequest,
+    #[fluvio(tag = 62)]
+    #[error("the batch is corrupted, its CRC doesn't match content")]
+    CorruptBatch,
 
     // Spu errors
     #[fluvio(tag = 1000)]
@@ -102,6 +117,15 @@ pub enum ErrorCode {
     #[fluvio(tag = 3001)]
     #[error("the partition is not a leader")]
     PartitionNotLeader,
+    #[fluvio(tag = 3003)]
+    #[error("the partition was not found")]
+    PartitionNotFound,
+    #[fluvio(tag = 3004)]
+    #[error("the partition is already being reassigned")]
+    PartitionReassignmentInProgress,
+    #[fluvio(tag = 3005)]
+    #[error("the partition replica assignment is invalid")]
+    PartitionInvalidReplicas,
 
     // Stream Fetch error
     #[fluvio(tag = 3002)]
@@ -179,6 +203,45 @@ pub enum ErrorCode {
     #[fluvio(tag = 9000)]
     #[error("a compression error occurred in the SPU")]
     CompressionError,
+
+    // Consumer Group errors
+    #[fluvio(tag = 10000)]
+    #[error("the consumer group was not found")]
+    ConsumerGroupNotFound,
+    #[fluvio(tag = 10001)]
+    #[error("the member is not part of the consumer group")]
+    ConsumerGroupMemberNotFound,
+    #[fluvio(tag = 10002)]
+    #[error("the consumer group consumes a different topic")]
+    ConsumerGroupTopicMismatch,
+    #[fluvio(tag = 10003)]
+    #[error("the consumer group generation is not current")]
+    ConsumerGroupIllegalGeneration,
+    #[fluvio(tag = 10004)]
+    #[error("the partition is not assigned to the member")]
+    ConsumerGroupPartitionNotAssigned,
+
+    // Transform errors
+    #[fluvio(tag = 11000)]
+    #[error("a transform error occurred")]
+    TransformError,
+    #[fluvio(tag = 11001)]
+    #[error("the transform was not found")]
+    TransformNotFound,
+    #[fluvio(tag = 11002)]
+    #[error("the transform already exists")]
+    TransformAlreadyExists,
+
+    // Quota errors
+    #[fluvio(tag = 12000)]
+    #[error("a quota error occurred")]
+    QuotaError,
+    #[fluvio(tag = 12001)]
+    #[error("the quota was not found")]
+    QuotaNotFound,
+    #[fluvio(tag = 12002)]
+    #[error("the quota already exists")]
+    QuotaAlreadyExists,
 }
 
 impl ErrorCode {
@@ -250,7 +313,16 @@ mod test {
         );
         assert_tag!(ErrorCode::MessageTooLarge, 10, 0);
         assert_tag!(ErrorCode::PermissionDenied, 13, 0);
+        assert_tag!(
+            ErrorCode::NotEnoughInSyncReplicas { in_sync: 1, min: 2 },
+            19,
+            0
+        );
+        assert_tag!(ErrorCode::OutOfOrderSequenceNumber, 45, 0);
+        assert_tag!(ErrorCode::InvalidProducerEpoch, 47, 0);
+        assert_tag!(ErrorCode::InvalidTxnState, 48, 0);
         assert_tag!(ErrorCode::StorageError, 56, 0);
+        assert_tag!(ErrorCode::CorruptBatch, 62, 0);
 
         // Spu errors
         assert_tag!(ErrorCode::SpuError, 1000, 0);
@@ -270,9 +342,25 @@ mod test {
         // Partition errors
         assert_tag!(ErrorCode::PartitionPendingInitialization, 3000, 0);
         assert_tag!(ErrorCode::PartitionNotLeader, 3001, 0);
+        assert_tag!(ErrorCode::PartitionNotFound, 3003, 0);
+        assert_tag!(ErrorCode::PartitionReassignmentInProgress, 3004, 0);
+        assert_tag!(ErrorCode::PartitionInvalidReplicas, 3005, 0);
 
         // Stream Fetch error
         assert_tag!(ErrorCode::FetchSessionNotFoud, 3002, 0);
+
+        // Consumer Group errors
+        assert_tag!(ErrorCode::ConsumerGroupNotFound, 10000, 0);
+        assert_tag!(ErrorCode::ConsumerGroupMemberNotFound, 10001, 0);
+        assert_tag!(ErrorCode::ConsumerGroupTopicMismatch, 10002, 0);
+        assert_tag!(ErrorCode::ConsumerGroupIllegalGeneration, 10003, 0);
+        assert_tag!(ErrorCode::ConsumerGroupPartitionNotAssigned, 10004, 0);
+        assert_tag!(ErrorCode::TransformError, 11000, 0);
+        assert_tag!(ErrorCode::TransformNotFound, 11001, 0);
+        assert_tag!(ErrorCode::TransformAlreadyExists, 11002, 0);
+        assert_tag!(ErrorCode::QuotaError, 12000, 0);
+        assert_tag!(ErrorCode::QuotaNotFound, 12001, 0);
+        assert_tag!(ErrorCode::QuotaAlreadyExists, 12002, 0);
     }
 
     #[test]
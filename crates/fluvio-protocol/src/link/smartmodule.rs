@@ -20,6 +20,9 @@ pub struct SmartModuleTransformRuntimeError {
     pub record_key: Option<RecordData>,
     /// The Record value that caused this error
     pub record_value: RecordData,
+    /// Whether error was returned by SmartModule or execution exceeded limit
+    #[fluvio(min_version = 20)]
+    pub error_kind: SmartModuleRuntimeErrorKind,
 }
 
 impl SmartModuleTransformRuntimeError {
@@ -39,6 +42,22 @@ impl SmartModuleTransformRuntimeError {
             kind,
             record_key,
             record_value,
+            error_kind: SmartModuleRuntimeErrorKind::Transform,
+        }
+    }
+
+    /// error for execution stopped by exceeding resource limit
+    pub fn limit_exceeded(
+        base_offset: Offset,
+        kind: SmartModuleKind,
+        error_kind: SmartModuleRuntimeErrorKind,
+    ) -> Self {
+        Self {
+            hint: format!("SmartModule execution stopped: {error_kind}"),
+            offset: base_offset,
+            kind,
+            error_kind,
+            ..Default::default()
         }
     }
 }
@@ -87,6 +106,8 @@ pub enum SmartModuleKind {
     Join,
     #[fluvio(min_version = 17, tag = 6)]
     Generic,
+    #[fluvio(min_version = 22, tag = 7)]
+    Window,
 }
 
 impl Default for SmartModuleKind {
@@ -102,6 +123,45 @@ impl fmt::Display for SmartModuleKind {
     }
 }
 
+/// Cause of SmartModule runtime error
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Encoder, Decoder)]
+pub enum SmartModuleRuntimeErrorKind {
+    /// SmartModule returned error
+    #[fluvio(tag = 0)]
+    Transform,
+    /// fuel budget for invocation is used up
+    #[fluvio(tag = 1)]
+    FuelExhausted,
+    /// linear memory would grow over limit
+    #[fluvio(tag = 2)]
+    MemoryLimitExceeded,
+    /// table would grow over limit
+    #[fluvio(tag = 3)]
+    TableLimitExceeded,
+    /// invocation took longer than allowed
+    #[fluvio(tag = 4)]
+    Timeout,
+}
+
+impl Default for SmartModuleRuntimeErrorKind {
+    fn default() -> Self {
+        Self::Transform
+    }
+}
+
+impl fmt::Display for SmartModuleRuntimeErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let msg = match self {
+            Self::Transform => "transform error",
+            Self::FuelExhausted => "fuel exhausted",
+            Self::MemoryLimitExceeded => "memory limit exceeded",
+            Self::TableLimitExceeded => "table limit exceeded",
+            Self::Timeout => "timeout",
+        };
+        write!(f, "{msg}")
+    }
+}
+
 /// Deprecated. A type representing the possible errors that may occur during DerivedStream execution.
 #[derive(thiserror::Error, Debug, Clone, Eq, PartialEq, Encoder, Decoder)]
 pub enum LegacySmartModuleError {
@@ -146,3 +206,36 @@ impl fmt::Display for SmartModuleInitRuntimeError {
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use std::io::Cursor;
+
+    use crate::{Encoder, Decoder};
+
+    use super::{SmartModuleTransformRuntimeError, SmartModuleKind, SmartModuleRuntimeErrorKind};
+
+    #[test]
+    fn test_error_kind_versioned() {
+        let error = SmartModuleTransformRuntimeError::limit_exceeded(
+            10,
+            SmartModuleKind::Map,
+            SmartModuleRuntimeErrorKind::Timeout,
+        );
+
+        let mut bytes = vec![];
+        error.encode(&mut bytes, 20).expect("encode");
+        let decoded = SmartModuleTransformRuntimeError::decode_from(&mut Cursor::new(&bytes), 20)
+            .expect("decode");
+        assert_eq!(decoded, error);
+
+        // older clients don't know error kind
+        let mut bytes = vec![];
+        error.encode(&mut bytes, 19).expect("encode");
+        let decoded = SmartModuleTransformRuntimeError::decode_from(&mut Cursor::new(&bytes), 19)
+            .expect("decode");
+        assert_eq!(decoded.error_kind, SmartModuleRuntimeErrorKind::Transform);
+        assert_eq!(decoded.offset, 10);
+    }
+}
@@ -18,8 +18,13 @@ use super::ConsumerRecord;
 use super::Record;
 use super::Offset;
 use super::Size;
+use super::RECORD_HEADERS_VERSION;
 
 pub const COMPRESSION_CODEC_MASK: i16 = 0x07;
+/// batch is written by producer inside transaction
+pub const TRANSACTIONAL_FLAG_MASK: i16 = 0x10;
+/// batch holds transaction marker instead of records of producer
+pub const CONTROL_FLAG_MASK: i16 = 0x20;
 pub const NO_TIMESTAMP: i64 = -1;
 
 pub trait BatchRecords: Default + Debug + Encoder + Decoder + Send + Sync {
@@ -170,7 +175,7 @@ impl TryFrom<Batch<RawRecords>> for Batch {
         let records = batch.memory_records()?;
         Ok(Batch {
             base_offset: batch.base_offset,
-            batch_len: (BATCH_HEADER_SIZE + records.write_size(0)) as i32,
+            batch_len: (BATCH_HEADER_SIZE + records.write_size(RECORD_HEADERS_VERSION)) as i32,
             header: batch.header,
             records,
         })
@@ -180,20 +185,7 @@ impl TryFrom<Batch<RawRecords>> for Batch {
 impl TryFrom<Batch> for Batch<RawRecords> {
     type Error = CompressionError;
     fn try_from(f: Batch) -> Result<Self, Self::Error> {
-        let mut buf = Vec::new();
-        f.records.encode(&mut buf, 0)?;
-
-        let compression = f.get_compression()?;
-        let compressed_records = compression.compress(&buf)?;
-        let compressed_records_len = compressed_records.len() as i32;
-        let records = RawRecords(compressed_records);
-
-        Ok(Batch {
-            base_offset: f.base_offset,
-            batch_len: compressed_records_len,
-            header: f.header,
-            records,
-        })
+        f.try_into_raw(RECORD_HEADERS_VERSION)
     }
 }
 
@@ -231,15 +223,37 @@ impl Batch {
     /// add new record, this will update the offset to correct
     pub fn add_record(&mut self, record: Record) {
         self.add_records(&mut vec![record]);
-        self.batch_len = (BATCH_HEADER_SIZE + self.records.write_size(0)) as i32;
+        self.batch_len =
+            (BATCH_HEADER_SIZE + self.records.write_size(RECORD_HEADERS_VERSION)) as i32;
     }
 
     pub fn add_records(&mut self, records: &mut Vec<Record>) {
         self.records.append(records);
-        self.batch_len = (BATCH_HEADER_SIZE + self.records.write_size(0)) as i32;
+        self.batch_len =
+            (BATCH_HEADER_SIZE + self.records.write_size(RECORD_HEADERS_VERSION)) as i32;
         self.update_offset_deltas();
     }
 
+    /// encode records with given record version into raw batch.
+    /// Records encoded with version before [`RECORD_HEADERS_VERSION`] don't carry headers,
+    /// so they can be read by decoders which don't know headers.
+    pub fn try_into_raw(self, version: Version) -> Result<Batch<RawRecords>, CompressionError> {
+        let mut buf = Vec::new();
+        self.records.encode(&mut buf, version)?;
+
+        let compression = self.get_compression()?;
+        let compressed_records = compression.compress(&buf)?;
+        let compressed_records_len = compressed_records.len() as i32;
+        let records = RawRecords(compressed_records);
+
+        Ok(Batch {
+            base_offset: self.base_offset,
+            batch_len: compressed_records_len,
+            header: self.header,
+            records,
+        })
+    }
+
     pub fn update_offset_deltas(&mut self) {
         for (index, record) in self.records.iter_mut().enumerate() {
             record.preamble.set_offset_delta(index as Offset);
@@ -254,29 +268,55 @@ impl Batch {
         let base_offset = self.base_offset;
         let first_timestamp = self.header.first_timestamp;
 
-        self.records
-            .into_iter()
-            .enumerate()
-            .map(move |(relative, record)| ConsumerRecord {
-                partition,
-                offset: base_offset + relative as Offset,
-                timestamp_base: first_timestamp,
-                record,
-            })
+        self.records.into_iter().map(move |record| ConsumerRecord {
+            partition,
+            offset: base_offset + record.preamble.offset_delta(),
+            timestamp_base: first_timestamp,
+            record,
+        })
     }
 }
 impl Batch<RawRecords> {
+    /// check crc of header against batch content,
+    /// mismatch means batch was corrupted after it was encoded
+    pub fn validate_crc(&self) -> bool {
+        self.header.crc == self.header.compute_crc(&self.records.0)
+    }
+
+    /// drop records before offset, remaining records keep their offsets.
+    /// Batch is encoded again only if it starts before offset
+    pub fn skip_records_before(&mut self, offset: Offset) -> Result<(), CompressionError> {
+        if self.base_offset >= offset {
+            return Ok(());
+        }
+        let base_offset = self.base_offset;
+        let mut records = self.memory_records()?;
+        records.retain(|record| base_offset + record.preamble.offset_delta() >= offset);
+
+        let batch = Batch {
+            base_offset,
+            batch_len: 0,
+            header: self.header.clone(),
+            records,
+        };
+        let mut raw = batch.try_into_raw(RECORD_HEADERS_VERSION)?;
+        raw.batch_len = (BATCH_HEADER_SIZE + raw.records.0.len()) as i32;
+        raw.header.crc = raw.header.compute_crc(&raw.records.0);
+        *self = raw;
+        Ok(())
+    }
+
     pub fn memory_records(&self) -> Result<MemoryRecords, CompressionError> {
         let compression = self.get_compression()?;
 
         let mut records: MemoryRecords = Default::default();
         if let Compression::None = compression {
-            records.decode(&mut &self.records.0[..], 0)?;
+            records.decode(&mut &self.records.0[..], RECORD_HEADERS_VERSION)?;
         } else {
             let decompressed = compression
                 .uncompress(&self.records.0[..])?
                 .ok_or(CompressionError::UnreachableError)?;
-            records.decode(&mut &decompressed[..], 0)?;
+            records.decode(&mut &decompressed[..], RECORD_HEADERS_VERSION)?;
         }
         Ok(records)
     }
@@ -298,7 +338,8 @@ impl<T: Into<MemoryRecords>> From<T> for Batch {
 
         batch.records = records;
         let len = batch.records.len() as i32;
-        batch.batch_len = (BATCH_HEADER_SIZE + batch.records.write_size(0)) as i32;
+        batch.batch_len =
+            (BATCH_HEADER_SIZE + batch.records.write_size(RECORD_HEADERS_VERSION)) as i32;
         batch.header.last_offset_delta = if len > 0 { len - 1 } else { len };
         batch
     }
@@ -357,13 +398,7 @@ where
 
         let mut out: Vec<u8> = Vec::new();
         let buf = &mut out;
-        self.header.attributes.encode(buf, version)?;
-        self.header.last_offset_delta.encode(buf, version)?;
-        self.header.first_timestamp.encode(buf, version)?;
-        self.header.max_time_stamp.encode(buf, version)?;
-        self.header.producer_id.encode(buf, version)?;
-        self.header.producer_epoch.encode(buf, version)?;
-        self.header.first_sequence.encode(buf, version)?;
+        self.header.encode_crc_fields(buf, version)?;
         self.records.encode(buf, version)?;
 
         let crc = crc32c::crc32c(&out);
@@ -402,6 +437,26 @@ pub struct BatchHeader {
 }
 
 impl BatchHeader {
+    /// encode header fields covered by crc, they follow crc in encoded batch
+    fn encode_crc_fields<T: BufMut>(&self, dest: &mut T, version: Version) -> Result<(), Error> {
+        self.attributes.encode(dest, version)?;
+        self.last_offset_delta.encode(dest, version)?;
+        self.first_timestamp.encode(dest, version)?;
+        self.max_time_stamp.encode(dest, version)?;
+        self.producer_id.encode(dest, version)?;
+        self.producer_epoch.encode(dest, version)?;
+        self.first_sequence.encode(dest, version)?;
+        Ok(())
+    }
+
+    /// crc32c of header fields following crc and encoded records, same as computed by encoding
+    pub fn compute_crc(&self, records: &[u8]) -> u32 {
+        let mut fields = Vec::with_capacity(BATCH_HEADER_SIZE);
+        // encoding into vec never fails
+        let _ = self.encode_crc_fields(&mut fields, 0);
+        crc32c::crc32c_append(crc32c::crc32c(&fields), records)
+    }
+
     fn get_compression(&self) -> Result<Compression, CompressionError> {
         let compression_bits = self.attributes & COMPRESSION_CODEC_MASK;
         Compression::try_from(compression_bits as i8)
@@ -411,6 +466,30 @@ impl BatchHeader {
         let compression_bits = compression as i16 & COMPRESSION_CODEC_MASK;
         self.attributes = (self.attributes & !COMPRESSION_CODEC_MASK) | compression_bits;
     }
+
+    pub fn is_transactional(&self) -> bool {
+        self.attributes & TRANSACTIONAL_FLAG_MASK != 0
+    }
+
+    pub fn set_transactional(&mut self, transactional: bool) {
+        self.set_flag(TRANSACTIONAL_FLAG_MASK, transactional);
+    }
+
+    pub fn is_control(&self) -> bool {
+        self.attributes & CONTROL_FLAG_MASK != 0
+    }
+
+    pub fn set_control(&mut self, control: bool) {
+        self.set_flag(CONTROL_FLAG_MASK, control);
+    }
+
+    fn set_flag(&mut self, mask: i16, value: bool) {
+        if value {
+            self.attributes |= mask;
+        } else {
+            self.attributes &= !mask;
+        }
+    }
 }
 impl Default for BatchHeader {
     fn default() -> Self {
@@ -508,6 +587,64 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn test_validate_crc() -> Result<(), IoError> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        batch.add_record(Record::new("test"));
+        batch.header.first_timestamp = 1555478494747;
+        let bytes = batch.as_bytes(0)?;
+
+        let raw = Batch::<RawRecords>::decode_from(&mut Cursor::new(bytes.clone()), 0)?;
+        assert!(raw.validate_crc());
+
+        // flip bit in last byte of record value
+        let mut corrupted = bytes.to_vec();
+        let last = corrupted.len() - 1;
+        corrupted[last] ^= 0x01;
+        let raw = Batch::<RawRecords>::decode_from(&mut Cursor::new(corrupted.clone()), 0)?;
+        assert!(!raw.validate_crc());
+
+        // header fields after crc are covered too
+        let mut corrupted = bytes.to_vec();
+        corrupted[BATCH_FILE_HEADER_SIZE - 1] ^= 0x01;
+        let raw = Batch::<RawRecords>::decode_from(&mut Cursor::new(corrupted), 0)?;
+        assert!(!raw.validate_crc());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_skip_records_before() -> Result<(), IoError> {
+        let mut batch = Batch::<MemoryRecords>::default();
+        for value in ["a", "b", "c", "d"] {
+            batch.add_record(Record::new(value));
+        }
+        batch.set_base_offset(10);
+        let bytes = batch.as_bytes(0)?;
+        let mut raw = Batch::<RawRecords>::decode_from(&mut Cursor::new(bytes), 0)?;
+
+        raw.skip_records_before(12).expect("skip");
+        assert!(raw.validate_crc());
+        assert_eq!(raw.get_base_offset(), 10);
+        assert_eq!(raw.get_last_offset(), 13);
+        let records = raw.memory_records().expect("records");
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].value().as_ref(), b"c");
+        assert_eq!(10 + records[0].preamble.offset_delta(), 12);
+
+        // encoded batch decodes same as batch produced without skipped records
+        let bytes = raw.as_bytes(0)?;
+        let decoded = Batch::<RawRecords>::decode_from(&mut Cursor::new(bytes), 0)?;
+        assert!(decoded.validate_crc());
+        assert_eq!(decoded.memory_records().expect("records").len(), 2);
+
+        // batch starting at offset is kept as it is
+        raw.skip_records_before(10).expect("skip");
+        assert_eq!(raw.memory_records().expect("records").len(), 2);
+
+        Ok(())
+    }
+
     /*  raw batch encoded
 
     0000   02 00 00 00 45 00 00 c7 00 00 40 00 40 06 00 00
@@ -705,6 +842,26 @@ mod test {
         assert_eq!(batch_created.last_offset_delta(), 2);
     }
 
+    #[test]
+    fn test_transaction_flags() {
+        let mut header = BatchHeader::default();
+        header.set_compression(Compression::Gzip);
+        header.set_transactional(true);
+        assert!(header.is_transactional());
+        assert!(!header.is_control());
+
+        header.set_control(true);
+        header.set_transactional(false);
+        assert!(header.is_control());
+        assert!(!header.is_transactional());
+
+        // flags don't change compression
+        assert_eq!(
+            header.get_compression().expect("compression"),
+            Compression::Gzip
+        );
+    }
+
     #[test]
     fn test_into_consumer_records_iter() {
         let mut batch = Batch::from(vec![
@@ -730,6 +887,24 @@ mod test {
         });
     }
 
+    #[test]
+    fn test_raw_batch_headers() {
+        let batch = Batch::from(vec![Record::new("value").with_header("trace-id", "abc")]);
+
+        let raw: Batch<RawRecords> = batch.clone().try_into().expect("raw");
+        let records = raw.memory_records().expect("records");
+        assert_eq!(
+            records[0].header("trace-id").map(|value| value.as_ref()),
+            Some(b"abc".as_ref())
+        );
+
+        // batch for decoders before headers doesn't carry them
+        let raw = batch.try_into_raw(0).expect("raw");
+        let records = raw.memory_records().expect("records");
+        assert!(records[0].headers().is_empty());
+        assert_eq!(records[0].value.as_ref(), b"value");
+    }
+
     #[test]
     fn test_batch_len() {
         let mem_records = vec![Record::default(), Record::default(), Record::default()];
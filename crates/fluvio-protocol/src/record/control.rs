@@ -0,0 +1,197 @@
+use std::io::{Cursor, Error, ErrorKind};
+
+use crate::{Decoder, Encoder};
+
+use super::{Batch, BatchRecords, MemoryRecords, Record, RecordData, ReplicaKey};
+
+/// version of control record key
+pub const CONTROL_RECORD_VERSION: i16 = 0;
+
+/// Type of control record.
+/// Transaction is coordinated by first partition it writes to: partitions are registered
+/// at coordinator before they are written, marker written to coordinator is decision of
+/// transaction which is then written to all other partitions.
+#[repr(i16)]
+#[derive(Encoder, Decoder, Default, Debug, Clone, Copy, Eq, PartialEq)]
+#[fluvio(encode_discriminant)]
+pub enum ControlRecordType {
+    /// transaction is aborted
+    #[default]
+    Abort = 0,
+    /// transaction is committed
+    Commit = 1,
+    /// partitions joined transaction, value is [`TransactionPartitions`]
+    AddPartitions = 2,
+    /// decision of transaction is written to all its partitions
+    Complete = 3,
+}
+
+impl ControlRecordType {
+    /// marker which ends transaction in partition
+    pub fn is_marker(&self) -> bool {
+        matches!(self, Self::Abort | Self::Commit)
+    }
+}
+
+/// Key of record in control batch
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+pub struct ControlRecordKey {
+    pub version: i16,
+    pub record_type: ControlRecordType,
+}
+
+/// Value of control record registering partitions at coordinator of transaction
+#[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+pub struct TransactionPartitions {
+    pub partitions: Vec<ReplicaKey>,
+}
+
+impl Batch {
+    /// control batch with empty value, such as marker ending transaction of producer
+    pub fn control(producer_id: i64, producer_epoch: i16, record_type: ControlRecordType) -> Self {
+        Self::control_with_value(
+            producer_id,
+            producer_epoch,
+            record_type,
+            RecordData::default(),
+        )
+    }
+
+    /// control batch registering partitions at coordinator of transaction
+    pub fn add_partitions(
+        producer_id: i64,
+        producer_epoch: i16,
+        partitions: Vec<ReplicaKey>,
+    ) -> Self {
+        let mut value = Vec::new();
+        // encoding into vec never fails
+        let _ = TransactionPartitions { partitions }.encode(&mut value, 0);
+        Self::control_with_value(
+            producer_id,
+            producer_epoch,
+            ControlRecordType::AddPartitions,
+            value.into(),
+        )
+    }
+
+    fn control_with_value(
+        producer_id: i64,
+        producer_epoch: i16,
+        record_type: ControlRecordType,
+        value: RecordData,
+    ) -> Self {
+        let key = ControlRecordKey {
+            version: CONTROL_RECORD_VERSION,
+            record_type,
+        };
+        let mut key_bytes = Vec::new();
+        // encoding into vec never fails
+        let _ = key.encode(&mut key_bytes, 0);
+
+        let mut batch = Batch::from(vec![Record::new_key_value(key_bytes, value)]);
+        let header = batch.get_mut_header();
+        header.set_transactional(true);
+        header.set_control(true);
+        header.producer_id = producer_id;
+        header.producer_epoch = producer_epoch;
+        batch
+    }
+}
+
+impl<R: BatchRecords> Batch<R> {
+    /// type of marker in control batch, none if batch holds data.
+    /// Control batches are never compressed, so records are decoded as they are
+    pub fn control_type(&self) -> Result<Option<ControlRecordType>, Error> {
+        Ok(self.control_record()?.map(|(record_type, _)| record_type))
+    }
+
+    /// partitions registered by control batch, empty for other batches
+    pub fn transaction_partitions(&self) -> Result<Vec<ReplicaKey>, Error> {
+        match self.control_record()? {
+            Some((ControlRecordType::AddPartitions, value)) => {
+                let value =
+                    TransactionPartitions::decode_from(&mut Cursor::new(value.as_ref()), 0)?;
+                Ok(value.partitions)
+            }
+            _ => Ok(vec![]),
+        }
+    }
+
+    fn control_record(&self) -> Result<Option<(ControlRecordType, RecordData)>, Error> {
+        if !self.get_header().is_control() {
+            return Ok(None);
+        }
+
+        let mut bytes = Vec::new();
+        self.records().encode(&mut bytes, 0)?;
+        let records = MemoryRecords::decode_from(&mut Cursor::new(bytes), 0)?;
+        let record = records
+            .into_iter()
+            .next()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "control batch without record"))?;
+        let key = record
+            .key()
+            .ok_or_else(|| Error::new(ErrorKind::InvalidData, "control batch without key"))?;
+        let key = ControlRecordKey::decode_from(&mut Cursor::new(key.as_ref()), 0)?;
+        Ok(Some((key.record_type, record.value().clone())))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use crate::record::{Batch, RawRecords, Record, ReplicaKey};
+
+    use super::ControlRecordType;
+
+    #[test]
+    fn test_control_batch() {
+        let batch = Batch::control(5, 1, ControlRecordType::Commit);
+        let header = batch.get_header();
+        assert!(header.is_control());
+        assert!(header.is_transactional());
+        assert_eq!(header.producer_id, 5);
+        assert_eq!(header.producer_epoch, 1);
+        assert_eq!(header.first_sequence, -1);
+        assert_eq!(batch.records_len(), 1);
+        assert_eq!(
+            batch.control_type().expect("control"),
+            Some(ControlRecordType::Commit)
+        );
+
+        // type is same after batch is sent as raw records
+        let raw: Batch<RawRecords> = Batch::control(5, 1, ControlRecordType::Abort)
+            .try_into()
+            .expect("raw");
+        assert_eq!(
+            raw.control_type().expect("control"),
+            Some(ControlRecordType::Abort)
+        );
+
+        let data = Batch::from(vec![Record::new("value")]);
+        assert_eq!(data.control_type().expect("data"), None);
+        assert!(data.transaction_partitions().expect("data").is_empty());
+    }
+
+    #[test]
+    fn test_add_partitions_batch() {
+        let partitions = vec![
+            ReplicaKey::new("orders", 0u32),
+            ReplicaKey::new("payments", 2u32),
+        ];
+        let raw: Batch<RawRecords> = Batch::add_partitions(5, 1, partitions.clone())
+            .try_into()
+            .expect("raw");
+        assert!(raw.get_header().is_transactional());
+        let record_type = raw.control_type().expect("control").expect("type");
+        assert_eq!(record_type, ControlRecordType::AddPartitions);
+        assert!(!record_type.is_marker());
+        assert_eq!(
+            raw.transaction_partitions().expect("partitions"),
+            partitions
+        );
+
+        let marker = Batch::control(5, 1, ControlRecordType::Commit);
+        assert!(marker.transaction_partitions().expect("marker").is_empty());
+    }
+}
@@ -32,6 +32,11 @@ use super::Offset;
 use fluvio_compression::CompressionError;
 use fluvio_types::Timestamp;
 
+/// Record version from which headers are encoded as key/value pairs.
+/// Earlier versions encode only header count, which is always zero,
+/// so decoders before this version can't skip headers by themselves.
+pub const RECORD_HEADERS_VERSION: Version = 1;
+
 /// maximum text to display
 static MAX_STRING_DISPLAY: Lazy<usize> = Lazy::new(|| {
     let var_value = std::env::var("FLV_MAX_STRING_DISPLAY").unwrap_or_default();
@@ -406,7 +411,7 @@ pub struct Record<B = RecordData> {
     pub preamble: RecordHeader,
     pub key: Option<B>,
     pub value: B,
-    pub headers: i64,
+    pub headers: Vec<(String, Bytes)>,
 }
 
 impl<B: Default> Record<B> {
@@ -444,6 +449,38 @@ impl<B: Default> Record<B> {
     pub fn into_key(self) -> Option<B> {
         self.key
     }
+
+    /// Returns all headers in order they were added
+    pub fn headers(&self) -> &[(String, Bytes)] {
+        &self.headers
+    }
+
+    /// Returns value of first header with given key
+    pub fn header(&self, key: &str) -> Option<&Bytes> {
+        self.headers
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, value)| value)
+    }
+
+    /// add header, existing header with same key is not replaced
+    pub fn add_header<K, V>(&mut self, key: K, value: V)
+    where
+        K: Into<String>,
+        V: Into<Bytes>,
+    {
+        self.headers.push((key.into(), value.into()));
+    }
+
+    /// builder style version of [`Record::add_header`]
+    pub fn with_header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        K: Into<String>,
+        V: Into<Bytes>,
+    {
+        self.add_header(key, value);
+        self
+    }
 }
 
 impl Record {
@@ -504,7 +541,11 @@ where
         let inner_size = self.preamble.write_size(version)
             + self.key.write_size(version)
             + self.value.write_size(version)
-            + self.headers.var_write_size();
+            + if version >= RECORD_HEADERS_VERSION {
+                headers_write_size(&self.headers)
+            } else {
+                0i64.var_write_size()
+            };
         let len: i64 = inner_size as i64;
         len.var_write_size() + inner_size
     }
@@ -517,7 +558,12 @@ where
         self.preamble.encode(&mut out, version)?;
         self.key.encode(&mut out, version)?;
         self.value.encode(&mut out, version)?;
-        self.headers.encode_varint(&mut out)?;
+        if version >= RECORD_HEADERS_VERSION {
+            encode_headers(&self.headers, &mut out)?;
+        } else {
+            // older decoders only read header count
+            0i64.encode_varint(&mut out)?;
+        }
         let len: i64 = out.len() as i64;
         trace!("record encode as {} bytes", len);
         len.encode_varint(dest)?;
@@ -540,22 +586,122 @@ where
 
         trace!("record contains: {} bytes", len);
 
+        if len < 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                format!("invalid record len: {len}"),
+            ));
+        }
         if (src.remaining() as i64) < len {
             return Err(Error::new(
                 ErrorKind::UnexpectedEof,
                 "not enough for record",
             ));
         }
+        let end = src.remaining() - len as usize;
         self.preamble.decode(src, version)?;
         trace!("offset delta: {}", self.preamble.offset_delta);
         self.key.decode(src, version)?;
         self.value.decode(src, version)?;
-        self.headers.decode_varint(src)?;
+        if version >= RECORD_HEADERS_VERSION {
+            self.headers = decode_headers(src)?;
+        } else {
+            let mut count: i64 = 0;
+            count.decode_varint(src)?;
+            self.headers = vec![];
+        }
+
+        if src.remaining() < end {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "record content is longer than record len",
+            ));
+        }
+        // skip fields added by later versions, such as headers for older version
+        let unknown = src.remaining() - end;
+        if unknown > 0 {
+            trace!(unknown, "skipping unknown record content");
+            src.advance(unknown);
+        }
 
         Ok(())
     }
 }
 
+// From RECORD_HEADERS_VERSION, headers are encoded as varint count followed by each header
+// as varint length prefixed key and value. Records without headers are encoded as single
+// zero count, which is same as previous encoding where only count was written.
+fn headers_write_size(headers: &[(String, Bytes)]) -> usize {
+    let count = headers.len() as i64;
+    headers
+        .iter()
+        .fold(count.var_write_size(), |sum, (key, value)| {
+            sum + (key.len() as i64).var_write_size()
+                + key.len()
+                + (value.len() as i64).var_write_size()
+                + value.len()
+        })
+}
+
+fn encode_headers<T>(headers: &[(String, Bytes)], dest: &mut T) -> Result<(), Error>
+where
+    T: BufMut,
+{
+    let count = headers.len() as i64;
+    count.encode_varint(dest)?;
+    for (key, value) in headers {
+        (key.len() as i64).encode_varint(dest)?;
+        dest.put_slice(key.as_bytes());
+        (value.len() as i64).encode_varint(dest)?;
+        dest.put_slice(value);
+    }
+    Ok(())
+}
+
+fn decode_headers<T>(src: &mut T) -> Result<Vec<(String, Bytes)>, Error>
+where
+    T: Buf,
+{
+    let mut count: i64 = 0;
+    count.decode_varint(src)?;
+    trace!("record headers: {}", count);
+
+    // count is not trusted for allocation, each header takes at least one byte
+    let mut headers = Vec::with_capacity((count.max(0) as usize).min(src.remaining()));
+    for _ in 0..count {
+        let key = decode_header_bytes(src)?;
+        let key = String::from_utf8(key.to_vec()).map_err(|err| {
+            Error::new(
+                ErrorKind::InvalidData,
+                format!("record header key is not utf8: {err}"),
+            )
+        })?;
+        let value = decode_header_bytes(src)?;
+        headers.push((key, value));
+    }
+    Ok(headers)
+}
+
+fn decode_header_bytes<T>(src: &mut T) -> Result<Bytes, Error>
+where
+    T: Buf,
+{
+    let mut len: i64 = 0;
+    len.decode_varint(src)?;
+    // negative length is null
+    if len <= 0 {
+        return Ok(Bytes::new());
+    }
+    let len = len as usize;
+    if src.remaining() < len {
+        return Err(Error::new(
+            ErrorKind::UnexpectedEof,
+            "not enough for record header",
+        ));
+    }
+    Ok(src.copy_to_bytes(len))
+}
+
 /// Record that can be used by Consumer which needs access to metadata
 pub struct ConsumerRecord {
     /// The offset of this Record into its partition
@@ -610,6 +756,16 @@ impl ConsumerRecord {
         self.inner().value().as_ref()
     }
 
+    /// Returns the headers of this Record
+    pub fn headers(&self) -> &[(String, Bytes)] {
+        self.inner().headers()
+    }
+
+    /// Returns value of first header with given key
+    pub fn header(&self, key: &str) -> Option<&Bytes> {
+        self.inner().header(key)
+    }
+
     /// Return the timestamp of the Record
     pub fn timestamp(&self) -> Timestamp {
         if self.timestamp_base <= 0 {
@@ -719,6 +875,95 @@ mod test {
         assert_eq!(record.value.as_ref(), decoded.value.as_ref());
     }
 
+    #[test]
+    fn test_record_headers_encoding() {
+        let record = Record::new_key_value("key", "value")
+            .with_header("content-type", "application/json")
+            .with_header("schema-id", Bytes::from_static(&[0x0, 0x1]));
+
+        let mut encoded = Vec::new();
+        record.encode(&mut encoded, RECORD_HEADERS_VERSION).unwrap();
+        assert_eq!(encoded.len(), record.write_size(RECORD_HEADERS_VERSION));
+
+        let decoded =
+            Record::<RecordData>::decode_from(&mut Cursor::new(encoded), RECORD_HEADERS_VERSION)
+                .unwrap();
+        assert_eq!(decoded.headers().len(), 2);
+        assert_eq!(
+            decoded.header("content-type").map(|v| v.as_ref()),
+            Some(b"application/json".as_ref())
+        );
+        assert_eq!(
+            decoded.header("schema-id").map(|v| v.as_ref()),
+            Some([0x0, 0x1].as_ref())
+        );
+        assert!(decoded.header("trace-id").is_none());
+        assert_eq!(decoded.value.as_ref(), b"value");
+    }
+
+    #[test]
+    fn test_decode_headers_huge_count() {
+        let mut encoded = Vec::new();
+        (i32::MAX as i64).encode_varint(&mut encoded).unwrap();
+        encoded.extend_from_slice(&[0x2, b'k', 0x2, b'v']);
+
+        let err = decode_headers(&mut Cursor::new(encoded)).expect_err("not enough headers");
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_decode_headers_with_pre_headers_version() {
+        let records = vec![
+            Record::new("first").with_header("trace-id", "abc"),
+            Record::new("second"),
+        ];
+        let mut encoded = Vec::new();
+        records
+            .encode(&mut encoded, RECORD_HEADERS_VERSION)
+            .unwrap();
+
+        // decoder before headers reads only count and skips rest of record
+        let decoded = Vec::<Record>::decode_from(&mut Cursor::new(encoded), 0).unwrap();
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].value.as_ref(), b"first");
+        assert!(decoded[0].headers().is_empty());
+        assert_eq!(decoded[1].value.as_ref(), b"second");
+    }
+
+    #[test]
+    fn test_encode_headers_dropped_before_headers_version() {
+        let record = Record::new("dog").with_header("trace-id", "abc");
+        let encoded = record.as_bytes(0).expect("encode");
+        assert_eq!(encoded.len(), record.write_size(0));
+        assert_eq!(
+            encoded.as_ref(),
+            Record::new("dog").as_bytes(0).unwrap().as_ref()
+        );
+    }
+
+    #[test]
+    fn test_decode_record_longer_than_len() {
+        let mut encoded = Vec::new();
+        let record = Record::new("dog");
+        record.encode(&mut encoded, 0).unwrap();
+        // shrink declared len below content
+        encoded[0] = 0x10;
+        let err = Record::<RecordData>::decode_from(&mut Cursor::new(encoded), 0)
+            .expect_err("content longer than len");
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_record_without_headers_encoding_unchanged() {
+        let mut record = Record::new("dog");
+        record.preamble.set_offset_delta(1);
+        let encoded = record.as_bytes(0).expect("encode");
+        assert_eq!(
+            encoded.as_ref(),
+            [0x12, 0x0, 0x0, 0x2, 0x0, 0x6, 0x64, 0x6f, 0x67, 0x0]
+        );
+    }
+
     // Test Specification:
     //
     // A record was encoded and written to a file, using the following code:
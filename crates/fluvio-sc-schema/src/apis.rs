@@ -22,6 +22,15 @@ pub enum AdminPublicApiKey {
     Delete = 1002,
     List = 1003,
     Watch = 1004,
+    InitProducerId = 1005,
+    JoinGroup = 1006,
+    Heartbeat = 1007,
+    LeaveGroup = 1008,
+    CommitOffsets = 1009,
+    FetchCommittedOffsets = 1010,
+    Update = 1011,
+    ReassignPartition = 1012,
+    DrainSpu = 1013,
 }
 
 impl Default for AdminPublicApiKey {
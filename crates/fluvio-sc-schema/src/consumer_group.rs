@@ -0,0 +1,117 @@
+//!
+//! # Consumer Group
+//!
+//! Consumer group coordinator in SC assigns partitions of topic to group members.
+//! Members keep assignment alive with heartbeats. Assignment changes when members join,
+//! leave or miss heartbeats, which is tracked by generation.
+//! Committed offsets are stored in SC metadata so restarted consumers can resume.
+//!
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::Offset;
+use fluvio_types::PartitionId;
+
+use crate::errors::ErrorCode;
+use crate::AdminPublicApiKey;
+
+pub use fluvio_controlplane_metadata::consumergroup::ConsumerOffset;
+
+/// Join consumer group, existing member can rejoin with its member id
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct JoinGroupRequest {
+    pub group: String,
+    pub topic: String,
+    pub member_id: Option<String>,
+    /// member is removed from group if no heartbeat is received within timeout
+    pub session_timeout_ms: u32,
+}
+
+impl Request for JoinGroupRequest {
+    const API_KEY: u16 = AdminPublicApiKey::JoinGroup as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = GroupAssignmentResponse;
+}
+
+/// Keep membership alive and get current assignment
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct HeartbeatRequest {
+    pub group: String,
+    pub member_id: String,
+}
+
+impl Request for HeartbeatRequest {
+    const API_KEY: u16 = AdminPublicApiKey::Heartbeat as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = GroupAssignmentResponse;
+}
+
+/// Assignment of member in current generation
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct GroupAssignmentResponse {
+    pub error_code: ErrorCode,
+    pub member_id: String,
+    pub generation: i32,
+    pub partitions: Vec<PartitionId>,
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct LeaveGroupRequest {
+    pub group: String,
+    pub member_id: String,
+}
+
+impl Request for LeaveGroupRequest {
+    const API_KEY: u16 = AdminPublicApiKey::LeaveGroup as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = GroupResponse;
+}
+
+/// Commit offsets of partitions assigned to member.
+/// Commit is rejected if generation is not current
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct CommitOffsetsRequest {
+    pub group: String,
+    pub member_id: String,
+    pub generation: i32,
+    pub offsets: Vec<ConsumerOffset>,
+}
+
+impl Request for CommitOffsetsRequest {
+    const API_KEY: u16 = AdminPublicApiKey::CommitOffsets as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = GroupResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct GroupResponse {
+    pub error_code: ErrorCode,
+}
+
+/// Fetch committed offsets of group
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct FetchCommittedOffsetsRequest {
+    pub group: String,
+}
+
+impl Request for FetchCommittedOffsetsRequest {
+    const API_KEY: u16 = AdminPublicApiKey::FetchCommittedOffsets as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = FetchCommittedOffsetsResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct FetchCommittedOffsetsResponse {
+    pub error_code: ErrorCode,
+    pub offsets: Vec<ConsumerOffset>,
+}
+
+impl FetchCommittedOffsetsResponse {
+    /// committed offset of partition
+    pub fn offset(&self, partition: PartitionId) -> Option<Offset> {
+        self.offsets
+            .iter()
+            .find(|committed| committed.partition == partition)
+            .map(|committed| committed.offset)
+    }
+}
@@ -8,6 +8,11 @@ pub mod versions;
 pub mod objects;
 pub mod shared;
 pub mod tableformat;
+pub mod producer;
+pub mod consumer_group;
+pub mod reassignment;
+pub mod transform;
+pub mod quota;
 
 mod apis;
 mod request;
@@ -67,6 +72,18 @@ mod error {
                 ApiError::Code(ErrorCode::TableFormatNotFound, _) => {
                     write!(f, "TableFormat not found")
                 }
+                ApiError::Code(ErrorCode::TransformAlreadyExists, _) => {
+                    write!(f, "Transform already exists")
+                }
+                ApiError::Code(ErrorCode::TransformNotFound, _) => {
+                    write!(f, "Transform not found")
+                }
+                ApiError::Code(ErrorCode::QuotaAlreadyExists, _) => {
+                    write!(f, "Quota already exists")
+                }
+                ApiError::Code(ErrorCode::QuotaNotFound, _) => {
+                    write!(f, "Quota not found")
+                }
                 ApiError::Code(_, Some(msg)) => {
                     write!(f, "{msg}")
                 }
@@ -121,6 +138,12 @@ mod admin {
         type DeleteKey: Encoder + Decoder + Debug + Default;
     }
 
+    /// Admin Object which can be changed in place
+    pub trait UpdatableAdminSpec: Spec + Encoder + Decoder {
+        type UpdateKey: Encoder + Decoder + Debug + Default;
+        type UpdateAction: Encoder + Decoder + Debug + Default;
+    }
+
     /// try to encode type object into dynamic type which can be downcast later
     pub trait TryEncodableFrom<T>: Sized + Encoder + Decoder {
         fn try_encode_from(value: T, version: Version) -> Result<Self>;
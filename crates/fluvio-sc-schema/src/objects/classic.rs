@@ -426,6 +426,8 @@ mod create {
     use crate::smartmodule::SmartModuleSpec;
     use crate::tableformat::TableFormatSpec;
     use crate::spg::SpuGroupSpec;
+    use crate::transform::TransformSpec;
+    use crate::quota::QuotaSpec;
 
     #[derive(Debug, Default, Encoder, Decoder)]
     pub struct ClassicObjectApiCreateRequest {
@@ -653,4 +655,10 @@ mod create {
             }
         }
     }
+
+    // transform is not supported by classic protocol
+    impl ClassicCreatableAdminSpec for TransformSpec {}
+
+    // quota is not supported by classic protocol
+    impl ClassicCreatableAdminSpec for QuotaSpec {}
 }
@@ -0,0 +1,29 @@
+//!
+//! # Producer Id
+//!
+//! Producer ids are assigned by SC to idempotent producers.
+//! SPU uses producer id, epoch and batch sequence to detect duplicate batches.
+//!
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+
+use crate::errors::ErrorCode;
+use crate::AdminPublicApiKey;
+
+/// Request new producer id from SC
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct InitProducerIdRequest {}
+
+impl Request for InitProducerIdRequest {
+    const API_KEY: u16 = AdminPublicApiKey::InitProducerId as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = InitProducerIdResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct InitProducerIdResponse {
+    pub error_code: ErrorCode,
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+}
@@ -0,0 +1,17 @@
+pub use fluvio_controlplane_metadata::quota::*;
+
+mod convert {
+
+    use crate::{DeletableAdminSpec, CreatableAdminSpec};
+
+    use crate::{AdminSpec};
+    use super::QuotaSpec;
+
+    impl AdminSpec for QuotaSpec {}
+
+    impl CreatableAdminSpec for QuotaSpec {}
+
+    impl DeletableAdminSpec for QuotaSpec {
+        type DeleteKey = String;
+    }
+}
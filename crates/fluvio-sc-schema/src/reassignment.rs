@@ -0,0 +1,39 @@
+//!
+//! # Partition Reassignment
+//!
+//! Moves replicas of partition to different SPUs. Target replicas are added first and
+//! catch up with leader, then leadership moves to target if needed and old replicas are removed.
+//! Draining SPU reassigns every partition hosted by SPU to other SPUs.
+//!
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::api::Request;
+use fluvio_types::{PartitionId, SpuId};
+
+use crate::{AdminPublicApiKey, Status};
+
+/// Move replicas of partition to target SPUs, first SPU becomes leader
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct ReassignPartitionRequest {
+    pub topic: String,
+    pub partition: PartitionId,
+    pub replicas: Vec<SpuId>,
+}
+
+impl Request for ReassignPartitionRequest {
+    const API_KEY: u16 = AdminPublicApiKey::ReassignPartition as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = Status;
+}
+
+/// Reassign all replicas hosted by SPU to other online SPUs
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct DrainSpuRequest {
+    pub spu: SpuId,
+}
+
+impl Request for DrainSpuRequest {
+    const API_KEY: u16 = AdminPublicApiKey::DrainSpu as u16;
+    const DEFAULT_API_VERSION: i16 = 0;
+    type Response = Status;
+}
@@ -17,8 +17,15 @@ use fluvio_protocol::core::{Decoder};
 use fluvio_protocol::link::versions::ApiVersionsRequest;
 
 use crate::AdminPublicApiKey;
+use crate::producer::InitProducerIdRequest;
+use crate::consumer_group::{
+    CommitOffsetsRequest, FetchCommittedOffsetsRequest, HeartbeatRequest, JoinGroupRequest,
+    LeaveGroupRequest,
+};
+use crate::reassignment::{DrainSpuRequest, ReassignPartitionRequest};
 use crate::objects::{
-    ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiWatchRequest,
+    ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
+    ObjectApiWatchRequest,
 };
 
 /// Non generic AdminRequest, This is typically used Decoding
@@ -29,6 +36,15 @@ pub enum AdminPublicDecodedRequest {
     DeleteRequest(RequestMessage<ObjectApiDeleteRequest>),
     ListRequest(RequestMessage<ObjectApiListRequest>),
     WatchRequest(RequestMessage<ObjectApiWatchRequest>),
+    InitProducerIdRequest(RequestMessage<InitProducerIdRequest>),
+    JoinGroupRequest(RequestMessage<JoinGroupRequest>),
+    HeartbeatRequest(RequestMessage<HeartbeatRequest>),
+    LeaveGroupRequest(RequestMessage<LeaveGroupRequest>),
+    CommitOffsetsRequest(RequestMessage<CommitOffsetsRequest>),
+    FetchCommittedOffsetsRequest(RequestMessage<FetchCommittedOffsetsRequest>),
+    UpdateRequest(RequestMessage<ObjectApiUpdateRequest>),
+    ReassignPartitionRequest(RequestMessage<ReassignPartitionRequest>),
+    DrainSpuRequest(RequestMessage<DrainSpuRequest>),
 }
 
 impl Default for AdminPublicDecodedRequest {
@@ -81,6 +97,27 @@ impl ApiMessage for AdminPublicDecodedRequest {
                 header,
                 ObjectApiWatchRequest::decode_from(src, version)?,
             ))),
+
+            AdminPublicApiKey::InitProducerId => {
+                api_decode!(Self, InitProducerIdRequest, src, header)
+            }
+            AdminPublicApiKey::JoinGroup => api_decode!(Self, JoinGroupRequest, src, header),
+            AdminPublicApiKey::Heartbeat => api_decode!(Self, HeartbeatRequest, src, header),
+            AdminPublicApiKey::LeaveGroup => api_decode!(Self, LeaveGroupRequest, src, header),
+            AdminPublicApiKey::CommitOffsets => {
+                api_decode!(Self, CommitOffsetsRequest, src, header)
+            }
+            AdminPublicApiKey::FetchCommittedOffsets => {
+                api_decode!(Self, FetchCommittedOffsetsRequest, src, header)
+            }
+            AdminPublicApiKey::Update => Ok(Self::UpdateRequest(RequestMessage::new(
+                header,
+                ObjectApiUpdateRequest::decode_from(src, version)?,
+            ))),
+            AdminPublicApiKey::ReassignPartition => {
+                api_decode!(Self, ReassignPartitionRequest, src, header)
+            }
+            AdminPublicApiKey::DrainSpu => api_decode!(Self, DrainSpuRequest, src, header),
         }
     }
 }
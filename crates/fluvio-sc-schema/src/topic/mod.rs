@@ -11,13 +11,123 @@ pub mod validate {
     }
 }
 
+pub use update::*;
+
+mod update {
+    use fluvio_protocol::{Encoder, Decoder};
+
+    use fluvio_types::PartitionCount;
+
+    use super::{CleanupPolicy, CompressionAlgorithm, TopicSpec, TopicStorageConfig};
+
+    /// Changes to configuration of existing topic.
+    /// Only values which are set are changed.
+    #[derive(Encoder, Decoder, Default, Debug, Clone, Eq, PartialEq)]
+    pub struct TopicUpdate {
+        pub cleanup_policy: Option<CleanupPolicy>,
+        pub storage: Option<TopicStorageConfig>,
+        pub compression_type: Option<CompressionAlgorithm>,
+        /// new partition count, can only be increased
+        pub partitions: Option<PartitionCount>,
+        #[fluvio(min_version = 12)]
+        pub min_in_sync_replicas: Option<u16>,
+    }
+
+    impl TopicUpdate {
+        /// true if there is nothing to change
+        pub fn is_empty(&self) -> bool {
+            self == &Self::default()
+        }
+
+        /// apply changes to topic spec, fails if change is not allowed
+        pub fn apply(self, spec: &mut TopicSpec) -> Result<(), String> {
+            if let Some(policy) = self.cleanup_policy {
+                spec.set_cleanup_policy(policy);
+            }
+            if let Some(storage) = self.storage {
+                match spec.get_storage_mut() {
+                    Some(current) => {
+                        if storage.segment_size.is_some() {
+                            current.segment_size = storage.segment_size;
+                        }
+                        if storage.max_partition_size.is_some() {
+                            current.max_partition_size = storage.max_partition_size;
+                        }
+                    }
+                    None => spec.set_storage(storage),
+                }
+            }
+            if let Some(compression_type) = self.compression_type {
+                spec.set_compression_type(compression_type);
+            }
+            if let Some(partitions) = self.partitions {
+                spec.set_partitions(partitions)?;
+            }
+            if let Some(min_in_sync_replicas) = self.min_in_sync_replicas {
+                spec.set_min_in_sync_replicas(min_in_sync_replicas);
+            }
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod test {
+        use super::*;
+        use crate::topic::SegmentBasedPolicy;
+
+        #[test]
+        fn test_apply_topic_update() {
+            let mut spec = TopicSpec::new_computed(1, 1, None);
+            spec.set_storage(TopicStorageConfig {
+                segment_size: Some(2000),
+                max_partition_size: Some(10000),
+            });
+
+            TopicUpdate {
+                cleanup_policy: Some(CleanupPolicy::Segment(SegmentBasedPolicy {
+                    time_in_seconds: 3600,
+                })),
+                storage: Some(TopicStorageConfig {
+                    segment_size: None,
+                    max_partition_size: Some(20000),
+                }),
+                compression_type: Some(CompressionAlgorithm::Gzip),
+                partitions: Some(3),
+                min_in_sync_replicas: Some(1),
+            }
+            .apply(&mut spec)
+            .expect("apply");
+
+            assert_eq!(spec.retention_secs(), 3600);
+            assert_eq!(
+                spec.get_storage(),
+                Some(&TopicStorageConfig {
+                    segment_size: Some(2000),
+                    max_partition_size: Some(20000),
+                })
+            );
+            assert_eq!(spec.get_compression_type(), &CompressionAlgorithm::Gzip);
+            assert_eq!(spec.partitions(), 3);
+            assert_eq!(spec.get_min_in_sync_replicas(), Some(1));
+
+            let decrease = TopicUpdate {
+                partitions: Some(2),
+                ..Default::default()
+            };
+            assert!(decrease.apply(&mut spec).is_err());
+            assert_eq!(spec.partitions(), 3);
+        }
+    }
+}
+
 mod convert {
 
     use crate::CreatableAdminSpec;
     use crate::DeletableAdminSpec;
+    use crate::UpdatableAdminSpec;
     use crate::{AdminSpec};
 
-    use super::TopicSpec;
+    use super::{TopicSpec, TopicUpdate};
 
     impl AdminSpec for TopicSpec {}
 
@@ -26,4 +136,9 @@ mod convert {
     impl DeletableAdminSpec for TopicSpec {
         type DeleteKey = String;
     }
+
+    impl UpdatableAdminSpec for TopicSpec {
+        type UpdateKey = String;
+        type UpdateAction = TopicUpdate;
+    }
 }
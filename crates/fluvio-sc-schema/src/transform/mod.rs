@@ -0,0 +1,17 @@
+pub use fluvio_controlplane_metadata::transform::*;
+
+mod convert {
+
+    use crate::{DeletableAdminSpec, CreatableAdminSpec};
+
+    use crate::{AdminSpec};
+    use super::TransformSpec;
+
+    impl AdminSpec for TransformSpec {}
+
+    impl CreatableAdminSpec for TransformSpec {}
+
+    impl DeletableAdminSpec for TransformSpec {
+        type DeleteKey = String;
+    }
+}
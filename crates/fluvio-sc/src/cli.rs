@@ -22,12 +22,17 @@ use fluvio_types::print_cli_err;
 use k8_client::K8Config;
 use fluvio_future::openssl::TlsAcceptor;
 use fluvio_future::openssl::SslVerifyMode;
+use fluvio_auth::DataPolicy;
 
+use crate::services::auth::AuthPolicy;
 use crate::services::auth::basic::BasicRbacPolicy;
+use crate::services::auth::resource::ResourceRbacPolicy;
 use crate::error::ScError;
 use crate::config::ScConfig;
 
-type Config = (ScConfig, Option<BasicRbacPolicy>);
+type Config = (ScConfig, Option<AuthPolicy>);
+
+const DEFAULT_NAMESPACE: &str = "default";
 
 /// cli options
 #[derive(Debug, Parser, Default)]
@@ -49,6 +54,10 @@ pub struct ScOpt {
     #[arg(short = 'n', long = "namespace", value_name = "namespace")]
     namespace: Option<String>,
 
+    /// store metadata in local files instead of kubernetes
+    #[arg(long, value_name = "path")]
+    metadata_dir: Option<PathBuf>,
+
     #[clap(flatten)]
     tls: TlsConfig,
 
@@ -66,6 +75,31 @@ pub struct ScOpt {
     )]
     auth_policy: Option<PathBuf>,
 
+    /// policy with permissions scoped to resource names
+    #[arg(
+        long = "resource-authorization-policy",
+        value_name = "resource authorization policy path",
+        env,
+        conflicts_with = "auth_policy"
+    )]
+    resource_auth_policy: Option<PathBuf>,
+
+    /// policy for producing and consuming topics, enforced by SPUs
+    #[arg(
+        long = "data-authorization-policy",
+        value_name = "data authorization policy path",
+        env
+    )]
+    data_policy: Option<PathBuf>,
+
+    /// users allowed to authenticate with password (SCRAM-SHA-256)
+    #[arg(long = "sasl-credentials", value_name = "sasl credentials path", env)]
+    sasl_credentials: Option<PathBuf>,
+
+    /// secret used to verify signed tokens
+    #[arg(long = "token-secret", value_name = "token secret path", env)]
+    token_secret: Option<PathBuf>,
+
     /// only allow white list of controllers
     #[arg(long)]
     white_list: Vec<String>,
@@ -81,6 +115,10 @@ impl ScOpt {
         self.local
     }
 
+    pub fn metadata_dir(&self) -> Option<&PathBuf> {
+        self.metadata_dir.as_ref()
+    }
+
     #[allow(clippy::type_complexity)]
     fn get_sc_and_k8_config(
         mut self,
@@ -116,16 +154,23 @@ impl ScOpt {
 
         config.namespace = self.namespace.unwrap();
         config.x509_auth_scopes = self.x509_auth_scopes;
+        config.sasl_credentials = self.sasl_credentials;
+        config.token_secret = self.token_secret;
         config.white_list = self.white_list.into_iter().collect();
 
         // Set Configuration Authorzation Policy
-        let policy = match self.auth_policy {
+        let policy = match (self.auth_policy, self.resource_auth_policy) {
             // Lookup a policy from a path
-            Some(p) => Some(BasicRbacPolicy::try_from(p)?),
+            (Some(p), _) => Some(AuthPolicy::Basic(BasicRbacPolicy::try_from(p)?)),
+            (None, Some(p)) => Some(AuthPolicy::Resource(ResourceRbacPolicy::try_from(p)?)),
             // Use root-only default policy if no policy path is found;
-            None => None,
+            (None, None) => None,
         };
 
+        if let Some(path) = self.data_policy {
+            config.data_policy = Some(DataPolicy::try_from(path)?);
+        }
+
         let mut tls = self.tls;
 
         // if tls is on, we need to assign public service(internal) to another port
@@ -151,6 +196,20 @@ impl ScOpt {
         }
     }
 
+    /// configuration when metadata is stored locally, no k8 config is needed
+    pub fn parse_local_or_exit(mut self) -> (Config, Option<(String, TlsConfig)>) {
+        if self.namespace.is_none() {
+            self.namespace = Some(DEFAULT_NAMESPACE.to_owned());
+        }
+        match self.as_sc_config() {
+            Err(err) => {
+                print_cli_err!(err);
+                process::exit(-1);
+            }
+            Ok(config) => config,
+        }
+    }
+
     pub fn parse_cli_or_exit(self) -> (Config, K8Config, Option<(String, TlsConfig)>) {
         match self.get_sc_and_k8_config() {
             Err(err) => {
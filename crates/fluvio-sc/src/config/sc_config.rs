@@ -8,6 +8,7 @@ use std::{io::Error as IoError, path::PathBuf};
 
 use fluvio_types::defaults::SC_PUBLIC_PORT;
 use fluvio_types::defaults::SC_PRIVATE_PORT;
+use fluvio_auth::DataPolicy;
 
 // -----------------------------------
 // Traits
@@ -26,7 +27,13 @@ pub struct ScConfig {
     pub run_k8_dispatchers: bool,
     pub namespace: String,
     pub x509_auth_scopes: Option<PathBuf>,
+    /// users allowed to authenticate with password
+    pub sasl_credentials: Option<PathBuf>,
+    /// secret for verifying signed tokens
+    pub token_secret: Option<PathBuf>,
     pub white_list: HashSet<String>,
+    /// produce/consume policy distributed to SPUs, SPUs allow everything if not set
+    pub data_policy: Option<DataPolicy>,
 }
 
 impl ::std::default::Default for ScConfig {
@@ -37,7 +44,10 @@ impl ::std::default::Default for ScConfig {
             run_k8_dispatchers: true,
             namespace: "default".to_owned(),
             x509_auth_scopes: None,
+            sasl_credentials: None,
+            token_secret: None,
             white_list: HashSet::new(),
+            data_policy: None,
         }
     }
 }
@@ -100,17 +100,18 @@ where
             return;
         }
 
-        // we only care about delete timestamp changes which are in metadata only
-        let changes = listener.sync_meta_changes().await;
+        // delete timestamp changes are in metadata, reassignment progress is reported in status
+        let changes = listener.sync_changes().await;
         if changes.is_empty() {
-            debug!("no partition metadata changes");
+            debug!("no partition changes");
             return;
         }
 
         let (updates, _) = changes.parts();
-        trace!(meta_changes = &*format!("{updates:#?}"), "metadata changes");
+        trace!(changes = &*format!("{updates:#?}"), "partition changes");
 
-        let actions = self.reducer.process_partition_update(updates).await;
+        let mut actions = self.reducer.process_partition_update(updates.clone()).await;
+        actions.extend(self.reducer.update_reassignments(updates).await);
 
         debug!("generated partition actions: {}", actions.len());
         for action in actions.into_iter() {
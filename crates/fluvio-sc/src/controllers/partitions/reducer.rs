@@ -112,6 +112,46 @@ where
             .collect()
     }
 
+    /// advance partition reassignments in progress, one step per change.
+    /// replicas removed from partition are also removed from status
+    #[instrument(skip(self, updates))]
+    pub async fn update_reassignments(
+        &self,
+        updates: Vec<PartitionMetadata<C>>,
+    ) -> Vec<PartitionWSAction<C>> {
+        let mut actions = vec![];
+        for partition in updates {
+            if partition.status.is_being_deleted {
+                continue;
+            }
+            if let Some(next_spec) = partition.spec.next_reassignment_step(&partition.status) {
+                if next_spec.reassignment.is_none() {
+                    info!(
+                        partition = %partition.key(),
+                        replicas = ?next_spec.replicas,
+                        "reassignment complete",
+                    );
+                    let mut status = partition.status.clone();
+                    status
+                        .replicas
+                        .retain(|replica| next_spec.replicas.contains(&replica.spu));
+                    actions.push(PartitionWSAction::UpdateStatus((
+                        partition.key.clone(),
+                        status,
+                    )));
+                } else {
+                    info!(
+                        partition = %partition.key(),
+                        leader = next_spec.leader,
+                        "target replicas in sync, changing leader",
+                    );
+                }
+                actions.push(PartitionWSAction::UpdateSpec((partition.key, next_spec)));
+            }
+        }
+        actions
+    }
+
     ///
     /// based on spu change, update election
     ///
@@ -230,6 +270,7 @@ where
                     // switch leader if online leader is different
                     for replica_status in partition_kv.status.replica_iter() {
                         if replica_status.spu == online_leader_spu_id
+                            && partition_kv.status.is_in_sync(online_leader_spu_id)
                             && policy
                                 .potential_leader_score(replica_status, &partition_kv.status.leader)
                                 .is_suitable()
@@ -9,7 +9,7 @@ use tracing::instrument;
 use fluvio_future::task::spawn;
 
 use crate::core::SharedContext;
-use crate::stores::topic::TopicSpec;
+use crate::stores::topic::{TopicSpec, TopicAdminMd, ReplicaSpec};
 use crate::stores::partition::PartitionSpec;
 use crate::stores::{StoreContext, K8ChangeListener};
 
@@ -51,6 +51,7 @@ impl TopicController {
         debug!("starting dispatch loop");
 
         let mut listener = self.topics.change_listener();
+        let mut partition_listener = self.partitions.change_listener();
 
         loop {
             self.sync_topics(&mut listener).await;
@@ -64,6 +65,10 @@ impl TopicController {
                 _ = listener.listen() => {
                     debug!("detected topic changes");
 
+                },
+                _ = partition_listener.listen() => {
+                    debug!("detected partition changes");
+                    self.sync_added_partitions(&mut partition_listener).await;
                 }
             }
         }
@@ -85,7 +90,41 @@ impl TopicController {
 
         let (updates, _) = changes.parts();
 
-        let actions = self.reducer.process_requests(updates).await;
+        self.process_topics(updates).await;
+    }
+
+    /// partitions added to topic are published once their leaders are online,
+    /// so topics which are adding partitions are processed again when partitions change
+    #[instrument(skip(self, listener))]
+    async fn sync_added_partitions(&mut self, listener: &mut K8ChangeListener<PartitionSpec>) {
+        if listener.sync_changes().await.is_empty() {
+            return;
+        }
+
+        let topics: Vec<_> = self
+            .topics
+            .store()
+            .clone_values()
+            .await
+            .into_iter()
+            .filter(|topic| match topic.spec.replicas() {
+                ReplicaSpec::Computed(param) => {
+                    topic.status.is_resolution_provisioned()
+                        && param.partitions as usize > topic.status.replica_map.len()
+                }
+                ReplicaSpec::Assigned(_) => false,
+            })
+            .collect();
+
+        if topics.is_empty() {
+            return;
+        }
+        debug!(topics = topics.len(), "topics adding partitions");
+        self.process_topics(topics).await;
+    }
+
+    async fn process_topics(&mut self, topics: Vec<TopicAdminMd>) {
+        let actions = self.reducer.process_requests(topics).await;
 
         if actions.topics.is_empty() && actions.partitions.is_empty() {
             debug!("no actions needed");
@@ -196,7 +196,49 @@ impl TopicNextState {
                     );
                     let mut next_state = TopicNextState::same_next_state(topic);
                     if next_state.resolution == TopicResolution::Provisioned {
-                        next_state.partitions = topic.create_new_partitions(partition_store).await;
+                        // clients use replica map as partition count, so added partitions are
+                        // only published in it once their leaders are online
+                        let mut updated_topic = topic.clone();
+                        if param.partitions as usize > topic.status.replica_map.len() {
+                            match added_partitions(topic, param, partition_store).await {
+                                Some((replica_map, true)) => {
+                                    debug!(
+                                        "topic: {} added partitions are online, count: {}",
+                                        topic.key(),
+                                        param.partitions
+                                    );
+                                    next_state.replica_map = replica_map;
+                                }
+                                Some((_, false)) => {
+                                    debug!(
+                                        "topic: {} waiting for leaders of added partitions",
+                                        topic.key()
+                                    );
+                                }
+                                None => match generate_replica_map_for_new_partitions(
+                                    spu_store,
+                                    param,
+                                    &topic.status.replica_map,
+                                )
+                                .await
+                                {
+                                    Ok(replica_map) => {
+                                        debug!(
+                                            "topic: {} adding partitions, count: {}",
+                                            topic.key(),
+                                            param.partitions
+                                        );
+                                        updated_topic.status.set_replica_map(replica_map);
+                                    }
+                                    Err(reason) => next_state.reason = reason,
+                                },
+                            }
+                        }
+                        next_state.partitions =
+                            updated_topic.create_new_partitions(partition_store).await;
+                        next_state
+                            .partitions
+                            .extend(topic.update_partition_configs(partition_store).await);
                     }
                     next_state
                 }
@@ -223,6 +265,9 @@ impl TopicNextState {
                     let mut next_state = TopicNextState::same_next_state(topic);
                     if next_state.resolution == TopicResolution::Provisioned {
                         next_state.partitions = topic.create_new_partitions(partition_store).await;
+                        next_state
+                            .partitions
+                            .extend(topic.update_partition_configs(partition_store).await);
                     }
                     next_state
                 }
@@ -231,6 +276,61 @@ impl TopicNextState {
     }
 }
 
+///
+/// Replica map of topic including partitions added beyond its replica map, and whether
+/// all added partitions have online leader. None if some added partition is not created yet
+///
+async fn added_partitions(
+    topic: &TopicAdminMd,
+    param: &TopicReplicaParam,
+    partition_store: &PartitionAdminStore,
+) -> Option<(ReplicaMap, bool)> {
+    let mut replica_map = topic.status.replica_map.clone();
+    let mut online = true;
+    for idx in replica_map.len() as PartitionId..param.partitions {
+        let partition = partition_store
+            .value(&ReplicaKey::new(topic.key(), idx))
+            .await?;
+        online &= partition.status.is_online();
+        replica_map.insert(idx, partition.spec.replicas.clone());
+    }
+    Some((replica_map, online))
+}
+
+///
+/// Generate replica map for partitions added to provisioned topic
+///  * existing partitions keep their replicas
+///  * new partitions continue round robin of existing partitions
+///
+#[instrument(level = "trace", skip(spus, param, replica_map))]
+pub async fn generate_replica_map_for_new_partitions(
+    spus: &SpuAdminStore,
+    param: &TopicReplicaParam,
+    replica_map: &ReplicaMap,
+) -> Result<ReplicaMap, String> {
+    let spu_count = spus.count().await as ReplicationFactor;
+    if spu_count < param.replication_factor {
+        return Err(format!(
+            "need {} more SPU to add partitions",
+            param.replication_factor - spu_count
+        ));
+    }
+
+    // partition 0 is assigned to spu at start index
+    let spu_ids = spus.spu_ids().await;
+    let from_index = replica_map
+        .get(&0)
+        .and_then(|replicas| replicas.first())
+        .and_then(|leader| spu_ids.iter().position(|id| id == leader))
+        .map(|index| index as u32);
+
+    let mut next_map = replica_map.clone();
+    for (partition, replicas) in generate_replica_map_for_topic(spus, param, from_index).await {
+        next_map.entry(partition).or_insert(replicas);
+    }
+    Ok(next_map)
+}
+
 ///
 /// Generate replica map for a specific topic
 ///
@@ -341,6 +441,88 @@ pub mod replica_map_test {
         assert_eq!(map_1xi, map_1xi_expected);
     }
 
+    #[fluvio_future::test]
+    async fn generate_replica_map_for_new_partitions_no_rack() {
+        let spus = SpuAdminStore::quick(vec![
+            (0, true, None),
+            (1, true, None),
+            (2, true, None),
+            (4, true, None),
+            (5000, true, None),
+        ]);
+
+        // existing 2 partitions, 1 replica - index 3
+        let mut current = BTreeMap::new();
+        current.insert(0, vec![4]);
+        current.insert(1, vec![5000]);
+
+        let param = (4, 1, false).into();
+        let next_map = generate_replica_map_for_new_partitions(&spus, &param, &current)
+            .await
+            .expect("replica map");
+        let mut expected = BTreeMap::new();
+        expected.insert(0, vec![4]);
+        expected.insert(1, vec![5000]);
+        expected.insert(2, vec![0]);
+        expected.insert(3, vec![1]);
+        assert_eq!(next_map, expected);
+
+        // not enough spus for replication
+        let param = (4, 6, false).into();
+        assert!(
+            generate_replica_map_for_new_partitions(&spus, &param, &current)
+                .await
+                .is_err()
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn added_partitions_published_when_online() {
+        let topic = TopicAdminMd::new(
+            "topic1",
+            (2, 1).into(),
+            TopicStatus::new(TopicResolution::Provisioned, vec![vec![0]], ""),
+        );
+        let param = (2, 1, false).into();
+        let partition = |idx: PartitionId, resolution: PartitionResolution| {
+            PartitionAdminMd::new(
+                ReplicaKey::new("topic1", idx),
+                vec![idx as SpuId].into(),
+                PartitionStatus::new2((idx as SpuId, 0, 0), vec![], 0, resolution),
+            )
+        };
+
+        // added partition is not created yet
+        let partitions =
+            PartitionAdminStore::bulk_new(vec![partition(0, PartitionResolution::Online)]);
+        assert!(added_partitions(&topic, &param, &partitions)
+            .await
+            .is_none());
+
+        let mut expected = BTreeMap::new();
+        expected.insert(0, vec![0]);
+        expected.insert(1, vec![1]);
+
+        // added partition has no leader yet
+        let partitions = PartitionAdminStore::bulk_new(vec![
+            partition(0, PartitionResolution::Online),
+            partition(1, PartitionResolution::Offline),
+        ]);
+        assert_eq!(
+            added_partitions(&topic, &param, &partitions).await,
+            Some((expected.clone(), false))
+        );
+
+        let partitions = PartitionAdminStore::bulk_new(vec![
+            partition(0, PartitionResolution::Online),
+            partition(1, PartitionResolution::Online),
+        ]);
+        assert_eq!(
+            added_partitions(&topic, &param, &partitions).await,
+            Some((expected, true))
+        );
+    }
+
     #[fluvio_future::test]
     async fn generate_replica_map_for_topic_2x_replicas_no_rack() {
         let spus = SpuAdminStore::quick(vec![
@@ -4,14 +4,20 @@
 //! Metadata stores a copy of the data from KV store in local memory.
 //!
 use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::ScConfig;
+use crate::core::group_coordinator::GroupCoordinator;
 use crate::stores::spu::*;
 use crate::stores::partition::*;
 use crate::stores::topic::*;
 use crate::stores::spg::*;
 use crate::stores::smartmodule::*;
 use crate::stores::tableformat::*;
+use crate::stores::consumergroup::*;
+use crate::stores::transform::*;
+use crate::stores::quota::*;
 use crate::stores::*;
 
 pub type SharedContext = Arc<Context>;
@@ -26,8 +32,13 @@ pub struct Context {
     spgs: StoreContext<SpuGroupSpec>,
     smartmodules: StoreContext<SmartModuleSpec>,
     tableformats: StoreContext<TableFormatSpec>,
+    consumergroups: StoreContext<ConsumerGroupSpec>,
+    transforms: StoreContext<TransformSpec>,
+    quotas: StoreContext<QuotaSpec>,
     health: SharedHealthCheck,
     config: ScConfig,
+    producer_id: AtomicI64,
+    group_coordinator: GroupCoordinator,
 }
 
 // -----------------------------------
@@ -48,8 +59,13 @@ impl Context {
             spgs: StoreContext::new(),
             smartmodules: StoreContext::new(),
             tableformats: StoreContext::new(),
+            consumergroups: StoreContext::new(),
+            transforms: StoreContext::new(),
+            quotas: StoreContext::new(),
             health: HealthCheck::shared(),
             config,
+            producer_id: AtomicI64::new(0),
+            group_coordinator: GroupCoordinator::default(),
         }
     }
 
@@ -80,6 +96,23 @@ impl Context {
         &self.tableformats
     }
 
+    pub fn consumergroups(&self) -> &StoreContext<ConsumerGroupSpec> {
+        &self.consumergroups
+    }
+
+    pub fn transforms(&self) -> &StoreContext<TransformSpec> {
+        &self.transforms
+    }
+
+    pub fn quotas(&self) -> &StoreContext<QuotaSpec> {
+        &self.quotas
+    }
+
+    /// membership and partition assignment of consumer groups
+    pub fn group_coordinator(&self) -> &GroupCoordinator {
+        &self.group_coordinator
+    }
+
     /// spu health channel
     pub fn health(&self) -> &SharedHealthCheck {
         &self.health
@@ -93,4 +126,41 @@ impl Context {
     pub fn namespace(&self) -> &str {
         &self.config.namespace
     }
+
+    /// assign new producer id for idempotent producer.
+    /// Ids are not persisted. Instead each id is current time in micros and never runs ahead
+    /// of clock, so ids assigned after SC restart are always greater than ones assigned before.
+    pub fn next_producer_id(&self) -> i64 {
+        loop {
+            let last = self.producer_id.load(Ordering::SeqCst);
+            let now = now_micros();
+            let next = if now > last {
+                now
+            } else if last - now < MAX_CLOCK_WAIT_MICROS {
+                // more than one id requested in same micro, wait for clock
+                std::hint::spin_loop();
+                continue;
+            } else {
+                // clock went backward, keep ids increasing
+                last + 1
+            };
+            if self
+                .producer_id
+                .compare_exchange(last, next, Ordering::SeqCst, Ordering::SeqCst)
+                .is_ok()
+            {
+                return next;
+            }
+        }
+    }
+}
+
+/// how far ahead of clock last producer id can be before waiting is given up
+const MAX_CLOCK_WAIT_MICROS: i64 = 1000;
+
+fn now_micros() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_micros() as i64)
+        .unwrap_or_default()
 }
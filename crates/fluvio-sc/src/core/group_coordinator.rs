@@ -0,0 +1,442 @@
+//!
+//! # Consumer Group Coordinator
+//!
+//! Keeps membership of consumer groups and assigns partitions to members.
+//! Membership is kept in memory only, members rejoin after SC restart.
+//! Committed offsets are stored in `ConsumerGroupSpec`. Commits are validated and staged
+//! together under group lock, then staged offsets of all members are written at once.
+//!
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, Instant};
+
+use async_lock::{Mutex, MutexGuard};
+use tracing::debug;
+
+use fluvio_controlplane_metadata::consumergroup::ConsumerGroupStatus;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_types::{PartitionCount, PartitionId};
+
+/// session timeout used when member doesn't specify one
+pub const DEFAULT_SESSION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Partitions assigned to member in generation
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Assignment {
+    pub member_id: String,
+    pub generation: i32,
+    pub partitions: Vec<PartitionId>,
+    pub status: ConsumerGroupStatus,
+}
+
+#[derive(Debug)]
+struct Member {
+    session_timeout: Duration,
+    last_seen: Instant,
+}
+
+impl Member {
+    fn is_expired(&self, now: Instant) -> bool {
+        now.duration_since(self.last_seen) > self.session_timeout
+    }
+}
+
+#[derive(Debug)]
+struct GroupState {
+    topic: String,
+    generation: i32,
+    partition_count: PartitionCount,
+    members: BTreeMap<String, Member>,
+    assignments: HashMap<String, Vec<PartitionId>>,
+    /// offsets committed by members but not yet written to spec
+    staged: BTreeMap<PartitionId, i64>,
+}
+
+impl GroupState {
+    fn new(topic: String) -> Self {
+        Self {
+            topic,
+            generation: 0,
+            partition_count: 0,
+            members: BTreeMap::new(),
+            assignments: HashMap::new(),
+            staged: BTreeMap::new(),
+        }
+    }
+
+    /// remove expired members and reassign partitions if group has changed
+    fn rebalance(&mut self, partition_count: PartitionCount, now: Instant) {
+        let before = self.members.len();
+        self.members.retain(|_, member| !member.is_expired(now));
+        let expired = before - self.members.len();
+
+        if expired > 0
+            || partition_count != self.partition_count
+            || self.assignments.len() != self.members.len()
+        {
+            self.partition_count = partition_count;
+            self.reassign();
+        }
+    }
+
+    /// assign partitions round robin to members sorted by id and start new generation
+    fn reassign(&mut self) {
+        self.generation += 1;
+        self.assignments = self
+            .members
+            .keys()
+            .map(|member_id| (member_id.clone(), vec![]))
+            .collect();
+
+        let member_ids: Vec<&String> = self.members.keys().collect();
+        if !member_ids.is_empty() {
+            for partition in 0..self.partition_count {
+                let member_id = member_ids[partition as usize % member_ids.len()];
+                if let Some(partitions) = self.assignments.get_mut(member_id) {
+                    partitions.push(partition);
+                }
+            }
+        }
+        debug!(
+            topic = %self.topic,
+            generation = self.generation,
+            members = self.members.len(),
+            "group rebalanced"
+        );
+    }
+
+    fn assignment(&self, member_id: &str) -> Result<Assignment, ErrorCode> {
+        let partitions = self
+            .assignments
+            .get(member_id)
+            .ok_or(ErrorCode::ConsumerGroupMemberNotFound)?;
+        Ok(Assignment {
+            member_id: member_id.to_owned(),
+            generation: self.generation,
+            partitions: partitions.clone(),
+            status: self.status(),
+        })
+    }
+
+    fn status(&self) -> ConsumerGroupStatus {
+        ConsumerGroupStatus {
+            generation: self.generation,
+            members: self.members.len() as u32,
+        }
+    }
+
+    fn join(
+        &mut self,
+        group: &str,
+        member_id: Option<String>,
+        session_timeout: Duration,
+        partition_count: PartitionCount,
+        now: Instant,
+    ) -> Result<Assignment, ErrorCode> {
+        let member_id =
+            member_id.unwrap_or_else(|| format!("{}-{:016x}", group, rand::random::<u64>()));
+        self.members.insert(
+            member_id.clone(),
+            Member {
+                session_timeout,
+                last_seen: now,
+            },
+        );
+        self.rebalance(partition_count, now);
+        self.assignment(&member_id)
+    }
+
+    fn heartbeat(
+        &mut self,
+        member_id: &str,
+        partition_count: PartitionCount,
+        now: Instant,
+    ) -> Result<Assignment, ErrorCode> {
+        self.rebalance(partition_count, now);
+        let member = self
+            .members
+            .get_mut(member_id)
+            .ok_or(ErrorCode::ConsumerGroupMemberNotFound)?;
+        member.last_seen = now;
+        self.assignment(member_id)
+    }
+
+    fn leave(&mut self, member_id: &str) -> Result<ConsumerGroupStatus, ErrorCode> {
+        self.members
+            .remove(member_id)
+            .ok_or(ErrorCode::ConsumerGroupMemberNotFound)?;
+        self.assignments.remove(member_id);
+        self.reassign();
+        Ok(self.status())
+    }
+
+    fn validate_commit(
+        &self,
+        member_id: &str,
+        generation: i32,
+        partitions: &[PartitionId],
+    ) -> Result<(), ErrorCode> {
+        if !self.members.contains_key(member_id) {
+            return Err(ErrorCode::ConsumerGroupMemberNotFound);
+        }
+        if generation != self.generation {
+            return Err(ErrorCode::ConsumerGroupIllegalGeneration);
+        }
+        let assigned = self
+            .assignments
+            .get(member_id)
+            .ok_or(ErrorCode::ConsumerGroupMemberNotFound)?;
+        if partitions
+            .iter()
+            .any(|partition| !assigned.contains(partition))
+        {
+            return Err(ErrorCode::ConsumerGroupPartitionNotAssigned);
+        }
+        Ok(())
+    }
+
+    /// stage offsets if member is allowed to commit them in generation
+    fn stage_commit(
+        &mut self,
+        member_id: &str,
+        generation: i32,
+        offsets: &[(PartitionId, i64)],
+    ) -> Result<(), ErrorCode> {
+        let partitions: Vec<PartitionId> =
+            offsets.iter().map(|(partition, _)| *partition).collect();
+        self.validate_commit(member_id, generation, &partitions)?;
+        self.staged.extend(offsets.iter().copied());
+        Ok(())
+    }
+}
+
+/// Tracks members of all consumer groups
+#[derive(Debug, Default)]
+pub struct GroupCoordinator {
+    groups: Mutex<HashMap<String, GroupState>>,
+    commit_lock: Mutex<()>,
+}
+
+impl GroupCoordinator {
+    /// add member to group. new member id is generated if not provided
+    pub async fn join(
+        &self,
+        group: &str,
+        topic: &str,
+        member_id: Option<String>,
+        session_timeout: Duration,
+        partition_count: PartitionCount,
+    ) -> Result<Assignment, ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let state = groups
+            .entry(group.to_owned())
+            .or_insert_with(|| GroupState::new(topic.to_owned()));
+        if state.topic != topic {
+            return Err(ErrorCode::ConsumerGroupTopicMismatch);
+        }
+        state.join(
+            group,
+            member_id,
+            session_timeout,
+            partition_count,
+            Instant::now(),
+        )
+    }
+
+    /// refresh member session and return its current assignment
+    pub async fn heartbeat(
+        &self,
+        group: &str,
+        member_id: &str,
+        partition_count: PartitionCount,
+    ) -> Result<Assignment, ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let state = groups
+            .get_mut(group)
+            .ok_or(ErrorCode::ConsumerGroupNotFound)?;
+        state.heartbeat(member_id, partition_count, Instant::now())
+    }
+
+    /// remove member from group, remaining members get new assignment
+    pub async fn leave(
+        &self,
+        group: &str,
+        member_id: &str,
+    ) -> Result<ConsumerGroupStatus, ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let state = groups
+            .get_mut(group)
+            .ok_or(ErrorCode::ConsumerGroupNotFound)?;
+        state.leave(member_id)
+    }
+
+    /// stage offsets to be written by next flush. Only partitions assigned to member in
+    /// current generation can be committed, which is checked under same lock as rebalance
+    pub async fn stage_commit(
+        &self,
+        group: &str,
+        member_id: &str,
+        generation: i32,
+        offsets: &[(PartitionId, i64)],
+    ) -> Result<(), ErrorCode> {
+        let mut groups = self.groups.lock().await;
+        let state = groups
+            .get_mut(group)
+            .ok_or(ErrorCode::ConsumerGroupNotFound)?;
+        state.stage_commit(member_id, generation, offsets)
+    }
+
+    /// serializes flushes of staged offsets to consumer group spec, so commits staged
+    /// while previous flush was written are written together by next one
+    pub async fn lock_commits(&self) -> MutexGuard<'_, ()> {
+        self.commit_lock.lock().await
+    }
+
+    /// take offsets staged since last flush
+    pub async fn take_staged(&self, group: &str) -> BTreeMap<PartitionId, i64> {
+        let mut groups = self.groups.lock().await;
+        groups
+            .get_mut(group)
+            .map(|state| std::mem::take(&mut state.staged))
+            .unwrap_or_default()
+    }
+
+    /// put back offsets of failed flush, offsets staged since take precedence
+    pub async fn restage(&self, group: &str, offsets: BTreeMap<PartitionId, i64>) {
+        let mut groups = self.groups.lock().await;
+        if let Some(state) = groups.get_mut(group) {
+            for (partition, offset) in offsets {
+                state.staged.entry(partition).or_insert(offset);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::{Duration, Instant};
+
+    use fluvio_protocol::link::ErrorCode;
+
+    use super::GroupState;
+
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn test_partitions_assigned_round_robin() {
+        let now = Instant::now();
+        let mut state = GroupState::new("topic".to_owned());
+
+        let first = state
+            .join("g", Some("a".to_owned()), TIMEOUT, 3, now)
+            .expect("join");
+        assert_eq!(first.generation, 1);
+        assert_eq!(first.partitions, vec![0, 1, 2]);
+
+        let second = state
+            .join("g", Some("b".to_owned()), TIMEOUT, 3, now)
+            .expect("join");
+        assert_eq!(second.generation, 2);
+        assert_eq!(second.partitions, vec![1]);
+
+        let first = state.heartbeat("a", 3, now).expect("heartbeat");
+        assert_eq!(first.generation, 2);
+        assert_eq!(first.partitions, vec![0, 2]);
+
+        // rejoin of existing member doesn't change assignment
+        let first = state
+            .join("g", Some("a".to_owned()), TIMEOUT, 3, now)
+            .expect("join");
+        assert_eq!(first.generation, 2);
+
+        // partition count change triggers rebalance
+        let first = state.heartbeat("a", 4, now).expect("heartbeat");
+        assert_eq!(first.generation, 3);
+        assert_eq!(first.partitions, vec![0, 2]);
+        let second = state.heartbeat("b", 4, now).expect("heartbeat");
+        assert_eq!(second.partitions, vec![1, 3]);
+    }
+
+    #[test]
+    fn test_expired_member_is_removed() {
+        let now = Instant::now();
+        let mut state = GroupState::new("topic".to_owned());
+        state
+            .join("g", Some("a".to_owned()), TIMEOUT, 2, now)
+            .expect("join");
+        state
+            .join("g", Some("b".to_owned()), TIMEOUT, 2, now)
+            .expect("join");
+
+        let later = now + Duration::from_secs(8);
+        state.heartbeat("a", 2, later).expect("heartbeat");
+
+        let expired = now + Duration::from_secs(12);
+        let first = state.heartbeat("a", 2, expired).expect("heartbeat");
+        assert_eq!(first.generation, 3);
+        assert_eq!(first.partitions, vec![0, 1]);
+        assert_eq!(
+            state.heartbeat("b", 2, expired),
+            Err(ErrorCode::ConsumerGroupMemberNotFound)
+        );
+    }
+
+    #[test]
+    fn test_commit_generation() {
+        let now = Instant::now();
+        let mut state = GroupState::new("topic".to_owned());
+        let first = state
+            .join("g", Some("a".to_owned()), TIMEOUT, 2, now)
+            .expect("join");
+        assert!(state
+            .validate_commit("a", first.generation, &[0, 1])
+            .is_ok());
+
+        state
+            .join("g", Some("b".to_owned()), TIMEOUT, 2, now)
+            .expect("join");
+        assert_eq!(
+            state.validate_commit("a", first.generation, &[0]),
+            Err(ErrorCode::ConsumerGroupIllegalGeneration)
+        );
+
+        // partition 1 moved to second member
+        assert_eq!(
+            state.validate_commit("a", first.generation + 1, &[0, 1]),
+            Err(ErrorCode::ConsumerGroupPartitionNotAssigned)
+        );
+        assert!(state
+            .validate_commit("a", first.generation + 1, &[0])
+            .is_ok());
+
+        state.leave("b").expect("leave");
+        assert_eq!(
+            state.validate_commit("b", 3, &[]),
+            Err(ErrorCode::ConsumerGroupMemberNotFound)
+        );
+    }
+
+    #[test]
+    fn test_stage_commit() {
+        let now = Instant::now();
+        let mut state = GroupState::new("topic".to_owned());
+        let first = state
+            .join("g", Some("a".to_owned()), TIMEOUT, 2, now)
+            .expect("join");
+
+        state
+            .stage_commit("a", first.generation, &[(0, 10), (1, 5)])
+            .expect("stage");
+        state
+            .stage_commit("a", first.generation, &[(0, 12)])
+            .expect("stage");
+        assert_eq!(
+            state.stage_commit("a", first.generation - 1, &[(1, 8)]),
+            Err(ErrorCode::ConsumerGroupIllegalGeneration)
+        );
+        assert_eq!(
+            state.staged.clone().into_iter().collect::<Vec<_>>(),
+            vec![(0, 12), (1, 5)]
+        );
+    }
+}
@@ -1,2 +1,3 @@
 mod context;
+pub mod group_coordinator;
 pub use self::context::*;
@@ -16,11 +16,11 @@ use crate::controllers::partitions::PartitionController;
 use crate::config::{ScConfig};
 use crate::services::start_internal_server;
 use crate::dispatcher::dispatcher::K8ClusterStateDispatcher;
-use crate::services::auth::basic::BasicRbacPolicy;
+use crate::services::auth::AuthPolicy;
 
 /// start the main loop
 pub async fn start_main_loop<C>(
-    sc_config_policy: (ScConfig, Option<BasicRbacPolicy>),
+    sc_config_policy: (ScConfig, Option<AuthPolicy>),
     metadata_client: SharedClient<C>,
 ) -> SharedContext
 where
@@ -32,6 +32,9 @@ where
     use crate::stores::spg::SpuGroupSpec;
     use crate::stores::tableformat::TableFormatSpec;
     use crate::stores::smartmodule::SmartModuleSpec;
+    use crate::stores::consumergroup::ConsumerGroupSpec;
+    use crate::stores::transform::TransformSpec;
+    use crate::stores::quota::QuotaSpec;
 
     let (sc_config, auth_policy) = sc_config_policy;
 
@@ -70,9 +73,27 @@ where
     );
 
     K8ClusterStateDispatcher::<SmartModuleSpec, C>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.smartmodules().clone(),
+    );
+
+    K8ClusterStateDispatcher::<ConsumerGroupSpec, C>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.consumergroups().clone(),
+    );
+
+    K8ClusterStateDispatcher::<TransformSpec, C>::start(
+        namespace.clone(),
+        metadata_client.clone(),
+        ctx.transforms().clone(),
+    );
+
+    K8ClusterStateDispatcher::<QuotaSpec, C>::start(
         namespace,
         metadata_client,
-        ctx.smartmodules().clone(),
+        ctx.quotas().clone(),
     );
 
     whitelist!(config, "spu", SpuController::start(ctx.clone()));
@@ -95,25 +116,49 @@ where
         use std::sync::Arc;
         use tracing::info;
 
+        use fluvio_auth::sasl::SaslAuthenticator;
+
         use crate::services::start_public_server;
         use crate::core::SharedContext;
 
-        use crate::services::auth::{AuthGlobalContext, RootAuthorization};
-        use crate::services::auth::basic::{BasicAuthorization, BasicRbacPolicy};
-
-        pub fn start(ctx: SharedContext, auth_policy_option: Option<BasicRbacPolicy>) {
-            if let Some(policy) = auth_policy_option {
-                info!("using basic authorization");
-                start_public_server(AuthGlobalContext::new(
-                    ctx,
-                    Arc::new(BasicAuthorization::new(policy)),
-                ));
-            } else {
-                info!("using root authorization");
-                start_public_server(AuthGlobalContext::new(
-                    ctx,
-                    Arc::new(RootAuthorization::new()),
-                ));
+        use crate::services::auth::{AuthGlobalContext, AuthPolicy, RootAuthorization};
+        use crate::services::auth::basic::BasicAuthorization;
+        use crate::services::auth::resource::ResourceAuthorization;
+
+        pub fn start(ctx: SharedContext, auth_policy_option: Option<AuthPolicy>) {
+            let config = ctx.config();
+            let sasl = SaslAuthenticator::load(
+                config.sasl_credentials.clone(),
+                config.token_secret.clone(),
+            )
+            .expect("unable to load sasl authenticator")
+            .map(|sasl| sasl.with_x509_proxy(config.x509_auth_scopes.is_some()));
+            if let Some(sasl) = &sasl {
+                info!(mechanisms = ?sasl.mechanisms(), "sasl authentication enabled");
+            }
+
+            match auth_policy_option {
+                Some(AuthPolicy::Basic(policy)) => {
+                    info!("using basic authorization");
+                    start_public_server(AuthGlobalContext::new(
+                        ctx,
+                        Arc::new(BasicAuthorization::new(policy).with_sasl(sasl)),
+                    ));
+                }
+                Some(AuthPolicy::Resource(policy)) => {
+                    info!("using resource authorization");
+                    start_public_server(AuthGlobalContext::new(
+                        ctx,
+                        Arc::new(ResourceAuthorization::new(policy).with_sasl(sasl)),
+                    ));
+                }
+                None => {
+                    info!("using root authorization");
+                    start_public_server(AuthGlobalContext::new(
+                        ctx,
+                        Arc::new(RootAuthorization::new().with_sasl(sasl)),
+                    ));
+                }
             }
         }
     }
@@ -12,6 +12,8 @@ mod migration;
 #[cfg(test)]
 mod fixture;
 
+use std::path::PathBuf;
+
 use k8_client::new_shared;
 use tracing::{info, error};
 
@@ -26,6 +28,10 @@ pub fn main_k8_loop(opt: ScOpt) {
     use crate::init::start_main_loop;
     use controllers::run_k8_operators;
 
+    if let Some(metadata_dir) = opt.metadata_dir().cloned() {
+        return main_local_metadata_loop(opt, metadata_dir);
+    }
+
     // parse configuration (program exits on error)
     let is_local = opt.is_local();
     println!("CLI Option: {opt:#?}");
@@ -76,6 +82,49 @@ pub fn main_k8_loop(opt: ScOpt) {
     });
 }
 
+/// run SC with metadata stored in local files, no kubernetes is needed
+fn main_local_metadata_loop(opt: ScOpt, metadata_dir: PathBuf) {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use fluvio_future::task::run_block_on;
+    use fluvio_future::timer::sleep;
+
+    use crate::dispatcher::metadata::LocalMetadataClient;
+    use crate::init::start_main_loop;
+
+    println!("CLI Option: {opt:#?}");
+    let ((sc_config, auth_policy), tls_option) = opt.parse_local_or_exit();
+
+    println!("Starting SC, platform: {}", crate::VERSION);
+
+    inspect_system();
+
+    run_block_on(async move {
+        info!(dir = %metadata_dir.display(), "initializing local metadata");
+        let metadata_client = Arc::new(
+            LocalMetadataClient::open(&metadata_dir).expect("problem opening local metadata"),
+        );
+
+        info!("starting main loop");
+        start_main_loop((sc_config.clone(), auth_policy), metadata_client).await;
+
+        if let Some((proxy_port, tls_config)) = tls_option {
+            let tls_acceptor = tls_config
+                .try_build_tls_acceptor()
+                .expect("can't build tls acceptor");
+            proxy::start_proxy(sc_config, (tls_acceptor, proxy_port)).await;
+        }
+
+        println!("Streaming Controller started successfully");
+
+        // do infinite loop
+        loop {
+            sleep(Duration::from_secs(60)).await;
+        }
+    });
+}
+
 /// print out system information
 fn inspect_system() {
     use sysinfo::System;
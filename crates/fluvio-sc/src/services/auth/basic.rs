@@ -2,23 +2,32 @@ use std::sync::Arc;
 
 use tracing::instrument;
 use async_trait::async_trait;
-pub use policy::BasicRbacPolicy;
+pub use policy::{BasicRbacPolicy, Action};
 
-use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, DataAction, AuthError};
 use fluvio_controlplane_metadata::extended::ObjectType;
 use fluvio_auth::x509::X509Identity;
+use fluvio_auth::sasl::{identity_from_connection, SaslAuthenticator};
 
 #[derive(Debug, Clone)]
 pub struct BasicAuthorization {
     policy: Arc<BasicRbacPolicy>,
+    sasl: Option<SaslAuthenticator>,
 }
 
 impl BasicAuthorization {
     pub fn new(policy: BasicRbacPolicy) -> Self {
         Self {
             policy: Arc::new(policy),
+            sasl: None,
         }
     }
+
+    /// authenticate clients with SASL in addition to mTLS
+    pub fn with_sasl(mut self, sasl: Option<SaslAuthenticator>) -> Self {
+        self.sasl = sasl;
+        self
+    }
 }
 
 #[async_trait]
@@ -30,7 +39,7 @@ impl Authorization for BasicAuthorization {
         &self,
         socket: &mut fluvio_socket::FluvioSocket,
     ) -> Result<Self::Context, AuthError> {
-        let identity = X509Identity::create_from_connection(socket).await?;
+        let identity = identity_from_connection(socket, self.sasl.as_ref()).await?;
         Ok(BasicAuthContext {
             identity,
             policy: self.policy.clone(),
@@ -44,6 +53,12 @@ pub struct BasicAuthContext {
     policy: Arc<BasicRbacPolicy>,
 }
 
+impl BasicAuthContext {
+    pub fn new(identity: X509Identity, policy: Arc<BasicRbacPolicy>) -> Self {
+        Self { identity, policy }
+    }
+}
+
 #[async_trait]
 impl AuthContext for BasicAuthContext {
     async fn allow_type_action(
@@ -65,6 +80,18 @@ impl AuthContext for BasicAuthContext {
     ) -> Result<bool, AuthError> {
         Ok(true)
     }
+
+    /// producing requires update and consuming requires read permission on topics
+    async fn allow_data_action(&self, action: DataAction, topic: &str) -> Result<bool, AuthError> {
+        self.policy
+            .evaluate(
+                action.into(),
+                ObjectType::Topic,
+                Some(topic),
+                &self.identity,
+            )
+            .await
+    }
 }
 
 /// basic policy module
@@ -79,7 +106,7 @@ mod policy {
     use tracing::debug;
     use serde::{Serialize, Deserialize};
 
-    use fluvio_auth::{AuthError, TypeAction, InstanceAction};
+    use fluvio_auth::{AuthError, TypeAction, InstanceAction, DataAction};
     use fluvio_auth::x509::X509Identity;
 
     use super::ObjectType;
@@ -100,6 +127,7 @@ mod policy {
             match action {
                 TypeAction::Create => Action::Create,
                 TypeAction::Read => Action::Read,
+                TypeAction::Update => Action::Update,
             }
         }
     }
@@ -107,11 +135,23 @@ mod policy {
     impl From<InstanceAction> for Action {
         fn from(action: InstanceAction) -> Self {
             match action {
+                InstanceAction::Create => Action::Create,
+                InstanceAction::Read => Action::Read,
+                InstanceAction::Update => Action::Update,
                 InstanceAction::Delete => Action::Delete,
             }
         }
     }
 
+    impl From<DataAction> for Action {
+        fn from(action: DataAction) -> Self {
+            match action {
+                DataAction::Produce => Action::Update,
+                DataAction::Consume => Action::Read,
+            }
+        }
+    }
+
     #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
     pub struct BasicRbacPolicy(pub HashMap<Role, HashMap<ObjectType, Vec<Action>>>);
 
@@ -173,6 +213,9 @@ mod policy {
             root_policy.insert(ObjectType::Topic, vec![Action::All]);
             root_policy.insert(ObjectType::Partition, vec![Action::All]);
             root_policy.insert(ObjectType::TableFormat, vec![Action::All]);
+            root_policy.insert(ObjectType::ConsumerGroup, vec![Action::All]);
+            root_policy.insert(ObjectType::Transform, vec![Action::All]);
+            root_policy.insert(ObjectType::Quota, vec![Action::All]);
 
             let mut policy = HashMap::new();
 
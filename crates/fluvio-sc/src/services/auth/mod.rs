@@ -1,7 +1,15 @@
 pub mod basic;
+pub mod resource;
 
 pub use common::*;
 
+/// policy used by public api authorization
+#[derive(Debug, Clone)]
+pub enum AuthPolicy {
+    Basic(basic::BasicRbacPolicy),
+    Resource(resource::ResourceRbacPolicy),
+}
+
 mod common {
 
     use std::sync::Arc;
@@ -9,9 +17,10 @@ mod common {
 
     use async_trait::async_trait;
 
-    use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, AuthError};
+    use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, DataAction, AuthError};
     use fluvio_socket::FluvioSocket;
     use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_auth::sasl::SaslAuthenticator;
 
     use crate::core::SharedContext;
 
@@ -31,24 +40,35 @@ mod common {
 
     /// Authorization that allows anything
     /// Used for personal development
-    #[derive(Debug, Clone)]
-    pub struct RootAuthorization {}
+    #[derive(Debug, Clone, Default)]
+    pub struct RootAuthorization {
+        sasl: Option<SaslAuthenticator>,
+    }
 
     #[async_trait]
     impl Authorization for RootAuthorization {
         type Context = RootAuthContext;
 
+        /// if SASL is enabled, client still has to authenticate
         async fn create_auth_context(
             &self,
-            _socket: &mut FluvioSocket,
+            socket: &mut FluvioSocket,
         ) -> Result<Self::Context, AuthError> {
+            if let Some(sasl) = &self.sasl {
+                sasl.create_identity(socket).await?;
+            }
             Ok(RootAuthContext {})
         }
     }
 
     impl RootAuthorization {
         pub fn new() -> Self {
-            Self {}
+            Self::default()
+        }
+
+        pub fn with_sasl(mut self, sasl: Option<SaslAuthenticator>) -> Self {
+            self.sasl = sasl;
+            self
         }
     }
 
@@ -74,6 +94,14 @@ mod common {
         ) -> Result<bool, AuthError> {
             Ok(true)
         }
+
+        async fn allow_data_action(
+            &self,
+            _action: DataAction,
+            _topic: &str,
+        ) -> Result<bool, AuthError> {
+            Ok(true)
+        }
     }
 
     /// Auth Service Context, this hold individual context that is enough enforce auth
@@ -89,4 +117,37 @@ mod common {
             Self { global_ctx, auth }
         }
     }
+
+    impl<AC: AuthContext> AuthServiceContext<AC> {
+        /// check if object type can be created under given name
+        pub async fn allow_create(&self, ty: ObjectType, name: &str) -> Result<bool, AuthError> {
+            Ok(self
+                .auth
+                .allow_type_action(ty.clone(), TypeAction::Create)
+                .await?
+                && self
+                    .auth
+                    .allow_instance_action(ty, InstanceAction::Create, name)
+                    .await?)
+        }
+
+        /// check if named object of type can be changed
+        pub async fn allow_update(&self, ty: ObjectType, name: &str) -> Result<bool, AuthError> {
+            Ok(self
+                .auth
+                .allow_type_action(ty.clone(), TypeAction::Update)
+                .await?
+                && self
+                    .auth
+                    .allow_instance_action(ty, InstanceAction::Update, name)
+                    .await?)
+        }
+
+        /// check if named object can be seen in list or watch
+        pub async fn allow_read(&self, ty: ObjectType, name: &str) -> Result<bool, AuthError> {
+            self.auth
+                .allow_instance_action(ty, InstanceAction::Read, name)
+                .await
+        }
+    }
 }
@@ -0,0 +1,255 @@
+use std::sync::Arc;
+
+use tracing::instrument;
+use async_trait::async_trait;
+pub use policy::{ResourcePermission, ResourceRbacPolicy};
+
+use fluvio_auth::{AuthContext, Authorization, TypeAction, InstanceAction, DataAction, AuthError};
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_auth::x509::X509Identity;
+use fluvio_auth::sasl::{identity_from_connection, SaslAuthenticator};
+
+/// Authorization with permissions scoped to resource names
+#[derive(Debug, Clone)]
+pub struct ResourceAuthorization {
+    policy: Arc<ResourceRbacPolicy>,
+    sasl: Option<SaslAuthenticator>,
+}
+
+impl ResourceAuthorization {
+    pub fn new(policy: ResourceRbacPolicy) -> Self {
+        Self {
+            policy: Arc::new(policy),
+            sasl: None,
+        }
+    }
+
+    /// authenticate clients with SASL in addition to mTLS
+    pub fn with_sasl(mut self, sasl: Option<SaslAuthenticator>) -> Self {
+        self.sasl = sasl;
+        self
+    }
+}
+
+#[async_trait]
+impl Authorization for ResourceAuthorization {
+    type Context = ResourceAuthContext;
+
+    #[instrument(level = "trace", skip(self, socket))]
+    async fn create_auth_context(
+        &self,
+        socket: &mut fluvio_socket::FluvioSocket,
+    ) -> Result<Self::Context, AuthError> {
+        let identity = identity_from_connection(socket, self.sasl.as_ref()).await?;
+        Ok(ResourceAuthContext {
+            identity,
+            policy: self.policy.clone(),
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct ResourceAuthContext {
+    identity: X509Identity,
+    policy: Arc<ResourceRbacPolicy>,
+}
+
+#[async_trait]
+impl AuthContext for ResourceAuthContext {
+    /// allowed if any permission of object type grants action,
+    /// individual instances are checked by `allow_instance_action`
+    async fn allow_type_action(
+        &self,
+        ty: ObjectType,
+        action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        Ok(self
+            .policy
+            .evaluate(action.into(), ty, None, &self.identity))
+    }
+
+    async fn allow_instance_action(
+        &self,
+        ty: ObjectType,
+        action: InstanceAction,
+        key: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(self
+            .policy
+            .evaluate(action.into(), ty, Some(key), &self.identity))
+    }
+
+    async fn allow_data_action(&self, action: DataAction, topic: &str) -> Result<bool, AuthError> {
+        Ok(self.policy.evaluate(
+            action.into(),
+            ObjectType::Topic,
+            Some(topic),
+            &self.identity,
+        ))
+    }
+}
+
+mod policy {
+
+    use std::fs::read;
+    use std::collections::HashMap;
+    use std::path::PathBuf;
+    use std::convert::TryFrom;
+
+    use tracing::debug;
+    use serde::{Serialize, Deserialize};
+
+    use fluvio_auth::name_matches;
+    use fluvio_auth::x509::X509Identity;
+
+    use crate::services::auth::basic::Action;
+    use super::ObjectType;
+
+    type Role = String;
+
+    /// Actions on objects whose name matches `name`.
+    /// `*` matches any name, a trailing `*` matches names with the given prefix.
+    #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct ResourcePermission {
+        pub object: ObjectType,
+        #[serde(default = "any_name")]
+        pub name: String,
+        pub actions: Vec<Action>,
+    }
+
+    fn any_name() -> String {
+        "*".to_owned()
+    }
+
+    impl ResourcePermission {
+        pub fn new(object: ObjectType, name: impl Into<String>, actions: Vec<Action>) -> Self {
+            Self {
+                object,
+                name: name.into(),
+                actions,
+            }
+        }
+
+        fn matches(&self, name: &str) -> bool {
+            name_matches(&self.name, name)
+        }
+
+        fn grants(&self, action: &Action) -> bool {
+            self.actions
+                .iter()
+                .any(|permission| permission == action || permission == &Action::All)
+        }
+    }
+
+    /// Role based policy where roles are scopes or certificate groups of identity
+    #[derive(Debug, Clone, Default, Serialize, Deserialize, Eq, PartialEq)]
+    pub struct ResourceRbacPolicy {
+        pub roles: HashMap<Role, Vec<ResourcePermission>>,
+    }
+
+    impl TryFrom<PathBuf> for ResourceRbacPolicy {
+        type Error = std::io::Error;
+        fn try_from(path: PathBuf) -> Result<Self, Self::Error> {
+            debug!("reading resource policy: {:#?}", path);
+            let file = read(path)?;
+            let policy: ResourceRbacPolicy = serde_json::from_slice(&file)?;
+            Ok(policy)
+        }
+    }
+
+    impl ResourceRbacPolicy {
+        /// without instance, action is allowed if any permission of object type grants it
+        pub fn evaluate(
+            &self,
+            action: Action,
+            object_type: ObjectType,
+            instance: Option<&str>,
+            identity: &X509Identity,
+        ) -> bool {
+            identity.roles().any(|role| {
+                self.roles
+                    .get(role)
+                    .map(|permissions| {
+                        permissions.iter().any(|permission| {
+                            permission.object == object_type
+                                && instance.map_or(true, |name| permission.matches(name))
+                                && permission.grants(&action)
+                        })
+                    })
+                    .unwrap_or(false)
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_auth::x509::X509Identity;
+
+    use crate::services::auth::basic::Action;
+    use super::policy::*;
+    use super::ObjectType;
+
+    fn policy() -> ResourceRbacPolicy {
+        let json = r#"{
+            "roles": {
+                "team-a": [
+                    {"object":"Topic","name":"team-a.*","actions":["Create","Read","Delete"]},
+                    {"object":"Partition","actions":["Read"]}
+                ],
+                "svc-x": [
+                    {"object":"SmartModule","name":"foo","actions":["Read"]}
+                ]
+            }
+        }"#;
+        serde_json::from_str(json).expect("parse")
+    }
+
+    #[test]
+    fn test_resource_policy_serialization() {
+        let policy = policy();
+        assert_eq!(
+            policy.roles.get("team-a").and_then(|p| p.get(1)),
+            Some(&ResourcePermission::new(
+                ObjectType::Partition,
+                "*",
+                vec![Action::Read]
+            ))
+        );
+    }
+
+    #[test]
+    fn test_resource_policy_enforcement() {
+        let policy = policy();
+        let team_a =
+            X509Identity::new("alice".to_owned(), vec![]).with_groups(vec!["team-a".to_owned()]);
+        let svc = X509Identity::new("svc".to_owned(), vec!["svc-x".to_owned()]);
+
+        assert!(policy.evaluate(Action::Create, ObjectType::Topic, None, &team_a));
+        assert!(policy.evaluate(
+            Action::Create,
+            ObjectType::Topic,
+            Some("team-a.events"),
+            &team_a
+        ));
+        assert!(!policy.evaluate(
+            Action::Create,
+            ObjectType::Topic,
+            Some("team-b.events"),
+            &team_a
+        ));
+        assert!(!policy.evaluate(
+            Action::Update,
+            ObjectType::Topic,
+            Some("team-a.events"),
+            &team_a
+        ));
+        assert!(policy.evaluate(Action::Read, ObjectType::Partition, Some("any-0"), &team_a));
+
+        assert!(policy.evaluate(Action::Read, ObjectType::SmartModule, Some("foo"), &svc));
+        assert!(!policy.evaluate(Action::Read, ObjectType::SmartModule, Some("bar"), &svc));
+        assert!(!policy.evaluate(Action::Create, ObjectType::SmartModule, None, &svc));
+        assert!(!policy.evaluate(Action::Read, ObjectType::Topic, None, &svc));
+    }
+}
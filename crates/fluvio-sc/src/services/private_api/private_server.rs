@@ -1,9 +1,12 @@
-use fluvio_controlplane_metadata::message::SmartModuleMsg;
+use fluvio_controlplane_metadata::message::{SmartModuleMsg, TransformMsg, QuotaMsg};
 use fluvio_controlplane_metadata::partition::Replica;
 use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
+use fluvio_controlplane_metadata::transform::{TransformSpec, TransformStatus};
+use fluvio_controlplane_metadata::quota::QuotaSpec;
 
 use fluvio_future::timer::sleep;
 use fluvio_service::ConnectInfo;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::io::Error as IoError;
 use std::io::ErrorKind;
@@ -21,7 +24,8 @@ use fluvio_service::{FluvioService, wait_for_request};
 use fluvio_socket::{FluvioSocket, SocketError, FluvioSink};
 use fluvio_controlplane::{
     InternalScRequest, InternalScKey, RegisterSpuResponse, UpdateLrsRequest, UpdateReplicaRequest,
-    UpdateSpuRequest, ReplicaRemovedRequest, UpdateSmartModuleRequest,
+    UpdateSpuRequest, ReplicaRemovedRequest, UpdateSmartModuleRequest, UpdateDataPolicyRequest,
+    UpdateTransformRequest, UpdateQuotaRequest, UpdateTransformOffsetsRequest,
 };
 use fluvio_controlplane_metadata::message::{ReplicaMsg, Message, SpuMsg};
 
@@ -112,8 +116,11 @@ async fn dispatch_loop(
     let mut spu_spec_listener = context.spus().change_listener();
     let mut partition_spec_listener = context.partitions().change_listener();
     let mut sm_spec_listener = context.smartmodules().change_listener();
+    let mut transform_spec_listener = context.transforms().change_listener();
+    let mut quota_spec_listener = context.quotas().change_listener();
 
     // send initial changes
+    send_data_policy(&context, &mut sink, spu_id).await?;
 
     let mut health_check_timer = sleep(Duration::from_secs(HEALTH_DURATION));
 
@@ -124,6 +131,8 @@ async fn dispatch_loop(
         send_spu_spec_changes(&mut spu_spec_listener, &mut sink, spu_id).await?;
         send_replica_spec_changes(&mut partition_spec_listener, &mut sink, spu_id).await?;
         send_smartmodule_changes(&mut sm_spec_listener, &mut sink, spu_id).await?;
+        send_transform_changes(&mut transform_spec_listener, &mut sink, spu_id).await?;
+        send_quota_changes(&mut quota_spec_listener, &mut sink, spu_id).await?;
 
         trace!(spu_id, "waiting for SPU channel");
 
@@ -150,6 +159,9 @@ async fn dispatch_loop(
                             },
                             InternalScRequest::ReplicaRemovedRequest(msg) => {
                                 receive_replica_remove(&context,msg.request).await;
+                            },
+                            InternalScRequest::UpdateTransformOffsetsRequest(msg) => {
+                                receive_transform_offsets(&context,msg.request).await;
                             }
                         }
                         // reset timer
@@ -175,6 +187,14 @@ async fn dispatch_loop(
             _ = partition_spec_listener.listen() => {
                 debug!("partition lister changed");
 
+            },
+
+            _ = transform_spec_listener.listen() => {
+                debug!("transform lister changed");
+            },
+
+            _ = quota_spec_listener.listen() => {
+                debug!("quota lister changed");
             }
 
         }
@@ -199,12 +219,13 @@ async fn receive_lrs_update(ctx: &SharedContext, requests: UpdateLrsRequest) {
         if let Some(partition) = read_guard.get(&lrs_req.id) {
             let mut current_status = partition.inner().status().clone();
             let key = lrs_req.id.clone();
-            let new_status = PartitionStatus::new2(
+            let mut new_status = PartitionStatus::new2(
                 lrs_req.leader,
                 lrs_req.replicas,
                 lrs_req.size,
                 PartitionResolution::Online,
             );
+            new_status.in_sync_replicas = lrs_req.in_sync_replicas;
             current_status.merge(new_status);
 
             actions.push(WSAction::UpdateStatus::<PartitionSpec>((
@@ -227,6 +248,49 @@ async fn receive_lrs_update(ctx: &SharedContext, requests: UpdateLrsRequest) {
     }
 }
 
+/// commit transform offsets reported by source partition leaders
+#[instrument(skip(ctx, request))]
+async fn receive_transform_offsets(ctx: &SharedContext, request: UpdateTransformOffsetsRequest) {
+    let offsets = request.into_offsets();
+    if offsets.is_empty() {
+        return;
+    }
+    debug!(?offsets, "received transform offsets");
+
+    let mut statuses: HashMap<String, TransformStatus> = HashMap::new();
+    let read_guard = ctx.transforms().store().read().await;
+    for transform_offset in offsets.into_iter() {
+        if !statuses.contains_key(&transform_offset.name) {
+            if let Some(transform) = read_guard.get(&transform_offset.name) {
+                statuses.insert(
+                    transform_offset.name.clone(),
+                    transform.inner().status().clone(),
+                );
+            } else {
+                debug!(name = %transform_offset.name, "transform no longer exists");
+                continue;
+            }
+        }
+        if let Some(status) = statuses.get_mut(&transform_offset.name) {
+            status.commit(transform_offset.partition, transform_offset.offset);
+        }
+    }
+    // skip writes when nothing moved
+    statuses.retain(|name, status| {
+        read_guard
+            .get(name)
+            .map(|transform| transform.inner().status() != status)
+            .unwrap_or(false)
+    });
+    drop(read_guard);
+
+    for (name, status) in statuses.into_iter() {
+        ctx.transforms()
+            .send_action(WSAction::UpdateStatus::<TransformSpec>((name, status)))
+            .await;
+    }
+}
+
 #[instrument(
     skip(ctx,request),
     fields(replica=%request.id)
@@ -427,3 +491,141 @@ async fn send_smartmodule_changes(
     sink.send_request(&message).await?;
     Ok(())
 }
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_transform_changes(
+    listener: &mut K8ChangeListener<TransformSpec>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    // status carries committed offsets, which new leaders resume from
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: true,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateTransformRequest::with_all(
+            epoch,
+            updates
+                .into_iter()
+                .map(|transform| transform.into())
+                .collect(),
+        )
+    } else {
+        let mut changes: Vec<TransformMsg> = updates
+            .into_iter()
+            .map(|transform| Message::update(transform.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|transform| Message::delete(transform.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateTransformRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending transforms to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+#[instrument(level = "trace", skip(sink))]
+async fn send_quota_changes(
+    listener: &mut K8ChangeListener<QuotaSpec>,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    use crate::stores::ChangeFlag;
+
+    if !listener.has_change() {
+        trace!("changes is empty, skipping");
+        return Ok(());
+    }
+
+    let changes = listener
+        .sync_changes_with_filter(&ChangeFlag {
+            spec: true,
+            status: false,
+            meta: true,
+        })
+        .await;
+    if changes.is_empty() {
+        trace!("spec changes is empty, skipping");
+        return Ok(());
+    }
+
+    let epoch = changes.epoch;
+
+    let is_sync_all = changes.is_sync_all();
+    let (updates, deletes) = changes.parts();
+
+    let request = if is_sync_all {
+        UpdateQuotaRequest::with_all(
+            epoch,
+            updates.into_iter().map(|quota| quota.into()).collect(),
+        )
+    } else {
+        let mut changes: Vec<QuotaMsg> = updates
+            .into_iter()
+            .map(|quota| Message::update(quota.into()))
+            .collect();
+        let mut deletes = deletes
+            .into_iter()
+            .map(|quota| Message::delete(quota.into()))
+            .collect();
+        changes.append(&mut deletes);
+        UpdateQuotaRequest::with_changes(epoch, changes)
+    };
+
+    debug!(?request, "sending quotas to spu");
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
+
+/// data policy is loaded at startup, so it is sent once per connection
+#[instrument(level = "trace", skip(ctx, sink))]
+async fn send_data_policy(
+    ctx: &SharedContext,
+    sink: &mut FluvioSink,
+    spu_id: SpuId,
+) -> Result<(), SocketError> {
+    let request = UpdateDataPolicyRequest::new(ctx.config().data_policy.clone());
+    debug!(
+        enabled = request.policy.is_some(),
+        "sending data policy to spu"
+    );
+
+    let mut message = RequestMessage::new_request(request);
+    message.get_mut_header().set_client_id("sc");
+
+    sink.send_request(&message).await?;
+    Ok(())
+}
@@ -8,9 +8,16 @@ use fluvio_protocol::link::versions::{
     ApiVersionKey, ApiVersionsRequest, ApiVersionsResponse, PlatformVersion,
 };
 use fluvio_sc_schema::objects::{
-    ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiWatchRequest,
+    ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest, ObjectApiUpdateRequest,
+    ObjectApiWatchRequest,
 };
 use fluvio_sc_schema::AdminPublicApiKey;
+use fluvio_sc_schema::producer::InitProducerIdRequest;
+use fluvio_sc_schema::consumer_group::{
+    CommitOffsetsRequest, FetchCommittedOffsetsRequest, HeartbeatRequest, JoinGroupRequest,
+    LeaveGroupRequest,
+};
+use fluvio_sc_schema::reassignment::{DrainSpuRequest, ReassignPartitionRequest};
 
 // Fluvi Client version 0.14.0 corresponds to Platform version 10.0.0
 
@@ -39,6 +46,11 @@ pub async fn handle_api_versions_request(
         ObjectApiDeleteRequest::MIN_API_VERSION,
         ObjectApiDeleteRequest::MAX_API_VERSION,
     ));
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::Update,
+        ObjectApiUpdateRequest::MIN_API_VERSION,
+        ObjectApiUpdateRequest::MAX_API_VERSION,
+    ));
 
     response.api_keys.push(make_version_key(
         AdminPublicApiKey::List,
@@ -52,6 +64,51 @@ pub async fn handle_api_versions_request(
         ObjectApiWatchRequest::MAX_API_VERSION,
     ));
 
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::InitProducerId,
+        InitProducerIdRequest::MIN_API_VERSION,
+        InitProducerIdRequest::MAX_API_VERSION,
+    ));
+
+    // consumer group versions
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::JoinGroup,
+        JoinGroupRequest::MIN_API_VERSION,
+        JoinGroupRequest::MAX_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::Heartbeat,
+        HeartbeatRequest::MIN_API_VERSION,
+        HeartbeatRequest::MAX_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::LeaveGroup,
+        LeaveGroupRequest::MIN_API_VERSION,
+        LeaveGroupRequest::MAX_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::CommitOffsets,
+        CommitOffsetsRequest::MIN_API_VERSION,
+        CommitOffsetsRequest::MAX_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::FetchCommittedOffsets,
+        FetchCommittedOffsetsRequest::MIN_API_VERSION,
+        FetchCommittedOffsetsRequest::MAX_API_VERSION,
+    ));
+
+    // reassignment versions
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::ReassignPartition,
+        ReassignPartitionRequest::MIN_API_VERSION,
+        ReassignPartitionRequest::MAX_API_VERSION,
+    ));
+    response.api_keys.push(make_version_key(
+        AdminPublicApiKey::DrainSpu,
+        DrainSpuRequest::MIN_API_VERSION,
+        DrainSpuRequest::MAX_API_VERSION,
+    ));
+
     trace!("flv api versions response: {:#?}", response);
 
     Ok(request.new_response(response))
@@ -0,0 +1,344 @@
+//!
+//! # Consumer Group Requests
+//!
+//! Membership is handled by group coordinator, committed offsets are stored in
+//! consumer group spec. Members need read permission on topic.
+//!
+
+use std::time::Duration;
+
+use tracing::{debug, instrument, trace};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::consumer_group::{
+    CommitOffsetsRequest, FetchCommittedOffsetsRequest, FetchCommittedOffsetsResponse,
+    GroupAssignmentResponse, GroupResponse, HeartbeatRequest, JoinGroupRequest, LeaveGroupRequest,
+};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_controlplane_metadata::consumergroup::{ConsumerGroupSpec, ConsumerGroupStatus};
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_types::PartitionCount;
+
+use crate::core::group_coordinator::{Assignment, DEFAULT_SESSION_TIMEOUT};
+use crate::services::auth::AuthServiceContext;
+use crate::stores::actions::WSAction;
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_join_group_request<AC: AuthContext>(
+    request: RequestMessage<JoinGroupRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<GroupAssignmentResponse>> {
+    let req = request.request();
+    debug!(group = %req.group, topic = %req.topic, "join group");
+
+    let response = match join_group(req, auth_ctx).await? {
+        Ok(assignment) => assignment_response(assignment),
+        Err(error_code) => GroupAssignmentResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+    Ok(request.new_response(response))
+}
+
+async fn join_group<AC: AuthContext>(
+    req: &JoinGroupRequest,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Result<Assignment, ErrorCode>> {
+    if let Err(error_code) = authorize(auth_ctx).await? {
+        return Ok(Err(error_code));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let partition_count = match partition_count(auth_ctx, &req.topic).await {
+        Some(count) => count,
+        None => return Ok(Err(ErrorCode::TopicNotFound)),
+    };
+
+    if let Err(error_code) = create_group(auth_ctx, req).await? {
+        return Ok(Err(error_code));
+    }
+
+    let session_timeout = if req.session_timeout_ms > 0 {
+        Duration::from_millis(req.session_timeout_ms as u64)
+    } else {
+        DEFAULT_SESSION_TIMEOUT
+    };
+
+    let result = ctx
+        .group_coordinator()
+        .join(
+            &req.group,
+            &req.topic,
+            req.member_id.clone(),
+            session_timeout,
+            partition_count,
+        )
+        .await;
+    if let Ok(assignment) = &result {
+        update_status(auth_ctx, &req.group, assignment.status.clone()).await;
+    }
+    Ok(result)
+}
+
+/// create group consuming topic if it doesn't exist yet.
+/// group created meanwhile by concurrent join is used as it is
+async fn create_group<AC: AuthContext>(
+    auth_ctx: &AuthServiceContext<AC>,
+    req: &JoinGroupRequest,
+) -> Result<Result<(), ErrorCode>> {
+    let ctx = &auth_ctx.global_ctx;
+    let _lock = ctx.group_coordinator().lock_commits().await;
+    let existing = match ctx.consumergroups().store().value(&req.group).await {
+        Some(group) => Some(group),
+        None => {
+            match ctx
+                .consumergroups()
+                .create_spec(req.group.clone(), ConsumerGroupSpec::new(&req.topic))
+                .await
+            {
+                Ok(_) => None,
+                Err(err) => match ctx.consumergroups().store().value(&req.group).await {
+                    Some(group) => {
+                        debug!(group = %req.group, %err, "consumer group already exists");
+                        Some(group)
+                    }
+                    None => return Err(err.into()),
+                },
+            }
+        }
+    };
+    match existing {
+        Some(group) if group.spec.topic != req.topic => {
+            Ok(Err(ErrorCode::ConsumerGroupTopicMismatch))
+        }
+        _ => Ok(Ok(())),
+    }
+}
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_heartbeat_request<AC: AuthContext>(
+    request: RequestMessage<HeartbeatRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<GroupAssignmentResponse>> {
+    let req = request.request();
+    trace!(group = %req.group, member_id = %req.member_id, "heartbeat");
+
+    let ctx = &auth_ctx.global_ctx;
+    let result = match ctx.consumergroups().store().value(&req.group).await {
+        Some(group) => match partition_count(auth_ctx, &group.spec.topic).await {
+            Some(count) => {
+                ctx.group_coordinator()
+                    .heartbeat(&req.group, &req.member_id, count)
+                    .await
+            }
+            None => Err(ErrorCode::TopicNotFound),
+        },
+        None => Err(ErrorCode::ConsumerGroupNotFound),
+    };
+
+    let response = match result {
+        Ok(assignment) => {
+            update_status(auth_ctx, &req.group, assignment.status.clone()).await;
+            assignment_response(assignment)
+        }
+        Err(error_code) => GroupAssignmentResponse {
+            error_code,
+            ..Default::default()
+        },
+    };
+    Ok(request.new_response(response))
+}
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_leave_group_request<AC: AuthContext>(
+    request: RequestMessage<LeaveGroupRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<GroupResponse>> {
+    let req = request.request();
+    debug!(group = %req.group, member_id = %req.member_id, "leave group");
+
+    let error_code = match auth_ctx
+        .global_ctx
+        .group_coordinator()
+        .leave(&req.group, &req.member_id)
+        .await
+    {
+        Ok(status) => {
+            update_status(auth_ctx, &req.group, status).await;
+            ErrorCode::None
+        }
+        Err(error_code) => error_code,
+    };
+    Ok(request.new_response(GroupResponse { error_code }))
+}
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_commit_offsets_request<AC: AuthContext>(
+    request: RequestMessage<CommitOffsetsRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<GroupResponse>> {
+    let req = request.request();
+    debug!(
+        group = %req.group,
+        member_id = %req.member_id,
+        generation = req.generation,
+        "commit offsets"
+    );
+
+    let error_code = match commit_offsets(req, auth_ctx).await? {
+        Ok(()) => ErrorCode::None,
+        Err(error_code) => error_code,
+    };
+    Ok(request.new_response(GroupResponse { error_code }))
+}
+
+async fn commit_offsets<AC: AuthContext>(
+    req: &CommitOffsetsRequest,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Result<(), ErrorCode>> {
+    if let Err(error_code) = authorize(auth_ctx).await? {
+        return Ok(Err(error_code));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let coordinator = ctx.group_coordinator();
+    let offsets: Vec<_> = req
+        .offsets
+        .iter()
+        .map(|committed| (committed.partition, committed.offset))
+        .collect();
+    if let Err(error_code) = coordinator
+        .stage_commit(&req.group, &req.member_id, req.generation, &offsets)
+        .await
+    {
+        return Ok(Err(error_code));
+    }
+
+    // offsets staged by members while previous flush was written are written by single update
+    let _commit = coordinator.lock_commits().await;
+    let staged = coordinator.take_staged(&req.group).await;
+    if staged.is_empty() {
+        trace!(group = %req.group, "offsets written by concurrent commit");
+        return Ok(Ok(()));
+    }
+    let current = match ctx.consumergroups().store().value(&req.group).await {
+        Some(group) => group.spec.clone(),
+        None => return Ok(Err(ErrorCode::ConsumerGroupNotFound)),
+    };
+    let mut spec = current.clone();
+    for (partition, offset) in &staged {
+        spec.commit(*partition, *offset);
+    }
+    if spec == current {
+        trace!(group = %req.group, "committed offsets not changed");
+        return Ok(Ok(()));
+    }
+    if let Err(err) = ctx
+        .consumergroups()
+        .create_spec(req.group.clone(), spec)
+        .await
+    {
+        // retried by next commit of group
+        coordinator.restage(&req.group, staged).await;
+        return Err(err.into());
+    }
+    Ok(Ok(()))
+}
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_fetch_committed_offsets_request<AC: AuthContext>(
+    request: RequestMessage<FetchCommittedOffsetsRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<FetchCommittedOffsetsResponse>> {
+    let req = request.request();
+    debug!(group = %req.group, "fetch committed offsets");
+
+    let response = if let Err(error_code) = authorize(auth_ctx).await? {
+        FetchCommittedOffsetsResponse {
+            error_code,
+            ..Default::default()
+        }
+    } else {
+        match auth_ctx
+            .global_ctx
+            .consumergroups()
+            .store()
+            .value(&req.group)
+            .await
+        {
+            Some(group) => FetchCommittedOffsetsResponse {
+                offsets: group.spec.offsets.clone(),
+                ..Default::default()
+            },
+            None => FetchCommittedOffsetsResponse {
+                error_code: ErrorCode::ConsumerGroupNotFound,
+                ..Default::default()
+            },
+        }
+    };
+    Ok(request.new_response(response))
+}
+
+/// consumer group members must be able to read topics
+async fn authorize<AC: AuthContext>(
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Result<(), ErrorCode>> {
+    match auth_ctx
+        .auth
+        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Read)
+        .await
+    {
+        Ok(true) => Ok(Ok(())),
+        Ok(false) => {
+            trace!("authorization failed");
+            Ok(Err(ErrorCode::PermissionDenied))
+        }
+        Err(_) => Err(anyhow!("authorization io error")),
+    }
+}
+
+async fn partition_count<AC: AuthContext>(
+    auth_ctx: &AuthServiceContext<AC>,
+    topic: &str,
+) -> Option<PartitionCount> {
+    auth_ctx
+        .global_ctx
+        .topics()
+        .store()
+        .value(topic)
+        .await
+        .map(|topic| topic.spec.partitions())
+}
+
+/// report membership in group status, status is informational so it is not awaited
+async fn update_status<AC: AuthContext>(
+    auth_ctx: &AuthServiceContext<AC>,
+    group: &str,
+    status: ConsumerGroupStatus,
+) {
+    let groups = auth_ctx.global_ctx.consumergroups();
+    let changed = groups
+        .store()
+        .value(group)
+        .await
+        .map(|current| current.status != status)
+        .unwrap_or(false);
+    if changed {
+        groups
+            .send_action(WSAction::UpdateStatus((group.to_owned(), status)))
+            .await;
+    }
+}
+
+fn assignment_response(assignment: Assignment) -> GroupAssignmentResponse {
+    GroupAssignmentResponse {
+        error_code: ErrorCode::None,
+        member_id: assignment.member_id,
+        generation: assignment.generation,
+        partitions: assignment.partitions,
+    }
+}
@@ -7,6 +7,8 @@ use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::{CustomSpuSpec};
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_controlplane_metadata::transform::TransformSpec;
+use fluvio_controlplane_metadata::quota::QuotaSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
 use fluvio_sc_schema::objects::{ObjectApiCreateRequest, CreateRequest};
@@ -34,6 +36,10 @@ pub async fn handle_create_request<AC: AuthContext>(
         super::smartmodule::handle_create_smartmodule_request(create, auth_context).await?
     } else if let Some(create) = req.downcast()? as Option<CreateRequest<TableFormatSpec>> {
         super::tableformat::handle_create_tableformat_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<TransformSpec>> {
+        super::transform::handle_create_transform_request(create, auth_context).await?
+    } else if let Some(create) = req.downcast()? as Option<CreateRequest<QuotaSpec>> {
+        super::quota::handle_create_quota_request(create, auth_context).await?
     } else {
         error!("unknown create request: {:#?}", req);
         Status::new(
@@ -59,7 +65,7 @@ mod create_handler {
     use fluvio_sc_schema::{AdminSpec, Status};
     use fluvio_sc_schema::objects::{CommonCreateRequest};
     use fluvio_controlplane_metadata::extended::SpecExt;
-    use fluvio_auth::{AuthContext, TypeAction};
+    use fluvio_auth::AuthContext;
 
     use crate::services::auth::AuthServiceContext;
 
@@ -80,11 +86,7 @@ mod create_handler {
 
         info!(%name, ty = %S::LABEL,"creating");
 
-        if let Ok(authorized) = auth_ctx
-            .auth
-            .allow_type_action(S::OBJECT_TYPE, TypeAction::Create)
-            .await
-        {
+        if let Ok(authorized) = auth_ctx.allow_create(S::OBJECT_TYPE, &name).await {
             if !authorized {
                 trace!("authorization failed");
                 return Ok(Status::new(
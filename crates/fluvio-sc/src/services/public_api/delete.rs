@@ -14,6 +14,8 @@ use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::spu::CustomSpuSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_controlplane_metadata::transform::TransformSpec;
+use fluvio_controlplane_metadata::quota::QuotaSpec;
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{Status, TryEncodableFrom};
 use fluvio_sc_schema::objects::{ObjectApiDeleteRequest, DeleteRequest};
@@ -41,6 +43,10 @@ pub async fn handle_delete_request<AC: AuthContext>(
         super::smartmodule::handle_delete_smartmodule(req.key(), auth_ctx).await?
     } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<TableFormatSpec>> {
         super::tableformat::handle_delete_tableformat(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<TransformSpec>> {
+        super::transform::handle_delete_transform(req.key(), auth_ctx).await?
+    } else if let Some(req) = del_req.downcast()? as Option<DeleteRequest<QuotaSpec>> {
+        super::quota::handle_delete_quota(req.key(), auth_ctx).await?
     } else {
         error!("unknown create request: {:#?}", del_req);
         Status::new(
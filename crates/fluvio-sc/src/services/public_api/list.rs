@@ -5,15 +5,21 @@ use fluvio_controlplane_metadata::{
     partition::PartitionSpec,
     smartmodule::SmartModuleSpec,
     tableformat::TableFormatSpec,
+    transform::TransformSpec,
+    quota::QuotaSpec,
 };
+use std::fmt::Debug;
+
 use tracing::{debug, instrument};
 use anyhow::Result;
 
+use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_sc_schema::{
-    objects::{ObjectApiListRequest, ObjectApiListResponse, ListRequest},
-    TryEncodableFrom,
+    objects::{ObjectApiListRequest, ObjectApiListResponse, ListRequest, ListResponse},
+    AdminSpec, TryEncodableFrom,
 };
+use fluvio_controlplane_metadata::extended::SpecExt;
 use fluvio_auth::{AuthContext};
 
 use crate::services::auth::AuthServiceContext;
@@ -29,46 +35,102 @@ pub async fn handle_list_request<AC: AuthContext>(
 
     let response = if let Some(req) = req.downcast()? as Option<ListRequest<TopicSpec>> {
         ObjectApiListResponse::try_encode_from(
-            super::topic::handle_fetch_topics_request(req.name_filters, auth_ctx).await?,
+            readable(
+                auth_ctx,
+                super::topic::handle_fetch_topics_request(req.name_filters, auth_ctx).await?,
+            )
+            .await?,
             header.api_version(),
         )?
     } else if let Some(req) = req.downcast()? as Option<ListRequest<SpuSpec>> {
         ObjectApiListResponse::try_encode_from(
-            super::spu::handle_fetch_spus_request(req.name_filters, auth_ctx).await?,
+            readable(
+                auth_ctx,
+                super::spu::handle_fetch_spus_request(req.name_filters, auth_ctx).await?,
+            )
+            .await?,
             header.api_version(),
         )?
     } else if let Some(req) = req.downcast()? as Option<ListRequest<SpuGroupSpec>> {
         ObjectApiListResponse::try_encode_from(
-            super::spg::handle_fetch_spu_groups_request(req.name_filters, auth_ctx).await?,
+            readable(
+                auth_ctx,
+                super::spg::handle_fetch_spu_groups_request(req.name_filters, auth_ctx).await?,
+            )
+            .await?,
             header.api_version(),
         )?
     } else if let Some(req) = req.downcast()? as Option<ListRequest<CustomSpuSpec>> {
         ObjectApiListResponse::try_encode_from(
-            super::spu::handle_fetch_custom_spu_request(req.name_filters, auth_ctx).await?,
+            readable(
+                auth_ctx,
+                super::spu::handle_fetch_custom_spu_request(req.name_filters, auth_ctx).await?,
+            )
+            .await?,
             header.api_version(),
         )?
     } else if let Some(req) = req.downcast()? as Option<ListRequest<PartitionSpec>> {
         ObjectApiListResponse::try_encode_from(
-            super::partition::handle_fetch_request(req.name_filters, auth_ctx).await?,
+            readable(
+                auth_ctx,
+                super::partition::handle_fetch_request(req.name_filters, auth_ctx).await?,
+            )
+            .await?,
             header.api_version(),
         )?
     } else if let Some(req) = req.downcast()? as Option<ListRequest<SmartModuleSpec>> {
         ObjectApiListResponse::try_encode_from(
-            fetch_smart_modules(
-                req.name_filters.into(),
-                req.summary,
-                &auth_ctx.auth,
-                auth_ctx.global_ctx.smartmodules(),
+            readable(
+                auth_ctx,
+                fetch_smart_modules(
+                    req.name_filters.into(),
+                    req.summary,
+                    &auth_ctx.auth,
+                    auth_ctx.global_ctx.smartmodules(),
+                )
+                .await?,
             )
             .await?,
             header.api_version(),
         )?
     } else if let Some(req) = req.downcast()? as Option<ListRequest<TableFormatSpec>> {
         ObjectApiListResponse::try_encode_from(
-            fetch::handle_fetch_request(
-                req.name_filters,
+            readable(
                 auth_ctx,
-                auth_ctx.global_ctx.tableformats(),
+                fetch::handle_fetch_request(
+                    req.name_filters,
+                    auth_ctx,
+                    auth_ctx.global_ctx.tableformats(),
+                )
+                .await?,
+            )
+            .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<TransformSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            readable(
+                auth_ctx,
+                fetch::handle_fetch_request(
+                    req.name_filters,
+                    auth_ctx,
+                    auth_ctx.global_ctx.transforms(),
+                )
+                .await?,
+            )
+            .await?,
+            header.api_version(),
+        )?
+    } else if let Some(req) = req.downcast()? as Option<ListRequest<QuotaSpec>> {
+        ObjectApiListResponse::try_encode_from(
+            readable(
+                auth_ctx,
+                fetch::handle_fetch_request(
+                    req.name_filters,
+                    auth_ctx,
+                    auth_ctx.global_ctx.quotas(),
+                )
+                .await?,
             )
             .await?,
             header.api_version(),
@@ -82,6 +144,25 @@ pub async fn handle_list_request<AC: AuthContext>(
     Ok(ResponseMessage::from_header(&header, response))
 }
 
+/// only keep objects which identity is allowed to read
+async fn readable<AC, S>(
+    auth_ctx: &AuthServiceContext<AC>,
+    list: ListResponse<S>,
+) -> Result<ListResponse<S>>
+where
+    AC: AuthContext,
+    S: AdminSpec + SpecExt,
+    S::Status: Encoder + Decoder + Debug,
+{
+    let mut objects = vec![];
+    for object in list.inner() {
+        if auth_ctx.allow_read(S::OBJECT_TYPE, &object.name).await? {
+            objects.push(object);
+        }
+    }
+    Ok(ListResponse::new(objects))
+}
+
 mod fetch {
 
     use std::io::{Error, ErrorKind};
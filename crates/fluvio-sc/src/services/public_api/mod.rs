@@ -7,9 +7,15 @@ mod partition;
 mod api_version;
 mod create;
 mod delete;
+mod update;
 mod list;
 mod watch;
+mod producer;
+mod consumer_group;
+mod reassignment;
 mod tableformat;
+mod transform;
+mod quota;
 mod derivedstream;
 
 pub use server::start_public_server;
@@ -31,7 +37,7 @@ mod server {
     where
         A: Authorization + Sync + Send + Debug + 'static,
         AuthGlobalContext<A>: Clone + Debug,
-        <A as Authorization>::Context: Send + Sync,
+        <A as Authorization>::Context: Send + Sync + 'static,
     {
         let addr = ctx.global_ctx.config().public_endpoint.clone();
         debug!("starting public api service");
@@ -0,0 +1,44 @@
+use tracing::{debug, instrument, trace};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::producer::{InitProducerIdRequest, InitProducerIdResponse};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::{AuthContext, TypeAction};
+
+use crate::services::auth::AuthServiceContext;
+
+/// assign new producer id, each id starts with epoch 0.
+/// Only clients allowed to create topics can get producer id
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_init_producer_id_request<AC: AuthContext>(
+    request: RequestMessage<InitProducerIdRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<InitProducerIdResponse>> {
+    let allowed = auth_ctx
+        .auth
+        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Create)
+        .await
+        .map_err(|_| anyhow!("authorization io error"))?;
+    if !allowed {
+        trace!("authorization failed");
+        let response = InitProducerIdResponse {
+            error_code: ErrorCode::PermissionDenied,
+            ..Default::default()
+        };
+        return Ok(request.new_response(response));
+    }
+
+    let producer_id = auth_ctx.global_ctx.next_producer_id();
+    debug!(producer_id, "assigned producer id");
+
+    let response = InitProducerIdResponse {
+        producer_id,
+        producer_epoch: 0,
+        ..Default::default()
+    };
+
+    Ok(request.new_response(response))
+}
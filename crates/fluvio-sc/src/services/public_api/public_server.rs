@@ -44,7 +44,7 @@ impl<A> PublicService<A> {
 impl<A> FluvioService for PublicService<A>
 where
     A: Authorization + Sync + Send,
-    <A as Authorization>::Context: Send + Sync,
+    <A as Authorization>::Context: Send + Sync + 'static,
 {
     type Context = AuthGlobalContext<A>;
     type Request = AdminPublicDecodedRequest;
@@ -100,6 +100,12 @@ where
                 shared_sink,
                 "delete  handler"
             ),
+            AdminPublicDecodedRequest::UpdateRequest(request) => call_service!(
+                request,
+                super::update::handle_update_request(request, &service_context),
+                shared_sink,
+                "update handler"
+            ),
 
             AdminPublicDecodedRequest::ListRequest(request) => call_service!(
                 request,
@@ -107,6 +113,54 @@ where
                 shared_sink,
                 "list handler"
             ),
+            AdminPublicDecodedRequest::InitProducerIdRequest(request) => call_service!(
+                request,
+                super::producer::handle_init_producer_id_request(request, &service_context),
+                shared_sink,
+                "init producer id handler"
+            ),
+            AdminPublicDecodedRequest::JoinGroupRequest(request) => call_service!(
+                request,
+                super::consumer_group::handle_join_group_request(request, &service_context),
+                shared_sink,
+                "join group handler"
+            ),
+            AdminPublicDecodedRequest::HeartbeatRequest(request) => call_service!(
+                request,
+                super::consumer_group::handle_heartbeat_request(request, &service_context),
+                shared_sink,
+                "heartbeat handler"
+            ),
+            AdminPublicDecodedRequest::LeaveGroupRequest(request) => call_service!(
+                request,
+                super::consumer_group::handle_leave_group_request(request, &service_context),
+                shared_sink,
+                "leave group handler"
+            ),
+            AdminPublicDecodedRequest::CommitOffsetsRequest(request) => call_service!(
+                request,
+                super::consumer_group::handle_commit_offsets_request(request, &service_context),
+                shared_sink,
+                "commit offsets handler"
+            ),
+            AdminPublicDecodedRequest::FetchCommittedOffsetsRequest(request) => call_service!(
+                request,
+                super::consumer_group::handle_fetch_committed_offsets_request(request, &service_context),
+                shared_sink,
+                "fetch committed offsets handler"
+            ),
+            AdminPublicDecodedRequest::ReassignPartitionRequest(request) => call_service!(
+                request,
+                super::reassignment::handle_reassign_partition_request(request, &service_context),
+                shared_sink,
+                "reassign partition handler"
+            ),
+            AdminPublicDecodedRequest::DrainSpuRequest(request) => call_service!(
+                request,
+                super::reassignment::handle_drain_spu_request(request, &service_context),
+                shared_sink,
+                "drain spu handler"
+            ),
             AdminPublicDecodedRequest::WatchRequest(request) =>
 
                 super::watch::handle_watch_request(
@@ -0,0 +1,112 @@
+//!
+//! # Create Quota Request
+//!
+//! Validates quota and sends it to KV store, SPUs pick it up from metadata pushed by SC.
+//!
+
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::quota::QuotaSpec;
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::AuthContext;
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for quota request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_quota_request<AC: AuthContext>(
+    req: CreateRequest<QuotaSpec>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating quota");
+
+    if auth_ctx
+        .global_ctx
+        .quotas()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        debug!("quota already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::QuotaAlreadyExists,
+            Some(format!("quota '{name}' already defined")),
+        ));
+    }
+
+    if let Ok(authorized) = auth_ctx.allow_create(QuotaSpec::OBJECT_TYPE, &name).await {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Some(status) = validate_quota(&auth_ctx.global_ctx, &name, &spec).await {
+        return Ok(status);
+    }
+
+    let status = process_quota_request(&auth_ctx.global_ctx, name, spec).await;
+    trace!("create quota response {:#?}", status);
+
+    Ok(status)
+}
+
+/// only one quota can apply to same clients, otherwise it would be ambiguous which one is enforced
+async fn validate_quota(ctx: &Context, name: &str, spec: &QuotaSpec) -> Option<Status> {
+    if let Err(err) = spec.validate() {
+        return Some(Status::new(
+            name.to_owned(),
+            ErrorCode::QuotaError,
+            Some(err),
+        ));
+    }
+
+    let existing = ctx
+        .quotas()
+        .store()
+        .read()
+        .await
+        .values()
+        .find(|quota| quota.spec.target == spec.target)
+        .map(|quota| quota.key_owned());
+    if let Some(existing) = existing {
+        debug!(%existing, "quota for same target exists");
+        return Some(Status::new(
+            name.to_owned(),
+            ErrorCode::QuotaAlreadyExists,
+            Some(format!(
+                "quota '{existing}' already applies to {}",
+                spec.target
+            )),
+        ));
+    }
+
+    None
+}
+
+/// Process quota, converts quota spec to K8 and sends to KV store
+#[instrument(skip(ctx, name, quota_spec))]
+async fn process_quota_request(ctx: &Context, name: String, quota_spec: QuotaSpec) -> Status {
+    if let Err(err) = ctx.quotas().create_spec(name.clone(), quota_spec).await {
+        let error = Some(err.to_string());
+        Status::new(name, ErrorCode::QuotaError, error)
+    } else {
+        info!(%name, "quota created");
+        Status::new_ok(name.clone())
+    }
+}
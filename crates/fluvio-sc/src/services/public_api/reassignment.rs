@@ -0,0 +1,310 @@
+//!
+//! # Partition Reassignment Requests
+//!
+//! Handlers only record target replicas in partition spec, partition controller
+//! moves replicas once target replicas are in sync.
+//!
+
+use std::collections::{HashMap, HashSet};
+
+use tracing::{debug, info, instrument, trace};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::api::{RequestMessage, ResponseMessage};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::reassignment::{DrainSpuRequest, ReassignPartitionRequest};
+use fluvio_controlplane_metadata::extended::{ObjectType, SpecExt};
+use fluvio_controlplane_metadata::partition::{PartitionSpec, ReplicaKey};
+use fluvio_controlplane_metadata::spu::SpuSpec;
+use fluvio_auth::AuthContext;
+use fluvio_types::SpuId;
+
+use crate::services::auth::AuthServiceContext;
+use crate::stores::spu::SpuLocalStorePolicy;
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_reassign_partition_request<AC: AuthContext>(
+    request: RequestMessage<ReassignPartitionRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<Status>> {
+    let req = request.request();
+    let key = ReplicaKey::new(req.topic.clone(), req.partition);
+    debug!(partition = %key, replicas = ?req.replicas, "reassign partition");
+
+    let status = reassign_partition(&key, &req.replicas, auth_ctx).await?;
+    trace!("reassign partition resp {:#?}", status);
+    Ok(request.new_response(status))
+}
+
+async fn reassign_partition<AC: AuthContext>(
+    key: &ReplicaKey,
+    replicas: &[SpuId],
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Status> {
+    let name = key.to_string();
+    if let Err(error_code) = authorize(auth_ctx, PartitionSpec::OBJECT_TYPE, &name).await? {
+        return Ok(Status::new(name, error_code, None));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    let mut spec = match ctx.partitions().store().spec(key).await {
+        Some(spec) => spec,
+        None => return Ok(Status::new(name, ErrorCode::PartitionNotFound, None)),
+    };
+
+    if spec.reassignment.is_some() {
+        return Ok(Status::new(
+            name,
+            ErrorCode::PartitionReassignmentInProgress,
+            None,
+        ));
+    }
+
+    let spu_ids = ctx.spus().store().spu_ids().await;
+    if let Err(error) = validate_replicas(replicas, &spu_ids) {
+        return Ok(Status::new(
+            name,
+            ErrorCode::PartitionInvalidReplicas,
+            Some(error),
+        ));
+    }
+
+    if spec.replicas == replicas {
+        trace!(partition = %key, "replicas not changed");
+        return Ok(Status::new_ok(name));
+    }
+
+    spec.start_reassignment(replicas.to_vec());
+    ctx.partitions().create_spec(key.clone(), spec).await?;
+    info!(partition = %key, ?replicas, "partition reassignment started");
+    Ok(Status::new_ok(name))
+}
+
+#[instrument(skip(request, auth_ctx))]
+pub async fn handle_drain_spu_request<AC: AuthContext>(
+    request: RequestMessage<DrainSpuRequest>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<ResponseMessage<Status>> {
+    let spu = request.request().spu;
+    debug!(spu, "drain spu");
+
+    let status = drain_spu(spu, auth_ctx).await?;
+    trace!("drain spu resp {:#?}", status);
+    Ok(request.new_response(status))
+}
+
+async fn drain_spu<AC: AuthContext>(
+    spu: SpuId,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Status> {
+    let name = spu.to_string();
+    if let Err(error_code) = authorize(auth_ctx, SpuSpec::OBJECT_TYPE, &name).await? {
+        return Ok(Status::new(name, error_code, None));
+    }
+
+    let ctx = &auth_ctx.global_ctx;
+    if ctx.spus().store().get_by_id(spu).await.is_none() {
+        return Ok(Status::new(name, ErrorCode::SpuNotFound, None));
+    }
+
+    let partitions: Vec<(ReplicaKey, PartitionSpec)> = ctx
+        .partitions()
+        .store()
+        .read()
+        .await
+        .values()
+        .map(|partition| (partition.key.clone(), partition.spec.clone()))
+        .collect();
+    let mut online = ctx.spus().store().online_spu_ids().await;
+    online.sort_unstable();
+
+    let assignments = match drain_assignments(spu, &partitions, &online) {
+        Ok(assignments) => assignments,
+        Err((error_code, error)) => return Ok(Status::new(name, error_code, Some(error))),
+    };
+
+    for (key, mut spec, target) in assignments {
+        debug!(partition = %key, ?target, "moving replica off spu");
+        spec.start_reassignment(target);
+        ctx.partitions().create_spec(key, spec).await?;
+    }
+    info!(spu, "spu drain started");
+    Ok(Status::new_ok(name))
+}
+
+/// moving replicas requires update permission on object type, not just on instance
+async fn authorize<AC: AuthContext>(
+    auth_ctx: &AuthServiceContext<AC>,
+    ty: ObjectType,
+    key: &str,
+) -> Result<Result<(), ErrorCode>> {
+    match auth_ctx.allow_update(ty, key).await {
+        Ok(true) => Ok(Ok(())),
+        Ok(false) => {
+            trace!("authorization failed");
+            Ok(Err(ErrorCode::PermissionDenied))
+        }
+        Err(_) => Err(anyhow!("authorization io error")),
+    }
+}
+
+/// target replicas must be unique, registered spus
+fn validate_replicas(replicas: &[SpuId], spu_ids: &[SpuId]) -> Result<(), String> {
+    if replicas.is_empty() {
+        return Err("replicas must not be empty".to_owned());
+    }
+    let mut seen = HashSet::new();
+    for spu in replicas {
+        if !seen.insert(spu) {
+            return Err(format!("spu {spu} is listed more than once"));
+        }
+        if !spu_ids.contains(spu) {
+            return Err(format!("spu {spu} is not registered"));
+        }
+    }
+    Ok(())
+}
+
+/// compute target replicas of partitions hosted by spu.
+/// spu is replaced by online spu hosting least replicas which is not already replica of partition.
+/// fails without any assignment if any partition can't be moved
+fn drain_assignments(
+    spu: SpuId,
+    partitions: &[(ReplicaKey, PartitionSpec)],
+    online: &[SpuId],
+) -> Result<Vec<(ReplicaKey, PartitionSpec, Vec<SpuId>)>, (ErrorCode, String)> {
+    let mut load: HashMap<SpuId, usize> = online
+        .iter()
+        .filter(|id| **id != spu)
+        .map(|id| (*id, 0))
+        .collect();
+    for (_, spec) in partitions {
+        for replica in &spec.replicas {
+            if let Some(count) = load.get_mut(replica) {
+                *count += 1;
+            }
+        }
+    }
+
+    let mut assignments = vec![];
+    for (key, spec) in partitions {
+        if let Some(target) = &spec.reassignment {
+            if spec.has_spu(&spu) || target.contains(&spu) {
+                return Err((
+                    ErrorCode::PartitionReassignmentInProgress,
+                    format!("partition {key} is already being reassigned"),
+                ));
+            }
+            continue;
+        }
+        if !spec.has_spu(&spu) {
+            continue;
+        }
+
+        // online keys are sorted, so ties go to lowest id
+        let replacement = online
+            .iter()
+            .filter(|id| load.contains_key(id) && !spec.has_spu(id))
+            .min_by_key(|id| load[id])
+            .copied()
+            .ok_or_else(|| {
+                (
+                    ErrorCode::PartitionInvalidReplicas,
+                    format!("no spu available to replace spu {spu} in partition {key}"),
+                )
+            })?;
+        if let Some(count) = load.get_mut(&replacement) {
+            *count += 1;
+        }
+
+        let target = spec
+            .replicas
+            .iter()
+            .map(|id| if *id == spu { replacement } else { *id })
+            .collect();
+        assignments.push((key.clone(), spec.clone(), target));
+    }
+    Ok(assignments)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use fluvio_auth::x509::X509Identity;
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_controlplane_metadata::partition::{PartitionSpec, ReplicaKey};
+    use fluvio_protocol::link::ErrorCode;
+
+    use crate::config::ScConfig;
+    use crate::core::Context;
+    use crate::services::auth::AuthServiceContext;
+    use crate::services::auth::basic::{Action, BasicAuthContext, BasicRbacPolicy};
+
+    use super::{drain_assignments, drain_spu, reassign_partition, validate_replicas};
+
+    #[test]
+    fn test_validate_replicas() {
+        let spus = vec![1, 2, 3];
+        assert!(validate_replicas(&[1, 2], &spus).is_ok());
+        assert!(validate_replicas(&[], &spus).is_err());
+        assert!(validate_replicas(&[1, 1], &spus).is_err());
+        assert!(validate_replicas(&[1, 4], &spus).is_err());
+    }
+
+    #[test]
+    fn test_drain_assignments() {
+        let partitions = vec![
+            (
+                ReplicaKey::new("t", 0_u32),
+                PartitionSpec::new(1, vec![1, 2]),
+            ),
+            (
+                ReplicaKey::new("t", 1_u32),
+                PartitionSpec::new(2, vec![2, 3]),
+            ),
+            (
+                ReplicaKey::new("t", 2_u32),
+                PartitionSpec::new(3, vec![3, 1]),
+            ),
+        ];
+
+        let assignments = drain_assignments(1, &partitions, &[1, 2, 3, 4]).expect("assignments");
+        let targets: Vec<(u32, Vec<i32>)> = assignments
+            .into_iter()
+            .map(|(key, _, target)| (key.partition, target))
+            .collect();
+        // spu 4 hosts fewest replicas, so it replaces spu 1 in both partitions
+        assert_eq!(targets, vec![(0, vec![4, 2]), (2, vec![3, 4])]);
+
+        // remaining spu 2 already hosts partition 0
+        let (error_code, _) = drain_assignments(1, &partitions, &[1, 2]).unwrap_err();
+        assert_eq!(error_code, ErrorCode::PartitionInvalidReplicas);
+    }
+
+    #[fluvio_future::test]
+    async fn test_reassignment_requires_admin() {
+        let mut policy = BasicRbacPolicy::default();
+        let mut reader = HashMap::new();
+        reader.insert(ObjectType::Partition, vec![Action::Read]);
+        reader.insert(ObjectType::Spu, vec![Action::Read]);
+        policy.0.insert("Reader".to_owned(), reader);
+
+        let identity = X509Identity::new("svc".to_owned(), vec!["Reader".to_owned()]);
+        let auth_ctx = AuthServiceContext::new(
+            Context::shared_metadata(ScConfig::default()),
+            BasicAuthContext::new(identity, Arc::new(policy)),
+        );
+
+        let status = reassign_partition(&ReplicaKey::new("t", 0_u32), &[1, 2], &auth_ctx)
+            .await
+            .expect("status");
+        assert_eq!(status.error_code, ErrorCode::PermissionDenied);
+
+        let status = drain_spu(1, &auth_ctx).await.expect("status");
+        assert_eq!(status.error_code, ErrorCode::PermissionDenied);
+    }
+}
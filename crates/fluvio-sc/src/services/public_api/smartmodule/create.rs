@@ -12,7 +12,7 @@ use fluvio_sc_schema::{Status};
 use fluvio_sc_schema::objects::{CreateRequest};
 use fluvio_sc_schema::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::extended::SpecExt;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::AuthContext;
 
 use crate::core::Context;
 use crate::services::auth::AuthServiceContext;
@@ -29,8 +29,7 @@ pub async fn handle_create_smartmodule_request<AC: AuthContext>(
     info!(%name,"creating smartmodule");
 
     if let Ok(authorized) = auth_ctx
-        .auth
-        .allow_type_action(SmartModuleSpec::OBJECT_TYPE, TypeAction::Create)
+        .allow_create(SmartModuleSpec::OBJECT_TYPE, &name)
         .await
     {
         if !authorized {
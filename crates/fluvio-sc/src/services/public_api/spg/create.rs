@@ -15,7 +15,7 @@ use fluvio_sc_schema::Status;
 use fluvio_sc_schema::objects::{CreateRequest};
 use fluvio_sc_schema::spg::SpuGroupSpec;
 use fluvio_controlplane_metadata::extended::SpecExt;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::AuthContext;
 
 use crate::core::Context;
 use crate::services::auth::AuthServiceContext;
@@ -36,8 +36,7 @@ pub async fn handle_create_spu_group_request<AC: AuthContext>(
          "creating spg");
 
     if let Ok(authorized) = auth_ctx
-        .auth
-        .allow_type_action(SpuGroupSpec::OBJECT_TYPE, TypeAction::Create)
+        .allow_create(SpuGroupSpec::OBJECT_TYPE, &name)
         .await
     {
         if !authorized {
@@ -12,7 +12,7 @@ use fluvio_sc_schema::{Status};
 use fluvio_sc_schema::objects::{CreateRequest};
 use fluvio_sc_schema::tableformat::TableFormatSpec;
 use fluvio_controlplane_metadata::extended::SpecExt;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::AuthContext;
 
 use crate::core::Context;
 use crate::services::auth::AuthServiceContext;
@@ -44,8 +44,7 @@ pub async fn handle_create_tableformat_request<AC: AuthContext>(
     }
 
     if let Ok(authorized) = auth_ctx
-        .auth
-        .allow_type_action(TableFormatSpec::OBJECT_TYPE, TypeAction::Create)
+        .allow_create(TableFormatSpec::OBJECT_TYPE, &name)
         .await
     {
         if !authorized {
@@ -18,7 +18,7 @@ use fluvio_controlplane_metadata::topic::ReplicaSpec;
 use fluvio_sc_schema::shared::validate_resource_name;
 use fluvio_sc_schema::Status;
 use fluvio_sc_schema::topic::TopicSpec;
-use fluvio_auth::{AuthContext, TypeAction};
+use fluvio_auth::AuthContext;
 use fluvio_controlplane_metadata::extended::SpecExt;
 
 use crate::core::Context;
@@ -39,11 +39,7 @@ pub(crate) async fn handle_create_topics_request<AC: AuthContext>(
 
     info!( topic = %name,"creating topic");
 
-    if let Ok(authorized) = auth_ctx
-        .auth
-        .allow_type_action(TopicSpec::OBJECT_TYPE, TypeAction::Create)
-        .await
-    {
+    if let Ok(authorized) = auth_ctx.allow_create(TopicSpec::OBJECT_TYPE, &name).await {
         if !authorized {
             trace!("authorization failed");
             return Ok(Status::new(
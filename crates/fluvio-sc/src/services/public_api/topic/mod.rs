@@ -1,7 +1,9 @@
 mod create;
 mod delete;
 mod fetch;
+mod update;
 
 pub(crate) use create::*;
 pub(crate) use delete::*;
 pub(crate) use fetch::*;
+pub(crate) use update::*;
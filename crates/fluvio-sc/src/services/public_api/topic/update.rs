@@ -0,0 +1,130 @@
+//!
+//! # Update Topic Request
+//!
+//! Update topic request handler. Changes configuration of existing topic in place,
+//! topic controller propagates new configuration to partitions.
+//!
+use tracing::{info, trace, instrument};
+use std::io::{Error, ErrorKind};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::topic::TopicUpdate;
+use fluvio_controlplane_metadata::topic::TopicSpec;
+use fluvio_auth::AuthContext;
+use fluvio_controlplane_metadata::extended::SpecExt;
+
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for update topic request
+#[instrument(skip(topic_name, update, auth_ctx))]
+pub async fn handle_update_topic<AC: AuthContext>(
+    topic_name: String,
+    update: TopicUpdate,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Status, Error> {
+    info!(%topic_name, ?update, "Updating topic");
+
+    if let Ok(authorized) = auth_ctx
+        .allow_update(TopicSpec::OBJECT_TYPE, &topic_name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                topic_name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(Error::new(ErrorKind::Interrupted, "authorization io error"));
+    }
+
+    let topics = auth_ctx.global_ctx.topics();
+    let current_spec = match topics.store().spec(&topic_name).await {
+        Some(spec) => spec,
+        None => {
+            return Ok(Status::new(
+                topic_name,
+                ErrorCode::TopicNotFound,
+                Some("not found".to_owned()),
+            ))
+        }
+    };
+
+    let mut spec = current_spec.clone();
+    if let Err(error) = update.apply(&mut spec) {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some(error),
+        ));
+    }
+
+    if let Some(error) = spec.validate_config() {
+        return Ok(Status::new(
+            topic_name,
+            ErrorCode::TopicInvalidConfiguration,
+            Some(error),
+        ));
+    }
+
+    if spec == current_spec {
+        trace!(%topic_name, "no change in topic config");
+        return Ok(Status::new_ok(topic_name));
+    }
+
+    let status = if let Err(err) = topics.create_spec(topic_name.clone(), spec).await {
+        Status::new(
+            topic_name.clone(),
+            ErrorCode::TopicError,
+            Some(err.to_string()),
+        )
+    } else {
+        info!(%topic_name, "topic updated");
+        Status::new_ok(topic_name)
+    };
+
+    trace!("flv update topic resp {:#?}", status);
+
+    Ok(status)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    use fluvio_auth::x509::X509Identity;
+    use fluvio_controlplane_metadata::extended::ObjectType;
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_sc_schema::topic::TopicUpdate;
+
+    use crate::config::ScConfig;
+    use crate::core::Context;
+    use crate::services::auth::AuthServiceContext;
+    use crate::services::auth::basic::{Action, BasicAuthContext, BasicRbacPolicy};
+
+    use super::handle_update_topic;
+
+    #[fluvio_future::test]
+    async fn test_update_topic_read_only_identity() {
+        let mut policy = BasicRbacPolicy::default();
+        let mut reader = HashMap::new();
+        reader.insert(ObjectType::Topic, vec![Action::Read]);
+        policy.0.insert("Reader".to_owned(), reader);
+
+        let identity = X509Identity::new("svc".to_owned(), vec!["Reader".to_owned()]);
+        let auth_ctx = AuthServiceContext::new(
+            Context::shared_metadata(ScConfig::default()),
+            BasicAuthContext::new(identity, Arc::new(policy)),
+        );
+
+        let status = handle_update_topic("topic1".to_owned(), TopicUpdate::default(), &auth_ctx)
+            .await
+            .expect("status");
+        assert_eq!(status.error_code, ErrorCode::PermissionDenied);
+    }
+}
@@ -0,0 +1,133 @@
+//!
+//! # Create Transform Request
+//!
+//! Validates source and target topics of transform and sends it to KV store.
+//! SPUs leading source partitions pick up transform from metadata and start processing.
+//!
+
+use tracing::{debug, info, trace, instrument};
+use anyhow::{anyhow, Result};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_sc_schema::Status;
+use fluvio_sc_schema::objects::CreateRequest;
+use fluvio_sc_schema::transform::{SmartModuleErrorPolicy, TransformSpec};
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::AuthContext;
+
+use crate::core::Context;
+use crate::services::auth::AuthServiceContext;
+
+/// Handler for transform request
+#[instrument(skip(req, auth_ctx))]
+pub async fn handle_create_transform_request<AC: AuthContext>(
+    req: CreateRequest<TransformSpec>,
+    auth_ctx: &AuthServiceContext<AC>,
+) -> Result<Status> {
+    let (create, spec) = req.parts();
+    let name = create.name;
+
+    info!(%name, "creating transform");
+
+    if auth_ctx
+        .global_ctx
+        .transforms()
+        .store()
+        .contains_key(&name)
+        .await
+    {
+        debug!("transform already exists");
+        return Ok(Status::new(
+            name.to_string(),
+            ErrorCode::TransformAlreadyExists,
+            Some(format!("transform '{name}' already defined")),
+        ));
+    }
+
+    if let Ok(authorized) = auth_ctx
+        .allow_create(TransformSpec::OBJECT_TYPE, &name)
+        .await
+    {
+        if !authorized {
+            trace!("authorization failed");
+            return Ok(Status::new(
+                name.clone(),
+                ErrorCode::PermissionDenied,
+                Some(String::from("permission denied")),
+            ));
+        }
+    } else {
+        return Err(anyhow!("authorization io error"));
+    }
+
+    if let Some(status) = validate_transform(&auth_ctx.global_ctx, &name, &spec).await {
+        return Ok(status);
+    }
+
+    let status = process_transform_request(&auth_ctx.global_ctx, name, spec).await;
+    trace!("create transform response {:#?}", status);
+
+    Ok(status)
+}
+
+/// transform must read from and write to existing distinct topics and run at least one SmartModule
+async fn validate_transform(ctx: &Context, name: &str, spec: &TransformSpec) -> Option<Status> {
+    if spec.transforms.is_empty() {
+        return Some(Status::new(
+            name.to_owned(),
+            ErrorCode::TransformError,
+            Some("transform requires at least one SmartModule".to_owned()),
+        ));
+    }
+
+    if spec.source == spec.target {
+        return Some(Status::new(
+            name.to_owned(),
+            ErrorCode::TransformError,
+            Some("source and target topic must be different".to_owned()),
+        ));
+    }
+
+    let dead_letter_topics = spec
+        .transforms
+        .iter()
+        .filter_map(|step| match &step.on_error {
+            SmartModuleErrorPolicy::DeadLetter(topic) => Some(topic),
+            _ => None,
+        });
+    for topic in [&spec.source, &spec.target]
+        .into_iter()
+        .chain(dead_letter_topics)
+    {
+        if !ctx.topics().store().contains_key(topic).await {
+            debug!(%topic, "transform topic not found");
+            return Some(Status::new(
+                name.to_owned(),
+                ErrorCode::TopicNotFound,
+                Some(format!("topic '{topic}' not found")),
+            ));
+        }
+    }
+
+    None
+}
+
+/// Process transform, converts transform spec to K8 and sends to KV store
+#[instrument(skip(ctx, name, transform_spec))]
+async fn process_transform_request(
+    ctx: &Context,
+    name: String,
+    transform_spec: TransformSpec,
+) -> Status {
+    if let Err(err) = ctx
+        .transforms()
+        .create_spec(name.clone(), transform_spec)
+        .await
+    {
+        let error = Some(err.to_string());
+        Status::new(name, ErrorCode::TransformError, error)
+    } else {
+        info!(%name, "transform created");
+        Status::new_ok(name.clone())
+    }
+}
@@ -18,6 +18,11 @@ use fluvio_controlplane_metadata::spu::SpuSpec;
 use fluvio_controlplane_metadata::topic::TopicSpec;
 use fluvio_controlplane_metadata::smartmodule::SmartModuleSpec;
 use fluvio_controlplane_metadata::tableformat::TableFormatSpec;
+use fluvio_controlplane_metadata::transform::TransformSpec;
+use fluvio_controlplane_metadata::quota::QuotaSpec;
+
+use fluvio_controlplane_metadata::extended::SpecExt;
+use fluvio_auth::AuthContext;
 
 use crate::services::auth::AuthServiceContext;
 use crate::stores::{StoreContext, K8ChangeListener};
@@ -27,10 +32,13 @@ use fluvio_controlplane_metadata::spg::SpuGroupSpec;
 #[instrument(skip(request, auth_ctx, sink, end_event))]
 pub fn handle_watch_request<AC>(
     request: RequestMessage<ObjectApiWatchRequest>,
-    auth_ctx: &AuthServiceContext<AC>,
+    auth_ctx: &Arc<AuthServiceContext<AC>>,
     sink: ExclusiveFlvSink,
     end_event: Arc<StickyEvent>,
-) -> Result<()> {
+) -> Result<()>
+where
+    AC: AuthContext + Send + Sync + 'static,
+{
     let (header, req) = request.get_header_request();
     debug!("handling watch header: {:#?}, request: {:#?}", header, req);
 
@@ -38,6 +46,7 @@ pub fn handle_watch_request<AC>(
         WatchController::<TopicSpec>::update(
             sink,
             end_event,
+            auth_ctx.clone(),
             auth_ctx.global_ctx.topics().clone(),
             header,
             false,
@@ -46,6 +55,7 @@ pub fn handle_watch_request<AC>(
         WatchController::<SpuSpec>::update(
             sink,
             end_event,
+            auth_ctx.clone(),
             auth_ctx.global_ctx.spus().clone(),
             header,
             false,
@@ -54,6 +64,7 @@ pub fn handle_watch_request<AC>(
         WatchController::<SpuGroupSpec>::update(
             sink,
             end_event,
+            auth_ctx.clone(),
             auth_ctx.global_ctx.spgs().clone(),
             header,
             false,
@@ -62,6 +73,7 @@ pub fn handle_watch_request<AC>(
         WatchController::<PartitionSpec>::update(
             sink,
             end_event,
+            auth_ctx.clone(),
             auth_ctx.global_ctx.partitions().clone(),
             header,
             false,
@@ -70,6 +82,7 @@ pub fn handle_watch_request<AC>(
         WatchController::<SmartModuleSpec>::update(
             sink,
             end_event,
+            auth_ctx.clone(),
             auth_ctx.global_ctx.smartmodules().clone(),
             header,
             req.summary,
@@ -78,10 +91,29 @@ pub fn handle_watch_request<AC>(
         WatchController::<TableFormatSpec>::update(
             sink,
             end_event,
+            auth_ctx.clone(),
             auth_ctx.global_ctx.tableformats().clone(),
             header,
             false,
         )
+    } else if (req.downcast()? as Option<WatchRequest<TransformSpec>>).is_some() {
+        WatchController::<TransformSpec>::update(
+            sink,
+            end_event,
+            auth_ctx.clone(),
+            auth_ctx.global_ctx.transforms().clone(),
+            header,
+            false,
+        )
+    } else if (req.downcast()? as Option<WatchRequest<QuotaSpec>>).is_some() {
+        WatchController::<QuotaSpec>::update(
+            sink,
+            end_event,
+            auth_ctx.clone(),
+            auth_ctx.global_ctx.quotas().clone(),
+            header,
+            false,
+        )
     } else {
         debug!("Invalid Watch Req {:?}", req);
         return Err(anyhow!("Not Valid Watch Request",));
@@ -91,17 +123,19 @@ pub fn handle_watch_request<AC>(
 }
 
 /// Watch controller for each object.  Note that return type may or not be the same as the object hence two separate spec
-struct WatchController<S: AdminSpec> {
+struct WatchController<S: AdminSpec, AC> {
     response_sink: ExclusiveFlvSink,
+    auth_ctx: Arc<AuthServiceContext<AC>>,
     store: StoreContext<S>,
     header: RequestHeader,
     summary: bool,
     end_event: Arc<StickyEvent>,
 }
 
-impl<S> WatchController<S>
+impl<S, AC> WatchController<S, AC>
 where
-    S: AdminSpec + 'static,
+    AC: AuthContext + Send + Sync + 'static,
+    S: AdminSpec + SpecExt + 'static,
     S: Encoder + Decoder + Send + Sync,
     S::Status: Encoder + Decoder + Send + Sync,
     S::IndexKey: ToString + Send + Sync,
@@ -110,6 +144,7 @@ where
     fn update(
         response_sink: ExclusiveFlvSink,
         end_event: Arc<StickyEvent>,
+        auth_ctx: Arc<AuthServiceContext<AC>>,
         store: StoreContext<S>,
         header: RequestHeader,
         summary: bool,
@@ -118,6 +153,7 @@ where
 
         let controller = Self {
             response_sink,
+            auth_ctx,
             store,
             header,
             end_event,
@@ -186,23 +222,26 @@ where
             let (updates, _) = changes.parts();
             MetadataUpdate::with_all(
                 epoch,
-                updates
+                self.readable(updates.into_iter().map(|u| u.into()).collect())
+                    .await
                     .into_iter()
-                    .map(|u| u.into())
                     .map(|d: Metadata<S>| if self.summary { d.summary() } else { d })
                     .collect(),
             )
         } else {
             let (updates, deletes) = changes.parts();
-            let mut changes: Vec<Message<Metadata<S>>> = updates
+            let mut changes: Vec<Message<Metadata<S>>> = self
+                .readable(updates.into_iter().map(|u| u.into()).collect())
+                .await
                 .into_iter()
-                .map(|u| u.into())
                 .map(|d: Metadata<S>| if self.summary { d.summary() } else { d })
                 .map(Message::update)
                 .collect();
-            let mut deletes = deletes
+            let mut deletes = self
+                .readable(deletes.into_iter().map(|d| d.into()).collect())
+                .await
                 .into_iter()
-                .map(|d| Message::delete(d.into()))
+                .map(Message::delete)
                 .collect();
             changes.append(&mut deletes);
             MetadataUpdate::with_changes(epoch, changes)
@@ -238,4 +277,17 @@ where
 
         true
     }
+
+    /// only keep objects which identity is allowed to read
+    async fn readable(&self, objects: Vec<Metadata<S>>) -> Vec<Metadata<S>> {
+        let mut allowed = vec![];
+        for object in objects {
+            match self.auth_ctx.allow_read(S::OBJECT_TYPE, &object.name).await {
+                Ok(true) => allowed.push(object),
+                Ok(false) => trace!(name = %object.name, "not authorized to watch"),
+                Err(err) => error!("authorization error: {}", err),
+            }
+        }
+        allowed
+    }
 }
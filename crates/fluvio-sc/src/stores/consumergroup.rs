@@ -0,0 +1 @@
+pub use fluvio_controlplane_metadata::consumergroup::*;
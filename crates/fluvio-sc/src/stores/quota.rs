@@ -0,0 +1,2 @@
+pub use fluvio_controlplane_metadata::quota::*;
+pub use fluvio_controlplane_metadata::store::k8::K8MetaItem;
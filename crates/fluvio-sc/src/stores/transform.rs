@@ -0,0 +1,2 @@
+pub use fluvio_controlplane_metadata::transform::*;
+pub use fluvio_controlplane_metadata::store::k8::K8MetaItem;
@@ -1,20 +1,74 @@
+use std::time::Duration;
+
 use derive_builder::Builder;
-use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleErrorPolicy, SmartModuleExtraParams, SmartModuleWindowConfig,
+};
 
 const DEFAULT_SMARTENGINE_VERSION: i16 = 17;
 
+// DO NOT INCREASE THIS VALUE HIGHER THAN i64::MAX / 2.
+// WASMTIME keeps fuel as i64 and has some strange behavior with `add_fuel` if trying to top fuel
+// up to a values close to i64:MAX
+pub(crate) const DEFAULT_FUEL: u64 = i64::MAX as u64 / 2;
+const DEFAULT_MAX_MEMORY_BYTES: usize = 1024 * 1024 * 1024;
+const DEFAULT_MAX_TABLE_ELEMENTS: u32 = 100_000;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Resources SmartModule can use.
+/// Execution exceeding any of them is stopped with runtime error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmartModuleLimits {
+    /// fuel available for each invocation
+    pub fuel: u64,
+    /// max size of each linear memory
+    pub max_memory_bytes: usize,
+    /// max number of elements of each table
+    pub max_table_elements: u32,
+    /// max wall-clock time of each invocation
+    pub timeout: Duration,
+}
+
+impl Default for SmartModuleLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_FUEL,
+            max_memory_bytes: DEFAULT_MAX_MEMORY_BYTES,
+            max_table_elements: DEFAULT_MAX_TABLE_ELEMENTS,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl SmartModuleLimits {
+    /// limits allowing everything either of limits allows
+    pub(crate) fn max(self, other: Self) -> Self {
+        Self {
+            fuel: self.fuel.max(other.fuel),
+            max_memory_bytes: self.max_memory_bytes.max(other.max_memory_bytes),
+            max_table_elements: self.max_table_elements.max(other.max_table_elements),
+            timeout: self.timeout.max(other.timeout),
+        }
+    }
+}
+
 /// Initial seed data to passed, this will be send back as part of the output
 #[derive(Debug, Clone)]
 #[non_exhaustive]
 pub enum SmartModuleInitialData {
     None,
     Aggregate { accumulator: Vec<u8> },
+    Window { config: SmartModuleWindowConfig },
 }
 
 impl SmartModuleInitialData {
     pub fn with_aggregate(accumulator: Vec<u8>) -> Self {
         Self::Aggregate { accumulator }
     }
+
+    pub fn with_window(config: SmartModuleWindowConfig) -> Self {
+        Self::Window { config }
+    }
 }
 
 impl Default for SmartModuleInitialData {
@@ -33,6 +87,10 @@ pub struct SmartModuleConfig {
     // this will be deprecated in the future
     #[builder(default, setter(into, strip_option))]
     pub(crate) version: Option<i16>,
+    #[builder(default)]
+    pub(crate) limits: SmartModuleLimits,
+    #[builder(default)]
+    pub(crate) error_policy: SmartModuleErrorPolicy,
 }
 
 impl SmartModuleConfigBuilder {
@@ -68,6 +126,8 @@ impl From<crate::transformation::TransformationStep> for SmartModuleConfig {
                 .collect::<std::collections::BTreeMap<String, String>>()
                 .into(),
             version: None,
+            limits: SmartModuleLimits::default(),
+            error_policy: step.on_error.into(),
         }
     }
 }
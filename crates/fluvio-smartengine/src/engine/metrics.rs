@@ -8,6 +8,7 @@ pub struct SmartModuleChainMetrics {
     records_out: AtomicU64,
     invocation_count: AtomicU64,
     fuel_used: AtomicU64,
+    records_failed: AtomicU64,
 }
 
 impl SmartModuleChainMetrics {
@@ -24,6 +25,10 @@ impl SmartModuleChainMetrics {
         self.fuel_used.fetch_add(value, Ordering::SeqCst);
     }
 
+    pub fn add_records_failed(&self, value: u64) {
+        self.records_failed.fetch_add(value, Ordering::SeqCst);
+    }
+
     pub fn bytes_in(&self) -> u64 {
         self.bytes_in.load(Ordering::SeqCst)
     }
@@ -38,4 +43,22 @@ impl SmartModuleChainMetrics {
     pub fn invocation_count(&self) -> u64 {
         self.invocation_count.load(Ordering::SeqCst)
     }
+
+    /// records skipped or dead-lettered because SmartModule failed to process them
+    pub fn records_failed(&self) -> u64 {
+        self.records_failed.load(Ordering::SeqCst)
+    }
+
+    /// accumulate counters of other metrics into this one
+    pub fn add(&self, other: &SmartModuleChainMetrics) {
+        self.bytes_in.fetch_add(other.bytes_in(), Ordering::SeqCst);
+        self.records_out
+            .fetch_add(other.records_out(), Ordering::SeqCst);
+        self.invocation_count
+            .fetch_add(other.invocation_count(), Ordering::SeqCst);
+        self.fuel_used
+            .fetch_add(other.fuel_used(), Ordering::SeqCst);
+        self.records_failed
+            .fetch_add(other.records_failed(), Ordering::SeqCst);
+    }
 }
@@ -3,7 +3,7 @@ pub mod metrics;
 mod config;
 pub use config::{
     SmartModuleConfig, SmartModuleConfigBuilder, SmartModuleConfigBuilderError,
-    SmartModuleInitialData,
+    SmartModuleInitialData, SmartModuleLimits,
 };
 mod error;
 
@@ -14,4 +14,6 @@ pub type WasmSlice = (i32, i32, u32);
 pub type Version = i16;
 
 mod wasmtime;
-pub use self::wasmtime::{SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance};
+pub use self::wasmtime::{
+    SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance, SmartModuleDeadLetter,
+};
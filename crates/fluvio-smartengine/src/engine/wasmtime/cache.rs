@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use anyhow::Result;
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+use wasmtime::{Engine, Module};
+
+const ARTIFACT_EXTENSION: &str = "cwasm";
+
+/// max compiled modules kept, ad-hoc modules sent by consumers are cached as well
+pub(crate) const DEFAULT_MAX_MODULES: usize = 64;
+
+#[derive(Debug)]
+struct CachedModule {
+    module: Module,
+    last_used: u64,
+}
+
+/// Compiled modules keyed by hash of wasm bytes.
+/// If directory is set, compiled artifacts are also stored on disk and survive restarts.
+/// Least recently used module is dropped from memory and disk once there are more than max modules.
+#[derive(Debug)]
+pub(crate) struct ModuleCache {
+    modules: Mutex<HashMap<String, CachedModule>>,
+    clock: AtomicU64,
+    max_modules: usize,
+    dir: Option<PathBuf>,
+}
+
+impl Default for ModuleCache {
+    fn default() -> Self {
+        Self::new(None)
+    }
+}
+
+impl ModuleCache {
+    pub(crate) fn new(dir: Option<PathBuf>) -> Self {
+        Self::with_max_modules(dir, DEFAULT_MAX_MODULES)
+    }
+
+    pub(crate) fn with_max_modules(dir: Option<PathBuf>, max_modules: usize) -> Self {
+        Self {
+            modules: Mutex::new(HashMap::new()),
+            clock: AtomicU64::new(0),
+            max_modules: max_modules.max(1),
+            dir,
+        }
+    }
+
+    fn tick(&self) -> u64 {
+        self.clock.fetch_add(1, Ordering::Relaxed)
+    }
+
+    fn key(bytes: &[u8]) -> String {
+        Sha256::digest(bytes)
+            .iter()
+            .fold(String::with_capacity(64), |mut key, byte| {
+                let _ = write!(key, "{byte:02x}");
+                key
+            })
+    }
+
+    fn artifact_path(&self, key: &str) -> Option<PathBuf> {
+        self.dir
+            .as_ref()
+            .map(|dir| dir.join(key).with_extension(ARTIFACT_EXTENSION))
+    }
+
+    /// compiled module for wasm bytes, compiles only if not found in memory or on disk
+    pub(crate) fn get_or_compile(&self, engine: &Engine, bytes: &[u8]) -> Result<Module> {
+        let key = Self::key(bytes);
+        if let Ok(mut modules) = self.modules.lock() {
+            if let Some(cached) = modules.get_mut(&key) {
+                debug!(%key, "compiled module found");
+                cached.last_used = self.tick();
+                return Ok(cached.module.clone());
+            }
+        }
+
+        let module = match self.load_artifact(engine, &key) {
+            Some(module) => module,
+            None => {
+                debug!(%key, len = bytes.len(), "compiling module");
+                let module = Module::new(engine, bytes)?;
+                self.store_artifact(&key, &module);
+                module
+            }
+        };
+
+        self.insert(key, module.clone());
+        Ok(module)
+    }
+
+    fn insert(&self, key: String, module: Module) {
+        let mut modules = match self.modules.lock() {
+            Ok(modules) => modules,
+            Err(_) => return,
+        };
+        let last_used = self.tick();
+        modules.insert(key, CachedModule { module, last_used });
+        while modules.len() > self.max_modules {
+            let oldest = modules
+                .iter()
+                .min_by_key(|(_, cached)| cached.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                debug!(key = %oldest, "dropping least recently used module");
+                modules.remove(&oldest);
+                self.remove_artifact(&oldest);
+            }
+        }
+    }
+
+    fn load_artifact(&self, engine: &Engine, key: &str) -> Option<Module> {
+        let path = self.artifact_path(key)?;
+        if !path.exists() {
+            return None;
+        }
+        // SAFETY: artifacts are only written by `store_artifact` from modules compiled by wasmtime,
+        // incompatible engine or wasmtime version is rejected by `deserialize_file`
+        match unsafe { Module::deserialize_file(engine, &path) } {
+            Ok(module) => {
+                debug!(%key, "loaded compiled module from disk");
+                Some(module)
+            }
+            Err(err) => {
+                warn!(%key, %err, "discarding compiled module artifact");
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    fn store_artifact(&self, key: &str, module: &Module) {
+        let path = match self.artifact_path(key) {
+            Some(path) => path,
+            None => return,
+        };
+        let result = module.serialize().and_then(|bytes| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            // write to temporary file first so partial artifact is never loaded
+            let tmp = path.with_extension("tmp");
+            fs::write(&tmp, bytes)?;
+            fs::rename(&tmp, &path)?;
+            Ok(())
+        });
+        if let Err(err) = result {
+            warn!(%key, %err, "unable to store compiled module");
+        }
+    }
+
+    /// remove module compiled from wasm bytes from memory and disk
+    pub(crate) fn evict(&self, bytes: &[u8]) {
+        let key = Self::key(bytes);
+        if let Ok(mut modules) = self.modules.lock() {
+            if modules.remove(&key).is_some() {
+                debug!(%key, "evicted compiled module");
+            }
+        }
+        self.remove_artifact(&key);
+    }
+
+    fn remove_artifact(&self, key: &str) {
+        if let Some(path) = self.artifact_path(key) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn len(&self) -> usize {
+        self.modules.lock().map(|m| m.len()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use wasmtime::Engine;
+
+    use super::ModuleCache;
+
+    const MODULE: &[u8] = b"(module (func (export \"noop\")))";
+
+    #[test]
+    fn test_module_cache() {
+        let engine = Engine::default();
+        let cache = ModuleCache::default();
+
+        cache.get_or_compile(&engine, MODULE).expect("compile");
+        cache.get_or_compile(&engine, MODULE).expect("cached");
+        assert_eq!(cache.len(), 1);
+
+        cache.evict(MODULE);
+        assert_eq!(cache.len(), 0);
+    }
+
+    #[test]
+    fn test_module_cache_max_modules() {
+        const OTHER: &[u8] = b"(module (func (export \"other\")))";
+        const THIRD: &[u8] = b"(module (func (export \"third\")))";
+
+        let engine = Engine::default();
+        let cache = ModuleCache::with_max_modules(None, 2);
+
+        cache.get_or_compile(&engine, MODULE).expect("compile");
+        cache.get_or_compile(&engine, OTHER).expect("compile");
+        // module is used again, so other is least recently used
+        cache.get_or_compile(&engine, MODULE).expect("cached");
+        cache.get_or_compile(&engine, THIRD).expect("compile");
+        assert_eq!(cache.len(), 2);
+
+        let modules = cache.modules.lock().expect("lock");
+        assert!(modules.contains_key(&ModuleCache::key(MODULE)));
+        assert!(!modules.contains_key(&ModuleCache::key(OTHER)));
+    }
+
+    #[test]
+    fn test_module_cache_artifact() {
+        let engine = Engine::default();
+        let dir = std::env::temp_dir().join("fluvio-smartengine-cache-test");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let cache = ModuleCache::new(Some(dir.clone()));
+        cache.get_or_compile(&engine, MODULE).expect("compile");
+        let path = cache
+            .artifact_path(&ModuleCache::key(MODULE))
+            .expect("path");
+        assert!(path.exists());
+
+        // new cache, module is loaded from disk
+        let cache = ModuleCache::new(Some(dir));
+        let module = cache.get_or_compile(&engine, MODULE).expect("load");
+        assert!(module.get_export("noop").is_some());
+
+        cache.evict(MODULE);
+        assert!(!path.exists());
+    }
+}
@@ -1,33 +1,107 @@
 use std::fmt::{self, Debug};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::Duration;
 
 use anyhow::Result;
-use tracing::debug;
-use wasmtime::{Engine, Module};
+use tracing::{debug, warn};
+use wasmtime::Engine;
 
-use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleInput, SmartModuleOutput};
+use fluvio_protocol::record::Offset;
+use fluvio_smartmodule::Record;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleErrorPolicy, SmartModuleInput, SmartModuleKind, SmartModuleOutput,
+    SmartModuleTransformRuntimeError,
+};
 
-use crate::SmartModuleConfig;
+use crate::{SmartModuleConfig, SmartModuleLimits};
 
+use super::cache::ModuleCache;
 use super::init::SmartModuleInit;
 use super::instance::{SmartModuleInstance, SmartModuleInstanceContext};
 
 use super::metrics::SmartModuleChainMetrics;
 use super::state::WasmState;
-use super::transforms::create_transform;
+use super::transforms::{
+    create_transform, AGGREGATE_FN_NAME, ARRAY_MAP_FN_NAME, FILTER_FN_NAME, FILTER_MAP_FN_NAME,
+    MAP_FN_NAME, WINDOW_FN_NAME,
+};
+
+/// interval of engine epoch, granularity of SmartModule timeout
+pub(crate) const EPOCH_TICK: Duration = Duration::from_millis(10);
+
+/// Advances engine epoch so long running invocations hit their deadline.
+/// Thread is stopped when last engine is dropped.
+struct EpochTicker {
+    stop: Arc<AtomicBool>,
+}
+
+impl EpochTicker {
+    fn start(engine: Engine) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let spawned = thread::Builder::new()
+            .name("smartengine-epoch".to_owned())
+            .spawn(move || {
+                while !stopped.load(Ordering::Relaxed) {
+                    thread::sleep(EPOCH_TICK);
+                    engine.increment_epoch();
+                }
+            });
+        if let Err(err) = spawned {
+            warn!(%err, "unable to start epoch ticker, SmartModule timeout is disabled");
+        }
+        Self { stop }
+    }
+}
+
+impl Drop for EpochTicker {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
 
 #[derive(Clone)]
-pub struct SmartEngine(Engine);
+pub struct SmartEngine {
+    engine: Engine,
+    cache: Arc<ModuleCache>,
+    _ticker: Arc<EpochTicker>,
+}
 
 #[allow(clippy::new_without_default)]
 impl SmartEngine {
     pub fn new() -> Self {
         let mut config = wasmtime::Config::default();
         config.consume_fuel(true);
-        Self(Engine::new(&config).expect("Config is static"))
+        config.epoch_interruption(true);
+        let engine = Engine::new(&config).expect("Config is static");
+        Self {
+            _ticker: Arc::new(EpochTicker::start(engine.clone())),
+            engine,
+            cache: Arc::new(ModuleCache::default()),
+        }
+    }
+
+    /// store compiled modules in directory so they are not recompiled after restart
+    pub fn with_cache_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.cache = Arc::new(ModuleCache::new(Some(dir.into())));
+        self
+    }
+
+    /// drop compiled module of wasm bytes, used when SmartModule is changed or deleted
+    pub fn evict_module(&self, bytes: &[u8]) {
+        self.cache.evict(bytes)
     }
 
-    pub(crate) fn new_state(&self) -> WasmState {
-        WasmState::new(&self.0)
+    pub(crate) fn new_state(&self, limits: SmartModuleLimits) -> WasmState {
+        WasmState::new(&self.engine, limits)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn engine(&self) -> &Engine {
+        &self.engine
     }
 }
 
@@ -52,9 +126,16 @@ impl SmartModuleChainBuilder {
     /// stop adding smartmodule and return SmartModuleChain that can be executed
     pub fn initialize(self, engine: &SmartEngine) -> Result<SmartModuleChainInstance> {
         let mut instances = Vec::with_capacity(self.smart_modules.len());
-        let mut state = engine.new_state();
+        // store is shared by all modules in the chain, so it allows what any of them allows
+        let limits = self
+            .smart_modules
+            .iter()
+            .map(|(config, _)| config.limits)
+            .reduce(SmartModuleLimits::max)
+            .unwrap_or_default();
+        let mut state = engine.new_state(limits);
         for (config, bytes) in self.smart_modules {
-            let module = Module::new(&engine.0, bytes)?;
+            let module = engine.cache.get_or_compile(&engine.engine, &bytes)?;
             let version = config.version();
             let ctx = SmartModuleInstanceContext::instantiate(
                 &mut state,
@@ -64,13 +145,14 @@ impl SmartModuleChainBuilder {
             )?;
             let init = SmartModuleInit::try_instantiate(&ctx, &mut state)?;
             let transform = create_transform(&ctx, config.initial_data, &mut state)?;
-            let mut instance = SmartModuleInstance::new(ctx, init, transform);
+            let mut instance = SmartModuleInstance::new(ctx, init, transform, config.error_policy);
             instance.init(&mut state)?;
             instances.push(instance);
         }
         Ok(SmartModuleChainInstance {
             store: state,
             instances,
+            dead_letters: vec![],
         })
     }
 }
@@ -83,10 +165,20 @@ impl<T: Into<Vec<u8>>> From<(SmartModuleConfig, T)> for SmartModuleChainBuilder
     }
 }
 
+/// Record SmartModule failed to process, to be written to dead-letter topic
+#[derive(Debug)]
+pub struct SmartModuleDeadLetter {
+    /// topic failed record is written to
+    pub topic: String,
+    /// error together with failed record
+    pub error: SmartModuleTransformRuntimeError,
+}
+
 /// SmartModule Chain Instance that can be executed
 pub struct SmartModuleChainInstance {
     store: WasmState,
     instances: Vec<SmartModuleInstance>,
+    dead_letters: Vec<SmartModuleDeadLetter>,
 }
 
 impl Debug for SmartModuleChainInstance {
@@ -122,11 +214,13 @@ impl SmartModuleChainInstance {
             for instance in instances {
                 // pass raw inputs to transform instance
                 // each raw input may result in multiple records
-                self.store.top_up_fuel();
-                let output = instance.process(next_input, &mut self.store)?;
-                let fuel_used = self.store.get_used_fuel();
-                debug!(fuel_used, "fuel used");
-                metric.add_fuel_used(fuel_used);
+                let output = Self::process_with_policy(
+                    instance,
+                    next_input,
+                    &mut self.store,
+                    &mut self.dead_letters,
+                    metric,
+                )?;
 
                 if output.error.is_some() {
                     // encountered error, we stop processing and return partial output
@@ -137,11 +231,13 @@ impl SmartModuleChainInstance {
                 }
             }
 
-            self.store.top_up_fuel();
-            let output = last.process(next_input, &mut self.store)?;
-            let fuel_used = self.store.get_used_fuel();
-            debug!(fuel_used, "fuel used");
-            metric.add_fuel_used(fuel_used);
+            let output = Self::process_with_policy(
+                last,
+                next_input,
+                &mut self.store,
+                &mut self.dead_letters,
+                metric,
+            )?;
             let records_out = output.successes.len();
             metric.add_records_out(records_out as u64);
             debug!(records_out, "sm records out");
@@ -150,13 +246,165 @@ impl SmartModuleChainInstance {
             Ok(SmartModuleOutput::new(input.try_into()?))
         }
     }
+
+    /// records failed with dead-letter policy since last call, to be written to their topics
+    pub fn take_dead_letters(&mut self) -> Vec<SmartModuleDeadLetter> {
+        std::mem::take(&mut self.dead_letters)
+    }
+
+    /// state of each SmartModule in the chain, none for stateless ones
+    pub fn state(&self) -> Vec<Option<Vec<u8>>> {
+        self.instances
+            .iter()
+            .map(|instance| instance.state())
+            .collect()
+    }
+
+    /// restore state captured by [`Self::state`], state of different chain is rejected
+    pub fn restore_state(&mut self, state: Vec<Option<Vec<u8>>>) -> Result<()> {
+        let matches = state.len() == self.instances.len()
+            && state
+                .iter()
+                .zip(self.instances.iter())
+                .all(|(state, instance)| state.is_some() == instance.state().is_some());
+        if !matches {
+            anyhow::bail!("state doesn't match SmartModule chain");
+        }
+        for (state, instance) in state.into_iter().zip(self.instances.iter_mut()) {
+            if let Some(state) = state {
+                instance.restore_state(state);
+            }
+        }
+        Ok(())
+    }
+
+    /// process input by single instance applying its error policy.
+    /// Record failed with skip or dead-letter policy is dropped and processing
+    /// continues with records after it.
+    fn process_with_policy(
+        instance: &mut SmartModuleInstance,
+        input: SmartModuleInput,
+        store: &mut WasmState,
+        dead_letters: &mut Vec<SmartModuleDeadLetter>,
+        metric: &SmartModuleChainMetrics,
+    ) -> Result<SmartModuleOutput> {
+        let base_offset = input.base_offset();
+        let base_timestamp = input.base_timestamp();
+        let mut records: Vec<Record> = match instance.error_policy() {
+            SmartModuleErrorPolicy::Fail => vec![],
+            _ => input.clone().try_into()?,
+        };
+        let mut next_input = input;
+        let mut successes = vec![];
+        loop {
+            let output = Self::process_instance(instance, next_input, store)?;
+            let fuel_used = store.get_used_fuel();
+            debug!(fuel_used, "fuel used");
+            metric.add_fuel_used(fuel_used);
+            successes.extend(output.successes);
+
+            let mut error = match output.error {
+                Some(error) => error,
+                None => return Ok(SmartModuleOutput::new(successes)),
+            };
+            let remaining = records_after(&records, base_offset, error.offset);
+            // nothing to continue with if failed record is unknown
+            if *instance.error_policy() == SmartModuleErrorPolicy::Fail
+                || remaining.len() == records.len()
+            {
+                return Ok(SmartModuleOutput {
+                    successes,
+                    error: Some(error),
+                });
+            }
+
+            // limit errors don't carry record, so it is taken from input
+            if error.record_value.as_ref().is_empty() {
+                if let Some(record) = records
+                    .iter()
+                    .find(|record| base_offset + record.preamble.offset_delta() == error.offset)
+                {
+                    error.record_key = record.key.clone();
+                    error.record_value = record.value.clone();
+                }
+            }
+            metric.add_records_failed(1);
+            match instance.error_policy() {
+                SmartModuleErrorPolicy::DeadLetter(topic) => {
+                    debug!(%error, %topic, "dead-lettering record failed in SmartModule");
+                    dead_letters.push(SmartModuleDeadLetter {
+                        topic: topic.clone(),
+                        error,
+                    });
+                }
+                _ => debug!(%error, "skipping record failed in SmartModule"),
+            }
+
+            if remaining.is_empty() {
+                return Ok(SmartModuleOutput::new(successes));
+            }
+            records = remaining;
+            next_input = records.clone().try_into()?;
+            next_input.set_base_offset(base_offset);
+            next_input.set_base_timestamp(base_timestamp);
+        }
+    }
+
+    /// process input by single instance, execution exceeding limits is reported as runtime error
+    fn process_instance(
+        instance: &mut SmartModuleInstance,
+        input: SmartModuleInput,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let base_offset = input.base_offset();
+        store.start_invocation();
+        match instance.process(input, store) {
+            Ok(output) => Ok(output),
+            Err(err) => match store.exceeded_limit(&err) {
+                Some(error_kind) => {
+                    warn!(%error_kind, "SmartModule exceeded limit");
+                    let kind = match instance.transform_name() {
+                        FILTER_FN_NAME => SmartModuleKind::Filter,
+                        MAP_FN_NAME => SmartModuleKind::Map,
+                        FILTER_MAP_FN_NAME => SmartModuleKind::FilterMap,
+                        ARRAY_MAP_FN_NAME => SmartModuleKind::ArrayMap,
+                        AGGREGATE_FN_NAME => SmartModuleKind::Aggregate,
+                        WINDOW_FN_NAME => SmartModuleKind::Window,
+                        _ => SmartModuleKind::Generic,
+                    };
+                    Ok(SmartModuleOutput {
+                        successes: vec![],
+                        error: Some(SmartModuleTransformRuntimeError::limit_exceeded(
+                            base_offset,
+                            kind,
+                            error_kind,
+                        )),
+                    })
+                }
+                None => Err(err),
+            },
+        }
+    }
+}
+
+/// records following record at offset, records are processed in order so these are not processed yet
+fn records_after(records: &[Record], base_offset: Offset, offset: Offset) -> Vec<Record> {
+    records
+        .iter()
+        .filter(|record| base_offset + record.preamble.offset_delta() > offset)
+        .cloned()
+        .collect()
 }
 
 #[cfg(test)]
 mod test {
 
+    use fluvio_smartmodule::Record;
+
     use crate::SmartModuleConfig;
 
+    use super::records_after;
+
     #[test]
     fn test_param() {
         let config = SmartModuleConfig::builder()
@@ -166,6 +414,24 @@ mod test {
 
         assert_eq!(config.params.get("key"), Some(&"apple".to_string()));
     }
+
+    #[test]
+    fn test_records_after() {
+        let records: Vec<Record> = (0..4)
+            .map(|delta| {
+                let mut record = Record::new(delta.to_string());
+                record.preamble.set_offset_delta(delta);
+                record
+            })
+            .collect();
+
+        let remaining = records_after(&records, 10, 11);
+        assert_eq!(remaining.len(), 2);
+        assert_eq!(remaining[0].value.as_ref(), b"2");
+
+        assert_eq!(records_after(&records, 10, 9).len(), 4);
+        assert!(records_after(&records, 10, 13).is_empty());
+    }
 }
 
 #[cfg(test)]
@@ -291,6 +557,49 @@ mod chaining_test {
             output.successes[0].value().to_string(),
             "zeroapplebananaelephant"
         );
+
+        let state = chain.state();
+        assert_eq!(state, vec![None, Some(b"zeroapplebananaelephant".to_vec())]);
+
+        // new chain resumes from state of previous one
+        let mut chain_builder = SmartModuleChainBuilder::default();
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder()
+                .param("key", "a")
+                .build()
+                .unwrap(),
+            read_wasm_module(SM_FILTER_INIT),
+        );
+        chain_builder.add_smart_module(
+            SmartModuleConfig::builder().build().unwrap(),
+            read_wasm_module(SM_AGGEGRATE),
+        );
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .expect("failed to build chain");
+        chain.restore_state(state).expect("restore");
+
+        let input = vec![Record::new("grape")];
+        let output = chain
+            .process(SmartModuleInput::try_from(input).expect("input"), &metrics)
+            .expect("process");
+        assert_eq!(output.successes.len(), 1);
+        assert_eq!(
+            output.successes[0].value().to_string(),
+            "zeroapplebananaelephantgrape"
+        );
+    }
+
+    #[test]
+    fn test_restore_state_of_other_chain() {
+        let engine = SmartEngine::new();
+        let mut chain = SmartModuleChainBuilder::default()
+            .initialize(&engine)
+            .expect("failed to build chain");
+
+        assert!(chain.state().is_empty());
+        assert!(chain.restore_state(vec![]).is_ok());
+        assert!(chain.restore_state(vec![Some(b"sum".to_vec())]).is_err());
     }
 
     #[test]
@@ -9,7 +9,8 @@ use wasmtime::{Memory, Module, Caller, Extern, Instance, Func, AsContextMut, AsC
 use fluvio_protocol::{Encoder, Decoder};
 
 use fluvio_smartmodule::dataplane::smartmodule::{
-    SmartModuleExtraParams, SmartModuleInput, SmartModuleOutput, SmartModuleInitInput,
+    SmartModuleErrorPolicy, SmartModuleExtraParams, SmartModuleInput, SmartModuleOutput,
+    SmartModuleInitInput,
 };
 
 use super::error::EngineError;
@@ -21,6 +22,7 @@ pub(crate) struct SmartModuleInstance {
     ctx: SmartModuleInstanceContext,
     init: Option<SmartModuleInit>,
     transform: Box<dyn DowncastableTransform>,
+    error_policy: SmartModuleErrorPolicy,
 }
 
 impl SmartModuleInstance {
@@ -39,11 +41,13 @@ impl SmartModuleInstance {
         ctx: SmartModuleInstanceContext,
         init: Option<SmartModuleInit>,
         transform: Box<dyn DowncastableTransform>,
+        error_policy: SmartModuleErrorPolicy,
     ) -> Self {
         Self {
             ctx,
             init,
             transform,
+            error_policy,
         }
     }
 
@@ -55,6 +59,22 @@ impl SmartModuleInstance {
         self.transform.process(input, &mut self.ctx, store)
     }
 
+    pub(crate) fn transform_name(&self) -> &str {
+        self.transform.name()
+    }
+
+    pub(crate) fn error_policy(&self) -> &SmartModuleErrorPolicy {
+        &self.error_policy
+    }
+
+    pub(crate) fn state(&self) -> Option<Vec<u8>> {
+        self.transform.state()
+    }
+
+    pub(crate) fn restore_state(&mut self, state: Vec<u8>) {
+        self.transform.restore_state(state)
+    }
+
     // TODO: Move this to SPU
 
     pub fn init(&mut self, store: &mut impl AsContextMut) -> Result<(), Error> {
@@ -165,6 +185,14 @@ pub(crate) trait SmartModuleTransform: Send + Sync {
 
     /// return name of transform, this is used for identifying transform and debugging
     fn name(&self) -> &str;
+
+    /// state kept between invocations, none for stateless transforms
+    fn state(&self) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// replace state, used when resuming from checkpoint
+    fn restore_state(&mut self, _state: Vec<u8>) {}
 }
 
 // In order turn to any, need following magic trick
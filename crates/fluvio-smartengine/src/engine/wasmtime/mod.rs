@@ -4,6 +4,9 @@ pub(crate) mod init;
 pub(crate) mod state;
 pub(crate) mod engine;
 pub(crate) mod instance;
-pub use engine::{SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance};
+mod cache;
+pub use engine::{
+    SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance, SmartModuleDeadLetter,
+};
 
 use super::*;
@@ -1,15 +1,15 @@
-use std::cmp::max;
-
 use anyhow::Error;
 use wasmtime::{
-    AsContext, AsContextMut, Engine, Instance, IntoFunc, Module, Store, StoreContext,
-    StoreContextMut,
+    AsContext, AsContextMut, Engine, Instance, IntoFunc, Module, ResourceLimiter, Store,
+    StoreContext, StoreContextMut, Trap,
 };
 
-// DO NOT INCREASE THIS VALUE HIGHER THAN i64::MAX / 2.
-// WASMTIME keeps fuel as i64 and has some strange behavior with `add_fuel` if trying to top fuel
-// up to a values close to i64:MAX
-const DEFAULT_FUEL: u64 = i64::MAX as u64 / 2;
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleRuntimeErrorKind;
+
+use crate::engine::SmartModuleLimits;
+use crate::engine::config::DEFAULT_FUEL;
+
+use super::engine::EPOCH_TICK;
 
 #[cfg(not(feature = "wasi"))]
 pub type WasmState = WasmStore<()>;
@@ -17,48 +17,137 @@ pub type WasmState = WasmStore<()>;
 #[cfg(feature = "wasi")]
 pub type WasmState = WasmStore<wasmtime_wasi::WasiCtx>;
 
+/// Store data, user context together with limiter of memory and tables
+#[derive(Debug)]
+pub struct WasmData<T> {
+    ctx: T,
+    limiter: Limiter,
+}
+
+/// Rejects growing memory or table over limit and remembers which limit was hit
+#[derive(Debug)]
+struct Limiter {
+    limits: SmartModuleLimits,
+    exceeded: Option<SmartModuleRuntimeErrorKind>,
+}
+
+impl Limiter {
+    fn exceed(&mut self, kind: SmartModuleRuntimeErrorKind) -> anyhow::Result<bool> {
+        self.exceeded = Some(kind);
+        Err(Error::msg(kind.to_string()))
+    }
+}
+
+impl ResourceLimiter for Limiter {
+    fn memory_growing(
+        &mut self,
+        _current: usize,
+        desired: usize,
+        _maximum: Option<usize>,
+    ) -> anyhow::Result<bool> {
+        if desired > self.limits.max_memory_bytes {
+            self.exceed(SmartModuleRuntimeErrorKind::MemoryLimitExceeded)
+        } else {
+            Ok(true)
+        }
+    }
+
+    fn table_growing(
+        &mut self,
+        _current: u32,
+        desired: u32,
+        _maximum: Option<u32>,
+    ) -> anyhow::Result<bool> {
+        if desired > self.limits.max_table_elements {
+            self.exceed(SmartModuleRuntimeErrorKind::TableLimitExceeded)
+        } else {
+            Ok(true)
+        }
+    }
+}
+
 #[derive(Debug)]
-pub struct WasmStore<T>(Store<T>);
+pub struct WasmStore<T> {
+    store: Store<WasmData<T>>,
+    limits: SmartModuleLimits,
+}
 
 impl<T> AsContext for WasmStore<T> {
-    type Data = T;
+    type Data = WasmData<T>;
 
     fn as_context(&self) -> StoreContext<'_, Self::Data> {
-        self.0.as_context()
+        self.store.as_context()
     }
 }
 
 impl<T> AsContextMut for WasmStore<T> {
     fn as_context_mut(&mut self) -> StoreContextMut<'_, Self::Data> {
-        self.0.as_context_mut()
+        self.store.as_context_mut()
     }
 }
 
-impl WasmState {
-    // If current fuel is less than DEFAULT_FUEL, tops up fuel to DEFAULT_FUEL
+impl<T> WasmStore<T> {
+    fn with_ctx(engine: &Engine, ctx: T, limits: SmartModuleLimits) -> Self {
+        let limits = SmartModuleLimits {
+            fuel: limits.fuel.min(DEFAULT_FUEL),
+            ..limits
+        };
+        let data = WasmData {
+            ctx,
+            limiter: Limiter {
+                limits,
+                exceeded: None,
+            },
+        };
+        let mut store = Store::new(engine, data);
+        store.limiter(|data| &mut data.limiter);
+        let mut s = Self { store, limits };
+        s.start_invocation();
+        s
+    }
+
+    // If current fuel is less than fuel limit, tops up fuel to the limit
     pub fn top_up_fuel(&mut self) {
-        if let Ok(current_fuel) = self.0.consume_fuel(0) {
-            let amount_to_add = max(DEFAULT_FUEL - current_fuel, 0);
-            let _ = self.0.add_fuel(amount_to_add);
+        if let Ok(current_fuel) = self.store.consume_fuel(0) {
+            let amount_to_add = self.limits.fuel.saturating_sub(current_fuel);
+            let _ = self.store.add_fuel(amount_to_add);
         }
     }
 
     // Get amount of fuel used since last top up
     pub fn get_used_fuel(&mut self) -> u64 {
-        if let Ok(current_fuel) = self.0.consume_fuel(0) {
-            max(DEFAULT_FUEL - current_fuel, 0)
+        if let Ok(current_fuel) = self.store.consume_fuel(0) {
+            self.limits.fuel.saturating_sub(current_fuel)
         } else {
             0
         }
     }
+
+    /// reset fuel, deadline and limiter before SmartModule is called
+    pub(crate) fn start_invocation(&mut self) {
+        self.top_up_fuel();
+        let ticks = self.limits.timeout.as_millis() / EPOCH_TICK.as_millis();
+        self.store.set_epoch_deadline(ticks.max(1) as u64);
+        self.store.data_mut().limiter.exceeded = None;
+    }
+
+    /// limit which stopped execution, none if error was caused by something else
+    pub(crate) fn exceeded_limit(&mut self, err: &Error) -> Option<SmartModuleRuntimeErrorKind> {
+        if let Some(kind) = self.store.data_mut().limiter.exceeded.take() {
+            return Some(kind);
+        }
+        match err.downcast_ref::<Trap>() {
+            Some(Trap::OutOfFuel) => Some(SmartModuleRuntimeErrorKind::FuelExhausted),
+            Some(Trap::Interrupt) => Some(SmartModuleRuntimeErrorKind::Timeout),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(not(feature = "wasi"))]
 impl WasmStore<()> {
-    pub(crate) fn new(engine: &Engine) -> Self {
-        let mut s = Self(Store::new(engine, ()));
-        s.top_up_fuel();
-        s
+    pub(crate) fn new(engine: &Engine, limits: SmartModuleLimits) -> Self {
+        Self::with_ctx(engine, (), limits)
     }
 
     pub(crate) fn instantiate<Params, Args>(
@@ -75,14 +164,12 @@ impl WasmStore<()> {
 
 #[cfg(feature = "wasi")]
 impl WasmStore<wasmtime_wasi::WasiCtx> {
-    pub(crate) fn new(engine: &Engine) -> Self {
+    pub(crate) fn new(engine: &Engine, limits: SmartModuleLimits) -> Self {
         let wasi = wasmtime_wasi::WasiCtxBuilder::new()
             .inherit_stderr()
             .inherit_stdout()
             .build();
-        let mut s = Self(Store::new(engine, wasi));
-        s.top_up_fuel();
-        s
+        Self::with_ctx(engine, wasi, limits)
     }
 
     pub(crate) fn instantiate<Params, Args>(
@@ -91,7 +178,7 @@ impl WasmStore<wasmtime_wasi::WasiCtx> {
         host_fn: impl IntoFunc<<Self as AsContext>::Data, Params, Args>,
     ) -> Result<Instance, Error> {
         let mut linker = wasmtime::Linker::new(module.engine());
-        wasmtime_wasi::add_to_linker(&mut linker, |c| c)?;
+        wasmtime_wasi::add_to_linker(&mut linker, |data: &mut WasmData<_>| &mut data.ctx)?;
         let copy_records_fn_import = module
             .imports()
             .find(|import| import.name().eq("copy_records"))
@@ -104,3 +191,67 @@ impl WasmStore<wasmtime_wasi::WasiCtx> {
         linker.instantiate(self, module)
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleRuntimeErrorKind;
+    use wasmtime::{Module, ResourceLimiter};
+
+    use crate::engine::SmartModuleLimits;
+    use crate::SmartEngine;
+
+    use super::Limiter;
+
+    #[test]
+    fn test_limiter() {
+        let mut limiter = Limiter {
+            limits: SmartModuleLimits {
+                max_memory_bytes: 1024,
+                max_table_elements: 10,
+                ..Default::default()
+            },
+            exceeded: None,
+        };
+
+        assert!(limiter.memory_growing(0, 1024, None).expect("grow"));
+        assert!(limiter.exceeded.is_none());
+        assert!(limiter.memory_growing(1024, 2048, None).is_err());
+        assert_eq!(
+            limiter.exceeded,
+            Some(SmartModuleRuntimeErrorKind::MemoryLimitExceeded)
+        );
+
+        assert!(limiter.table_growing(0, 10, None).expect("grow"));
+        assert!(limiter.table_growing(10, 11, None).is_err());
+        assert_eq!(
+            limiter.exceeded,
+            Some(SmartModuleRuntimeErrorKind::TableLimitExceeded)
+        );
+    }
+
+    #[cfg(not(feature = "wasi"))]
+    #[test]
+    fn test_memory_limit_on_instantiate() {
+        let engine = SmartEngine::new();
+        let limits = SmartModuleLimits {
+            max_memory_bytes: 65536,
+            ..Default::default()
+        };
+        let mut state = engine.new_state(limits);
+        // two pages of initial memory are over the limit
+        let module = Module::new(
+            engine.engine(),
+            "(module (import \"env\" \"copy_records\" (func (param i32 i32))) (memory 2))",
+        )
+        .expect("module");
+
+        let err = state
+            .instantiate(&module, |_: i32, _: i32| {})
+            .expect_err("memory limit");
+        assert_eq!(
+            state.exceeded_limit(&err),
+            Some(SmartModuleRuntimeErrorKind::MemoryLimitExceeded)
+        );
+    }
+}
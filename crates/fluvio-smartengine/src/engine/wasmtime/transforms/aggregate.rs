@@ -15,7 +15,7 @@ use crate::engine::wasmtime::{
     state::WasmState,
 };
 
-const AGGREGATE_FN_NAME: &str = "aggregate";
+pub(crate) const AGGREGATE_FN_NAME: &str = "aggregate";
 
 type WasmAggregateFn = TypedFunc<(i32, i32, u32), i32>;
 
@@ -44,7 +44,7 @@ impl SmartModuleAggregate {
         // get initial -data
         let accumulator = match initial_data {
             SmartModuleInitialData::Aggregate { accumulator } => accumulator,
-            SmartModuleInitialData::None => {
+            SmartModuleInitialData::None | SmartModuleInitialData::Window { .. } => {
                 // if no initial data, then we initialize as default
                 vec![]
             }
@@ -99,6 +99,14 @@ impl SmartModuleTransform for SmartModuleAggregate {
     fn name(&self) -> &str {
         AGGREGATE_FN_NAME
     }
+
+    fn state(&self) -> Option<Vec<u8>> {
+        Some(self.accumulator.clone())
+    }
+
+    fn restore_state(&mut self, state: Vec<u8>) {
+        self.accumulator = state;
+    }
 }
 
 #[cfg(test)]
@@ -3,7 +3,11 @@ mod map;
 mod array_map;
 mod filter_map;
 mod aggregate;
+mod window;
 pub(crate) use instance::create_transform;
+pub(crate) use aggregate::AGGREGATE_FN_NAME;
+pub(crate) use window::WINDOW_FN_NAME;
+pub(crate) use simple_transform::{FILTER_FN_NAME, MAP_FN_NAME, FILTER_MAP_FN_NAME, ARRAY_MAP_FN_NAME};
 mod simple_transform;
 
 mod instance {
@@ -19,6 +23,7 @@ mod instance {
             SimpleTansform, FILTER_FN_NAME, MAP_FN_NAME, FILTER_MAP_FN_NAME, ARRAY_MAP_FN_NAME,
         },
         aggregate::SmartModuleAggregate,
+        window::SmartModuleWindow,
     };
 
     pub(crate) fn create_transform(
@@ -42,7 +47,12 @@ mod instance {
             .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
             Ok(tr)
-        } else if let Some(tr) = SmartModuleAggregate::try_instantiate(ctx, initial_data, store)?
+        } else if let Some(tr) =
+            SmartModuleAggregate::try_instantiate(ctx, initial_data.clone(), store)?
+                .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
+        {
+            Ok(tr)
+        } else if let Some(tr) = SmartModuleWindow::try_instantiate(ctx, initial_data, store)?
             .map(|transform| Box::new(transform) as Box<dyn DowncastableTransform>)
         {
             Ok(tr)
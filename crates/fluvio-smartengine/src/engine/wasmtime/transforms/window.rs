@@ -0,0 +1,333 @@
+use std::collections::BTreeMap;
+use std::convert::{TryFrom, TryInto};
+use std::fmt::Debug;
+use std::io::Cursor;
+
+use tracing::{debug, instrument};
+use anyhow::{anyhow, Result};
+use wasmtime::{AsContextMut, TypedFunc};
+
+use fluvio_protocol::{Encoder, Decoder};
+use fluvio_protocol::record::{RecordData, NO_TIMESTAMP};
+use fluvio_smartmodule::Record;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleInput, SmartModuleOutput, SmartModuleAggregateInput, SmartModuleAggregateOutput,
+    SmartModuleTransformErrorStatus, SmartModuleWindowConfig,
+};
+use crate::engine::SmartModuleInitialData;
+use crate::engine::wasmtime::{
+    instance::{SmartModuleInstanceContext, SmartModuleTransform},
+    state::WasmState,
+};
+
+pub(crate) const WINDOW_FN_NAME: &str = "window";
+
+pub(crate) const WINDOW_START_HEADER: &str = "window-start";
+pub(crate) const WINDOW_END_HEADER: &str = "window-end";
+
+/// most windows kept open by single stream, e.g. by many distinct keys
+pub(crate) const MAX_OPEN_WINDOWS: usize = 100_000;
+
+type WasmWindowFn = TypedFunc<(i32, i32, u32), i32>;
+
+/// Window and record key which records are aggregated by
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Encoder, Decoder)]
+struct WindowKey {
+    start: i64,
+    key: Option<Vec<u8>>,
+}
+
+/// Accumulators of open windows together with watermark
+#[derive(Debug, Default, PartialEq, Eq, Encoder, Decoder)]
+struct WindowState {
+    watermark: i64,
+    windows: BTreeMap<WindowKey, Vec<u8>>,
+}
+
+impl WindowState {
+    /// group records by windows they belong to, records of already closed windows are dropped
+    fn group(
+        &self,
+        config: &SmartModuleWindowConfig,
+        records: Vec<Record>,
+        base_timestamp: i64,
+    ) -> BTreeMap<WindowKey, Vec<Record>> {
+        let mut groups: BTreeMap<WindowKey, Vec<Record>> = BTreeMap::new();
+        if base_timestamp == NO_TIMESTAMP {
+            debug!(
+                records = records.len(),
+                "dropping records without timestamp"
+            );
+            return groups;
+        }
+        for record in records {
+            let timestamp = base_timestamp.saturating_add(record.timestamp_delta());
+            let key = if config.by_key {
+                record.key().map(|key| key.as_ref().to_vec())
+            } else {
+                None
+            };
+            for start in config.window_starts(timestamp) {
+                if start.saturating_add(config.size_ms) <= self.watermark {
+                    debug!(timestamp, start, "dropping late record");
+                    continue;
+                }
+                groups
+                    .entry(WindowKey {
+                        start,
+                        key: key.clone(),
+                    })
+                    .or_default()
+                    .push(record.clone());
+            }
+        }
+        groups
+    }
+
+    /// advance watermark and remove windows which are closed by it
+    fn close(
+        &mut self,
+        config: &SmartModuleWindowConfig,
+        max_timestamp: i64,
+    ) -> BTreeMap<WindowKey, Vec<u8>> {
+        self.watermark = self
+            .watermark
+            .max(max_timestamp.saturating_sub(config.lateness_ms));
+        // windows are ordered by start, so closed windows are ones starting before first open one
+        let open = self.windows.split_off(&WindowKey {
+            start: self
+                .watermark
+                .saturating_sub(config.size_ms)
+                .saturating_add(1),
+            key: None,
+        });
+        std::mem::replace(&mut self.windows, open)
+    }
+}
+
+pub(crate) struct SmartModuleWindow {
+    window_fn: WasmWindowFn,
+    config: SmartModuleWindowConfig,
+    state: WindowState,
+}
+
+impl Debug for SmartModuleWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "WindowFn")
+    }
+}
+
+impl SmartModuleWindow {
+    pub fn try_instantiate(
+        ctx: &SmartModuleInstanceContext,
+        initial_data: SmartModuleInitialData,
+        store: &mut impl AsContextMut,
+    ) -> Result<Option<Self>> {
+        let config = match initial_data {
+            SmartModuleInitialData::Window { config } => config,
+            _ => SmartModuleWindowConfig::default(),
+        };
+        config
+            .validate()
+            .map_err(|err| anyhow!("invalid window config: {err}"))?;
+
+        match ctx.get_wasm_func(&mut *store, WINDOW_FN_NAME) {
+            Some(func) => func
+                .typed(&mut *store)
+                .or_else(|_| func.typed(store))
+                .map(|window_fn| {
+                    Some(Self {
+                        window_fn,
+                        config,
+                        state: WindowState::default(),
+                    })
+                }),
+            None => Ok(None),
+        }
+    }
+
+    fn emit(window: WindowKey, accumulator: Vec<u8>, size_ms: i64, offset_delta: i64) -> Record {
+        let mut record = Record {
+            key: window.key.map(RecordData::from),
+            value: RecordData::from(accumulator),
+            ..Default::default()
+        }
+        .with_header(WINDOW_START_HEADER, window.start.to_string())
+        .with_header(
+            WINDOW_END_HEADER,
+            window.start.saturating_add(size_ms).to_string(),
+        );
+        record.preamble.set_offset_delta(offset_delta);
+        record
+    }
+}
+
+impl SmartModuleTransform for SmartModuleWindow {
+    #[instrument(skip(self,ctx,store),fields(offset = input.base_offset()))]
+    fn process(
+        &mut self,
+        input: SmartModuleInput,
+        ctx: &mut SmartModuleInstanceContext,
+        store: &mut WasmState,
+    ) -> Result<SmartModuleOutput> {
+        let base_offset = input.base_offset();
+        let base_timestamp = input.base_timestamp();
+        let records: Vec<Record> = input.try_into()?;
+        let last_offset_delta = records
+            .last()
+            .map(|record| record.preamble.offset_delta())
+            .unwrap_or_default();
+        let max_timestamp = records
+            .iter()
+            .map(|record| base_timestamp.saturating_add(record.timestamp_delta()))
+            .max()
+            .filter(|_| base_timestamp != NO_TIMESTAMP)
+            .unwrap_or(NO_TIMESTAMP);
+
+        let groups = self.state.group(&self.config, records, base_timestamp);
+        debug!(windows = groups.len(), "start window aggregation");
+        for (window, records) in groups {
+            if self.state.windows.len() >= MAX_OPEN_WINDOWS
+                && !self.state.windows.contains_key(&window)
+            {
+                return Err(anyhow!(
+                    "window SmartModule exceeded {MAX_OPEN_WINDOWS} open windows"
+                ));
+            }
+            let mut base = SmartModuleInput::try_from(records)?;
+            base.set_base_offset(base_offset);
+            base.set_base_timestamp(base_timestamp);
+            let input = SmartModuleAggregateInput {
+                base,
+                accumulator: self.state.windows.get(&window).cloned().unwrap_or_default(),
+            };
+            let slice = ctx.write_input(&input, &mut *store)?;
+            let window_output = self.window_fn.call(&mut *store, slice)?;
+
+            debug!(window_output);
+            if window_output < 0 {
+                let internal_error = SmartModuleTransformErrorStatus::try_from(window_output)
+                    .unwrap_or(SmartModuleTransformErrorStatus::UnknownError);
+                return Err(internal_error.into());
+            }
+
+            let output: SmartModuleAggregateOutput = ctx.read_output(&mut *store)?;
+            if output.base.error.is_some() {
+                return Ok(output.base);
+            }
+            self.state.windows.insert(window, output.accumulator);
+        }
+
+        let successes = self
+            .state
+            .close(&self.config, max_timestamp)
+            .into_iter()
+            .map(|(window, accumulator)| {
+                Self::emit(window, accumulator, self.config.size_ms, last_offset_delta)
+            })
+            .collect();
+        Ok(SmartModuleOutput::new(successes))
+    }
+
+    fn name(&self) -> &str {
+        WINDOW_FN_NAME
+    }
+
+    fn state(&self) -> Option<Vec<u8>> {
+        let mut bytes = vec![];
+        self.state.encode(&mut bytes, 0).ok()?;
+        Some(bytes)
+    }
+
+    fn restore_state(&mut self, state: Vec<u8>) {
+        match WindowState::decode_from(&mut Cursor::new(state), 0) {
+            Ok(state) => self.state = state,
+            Err(err) => debug!(%err, "discarding invalid window state"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_smartmodule::Record;
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleWindowConfig;
+
+    use super::{WindowKey, WindowState};
+
+    fn record(key: &str, timestamp_delta: i64) -> Record {
+        let mut record = Record::new_key_value(key, "1");
+        record.preamble.set_timestamp_delta(timestamp_delta);
+        record
+    }
+
+    fn starts<T>(groups: &std::collections::BTreeMap<WindowKey, T>) -> Vec<i64> {
+        groups.keys().map(|window| window.start).collect()
+    }
+
+    #[test]
+    fn test_tumbling_window() {
+        let config = SmartModuleWindowConfig::tumbling(10);
+        let mut state = WindowState::default();
+
+        let groups = state.group(&config, vec![record("a", 1), record("b", 12)], 100);
+        assert_eq!(starts(&groups), vec![100, 110]);
+        assert!(groups.keys().all(|window| window.key.is_none()));
+        for window in groups.into_keys() {
+            state.windows.insert(window, b"1".to_vec());
+        }
+
+        // watermark 112 closes window [100, 110) only
+        let closed = state.close(&config, 112);
+        assert_eq!(starts(&closed), vec![100]);
+        assert_eq!(starts(&state.windows), vec![110]);
+
+        // record of closed window is late
+        let groups = state.group(&config, vec![record("a", 5)], 100);
+        assert!(groups.is_empty());
+    }
+
+    #[test]
+    fn test_sliding_window_by_key_with_lateness() {
+        let config = SmartModuleWindowConfig::sliding(10, 5)
+            .with_lateness(5)
+            .with_key();
+        let mut state = WindowState::default();
+
+        let groups = state.group(&config, vec![record("a", 7), record("b", 7)], 100);
+        assert_eq!(starts(&groups), vec![100, 100, 105, 105]);
+        assert_eq!(
+            groups.keys().next().and_then(|window| window.key.clone()),
+            Some(b"a".to_vec())
+        );
+        for window in groups.into_keys() {
+            state.windows.insert(window, b"1".to_vec());
+        }
+
+        // watermark is 113 - 5, nothing is closed yet
+        assert!(state.close(&config, 113).is_empty());
+        // record is late but within allowed lateness
+        assert_eq!(
+            starts(&state.group(&config, vec![record("a", 9)], 100)),
+            vec![100, 105]
+        );
+
+        // watermark 110 closes windows starting at 100 for both keys
+        let closed = state.close(&config, 115);
+        assert_eq!(starts(&closed), vec![100, 100]);
+        assert_eq!(starts(&state.windows), vec![105, 105]);
+    }
+
+    #[test]
+    fn test_drop_records_without_timestamp() {
+        let config = SmartModuleWindowConfig::default();
+        let state = WindowState::default();
+        assert!(state
+            .group(
+                &config,
+                vec![record("a", 1)],
+                fluvio_protocol::record::NO_TIMESTAMP
+            )
+            .is_empty());
+    }
+}
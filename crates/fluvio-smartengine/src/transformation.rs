@@ -11,6 +11,8 @@ use serde::{
     de::{Visitor, self, SeqAccess, MapAccess},
 };
 
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleErrorPolicy;
+
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct TransformationConfig {
     pub transforms: Vec<TransformationStep>,
@@ -51,6 +53,12 @@ pub struct TransformationStep {
     pub uses: String,
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub with: BTreeMap<String, JsonString>,
+    #[serde(
+        default,
+        rename = "on-error",
+        skip_serializing_if = "SmartModuleErrorPolicy::is_fail"
+    )]
+    pub on_error: SmartModuleErrorPolicy,
 }
 
 impl Display for TransformationStep {
@@ -168,14 +176,16 @@ mod tests {
                         with: BTreeMap::from([(
                             "spec".to_string(),
                             JsonString("[{\"operation\":\"shift\",\"spec\":{\"payload\":{\"device\":\"device\"}}},{\"operation\":\"default\",\"spec\":{\"device\":{\"type\":\"mobile\"}}}]".to_string())
-                        )])
+                        )]),
+                        on_error: SmartModuleErrorPolicy::Skip,
                     },
                     TransformationStep {
                         uses: "infinyon/json-sql@0.1.0".to_string(),
                         with: BTreeMap::from([(
                             "mapping".to_string(),
                             JsonString("{\"map-columns\":{\"device_id\":{\"json-key\":\"device.device_id\",\"value\":{\"default\":\"0\",\"required\":true,\"type\":\"int\"}},\"record\":{\"json-key\":\"$\",\"value\":{\"required\":true,\"type\":\"jsonb\"}}},\"table\":\"topic_message_demo\"}".to_string())
-                        )])
+                        )]),
+                        on_error: SmartModuleErrorPolicy::DeadLetter("sql-errors".to_string()),
                     }
                 ]
             }
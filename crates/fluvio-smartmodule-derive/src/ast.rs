@@ -26,6 +26,7 @@ pub enum SmartModuleKind {
     Map,
     ArrayMap,
     FilterMap,
+    Window,
 }
 
 impl SmartModuleKind {
@@ -43,6 +44,7 @@ impl SmartModuleKind {
                                 "array_map" => Some(Self::ArrayMap),
                                 "filter_map" => Some(Self::FilterMap),
                                 "init" => Some(Self::Init),
+                                "window" => Some(Self::Window),
                                 _ => None,
                             }
                         })
@@ -6,6 +6,7 @@ mod map;
 mod array_map;
 mod filter_map;
 mod aggregate;
+mod window;
 mod init;
 mod transform;
 pub mod opt;
@@ -18,5 +19,6 @@ pub fn generate_smartmodule(config: &SmartModuleConfig, func: &SmartModuleFn) ->
         SmartModuleKind::Aggregate => self::aggregate::generate_aggregate_smartmodule(func),
         SmartModuleKind::ArrayMap => self::array_map::generate_array_map_smartmodule(func),
         SmartModuleKind::Init => self::init::generate_init_smartmodule(func),
+        SmartModuleKind::Window => self::window::generate_window_smartmodule(func),
     }
 }
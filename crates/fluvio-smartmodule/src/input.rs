@@ -2,7 +2,7 @@ use std::{collections::BTreeMap, fmt::Display};
 use std::fmt;
 use std::io::Cursor;
 
-use fluvio_protocol::record::Offset;
+use fluvio_protocol::record::{Offset, NO_TIMESTAMP, RECORD_HEADERS_VERSION};
 use fluvio_protocol::{Encoder, Decoder, record::Record};
 
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
@@ -38,6 +38,9 @@ pub struct SmartModuleInput {
     params: SmartModuleExtraParams,
     #[fluvio(min_version = 16)]
     join_record: Vec<u8>,
+    /// Timestamp of first record in batch, records carry delta from it
+    #[fluvio(min_version = 22)]
+    base_timestamp: i64,
 }
 
 impl SmartModuleInput {
@@ -57,6 +60,19 @@ impl SmartModuleInput {
         self.base_offset = base_offset;
     }
 
+    /// timestamp of first record in batch, `NO_TIMESTAMP` if batch has no timestamps
+    pub fn base_timestamp(&self) -> i64 {
+        if self.base_timestamp <= 0 {
+            NO_TIMESTAMP
+        } else {
+            self.base_timestamp
+        }
+    }
+
+    pub fn set_base_timestamp(&mut self, base_timestamp: i64) {
+        self.base_timestamp = base_timestamp;
+    }
+
     pub fn raw_bytes(&self) -> &[u8] {
         &self.raw_bytes
     }
@@ -74,7 +90,7 @@ impl TryFrom<Vec<Record>> for SmartModuleInput {
     type Error = std::io::Error;
     fn try_from(records: Vec<Record>) -> Result<Self, Self::Error> {
         let mut raw_bytes = Vec::new();
-        records.encode(&mut raw_bytes, 0)?;
+        records.encode(&mut raw_bytes, RECORD_HEADERS_VERSION)?;
         Ok(SmartModuleInput {
             raw_bytes,
             ..Default::default()
@@ -86,7 +102,10 @@ impl TryInto<Vec<Record>> for SmartModuleInput {
     type Error = std::io::Error;
 
     fn try_into(mut self) -> Result<Vec<Record>, Self::Error> {
-        Decoder::decode_from(&mut Cursor::new(&mut self.raw_bytes), 0)
+        Decoder::decode_from(
+            &mut Cursor::new(&mut self.raw_bytes),
+            RECORD_HEADERS_VERSION,
+        )
     }
 }
 
@@ -111,6 +130,131 @@ pub struct SmartModuleAggregateInput {
     pub accumulator: Vec<u8>,
 }
 
+/// Windows that Window SmartModule aggregates records into.
+/// Window covers `size_ms` of record timestamps and new window starts every `slide_ms`,
+/// so windows are tumbling when both are same and sliding when slide is shorter.
+/// Window is closed and emitted once watermark, highest record timestamp seen
+/// minus `lateness_ms`, passes its end. Records arriving after that are dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Encoder, Decoder)]
+pub struct SmartModuleWindowConfig {
+    pub size_ms: i64,
+    pub slide_ms: i64,
+    pub lateness_ms: i64,
+    /// aggregate records with different keys in separate windows
+    pub by_key: bool,
+}
+
+impl Default for SmartModuleWindowConfig {
+    fn default() -> Self {
+        Self::tumbling(60_000)
+    }
+}
+
+impl SmartModuleWindowConfig {
+    pub fn tumbling(size_ms: i64) -> Self {
+        Self::sliding(size_ms, size_ms)
+    }
+
+    pub fn sliding(size_ms: i64, slide_ms: i64) -> Self {
+        Self {
+            size_ms,
+            slide_ms,
+            lateness_ms: 0,
+            by_key: false,
+        }
+    }
+
+    pub fn with_lateness(mut self, lateness_ms: i64) -> Self {
+        self.lateness_ms = lateness_ms;
+        self
+    }
+
+    pub fn with_key(mut self) -> Self {
+        self.by_key = true;
+        self
+    }
+
+    /// longest window, so windows can't overflow timestamps
+    pub const MAX_SIZE_MS: i64 = 366 * 24 * 60 * 60 * 1000;
+    /// most windows single record belongs to, `size_ms / slide_ms`
+    pub const MAX_WINDOWS_PER_RECORD: i64 = 1024;
+
+    /// check config can be used to aggregate records
+    pub fn validate(&self) -> Result<(), String> {
+        if self.size_ms <= 0 || self.size_ms > Self::MAX_SIZE_MS {
+            return Err(format!(
+                "window size must be between 1 and {} ms, got {}",
+                Self::MAX_SIZE_MS,
+                self.size_ms
+            ));
+        }
+        if self.slide_ms <= 0 || self.slide_ms > self.size_ms {
+            return Err(format!(
+                "window slide must be between 1 and window size {} ms, got {}",
+                self.size_ms, self.slide_ms
+            ));
+        }
+        if self.size_ms / self.slide_ms > Self::MAX_WINDOWS_PER_RECORD {
+            return Err(format!(
+                "window size {} ms over slide {} ms exceeds {} windows per record",
+                self.size_ms,
+                self.slide_ms,
+                Self::MAX_WINDOWS_PER_RECORD
+            ));
+        }
+        if self.lateness_ms < 0 || self.lateness_ms > Self::MAX_SIZE_MS {
+            return Err(format!(
+                "window lateness must be between 0 and {} ms, got {}",
+                Self::MAX_SIZE_MS,
+                self.lateness_ms
+            ));
+        }
+        Ok(())
+    }
+
+    /// start of each window containing timestamp, oldest first.
+    /// Windows which would overflow timestamp range are skipped
+    pub fn window_starts(&self, timestamp: i64) -> impl Iterator<Item = i64> {
+        let size = self.size_ms.max(1);
+        let slide = self.slide_ms.clamp(1, size);
+        let last = timestamp.checked_sub(timestamp.rem_euclid(slide));
+        // distance of windows from last one is below size, so it can't overflow
+        let count = (size - 1) / slide;
+        last.into_iter().flat_map(move |last| {
+            (0..=count)
+                .rev()
+                .filter_map(move |n| last.checked_sub(n * slide))
+                .filter(move |start| start.checked_add(size).map_or(true, |end| end > timestamp))
+        })
+    }
+}
+
+/// What happens when SmartModule fails to process a record
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+#[cfg_attr(
+    feature = "use_serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "kebab-case")
+)]
+pub enum SmartModuleErrorPolicy {
+    /// stop processing and return error
+    #[default]
+    #[fluvio(tag = 0)]
+    Fail,
+    /// drop the failing record and continue with next one
+    #[fluvio(tag = 1)]
+    Skip,
+    /// write the failing record with error hint to topic and continue with next one
+    #[fluvio(tag = 2)]
+    DeadLetter(String),
+}
+
+impl SmartModuleErrorPolicy {
+    pub fn is_fail(&self) -> bool {
+        matches!(self, Self::Fail)
+    }
+}
+
 /// Input to SmartModule Init
 #[derive(Debug, Default, Clone, Encoder, Decoder)]
 pub struct SmartModuleInitInput {
@@ -144,4 +288,85 @@ mod tests {
         assert_eq!(records_decoded[1].value.as_ref(), b"fruit");
         assert_eq!(records_decoded[2].value.as_ref(), b"banana");
     }
+
+    #[test]
+    fn test_window_starts() {
+        let tumbling = SmartModuleWindowConfig::tumbling(60);
+        assert_eq!(tumbling.window_starts(125).collect::<Vec<_>>(), vec![120]);
+        assert_eq!(tumbling.window_starts(120).collect::<Vec<_>>(), vec![120]);
+
+        let sliding = SmartModuleWindowConfig::sliding(60, 20);
+        assert_eq!(
+            sliding.window_starts(125).collect::<Vec<_>>(),
+            vec![80, 100, 120]
+        );
+        assert_eq!(
+            sliding.window_starts(120).collect::<Vec<_>>(),
+            vec![80, 100, 120]
+        );
+
+        // window is not multiple of slide
+        let sliding = SmartModuleWindowConfig::sliding(50, 20);
+        assert_eq!(
+            sliding.window_starts(125).collect::<Vec<_>>(),
+            vec![80, 100, 120]
+        );
+        assert_eq!(
+            sliding.window_starts(131).collect::<Vec<_>>(),
+            vec![100, 120]
+        );
+
+        // windows past end of timestamp range are skipped
+        assert!(tumbling.window_starts(i64::MIN).next().is_none());
+        assert_eq!(
+            sliding.window_starts(i64::MIN + 10).collect::<Vec<_>>(),
+            vec![i64::MIN + 8]
+        );
+    }
+
+    #[test]
+    fn test_window_config_validate() {
+        assert!(SmartModuleWindowConfig::tumbling(60).validate().is_ok());
+        assert!(SmartModuleWindowConfig::sliding(60, 20).validate().is_ok());
+
+        assert!(SmartModuleWindowConfig::tumbling(0).validate().is_err());
+        assert!(SmartModuleWindowConfig::tumbling(-5).validate().is_err());
+        assert!(SmartModuleWindowConfig::sliding(60, 0).validate().is_err());
+        assert!(SmartModuleWindowConfig::sliding(60, 120)
+            .validate()
+            .is_err());
+        assert!(SmartModuleWindowConfig::tumbling(i64::MAX)
+            .validate()
+            .is_err());
+        assert!(SmartModuleWindowConfig::sliding(1_000_000, 1)
+            .validate()
+            .is_err());
+        assert!(SmartModuleWindowConfig::tumbling(60)
+            .with_lateness(-1)
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_record_headers_in_sm_input() {
+        //given
+        let records = vec![Record::new("apple").with_header("content-type", "text/plain")];
+
+        //when
+        let sm_input: SmartModuleInput = records
+            .try_into()
+            .expect("records to input conversion failed");
+
+        let records_decoded: Vec<Record> = sm_input
+            .try_into()
+            .expect("input to records conversion failed");
+
+        //then
+        assert_eq!(
+            records_decoded[0]
+                .header("content-type")
+                .map(|value| value.as_ref()),
+            Some(b"text/plain".as_ref())
+        );
+    }
 }
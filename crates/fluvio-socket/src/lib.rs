@@ -1,5 +1,7 @@
 mod error;
 mod multiplexing;
+pub mod sasl;
+pub mod scram;
 mod sink;
 mod socket;
 mod stream;
@@ -0,0 +1,152 @@
+//!
+//! # SASL Authentication
+//!
+//! Handshake performed right after connection, before any other request.
+//! Client selects mechanism then exchanges authentication messages until server completes.
+//!
+
+use std::fmt;
+use std::io::{Error as IoError, ErrorKind};
+
+use tracing::{debug, instrument};
+
+use fluvio_protocol::api::{Request, RequestMessage};
+use fluvio_protocol::derive::{Encoder, Decoder};
+
+use crate::scram::{ScramClient, SCRAM_SHA_256};
+use crate::{FluvioSocket, SocketError};
+
+pub const SASL_HANDSHAKE_API_KEY: u16 = 9;
+pub const SASL_AUTHENTICATE_API_KEY: u16 = 10;
+
+/// mechanism for signed tokens issued by cluster
+pub const TOKEN: &str = "TOKEN";
+
+#[derive(Decoder, Encoder, Debug, Default)]
+pub struct SaslHandshakeRequest {
+    pub mechanism: String,
+}
+
+impl Request for SaslHandshakeRequest {
+    const API_KEY: u16 = SASL_HANDSHAKE_API_KEY;
+    type Response = SaslHandshakeResponse;
+}
+
+#[derive(Decoder, Encoder, Debug, Default)]
+pub struct SaslHandshakeResponse {
+    pub success: bool,
+    /// mechanisms enabled on server
+    pub mechanisms: Vec<String>,
+}
+
+#[derive(Decoder, Encoder, Debug, Default)]
+pub struct SaslAuthenticateRequest {
+    pub auth_bytes: Vec<u8>,
+}
+
+impl Request for SaslAuthenticateRequest {
+    const API_KEY: u16 = SASL_AUTHENTICATE_API_KEY;
+    type Response = SaslAuthenticateResponse;
+}
+
+#[derive(Decoder, Encoder, Debug, Default)]
+pub struct SaslAuthenticateResponse {
+    pub success: bool,
+    pub error_message: Option<String>,
+    pub auth_bytes: Vec<u8>,
+    /// no more messages are expected
+    pub complete: bool,
+}
+
+/// Credentials used to authenticate client
+#[derive(Clone, PartialEq, Eq)]
+pub enum Credentials {
+    Password { username: String, password: String },
+    Token(String),
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Password { username, .. } => write!(f, "Password {{ username: {username} }}"),
+            Self::Token(_) => write!(f, "Token"),
+        }
+    }
+}
+
+impl Credentials {
+    pub fn mechanism(&self) -> &'static str {
+        match self {
+            Self::Password { .. } => SCRAM_SHA_256,
+            Self::Token(_) => TOKEN,
+        }
+    }
+}
+
+fn denied(msg: impl Into<String>) -> SocketError {
+    let msg = msg.into();
+    SocketError::Io {
+        source: IoError::new(ErrorKind::PermissionDenied, msg.clone()),
+        msg,
+    }
+}
+
+async fn authenticate_step(
+    socket: &mut FluvioSocket,
+    auth_bytes: Vec<u8>,
+) -> Result<SaslAuthenticateResponse, SocketError> {
+    let request = RequestMessage::new_request(SaslAuthenticateRequest { auth_bytes });
+    let response = socket.send(&request).await?.response;
+    if response.success {
+        Ok(response)
+    } else {
+        Err(denied(
+            response
+                .error_message
+                .unwrap_or_else(|| "authentication failed".to_owned()),
+        ))
+    }
+}
+
+fn utf8(bytes: Vec<u8>) -> Result<String, SocketError> {
+    String::from_utf8(bytes)
+        .map_err(|_| IoError::new(ErrorKind::InvalidData, "invalid authentication message").into())
+}
+
+/// authenticate client with server before other requests are sent
+#[instrument(skip(socket, credentials))]
+pub async fn authenticate(
+    socket: &mut FluvioSocket,
+    credentials: &Credentials,
+) -> Result<(), SocketError> {
+    let mechanism = credentials.mechanism();
+    debug!(mechanism, "sasl handshake");
+
+    let handshake = RequestMessage::new_request(SaslHandshakeRequest {
+        mechanism: mechanism.to_owned(),
+    });
+    let response = socket.send(&handshake).await?.response;
+    if !response.success {
+        return Err(denied(format!(
+            "mechanism {mechanism} is not enabled, server supports: {:?}",
+            response.mechanisms
+        )));
+    }
+
+    match credentials {
+        Credentials::Token(token) => {
+            authenticate_step(socket, token.as_bytes().to_vec()).await?;
+        }
+        Credentials::Password { username, password } => {
+            let mut client = ScramClient::new(username, password);
+            let server_first =
+                authenticate_step(socket, client.client_first().into_bytes()).await?;
+            let client_final = client.client_final(&utf8(server_first.auth_bytes)?)?;
+            let server_final = authenticate_step(socket, client_final.into_bytes()).await?;
+            client.verify_server_final(&utf8(server_final.auth_bytes)?)?;
+        }
+    }
+
+    debug!(mechanism, "sasl authenticated");
+    Ok(())
+}
@@ -0,0 +1,316 @@
+//!
+//! # SCRAM-SHA-256
+//!
+//! Salted challenge response exchange (RFC 5802, RFC 7677) without channel binding.
+//! Client and server share message formats and key derivation.
+//!
+
+use std::io::{Error as IoError, ErrorKind};
+
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+pub const SCRAM_SHA_256: &str = "SCRAM-SHA-256";
+pub const DEFAULT_ITERATIONS: u32 = 4096;
+
+const GS2_HEADER: &str = "n,,";
+const NONCE_LEN: usize = 24;
+
+type HmacSha256 = Hmac<Sha256>;
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("hmac accepts any key length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn sha256(input: &[u8]) -> Vec<u8> {
+    Sha256::digest(input).to_vec()
+}
+
+/// compare secrets without exiting at first difference, so timing doesn't tell how much matched
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    let diff = left
+        .iter()
+        .zip(right)
+        .fold(0u8, |diff, (l, r)| diff | (l ^ r));
+    left.len() == right.len() && std::hint::black_box(diff) == 0
+}
+
+fn xor(left: &[u8], right: &[u8]) -> Vec<u8> {
+    left.iter().zip(right).map(|(l, r)| l ^ r).collect()
+}
+
+/// PBKDF2 with HMAC-SHA-256, producing single block
+fn salted_password(password: &str, salt: &[u8], iterations: u32) -> Vec<u8> {
+    let mut block = salt.to_vec();
+    block.extend_from_slice(&1u32.to_be_bytes());
+    let mut u = hmac(password.as_bytes(), &block);
+    let mut result = u.clone();
+    for _ in 1..iterations {
+        u = hmac(password.as_bytes(), &u);
+        result = xor(&result, &u);
+    }
+    result
+}
+
+fn nonce() -> String {
+    let mut bytes = [0u8; NONCE_LEN];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    STANDARD.encode(bytes)
+}
+
+fn invalid(msg: &str) -> IoError {
+    IoError::new(ErrorKind::InvalidData, format!("scram: {msg}"))
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, IoError> {
+    STANDARD
+        .decode(value)
+        .map_err(|_| invalid("invalid base64"))
+}
+
+/// value of attribute `key` in comma separated `k=v` message
+fn attribute<'a>(message: &'a str, key: char) -> Result<&'a str, IoError> {
+    message
+        .split(',')
+        .find_map(|part| {
+            part.strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix('='))
+        })
+        .ok_or_else(|| invalid(&format!("missing attribute {key}")))
+}
+
+fn escape_username(username: &str) -> String {
+    username.replace('=', "=3D").replace(',', "=2C")
+}
+
+fn unescape_username(username: &str) -> String {
+    username.replace("=2C", ",").replace("=3D", "=")
+}
+
+/// Credential stored by server, password itself is never kept
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScramCredential {
+    pub salt: Vec<u8>,
+    pub iterations: u32,
+    pub stored_key: Vec<u8>,
+    pub server_key: Vec<u8>,
+}
+
+impl ScramCredential {
+    /// derive credential from password with random salt
+    pub fn new(password: &str, iterations: u32) -> Self {
+        let mut salt = vec![0u8; 16];
+        rand::thread_rng().fill_bytes(&mut salt);
+        Self::with_salt(password, salt, iterations)
+    }
+
+    pub fn with_salt(password: &str, salt: Vec<u8>, iterations: u32) -> Self {
+        let salted = salted_password(password, &salt, iterations);
+        let client_key = hmac(&salted, b"Client Key");
+        Self {
+            stored_key: sha256(&client_key),
+            server_key: hmac(&salted, b"Server Key"),
+            salt,
+            iterations,
+        }
+    }
+}
+
+/// Client side of exchange
+#[derive(Debug)]
+pub struct ScramClient {
+    username: String,
+    password: String,
+    client_nonce: String,
+    server_signature: Option<Vec<u8>>,
+}
+
+impl ScramClient {
+    pub fn new(username: impl Into<String>, password: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            password: password.into(),
+            client_nonce: nonce(),
+            server_signature: None,
+        }
+    }
+
+    fn client_first_bare(&self) -> String {
+        format!(
+            "n={},r={}",
+            escape_username(&self.username),
+            self.client_nonce
+        )
+    }
+
+    pub fn client_first(&self) -> String {
+        format!("{GS2_HEADER}{}", self.client_first_bare())
+    }
+
+    /// compute proof from server challenge
+    pub fn client_final(&mut self, server_first: &str) -> Result<String, IoError> {
+        let nonce = attribute(server_first, 'r')?;
+        if !nonce.starts_with(&self.client_nonce) {
+            return Err(invalid("server nonce does not extend client nonce"));
+        }
+        let salt = decode(attribute(server_first, 's')?)?;
+        let iterations: u32 = attribute(server_first, 'i')?
+            .parse()
+            .map_err(|_| invalid("invalid iteration count"))?;
+
+        let without_proof = format!("c={},r={nonce}", STANDARD.encode(GS2_HEADER));
+        let auth_message = format!(
+            "{},{server_first},{without_proof}",
+            self.client_first_bare()
+        );
+
+        let salted = salted_password(&self.password, &salt, iterations);
+        let client_key = hmac(&salted, b"Client Key");
+        let client_signature = hmac(&sha256(&client_key), auth_message.as_bytes());
+        let proof = xor(&client_key, &client_signature);
+        let server_key = hmac(&salted, b"Server Key");
+        self.server_signature = Some(hmac(&server_key, auth_message.as_bytes()));
+
+        Ok(format!("{without_proof},p={}", STANDARD.encode(proof)))
+    }
+
+    /// make sure server knows credential as well
+    pub fn verify_server_final(&self, server_final: &str) -> Result<(), IoError> {
+        if let Ok(error) = attribute(server_final, 'e') {
+            return Err(IoError::new(
+                ErrorKind::PermissionDenied,
+                format!("scram: {error}"),
+            ));
+        }
+        let signature = decode(attribute(server_final, 'v')?)?;
+        match &self.server_signature {
+            Some(expected) if *expected == signature => Ok(()),
+            _ => Err(invalid("server signature mismatch")),
+        }
+    }
+}
+
+/// Server side of exchange
+#[derive(Debug, Default)]
+pub struct ScramServer {
+    client_first_bare: String,
+    server_first: String,
+    nonce: String,
+}
+
+impl ScramServer {
+    /// user name requested by client
+    pub fn username(client_first: &str) -> Result<String, IoError> {
+        let bare = client_first
+            .strip_prefix(GS2_HEADER)
+            .ok_or_else(|| invalid("channel binding is not supported"))?;
+        Ok(unescape_username(attribute(bare, 'n')?))
+    }
+
+    /// create challenge for client
+    pub fn server_first(
+        &mut self,
+        client_first: &str,
+        credential: &ScramCredential,
+    ) -> Result<String, IoError> {
+        let bare = client_first
+            .strip_prefix(GS2_HEADER)
+            .ok_or_else(|| invalid("channel binding is not supported"))?;
+        let client_nonce = attribute(bare, 'r')?;
+        self.client_first_bare = bare.to_owned();
+        self.nonce = format!("{client_nonce}{}", nonce());
+        self.server_first = format!(
+            "r={},s={},i={}",
+            self.nonce,
+            STANDARD.encode(&credential.salt),
+            credential.iterations
+        );
+        Ok(self.server_first.clone())
+    }
+
+    /// verify client proof, returns server final message which proves server knows credential
+    pub fn server_final(
+        &self,
+        client_final: &str,
+        credential: &ScramCredential,
+    ) -> Result<String, IoError> {
+        if attribute(client_final, 'r')? != self.nonce {
+            return Err(invalid("nonce mismatch"));
+        }
+        let (without_proof, proof) = client_final
+            .rsplit_once(",p=")
+            .ok_or_else(|| invalid("missing proof"))?;
+        let proof = decode(proof)?;
+
+        let auth_message = format!(
+            "{},{},{without_proof}",
+            self.client_first_bare, self.server_first
+        );
+        let client_signature = hmac(&credential.stored_key, auth_message.as_bytes());
+        let client_key = xor(&proof, &client_signature);
+        if proof.len() != client_signature.len()
+            || !constant_time_eq(&sha256(&client_key), &credential.stored_key)
+        {
+            return Err(IoError::new(
+                ErrorKind::PermissionDenied,
+                "scram: invalid credentials",
+            ));
+        }
+
+        let server_signature = hmac(&credential.server_key, auth_message.as_bytes());
+        Ok(format!("v={}", STANDARD.encode(server_signature)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::{constant_time_eq, ScramClient, ScramCredential, ScramServer};
+
+    fn exchange(password: &str) -> Result<(), std::io::Error> {
+        let credential = ScramCredential::new("secret", 16);
+        let mut client = ScramClient::new("app,user", password);
+        let client_first = client.client_first();
+        assert_eq!(
+            ScramServer::username(&client_first).expect("username"),
+            "app,user"
+        );
+
+        let mut server = ScramServer::default();
+        let server_first = server
+            .server_first(&client_first, &credential)
+            .expect("server first");
+        let client_final = client.client_final(&server_first)?;
+        let server_final = server.server_final(&client_final, &credential)?;
+        client.verify_server_final(&server_final)
+    }
+
+    #[test]
+    fn test_scram_exchange() {
+        assert!(exchange("secret").is_ok());
+        assert!(exchange("wrong").is_err());
+    }
+
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"key", b"key"));
+        assert!(!constant_time_eq(b"key", b"kez"));
+        assert!(!constant_time_eq(b"key", b"ke"));
+    }
+
+    #[test]
+    fn test_scram_credential_deterministic() {
+        let first = ScramCredential::with_salt("secret", vec![1, 2, 3], 16);
+        let second = ScramCredential::with_salt("secret", vec![1, 2, 3], 16);
+        assert_eq!(first, second);
+        assert_ne!(
+            first.stored_key,
+            ScramCredential::with_salt("other", vec![1, 2, 3], 16).stored_key
+        );
+    }
+}
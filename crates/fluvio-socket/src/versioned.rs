@@ -15,6 +15,7 @@ use fluvio_future::net::{DomainConnector, DefaultDomainConnector};
 use fluvio_future::retry::retry_if;
 
 use crate::{SocketError, FluvioSocket, SharedMultiplexerSocket, AsyncResponse};
+use crate::sasl::{self, Credentials};
 
 /// Frame with request and response
 pub trait SerialFrame: Display {
@@ -86,6 +87,7 @@ pub struct ClientConfig {
     client_id: String,
     connector: DomainConnector,
     use_spu_local_address: bool,
+    credentials: Option<Credentials>,
 }
 
 impl Debug for ClientConfig {
@@ -121,6 +123,7 @@ impl ClientConfig {
             client_id: "fluvio".to_owned(),
             connector,
             use_spu_local_address,
+            credentials: None,
         }
     }
 
@@ -149,12 +152,24 @@ impl ClientConfig {
         self.addr = domain
     }
 
+    pub fn credentials(&self) -> Option<&Credentials> {
+        self.credentials.as_ref()
+    }
+
+    /// authenticate with credentials when connecting
+    pub fn set_credentials(&mut self, credentials: Option<Credentials>) {
+        self.credentials = credentials;
+    }
+
     #[instrument(skip(self))]
     pub async fn connect(self) -> Result<VersionedSocket, SocketError> {
         debug!(add = %self.addr, "try connection to");
-        let socket =
+        let mut socket =
             FluvioSocket::connect_with_connector(&self.addr, self.connector.as_ref()).await?;
         info!(add = %self.addr, "connect to socket");
+        if let Some(credentials) = &self.credentials {
+            sasl::authenticate(&mut socket, credentials).await?;
+        }
         VersionedSocket::connect(socket, Arc::new(self)).await
     }
 
@@ -170,6 +185,7 @@ impl ClientConfig {
             client_id: self.client_id.clone(),
             connector,
             use_spu_local_address: self.use_spu_local_address,
+            credentials: self.credentials.clone(),
         }
     }
 }
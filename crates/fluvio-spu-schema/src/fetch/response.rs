@@ -1,7 +1,8 @@
+use std::collections::{BTreeSet, HashSet};
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
-use fluvio_protocol::record::BatchRecords;
+use fluvio_protocol::record::{Batch, BatchRecords};
 use fluvio_protocol::{Decoder, Encoder};
 use fluvio_protocol::derive::FluvioDefault;
 use fluvio_protocol::record::RecordSet;
@@ -91,12 +92,64 @@ impl<R: BatchRecords> FetchablePartitionResponse<RecordSet<R>> {
     }
 }
 
-#[derive(Encoder, Decoder, FluvioDefault, Debug)]
+/// Transaction aborted in fetched range, its batches start at first offset
+/// and end with abort marker of producer
+#[derive(Encoder, Decoder, FluvioDefault, Debug, Clone, Eq, PartialEq)]
 pub struct AbortedTransaction {
     pub producer_id: i64,
     pub first_offset: i64,
 }
 
+/// Skips batches of aborted transactions and transaction markers,
+/// so consumer with committed isolation only gets committed records.
+/// Aborted transactions of response must be added before its batches are checked in offset order.
+#[derive(Debug, Default)]
+pub struct AbortedTransactionFilter {
+    /// aborted transactions not reached yet, ordered by first offset
+    pending: BTreeSet<(Offset, i64)>,
+    /// producers whose transactional batches are skipped until their marker
+    aborted_producers: HashSet<i64>,
+    last_base_offset: Option<Offset>,
+}
+
+impl AbortedTransactionFilter {
+    pub fn add(&mut self, aborted: impl IntoIterator<Item = AbortedTransaction>) {
+        for transaction in aborted {
+            // transactions started before last batch are already tracked or have ended
+            if self
+                .last_base_offset
+                .map_or(true, |offset| transaction.first_offset > offset)
+            {
+                self.pending
+                    .insert((transaction.first_offset, transaction.producer_id));
+            }
+        }
+    }
+
+    /// return true if batch should be delivered to consumer
+    pub fn retain<R: BatchRecords>(&mut self, batch: &Batch<R>) -> bool {
+        let base_offset = batch.get_base_offset();
+        self.last_base_offset = Some(base_offset);
+        while let Some(&(first_offset, producer_id)) = self.pending.iter().next() {
+            if first_offset > base_offset {
+                break;
+            }
+            self.pending.remove(&(first_offset, producer_id));
+            self.aborted_producers.insert(producer_id);
+        }
+
+        let header = batch.get_header();
+        if header.is_control() {
+            // partitions may be registered in middle of transaction at its coordinator
+            if matches!(batch.control_type(), Ok(Some(record_type)) if record_type.is_marker()) {
+                self.aborted_producers.remove(&header.producer_id);
+            }
+            return false;
+        }
+        !(header.is_transactional() && self.aborted_producers.contains(&header.producer_id))
+    }
+}
+
 // -----------------------------------
 // Implementation
 // -----------------------------------
@@ -191,3 +244,51 @@ mod file {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::record::{Batch, ControlRecordType, Record};
+
+    use super::{AbortedTransaction, AbortedTransactionFilter};
+
+    fn batch(base_offset: i64, producer_id: i64, transactional: bool) -> Batch {
+        let mut batch = Batch::from(vec![Record::new("value")]);
+        batch.set_base_offset(base_offset);
+        batch.get_mut_header().producer_id = producer_id;
+        batch.get_mut_header().set_transactional(transactional);
+        batch
+    }
+
+    fn marker(base_offset: i64, producer_id: i64, record_type: ControlRecordType) -> Batch {
+        let mut batch = Batch::control(producer_id, 0, record_type);
+        batch.set_base_offset(base_offset);
+        batch
+    }
+
+    #[test]
+    fn test_aborted_transaction_filter() {
+        let mut filter = AbortedTransactionFilter::default();
+        filter.add(vec![AbortedTransaction {
+            producer_id: 1,
+            first_offset: 1,
+        }]);
+
+        assert!(filter.retain(&batch(0, -1, false)));
+        // aborted transaction of producer 1 interleaved with committed one of producer 2
+        assert!(!filter.retain(&batch(1, 1, true)));
+        assert!(filter.retain(&batch(2, 2, true)));
+        assert!(!filter.retain(&marker(3, 1, ControlRecordType::AddPartitions)));
+        assert!(!filter.retain(&batch(3, 1, true)));
+        assert!(!filter.retain(&marker(4, 2, ControlRecordType::Commit)));
+        assert!(!filter.retain(&marker(5, 1, ControlRecordType::Abort)));
+
+        // transaction reported again by later response is not skipped twice
+        filter.add(vec![AbortedTransaction {
+            producer_id: 1,
+            first_offset: 1,
+        }]);
+        assert!(filter.retain(&batch(6, 1, true)));
+        assert!(!filter.retain(&marker(7, 1, ControlRecordType::Commit)));
+    }
+}
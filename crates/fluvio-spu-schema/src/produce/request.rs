@@ -24,6 +24,9 @@ pub type DefaultTopicRequest = TopicProduceData<RecordSet<RawRecords>>;
 
 const PRODUCER_TRANSFORMATION_API_VERSION: i16 = 8;
 
+/// version from which records of produced batches carry headers
+pub const PRODUCE_RECORD_HEADERS_API_VERSION: i16 = 9;
+
 #[derive(FluvioDefault, Debug)]
 pub struct ProduceRequest<R> {
     /// The transactional ID, or null if the producer is not transactional.
@@ -53,7 +56,7 @@ where
     const API_KEY: u16 = 0;
 
     const MIN_API_VERSION: i16 = 0;
-    const DEFAULT_API_VERSION: i16 = PRODUCER_TRANSFORMATION_API_VERSION;
+    const DEFAULT_API_VERSION: i16 = PRODUCE_RECORD_HEADERS_API_VERSION;
 
     type Response = ProduceResponse;
 }
@@ -9,12 +9,15 @@ use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::record::PartitionOffset;
 use fluvio_protocol::record::ReplicaKey;
 
-use fluvio_types::PartitionId;
+use fluvio_protocol::record::Offset;
+use fluvio_types::{PartitionId, Timestamp};
 
-use crate::COMMON_VERSION;
 use crate::errors::ErrorCode;
 use super::SpuServerApiKey;
 
+/// Version which supports resolving timestamp to offset
+pub const FETCH_OFFSET_TIMESTAMP_API: i16 = 20;
+
 // -----------------------------------
 // FlvFetchOffsetsRequest
 // -----------------------------------
@@ -28,7 +31,7 @@ pub struct FetchOffsetsRequest {
 
 impl Request for FetchOffsetsRequest {
     const API_KEY: u16 = SpuServerApiKey::FetchOffsets as u16;
-    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    const DEFAULT_API_VERSION: i16 = FETCH_OFFSET_TIMESTAMP_API;
     type Response = FetchOffsetsResponse;
 }
 
@@ -40,6 +43,20 @@ impl FetchOffsetsRequest {
                 name: topic,
                 partitions: vec![FetchOffsetPartition {
                     partition_index: partition,
+                    timestamp: None,
+                }],
+            }],
+        }
+    }
+
+    /// create request with a single topic and partition which also resolves offset of timestamp
+    pub fn new_with_timestamp(topic: String, partition: u32, timestamp: Timestamp) -> Self {
+        Self {
+            topics: vec![FetchOffsetTopic {
+                name: topic,
+                partitions: vec![FetchOffsetPartition {
+                    partition_index: partition,
+                    timestamp: Some(timestamp),
                 }],
             }],
         }
@@ -59,6 +76,10 @@ pub struct FetchOffsetTopic {
 pub struct FetchOffsetPartition {
     /// The partition index.
     pub partition_index: PartitionId,
+
+    /// Timestamp in milliseconds to resolve into offset
+    #[fluvio(min_version = 20)]
+    pub timestamp: Option<Timestamp>,
 }
 
 // -----------------------------------
@@ -109,6 +130,11 @@ pub struct FetchOffsetPartitionResponse {
 
     /// Last readable offset
     pub last_stable_offset: i64,
+
+    /// Offset of first batch with records at or after requested timestamp.
+    /// Last readable offset if all records are older
+    #[fluvio(min_version = 20)]
+    pub timestamp_offset: Option<Offset>,
 }
 
 impl fmt::Display for FetchOffsetPartitionResponse {
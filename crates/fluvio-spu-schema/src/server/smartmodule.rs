@@ -10,7 +10,9 @@ use flate2::{
 };
 
 use fluvio_protocol::{Encoder, Decoder};
-use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
+use fluvio_smartmodule::dataplane::smartmodule::{
+    SmartModuleErrorPolicy, SmartModuleExtraParams, SmartModuleWindowConfig,
+};
 
 /// The request payload when using a Consumer SmartModule.
 ///
@@ -36,6 +38,9 @@ pub struct SmartModuleInvocation {
     pub wasm: SmartModuleInvocationWasm,
     pub kind: SmartModuleKind,
     pub params: SmartModuleExtraParams,
+    /// what to do with records SmartModule fails to process
+    #[fluvio(min_version = 23)]
+    pub error_policy: SmartModuleErrorPolicy,
 }
 
 #[derive(Clone, Encoder, Decoder)]
@@ -143,6 +148,9 @@ pub enum SmartModuleContextData {
         topic: String,
         derivedstream: String,
     },
+    #[fluvio(tag = 4)]
+    #[fluvio(min_version = SMARTMODULE_WINDOW_API)]
+    Window(SmartModuleWindowConfig),
 }
 
 /// Different possible representations of WASM modules.
@@ -222,4 +230,51 @@ mod tests {
             panic!("not adhoc")
         }
     }
+
+    fn invocation() -> SmartModuleInvocation {
+        SmartModuleInvocation {
+            wasm: SmartModuleInvocationWasm::Predefined("module".to_owned()),
+            kind: SmartModuleKind::Filter,
+            params: Default::default(),
+            error_policy: SmartModuleErrorPolicy::DeadLetter("errors".to_owned()),
+        }
+    }
+
+    #[test]
+    fn test_encode_error_policy_before_version() {
+        let mut dest = Vec::new();
+        invocation().encode(&mut dest, 22).expect("should encode");
+
+        let mut value = SmartModuleInvocation::default();
+        value
+            .decode(&mut io::Cursor::new(&dest), 22)
+            .expect("should decode");
+        assert_eq!(value.error_policy, SmartModuleErrorPolicy::Fail);
+        assert!(
+            matches!(value.wasm, SmartModuleInvocationWasm::Predefined(name) if name == "module")
+        );
+
+        // error policy is not understood by older SPU, so it must not be written
+        let mut with_policy = Vec::new();
+        invocation()
+            .encode(&mut with_policy, 23)
+            .expect("should encode");
+        assert!(dest.len() < with_policy.len());
+    }
+
+    #[test]
+    fn test_encode_error_policy() {
+        let mut dest = Vec::new();
+        invocation().encode(&mut dest, 23).expect("should encode");
+
+        let mut value = SmartModuleInvocation::default();
+        value
+            .decode(&mut io::Cursor::new(&dest), 23)
+            .expect("should decode");
+        assert_eq!(
+            value.error_policy,
+            SmartModuleErrorPolicy::DeadLetter("errors".to_owned())
+        );
+        assert!(matches!(value.kind, SmartModuleKind::Filter));
+    }
 }
@@ -16,7 +16,6 @@ use fluvio_protocol::record::RecordSet;
 use fluvio_smartmodule::dataplane::smartmodule::SmartModuleExtraParams;
 use fluvio_types::{PartitionId, defaults::FLUVIO_CLIENT_MAX_FETCH_BYTES};
 
-use crate::COMMON_VERSION;
 use crate::fetch::FetchablePartitionResponse;
 use crate::isolation::Isolation;
 
@@ -46,6 +45,24 @@ pub const SMART_MODULE_API: i16 = 16;
 pub const GENERIC_SMARTMODULE_API: i16 = 17;
 pub const CHAIN_SMARTMODULE_API: i16 = 18;
 
+// version for SmartModule runtime error kind, reported when execution exceeds limits
+pub const SMARTMODULE_LIMITS_API: i16 = 20;
+
+// version for checkpointed SmartModule state
+pub const SMARTMODULE_CHECKPOINT_API: i16 = 21;
+
+// version for window SmartModule and record timestamps in SmartModule input
+pub const SMARTMODULE_WINDOW_API: i16 = 22;
+
+// version for SmartModule error policy, records failed in SmartModule can be skipped or dead-lettered
+pub const SMARTMODULE_ERROR_POLICY_API: i16 = 23;
+
+// version for throttle time of clients exceeding quota
+pub const QUOTA_THROTTLE_API: i16 = 24;
+
+// version for record headers, records processed by SmartModule are sent without headers before it
+pub const RECORD_HEADERS_API: i16 = 25;
+
 /// Fetch records continuously
 /// Output will be send back as stream
 #[allow(deprecated)]
@@ -79,6 +96,11 @@ pub struct StreamFetchRequest<R> {
     #[builder(default)]
     #[fluvio(min_version = 18)]
     pub smartmodules: Vec<SmartModuleInvocation>,
+    /// SmartModule state is checkpointed under this name and restored
+    /// when stream with same name is started again
+    #[builder(default)]
+    #[fluvio(min_version = 21)]
+    pub checkpoint: Option<String>,
     #[builder(setter(skip))]
     data: PhantomData<R>,
 }
@@ -97,7 +119,7 @@ where
     R: Debug + Decoder + Encoder,
 {
     const API_KEY: u16 = SpuServerApiKey::StreamFetch as u16;
-    const DEFAULT_API_VERSION: i16 = COMMON_VERSION;
+    const DEFAULT_API_VERSION: i16 = RECORD_HEADERS_API;
     type Response = StreamFetchResponse<R>;
 }
 
@@ -113,6 +135,10 @@ pub struct StreamFetchResponse<R> {
     pub topic: String,
     pub stream_id: u32,
     pub partition: FetchablePartitionResponse<R>,
+    /// The duration in milliseconds consumer should wait before acknowledging records,
+    /// or zero if consumer did not exceed its quota.
+    #[fluvio(min_version = 24)]
+    pub throttle_time_ms: i32,
 }
 
 #[cfg(feature = "file")]
@@ -147,6 +173,9 @@ mod file {
             self.topic.encode(src, version)?;
             self.stream_id.encode(src, version)?;
             self.partition.file_encode(src, data, version)?;
+            if version >= QUOTA_THROTTLE_API {
+                self.throttle_time_ms.encode(src, version)?;
+            }
             Ok(())
         }
     }
@@ -213,6 +242,32 @@ mod tests {
         assert!(matches!(sm.kind, SmartModuleKind::Filter));
     }
 
+    #[test]
+    fn test_stream_fetch_response_throttle_time() {
+        let value = DefaultStreamFetchResponse {
+            topic: "one".to_string(),
+            throttle_time_ms: 100,
+            ..Default::default()
+        };
+
+        let mut current = Vec::new();
+        value
+            .encode(&mut current, QUOTA_THROTTLE_API)
+            .expect("should encode");
+        let mut decoded = DefaultStreamFetchResponse::default();
+        decoded
+            .decode(&mut std::io::Cursor::new(&current), QUOTA_THROTTLE_API)
+            .expect("should decode");
+        assert_eq!(decoded.throttle_time_ms, 100);
+
+        // older clients don't receive throttle time
+        let mut older = Vec::new();
+        value
+            .encode(&mut older, QUOTA_THROTTLE_API - 1)
+            .expect("should encode");
+        assert_eq!(older.len() + 4, current.len());
+    }
+
     #[test]
     fn test_zip_unzip_works() {
         const ORIG_LEN: usize = 1024;
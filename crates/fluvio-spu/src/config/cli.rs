@@ -5,8 +5,10 @@
 //! system parameters.
 //!
 use std::io::Error as IoError;
+use std::path::PathBuf;
 use std::process;
 use std::io::ErrorKind;
+use std::time::Duration;
 
 use tracing::debug;
 use tracing::info;
@@ -15,6 +17,7 @@ use clap::Parser;
 use fluvio_types::print_cli_err;
 use fluvio_types::SpuId;
 use fluvio_future::openssl::TlsAcceptor;
+use fluvio_storage::tiered::{RemoteStoreConfig, S3Config};
 
 use super::SpuConfig;
 
@@ -50,6 +53,26 @@ pub struct SpuOpt {
     #[arg(long, value_name = "integer", env = "FLV_LOG_INDEX_MAX_INTERVAL_BYTES")]
     pub index_max_interval_bytes: Option<u32>,
 
+    #[clap(flatten)]
+    remote_tier: RemoteTierOpt,
+
+    /// min replicas in sync with leader to accept produce with committed isolation,
+    /// can be overridden by topic
+    #[arg(long, value_name = "integer", env = "FLV_MIN_IN_SYNC_REPLICAS")]
+    pub min_in_sync_replicas: Option<u16>,
+
+    /// follower which hasn't caught up with leader for this long is removed from in-sync replicas
+    #[arg(long, value_name = "integer", env = "FLV_REPLICA_MAX_LAG_MS")]
+    pub replica_max_lag_ms: Option<u64>,
+
+    /// follower which is behind leader by more records is removed from in-sync replicas
+    #[arg(long, value_name = "integer", env = "FLV_REPLICA_MAX_LAG_OFFSETS")]
+    pub replica_max_lag_offsets: Option<u64>,
+
+    /// transaction which is not committed or aborted for this long is aborted
+    #[arg(long, value_name = "integer", env = "FLV_TRANSACTION_TIMEOUT_MS")]
+    pub transaction_timeout_ms: Option<u64>,
+
     /// max bytes to transfer between leader and follower
     #[arg(
         long,
@@ -59,8 +82,47 @@ pub struct SpuOpt {
     )]
     pub peer_max_bytes: u32,
 
+    /// Serve metrics in prometheus format on this address
+    #[arg(long, value_name = "host:port", env = "FLV_SPU_METRICS_ADDR")]
+    pub metrics_addr: Option<String>,
+
     #[clap(flatten)]
     tls: TlsConfig,
+
+    #[arg(
+        long = "authorization-scopes",
+        value_name = "authorization scopes path",
+        env
+    )]
+    x509_auth_scopes: Option<PathBuf>,
+
+    /// users allowed to authenticate with password (SCRAM-SHA-256)
+    #[arg(long = "sasl-credentials", value_name = "sasl credentials path", env)]
+    sasl_credentials: Option<PathBuf>,
+
+    /// secret used to verify signed tokens
+    #[arg(long = "token-secret", value_name = "token secret path", env)]
+    token_secret: Option<PathBuf>,
+
+    /// directory for compiled SmartModules, reused after restart
+    #[arg(long = "smartmodule-cache-dir", value_name = "dir", env)]
+    smartmodule_cache_dir: Option<PathBuf>,
+
+    /// fuel available to each SmartModule invocation
+    #[arg(long = "smartmodule-max-fuel", value_name = "fuel", env)]
+    smartmodule_max_fuel: Option<u64>,
+
+    /// max size of SmartModule linear memory
+    #[arg(long = "smartmodule-max-memory", value_name = "bytes", env)]
+    smartmodule_max_memory: Option<usize>,
+
+    /// max number of elements of SmartModule table
+    #[arg(long = "smartmodule-max-table-elements", value_name = "integer", env)]
+    smartmodule_max_table_elements: Option<u32>,
+
+    /// max time of each SmartModule invocation
+    #[arg(long = "smartmodule-timeout-ms", value_name = "integer", env)]
+    smartmodule_timeout_ms: Option<u64>,
 }
 
 impl SpuOpt {
@@ -74,8 +136,6 @@ impl SpuOpt {
 
     #[allow(clippy::wrong_self_convention)]
     fn as_spu_config(self) -> Result<(SpuConfig, Option<String>), IoError> {
-        use std::path::PathBuf;
-
         let mut config = SpuConfig {
             id: match self.id {
                 Some(id) => id,
@@ -112,6 +172,24 @@ impl SpuOpt {
             config.log.index_max_interval_bytes = index_max_interval_bytes;
         }
 
+        config.log.remote = self.remote_tier.remote_store()?;
+        if let Some(remote_after_seconds) = self.remote_tier.remote_after_seconds {
+            config.log.remote_after_seconds = remote_after_seconds;
+        }
+
+        if let Some(min_in_sync_replicas) = self.min_in_sync_replicas {
+            info!("overriding min in sync replicas: {}", min_in_sync_replicas);
+            config.replication.min_in_sync_replicas = min_in_sync_replicas;
+        }
+
+        if let Some(replica_max_lag_ms) = self.replica_max_lag_ms {
+            config.replication.replica_max_lag_ms = replica_max_lag_ms;
+        }
+        config.replication.replica_max_lag_offsets = self.replica_max_lag_offsets;
+        if let Some(transaction_timeout_ms) = self.transaction_timeout_ms {
+            config.replication.transaction_timeout_ms = transaction_timeout_ms;
+        }
+
         if let Some(public_addr) = self.bind_public {
             info!("overriding public addr: {}", public_addr);
             config.public_endpoint = public_addr;
@@ -137,6 +215,29 @@ impl SpuOpt {
         }
 
         config.peer_max_bytes = self.peer_max_bytes;
+        config.x509_auth_scopes = self.x509_auth_scopes;
+        config.sasl_credentials = self.sasl_credentials;
+        config.token_secret = self.token_secret;
+        config.smartmodule_cache_dir = self.smartmodule_cache_dir;
+
+        let limits = &mut config.smartmodule_limits;
+        if let Some(fuel) = self.smartmodule_max_fuel {
+            limits.fuel = fuel;
+        }
+        if let Some(max_memory) = self.smartmodule_max_memory {
+            limits.max_memory_bytes = max_memory;
+        }
+        if let Some(max_table_elements) = self.smartmodule_max_table_elements {
+            limits.max_table_elements = max_table_elements;
+        }
+        if let Some(timeout_ms) = self.smartmodule_timeout_ms {
+            limits.timeout = Duration::from_millis(timeout_ms);
+        }
+
+        if let Some(metrics_addr) = self.metrics_addr {
+            info!("serving prometheus metrics on: {}", metrics_addr);
+            config.metrics_endpoint = Some(metrics_addr);
+        }
 
         Ok((config, tls_port))
     }
@@ -257,3 +358,82 @@ struct TlsConfig {
     /// TLS: address of non tls public service, required
     pub bind_non_tls_public: Option<String>,
 }
+
+/// remote tier where closed segments are offloaded
+#[derive(Debug, Parser, Default)]
+struct RemoteTierOpt {
+    /// offload closed segments to this directory
+    #[arg(long, value_name = "dir", env = "FLV_REMOTE_TIER_DIR")]
+    remote_tier_dir: Option<PathBuf>,
+
+    /// offload closed segments to this S3 bucket
+    #[arg(
+        long,
+        value_name = "bucket",
+        env = "FLV_REMOTE_TIER_S3_BUCKET",
+        conflicts_with = "remote_tier_dir"
+    )]
+    remote_tier_s3_bucket: Option<String>,
+
+    /// region of S3 bucket
+    #[arg(
+        long,
+        value_name = "region",
+        env = "FLV_REMOTE_TIER_S3_REGION",
+        default_value = "us-east-1"
+    )]
+    remote_tier_s3_region: String,
+
+    /// url of S3 compatible store, AWS endpoint of region is used if not set
+    #[arg(long, value_name = "url", env = "FLV_REMOTE_TIER_S3_ENDPOINT")]
+    remote_tier_s3_endpoint: Option<String>,
+
+    /// access key id of S3 credentials
+    #[arg(long, env = "AWS_ACCESS_KEY_ID", hide_env_values = true)]
+    remote_tier_s3_access_key_id: Option<String>,
+
+    /// secret access key of S3 credentials
+    #[arg(long, env = "AWS_SECRET_ACCESS_KEY", hide_env_values = true)]
+    remote_tier_s3_secret_access_key: Option<String>,
+
+    /// age in seconds after which closed segments are offloaded
+    #[arg(long, value_name = "seconds", env = "FLV_REMOTE_TIER_AFTER_SECONDS")]
+    remote_after_seconds: Option<u32>,
+}
+
+impl RemoteTierOpt {
+    fn remote_store(&self) -> Result<Option<RemoteStoreConfig>, IoError> {
+        if let Some(dir) = &self.remote_tier_dir {
+            info!(dir = %dir.display(), "offloading segments to directory");
+            return Ok(Some(RemoteStoreConfig::Local { dir: dir.clone() }));
+        }
+
+        let bucket = match &self.remote_tier_s3_bucket {
+            Some(bucket) => bucket.clone(),
+            None => return Ok(None),
+        };
+        let credentials = self
+            .remote_tier_s3_access_key_id
+            .clone()
+            .zip(self.remote_tier_s3_secret_access_key.clone());
+        let (access_key_id, secret_access_key) = credentials.ok_or_else(|| {
+            IoError::new(
+                ErrorKind::InvalidInput,
+                "S3 access key id and secret access key must be specified",
+            )
+        })?;
+        let region = self.remote_tier_s3_region.clone();
+        let endpoint = self
+            .remote_tier_s3_endpoint
+            .clone()
+            .unwrap_or_else(|| format!("https://s3.{region}.amazonaws.com"));
+        info!(%endpoint, %bucket, "offloading segments to S3");
+        Ok(Some(RemoteStoreConfig::S3(S3Config {
+            endpoint,
+            region,
+            bucket,
+            access_key_id,
+            secret_access_key,
+        })))
+    }
+}
@@ -25,23 +25,37 @@ use fluvio_types::defaults::SPU_RETRY_SC_TIMEOUT_MS;
 // environment variables
 
 use fluvio_types::defaults::SPU_MIN_IN_SYNC_REPLICAS;
+use fluvio_types::defaults::SPU_REPLICA_MAX_LAG_MS;
+use fluvio_types::defaults::SPU_TRANSACTION_TIMEOUT_MS;
 use fluvio_types::defaults::FLV_LOG_BASE_DIR;
 use fluvio_types::defaults::FLV_LOG_SIZE;
 use fluvio_types::SpuId;
 use fluvio_storage::config::ReplicaConfig;
+use fluvio_storage::tiered::RemoteStoreConfig;
+use fluvio_smartengine::SmartModuleLimits;
 use fluvio_types::defaults::{
     STORAGE_FLUSH_IDLE_MSEC, STORAGE_FLUSH_WRITE_COUNT, STORAGE_MAX_BATCH_SIZE,
+    STORAGE_REMOTE_AFTER_SECONDS,
 };
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ReplicationConfig {
     pub min_in_sync_replicas: u16,
+    // follower which hasn't caught up with leader for this long is out of sync
+    pub replica_max_lag_ms: u64,
+    // follower which is behind leader by more records is out of sync, not checked if not set
+    pub replica_max_lag_offsets: Option<u64>,
+    // transaction without commit or abort for this long is aborted by leader
+    pub transaction_timeout_ms: u64,
 }
 
 impl Default for ReplicationConfig {
     fn default() -> Self {
         Self {
             min_in_sync_replicas: SPU_MIN_IN_SYNC_REPLICAS,
+            replica_max_lag_ms: SPU_REPLICA_MAX_LAG_MS,
+            replica_max_lag_offsets: None,
+            transaction_timeout_ms: SPU_TRANSACTION_TIMEOUT_MS,
         }
     }
 }
@@ -56,6 +70,9 @@ pub struct Log {
     pub flush_write_count: u32,
     pub flush_idle_msec: u32,
     pub max_batch_size: u32,
+    // closed segments are offloaded here, kept only locally if not set
+    pub remote: Option<RemoteStoreConfig>,
+    pub remote_after_seconds: u32,
 }
 
 impl Default for Log {
@@ -71,6 +88,8 @@ impl Default for Log {
             flush_write_count: STORAGE_FLUSH_WRITE_COUNT,
             flush_idle_msec: STORAGE_FLUSH_IDLE_MSEC,
             max_batch_size: STORAGE_MAX_BATCH_SIZE,
+            remote: None,
+            remote_after_seconds: STORAGE_REMOTE_AFTER_SECONDS,
         }
     }
 }
@@ -95,6 +114,24 @@ pub struct SpuConfig {
     pub log: Log,
 
     pub peer_max_bytes: u32,
+
+    // prometheus metrics endpoint, disabled if not set
+    pub metrics_endpoint: Option<String>,
+
+    // scopes of x509 principals, identity of connection is checked against data policy if set
+    pub x509_auth_scopes: Option<PathBuf>,
+
+    // users allowed to authenticate with password
+    pub sasl_credentials: Option<PathBuf>,
+
+    // secret for verifying signed tokens
+    pub token_secret: Option<PathBuf>,
+
+    // compiled SmartModules are stored here, kept only in memory if not set
+    pub smartmodule_cache_dir: Option<PathBuf>,
+
+    // fuel, memory and time available to each SmartModule invocation
+    pub smartmodule_limits: SmartModuleLimits,
 }
 
 impl Default for SpuConfig {
@@ -109,6 +146,12 @@ impl Default for SpuConfig {
             sc_retry_ms: SPU_RETRY_SC_TIMEOUT_MS,
             log: Log::default(),
             peer_max_bytes: fluvio_storage::FileReplica::PREFER_MAX_LEN,
+            metrics_endpoint: None,
+            x509_auth_scopes: None,
+            sasl_credentials: None,
+            token_secret: None,
+            smartmodule_cache_dir: None,
+            smartmodule_limits: SmartModuleLimits::default(),
         }
     }
 }
@@ -144,6 +187,13 @@ impl SpuConfig {
     pub fn storage(&self) -> &Log {
         &self.log
     }
+
+    /// directory of SmartModule state checkpoints
+    pub fn smartmodule_checkpoint_dir(&self) -> PathBuf {
+        self.log
+            .base_dir
+            .join(format!("smartmodule-checkpoints-{}", self.id))
+    }
 }
 
 impl From<&SpuConfig> for ReplicaConfig {
@@ -157,6 +207,8 @@ impl From<&SpuConfig> for ReplicaConfig {
             .flush_write_count(log.flush_write_count)
             .flush_idle_msec(log.flush_idle_msec)
             .max_batch_size(log.max_batch_size)
+            .remote(log.remote.clone())
+            .remote_after_seconds(log.remote_after_seconds)
             .build()
     }
 }
@@ -9,18 +9,20 @@ use futures_util::stream::StreamExt;
 
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
-use fluvio_controlplane::{InternalSpuApi, UpdateSmartModuleRequest};
+use fluvio_controlplane::{InternalSpuApi, UpdateSmartModuleRequest, UpdateDataPolicyRequest};
+use fluvio_controlplane::{UpdateTransformRequest, UpdateQuotaRequest};
 use fluvio_controlplane::InternalSpuRequest;
 use fluvio_controlplane::RegisterSpuRequest;
-use fluvio_controlplane::{UpdateSpuRequest, UpdateLrsRequest};
+use fluvio_controlplane::{UpdateSpuRequest, UpdateLrsRequest, UpdateTransformOffsetsRequest};
 use fluvio_controlplane::UpdateReplicaRequest;
 use fluvio_protocol::api::RequestMessage;
+use fluvio_spu_schema::server::smartmodule::SmartModuleInvocationWasm;
 use fluvio_socket::{FluvioSocket, SocketError, FluvioSink};
 use fluvio_storage::FileReplica;
-use crate::core::SharedGlobalContext;
+use crate::core::{SharedGlobalContext, SpecChange};
 use crate::InternalServerError;
 
-use super::message_sink::{SharedStatusUpdate};
+use super::message_sink::{SharedStatusUpdate, SharedTransformOffsets};
 
 // keep track of various internal state of dispatcher
 #[derive(Default)]
@@ -29,6 +31,8 @@ struct DispatcherCounter {
     pub spu_changes: u64,     // spu changes received from sc
     pub reconnect: u64,       // number of reconnect to sc
     pub smartmodule: u64,     // number of sm updates from sc
+    pub transform: u64,       // number of transform updates from sc
+    pub quota: u64,           // number of quota updates from sc
 }
 
 /// Controller for handling connection to SC
@@ -36,6 +40,7 @@ struct DispatcherCounter {
 pub struct ScDispatcher<S> {
     ctx: SharedGlobalContext<S>,
     status_update: SharedStatusUpdate,
+    transform_offsets: SharedTransformOffsets,
     counter: DispatcherCounter,
 }
 
@@ -43,6 +48,7 @@ impl ScDispatcher<FileReplica> {
     pub fn new(ctx: SharedGlobalContext<FileReplica>) -> Self {
         Self {
             status_update: ctx.status_update_owned(),
+            transform_offsets: ctx.transform_offsets_owned(),
             ctx,
             counter: DispatcherCounter::default(),
         }
@@ -133,6 +139,7 @@ impl ScDispatcher<FileReplica> {
 
                 _ = status_timer.next() =>  {
                     self.send_status_back_to_sc(&mut sink).await?;
+                    self.send_transform_offsets_to_sc(&mut sink).await?;
                 },
 
                 sc_request = api_stream.next() => {
@@ -156,6 +163,17 @@ impl ScDispatcher<FileReplica> {
                                 break;
                             }
                         },
+                        Some(Ok(InternalSpuRequest::UpdateDataPolicyRequest(request))) => {
+                            self.handle_update_data_policy_request(request);
+                        },
+                        Some(Ok(InternalSpuRequest::UpdateTransformRequest(request))) => {
+                            self.counter.transform += 1;
+                            self.handle_update_transform_request(request);
+                        },
+                        Some(Ok(InternalSpuRequest::UpdateQuotaRequest(request))) => {
+                            self.counter.quota += 1;
+                            self.handle_update_quota_request(request);
+                        },
 
                         Some(_) => {
                             debug!("no more sc msg content, end");
@@ -197,6 +215,26 @@ impl ScDispatcher<FileReplica> {
         })
     }
 
+    /// commit offsets of transforms led by this spu
+    #[instrument(skip(self))]
+    async fn send_transform_offsets_to_sc(
+        &mut self,
+        sc_sink: &mut FluvioSink,
+    ) -> Result<(), SocketError> {
+        let offsets = self.transform_offsets.remove_all().await;
+        if offsets.is_empty() {
+            return Ok(());
+        }
+
+        trace!(?offsets, "sending transform offsets to sc");
+        let message = RequestMessage::new_request(UpdateTransformOffsetsRequest::new(offsets));
+
+        sc_sink.send_request(&message).await.map_err(|err| {
+            error!("error sending transform offsets: {:#?}", err);
+            err
+        })
+    }
+
     /// register local spu to sc
     #[instrument(
         skip(self),
@@ -364,8 +402,98 @@ impl ScDispatcher<FileReplica> {
                 .apply_changes(request.changes)
         };
 
-        debug!(actions = actions.count(), "finished SmartModule update");
+        let count = actions.count();
+        // compiled module of previous version is no longer needed
+        for action in actions.into_iter() {
+            match action {
+                SpecChange::Mod(new, old) if new.spec.wasm.payload != old.spec.wasm.payload => {
+                    self.evict_smartmodule(old.spec.wasm.payload.into());
+                }
+                SpecChange::Delete(old) => {
+                    self.ctx.metrics().remove_smartmodule(&old.name);
+                    self.evict_smartmodule(old.spec.wasm.payload.into());
+                }
+                _ => {}
+            }
+        }
+
+        debug!(actions = count, "finished SmartModule update");
 
         Ok(())
     }
+
+    /// modules are compiled from unzipped wasm, so it is unzipped the same way as for chain
+    fn evict_smartmodule(&self, gzipped: Vec<u8>) {
+        match SmartModuleInvocationWasm::AdHoc(gzipped).into_raw() {
+            Ok(raw) => self.ctx.smartengine().evict_module(&raw),
+            Err(err) => warn!(%err, "unable to unzip SmartModule, compiled module not evicted"),
+        }
+    }
+
+    ///
+    /// Handle transform update sent by SC, transform controller picks up changes from store
+    ///
+    #[instrument(skip(self, req_msg), name = "update_transform_request")]
+    fn handle_update_transform_request(&mut self, req_msg: RequestMessage<UpdateTransformRequest>) {
+        let (_, request) = req_msg.get_header_request();
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received transform sync all"
+            );
+            self.ctx.transform_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received transform changes"
+            );
+            self.ctx
+                .transform_localstore()
+                .apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished transform update");
+    }
+
+    ///
+    /// Handle quota update sent by SC, quotas are looked up from store on every request
+    ///
+    #[instrument(skip(self, req_msg), name = "update_quota_request")]
+    fn handle_update_quota_request(&mut self, req_msg: RequestMessage<UpdateQuotaRequest>) {
+        let (_, request) = req_msg.get_header_request();
+
+        let actions = if !request.all.is_empty() {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.all.len(),
+                "received quota sync all"
+            );
+            self.ctx.quota_localstore().sync_all(request.all)
+        } else {
+            debug!(
+                epoch = request.epoch,
+                item_count = request.changes.len(),
+                "received quota changes"
+            );
+            self.ctx.quota_localstore().apply_changes(request.changes)
+        };
+
+        debug!(actions = actions.count(), "finished quota update");
+    }
+
+    ///
+    /// Handle data policy sent by SC
+    ///
+    #[instrument(skip(self, req_msg), name = "update_data_policy_request")]
+    fn handle_update_data_policy_request(
+        &mut self,
+        req_msg: RequestMessage<UpdateDataPolicyRequest>,
+    ) {
+        let (_, request) = req_msg.get_header_request();
+        info!(enabled = request.policy.is_some(), "received data policy");
+        self.ctx.set_data_policy(request.policy);
+    }
 }
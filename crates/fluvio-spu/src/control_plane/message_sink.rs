@@ -1,11 +1,14 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use async_lock::Mutex;
 
-use fluvio_controlplane::{LrsRequest};
+use fluvio_controlplane::{LrsRequest, TransformOffset};
+use fluvio_protocol::record::Offset;
+use fluvio_types::PartitionId;
 
 pub type SharedStatusUpdate = Arc<StatusMessageSink>;
+pub type SharedTransformOffsets = Arc<TransformOffsetSink>;
 
 /// channel used to send message to sc
 #[derive(Debug)]
@@ -28,3 +31,26 @@ impl StatusMessageSink {
         lock.drain().collect()
     }
 }
+
+/// transform offsets to be committed to sc
+#[derive(Debug)]
+pub struct TransformOffsetSink(Mutex<HashMap<(String, PartitionId), Offset>>);
+
+impl TransformOffsetSink {
+    pub fn shared() -> Arc<Self> {
+        Arc::new(Self(Mutex::new(HashMap::new())))
+    }
+
+    /// newer offset of same transform partition overwrites previous if it has not been sent
+    pub async fn send(&self, offset: TransformOffset) {
+        let mut lock = self.0.lock().await;
+        lock.insert((offset.name, offset.partition), offset.offset);
+    }
+
+    pub async fn remove_all(&self) -> Vec<TransformOffset> {
+        let mut lock = self.0.lock().await;
+        lock.drain()
+            .map(|((name, partition), offset)| TransformOffset::new(name, partition, offset))
+            .collect()
+    }
+}
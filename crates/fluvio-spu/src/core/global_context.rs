@@ -3,12 +3,14 @@
 //!
 //! Global Context maintains states need to be shared across in the SPU
 
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::fmt::Debug;
 
 use fluvio_smartengine::SmartEngine;
 use tracing::{debug, error, instrument};
 
+use fluvio_auth::DataPolicy;
+use fluvio_auth::sasl::SaslAuthenticator;
 use fluvio_controlplane_metadata::partition::Replica;
 use fluvio_types::SpuId;
 use fluvio_storage::{ReplicaStorage};
@@ -20,6 +22,7 @@ use crate::replication::leader::{
     SharedReplicaLeadersState, ReplicaLeadersState, FollowerNotifier, SharedSpuUpdates,
 };
 use crate::control_plane::{StatusMessageSink, SharedStatusUpdate};
+use crate::control_plane::{TransformOffsetSink, SharedTransformOffsets};
 use crate::core::metrics::SpuMetrics;
 
 use super::leader_client::LeaderConnections;
@@ -27,6 +30,8 @@ use super::smartmodule::SmartModuleLocalStore;
 use super::spus::SharedSpuLocalStore;
 use super::SharedReplicaLocalStore;
 use super::smartmodule::SharedSmartModuleLocalStore;
+use super::transform::{TransformLocalStore, SharedTransformLocalStore};
+use super::quota::{QuotaLocalStore, SharedQuotaLocalStore, QuotaManager};
 use super::spus::SpuLocalStore;
 use super::replica::ReplicaStore;
 use super::SharedSpuConfig;
@@ -39,13 +44,19 @@ pub struct GlobalContext<S> {
     spu_localstore: SharedSpuLocalStore,
     replica_localstore: SharedReplicaLocalStore,
     smartmodule_localstore: SharedSmartModuleLocalStore,
+    transform_localstore: SharedTransformLocalStore,
+    quota_localstore: SharedQuotaLocalStore,
+    quotas: Arc<QuotaManager>,
     leaders_state: SharedReplicaLeadersState<S>,
     followers_state: SharedFollowersState<S>,
     spu_followers: SharedSpuUpdates,
     status_update: SharedStatusUpdate,
+    transform_offsets: SharedTransformOffsets,
     sm_engine: SmartEngine,
     leaders: Arc<LeaderConnections>,
     metrics: Arc<SpuMetrics>,
+    data_policy: RwLock<Option<Arc<DataPolicy>>>,
+    sasl: Option<SaslAuthenticator>,
 }
 
 // -----------------------------------
@@ -64,19 +75,36 @@ where
         let spus = SpuLocalStore::new_shared();
         let replicas = ReplicaStore::new_shared();
         let metrics = Arc::new(SpuMetrics::new());
+        let quotas = QuotaLocalStore::new_shared();
+        let sasl = SaslAuthenticator::load(
+            spu_config.sasl_credentials.clone(),
+            spu_config.token_secret.clone(),
+        )
+        .expect("unable to load sasl authenticator")
+        .map(|sasl| sasl.with_x509_proxy(spu_config.x509_auth_scopes.is_some()));
+        let sm_engine = match &spu_config.smartmodule_cache_dir {
+            Some(dir) => SmartEngine::new().with_cache_dir(dir),
+            None => SmartEngine::new(),
+        };
 
         GlobalContext {
             spu_localstore: spus.clone(),
             replica_localstore: replicas.clone(),
             smartmodule_localstore: SmartModuleLocalStore::new_shared(),
+            transform_localstore: TransformLocalStore::new_shared(),
+            quota_localstore: quotas.clone(),
+            quotas: Arc::new(QuotaManager::new(quotas)),
             config: Arc::new(spu_config),
             leaders_state: ReplicaLeadersState::new_shared(),
             followers_state: FollowersState::new_shared(),
             spu_followers: FollowerNotifier::shared(),
             status_update: StatusMessageSink::shared(),
-            sm_engine: SmartEngine::new(),
+            transform_offsets: TransformOffsetSink::shared(),
+            sm_engine,
             leaders: LeaderConnections::shared(spus, replicas),
             metrics,
+            data_policy: RwLock::new(None),
+            sasl,
         }
     }
 
@@ -101,6 +129,19 @@ where
         &self.smartmodule_localstore
     }
 
+    pub fn transform_localstore(&self) -> &TransformLocalStore {
+        &self.transform_localstore
+    }
+
+    pub fn quota_localstore(&self) -> &QuotaLocalStore {
+        &self.quota_localstore
+    }
+
+    /// usage of clients against quotas
+    pub(crate) fn quotas(&self) -> Arc<QuotaManager> {
+        self.quotas.clone()
+    }
+
     pub fn leaders_state(&self) -> &ReplicaLeadersState<S> {
         &self.leaders_state
     }
@@ -134,6 +175,15 @@ where
         self.status_update.clone()
     }
 
+    /// offsets committed by transforms, sent to sc
+    pub fn transform_offsets(&self) -> &TransformOffsetSink {
+        &self.transform_offsets
+    }
+
+    pub fn transform_offsets_owned(&self) -> SharedTransformOffsets {
+        self.transform_offsets.clone()
+    }
+
     /// notify all follower handlers with SPU changes
     #[instrument(skip(self))]
     pub async fn sync_follower_update(&self) {
@@ -146,7 +196,10 @@ where
         self.sm_engine.clone()
     }
 
-    #[allow(unused)]
+    pub fn smartengine(&self) -> &SmartEngine {
+        &self.sm_engine
+    }
+
     pub fn leaders(&self) -> Arc<LeaderConnections> {
         self.leaders.clone()
     }
@@ -154,6 +207,25 @@ where
     pub(crate) fn metrics(&self) -> Arc<SpuMetrics> {
         self.metrics.clone()
     }
+
+    /// policy for producing and consuming, received from SC
+    pub fn data_policy(&self) -> Option<Arc<DataPolicy>> {
+        self.data_policy
+            .read()
+            .ok()
+            .and_then(|policy| policy.clone())
+    }
+
+    /// authenticator of public connections if SASL is enabled
+    pub fn sasl(&self) -> Option<&SaslAuthenticator> {
+        self.sasl.as_ref()
+    }
+
+    pub fn set_data_policy(&self, policy: Option<DataPolicy>) {
+        if let Ok(mut current) = self.data_policy.write() {
+            *current = policy.map(Arc::new);
+        }
+    }
 }
 
 mod file_replica {
@@ -311,12 +383,48 @@ mod file_replica {
                                     }
                                 }
                             } else if new_replica.leader == local_id {
-                                if self.leaders_state().get(&new_replica.id).await.is_some() {
+                                if let Some(leader) =
+                                    self.leaders_state().get(&new_replica.id).await
+                                {
+                                    leader
+                                        .update_storage_config(self.config(), &new_replica)
+                                        .await;
+                                    if new_replica.replicas != old_replica.replicas
+                                        || new_replica.min_in_sync_replicas
+                                            != old_replica.min_in_sync_replicas
+                                    {
+                                        leader.update_followers(&new_replica).await;
+                                    }
                                 } else {
                                     error!("leader controller was not found: {}", new_replica.id);
                                 }
                             } else {
-                                self.followers_state().update_replica(new_replica).await;
+                                // replica set may change by reassignment
+                                match (
+                                    old_replica.replicas.contains(&local_id),
+                                    new_replica.replicas.contains(&local_id),
+                                ) {
+                                    (false, true) => {
+                                        if let Err(err) = self
+                                            .followers_state_owned()
+                                            .add_replica(self, new_replica)
+                                            .await
+                                        {
+                                            outputs.push(ReplicaChange::StorageError(err));
+                                        }
+                                    }
+                                    (true, false) => {
+                                        self.remove_follower_replica(new_replica).await
+                                    }
+                                    (true, true) => {
+                                        self.followers_state()
+                                            .update_replica(self, new_replica)
+                                            .await
+                                    }
+                                    (false, false) => {
+                                        debug!(replica = %new_replica.id, "not application to this spu, ignoring");
+                                    }
+                                }
                             }
                         }
                     }
@@ -334,6 +442,7 @@ mod file_replica {
             )
         )]
         async fn remove_leader_replica(&self, replica: Replica) -> ReplicaRemovedRequest {
+            self.metrics().remove_partition(&replica.id);
             // try to send message to leader controller if still exists
             if let Some(previous_state) = self.leaders_state().remove(&replica.id).await {
                 if let Err(err) = previous_state.remove().await {
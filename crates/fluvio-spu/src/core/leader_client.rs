@@ -1,5 +1,7 @@
 use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use anyhow::{anyhow, Result};
 use async_lock::Mutex;
 use async_trait::async_trait;
 
@@ -7,13 +9,22 @@ use fluvio::metrics::ClientMetrics;
 use fluvio::{FluvioError, PartitionConsumer};
 use fluvio::spu::{SpuDirectory, SpuSocket};
 use fluvio_controlplane_metadata::partition::ReplicaKey;
-use fluvio_socket::{MultiplexerSocket, ClientConfig, VersionedSerialSocket};
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, RawRecords, RecordSet};
+use fluvio_socket::{FluvioSocket, MultiplexerSocket, ClientConfig, VersionedSerialSocket};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultProduceRequest, DefaultTopicRequest};
 use fluvio_types::{SpuId, PartitionId};
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
+
+use crate::services::internal::{PeerProduceRequest, ReplicateCheckpointRequest};
 
 use super::SharedReplicaLocalStore;
 use super::spus::SharedSpuLocalStore;
 
+const PRODUCE_TIMEOUT: Duration = Duration::from_secs(10);
+
 /// maintain connections to all leaders
 #[allow(dead_code)]
 #[derive(Debug, Default)]
@@ -21,6 +32,7 @@ pub struct LeaderConnections {
     spus: SharedSpuLocalStore,
     replicas: SharedReplicaLocalStore,
     leaders: Arc<Mutex<HashMap<SpuId, SpuSocket>>>,
+    peers: Mutex<HashMap<SpuId, Arc<Mutex<FluvioSocket>>>>,
     metrics: Arc<ClientMetrics>,
 }
 
@@ -30,6 +42,7 @@ impl LeaderConnections {
             spus,
             replicas,
             leaders: Default::default(),
+            peers: Default::default(),
             metrics: Arc::new(ClientMetrics::new()),
         }
     }
@@ -67,6 +80,135 @@ impl LeaderConnections {
     {
         PartitionConsumer::new(topic.into(), partition, self.clone(), self.metrics.clone())
     }
+
+    /// partition of topic which records of source partition are produced to,
+    /// none if topic doesn't exist
+    pub fn mapped_partition(&self, topic: &str, source: PartitionId) -> Option<ReplicaKey> {
+        let partitions = self
+            .replicas
+            .all_keys()
+            .into_iter()
+            .filter(|replica| replica.topic == topic)
+            .count();
+        target_partition(source, partitions).map(|partition| ReplicaKey::new(topic, partition))
+    }
+
+    /// connection to private endpoint of peer spu, shared by produce requests to that spu
+    async fn peer_socket(&self, spu: SpuId) -> Result<Arc<Mutex<FluvioSocket>>> {
+        let mut peers = self.peers.lock().await;
+        if let Some(socket) = peers.get(&spu) {
+            return Ok(socket.clone());
+        }
+        let spec = self.spus.spec(&spu).ok_or(FluvioError::SPUNotFound(spu))?;
+        debug!(spu, endpoint = %spec.private_endpoint, "connecting to peer");
+        let socket = Arc::new(Mutex::new(
+            FluvioSocket::connect(&spec.private_endpoint.to_string()).await?,
+        ));
+        peers.insert(spu, socket.clone());
+        Ok(socket)
+    }
+
+    /// produce batch to leader of replica, records are new records of replica so they are
+    /// timestamped now. Records are sent to private endpoint of leader, which is trusted
+    /// like replication, so it works regardless of authorization of public endpoint.
+    #[instrument(skip(self, batch))]
+    pub async fn produce(&self, replica: &ReplicaKey, mut batch: Batch) -> Result<()> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as i64)
+            .unwrap_or_default();
+        batch.header.first_timestamp = now;
+        batch.header.max_time_stamp = now;
+        let records = RecordSet::default().add(Batch::<RawRecords>::try_from(batch)?);
+
+        let request = DefaultProduceRequest {
+            isolation: Isolation::ReadCommitted,
+            timeout: PRODUCE_TIMEOUT,
+            topics: vec![DefaultTopicRequest {
+                name: replica.topic.clone(),
+                partitions: vec![DefaultPartitionRequest {
+                    partition_index: replica.partition,
+                    records,
+                }],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let leader = self
+            .replicas
+            .spec(replica)
+            .ok_or_else(|| FluvioError::TopicNotFound(replica.to_string()))?
+            .leader;
+        let socket = self.peer_socket(leader).await?;
+        let result = socket
+            .lock()
+            .await
+            .send(&RequestMessage::new_request(PeerProduceRequest { request }))
+            .await;
+        let response = match result {
+            Ok(response) => response.response,
+            Err(err) => {
+                // reconnect on next produce
+                self.peers.lock().await.remove(&leader);
+                return Err(err.into());
+            }
+        };
+        for partition in response
+            .responses
+            .iter()
+            .flat_map(|topic| topic.partitions.iter())
+        {
+            if partition.error_code != ErrorCode::None {
+                return Err(anyhow!(
+                    "producing to {} failed: {}",
+                    replica,
+                    partition.error_code
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// send checkpoint to followers of partition, failure is only logged
+    /// since new leader can still fall back to older checkpoint
+    #[instrument(skip(self, request), fields(name = %request.name, replica = %request.replica))]
+    pub async fn replicate_checkpoint(
+        &self,
+        followers: &[SpuId],
+        request: ReplicateCheckpointRequest,
+    ) {
+        let request = RequestMessage::new_request(request);
+        for follower in followers {
+            let socket = match self.peer_socket(*follower).await {
+                Ok(socket) => socket,
+                Err(err) => {
+                    warn!(follower, %err, "unable to connect to follower");
+                    continue;
+                }
+            };
+            let result = socket.lock().await.send(&request).await;
+            match result {
+                Ok(response) if response.response.error_code.is_error() => {
+                    warn!(follower, error = %response.response.error_code, "checkpoint not replicated");
+                }
+                Ok(_) => debug!(follower, "checkpoint replicated"),
+                Err(err) => {
+                    warn!(follower, %err, "unable to replicate checkpoint");
+                    self.peers.lock().await.remove(follower);
+                }
+            }
+        }
+    }
+}
+
+/// source partition is mapped to target partition, so records of partition keep their order
+fn target_partition(source: PartitionId, partitions: usize) -> Option<PartitionId> {
+    if partitions == 0 {
+        None
+    } else {
+        Some(source % partitions as PartitionId)
+    }
 }
 
 #[async_trait]
@@ -130,3 +272,17 @@ impl SpuDirectory for LeaderConnections {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+
+    use super::target_partition;
+
+    #[test]
+    fn test_target_partition() {
+        assert_eq!(target_partition(0, 3), Some(0));
+        assert_eq!(target_partition(4, 3), Some(1));
+        assert_eq!(target_partition(1, 1), Some(0));
+        assert_eq!(target_partition(1, 0), None);
+    }
+}
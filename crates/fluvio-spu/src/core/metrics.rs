@@ -1,18 +1,47 @@
 use std::{
-    sync::atomic::{AtomicU64, Ordering},
+    collections::HashMap,
+    fmt::Write,
+    hash::Hash,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU64, Ordering},
+    },
     ops::AddAssign,
+    time::Duration,
 };
 
-use fluvio_protocol::record::Batch;
+use fluvio_protocol::record::{Batch, Offset};
+use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_smartengine::metrics::SmartModuleChainMetrics;
 use fluvio_spu_schema::fetch::FilePartitionResponse;
+use fluvio_types::SpuId;
 use serde::Serialize;
 
+/// max distinct client ids and SmartModule chains labelled, both are chosen by clients.
+/// Metrics of labels beyond it are added to `OVERFLOW_LABEL`
+const MAX_CLIENT_LABELS: usize = 1000;
+const OVERFLOW_LABEL: &str = "other";
+
+/// longer label values set by clients are truncated
+const MAX_LABEL_LEN: usize = 128;
+
+/// upper bounds of latency histogram buckets, in seconds
+const LATENCY_BUCKETS: [f64; 12] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
 #[derive(Default, Debug, Serialize)]
 pub(crate) struct SpuMetrics {
     inbound: Activity,
     outbound: Activity,
     smartmodule: SmartModuleChainMetrics,
+    // labelled metrics are only exposed in prometheus format
+    #[serde(skip)]
+    partitions: Labelled<ReplicaKey, PartitionMetrics>,
+    #[serde(skip)]
+    clients: Labelled<String, ClientMetrics>,
+    #[serde(skip)]
+    smartmodules: Labelled<String, SmartModuleChainMetrics>,
 }
 
 impl SpuMetrics {
@@ -31,6 +60,429 @@ impl SpuMetrics {
     pub fn chain_metrics(&self) -> &SmartModuleChainMetrics {
         &self.smartmodule
     }
+
+    pub fn partition(&self, replica: &ReplicaKey) -> Arc<PartitionMetrics> {
+        self.partitions.get_or_default(replica)
+    }
+
+    pub fn client(&self, client_id: &str) -> Arc<ClientMetrics> {
+        self.clients.get_bounded(client_id)
+    }
+
+    pub fn smartmodule(&self, name: &str) -> Arc<SmartModuleChainMetrics> {
+        self.smartmodules.get_bounded(name)
+    }
+
+    /// drop metrics of partition removed from this SPU
+    pub(crate) fn remove_partition(&self, replica: &ReplicaKey) {
+        self.partitions.retain(|key| key != replica);
+    }
+
+    /// drop metrics of chains using deleted SmartModule
+    pub(crate) fn remove_smartmodule(&self, name: &str) {
+        self.smartmodules
+            .retain(|chain| !chain.split(',').any(|chain_name| chain_name == name));
+    }
+
+    /// record records written by producer to replica
+    pub(crate) fn record_produce(
+        &self,
+        replica: &ReplicaKey,
+        client_id: &str,
+        connector: bool,
+        value: IncreaseValue,
+        elapsed: Duration,
+    ) {
+        self.inbound.increase_by_value(connector, value);
+        let partition = self.partition(replica);
+        partition.inbound.increase(value.records, value.bytes);
+        partition.produce_latency.observe(elapsed);
+        self.client(client_id)
+            .inbound
+            .increase(value.records, value.bytes);
+    }
+
+    /// record records sent back to consumer from replica
+    pub(crate) fn record_fetch(
+        &self,
+        replica: &ReplicaKey,
+        client_id: &str,
+        connector: bool,
+        value: IncreaseValue,
+        elapsed: Duration,
+    ) {
+        self.outbound.increase_by_value(connector, value);
+        let partition = self.partition(replica);
+        partition.outbound.increase(value.records, value.bytes);
+        partition.fetch_latency.observe(elapsed);
+        self.client(client_id)
+            .outbound
+            .increase(value.records, value.bytes);
+    }
+
+    /// add metrics of single smartmodule chain invocation to totals and to chain's own metrics
+    pub(crate) fn record_smartmodule(&self, name: &str, invocation: &SmartModuleChainMetrics) {
+        self.smartmodule.add(invocation);
+        self.smartmodule(name).add(invocation);
+    }
+
+    /// render labelled metrics and replica statistics in prometheus text format
+    pub(crate) fn render_prometheus(&self, replicas: &[ReplicaStats]) -> String {
+        let mut out = String::new();
+
+        let partitions = self.partitions.snapshot();
+        let partition_counters: [(&str, &str, fn(&PartitionMetrics) -> u64); 4] = [
+            (
+                "fluvio_spu_partition_records_in_total",
+                "Records written to partition",
+                |m| m.inbound.records(),
+            ),
+            (
+                "fluvio_spu_partition_bytes_in_total",
+                "Bytes written to partition",
+                |m| m.inbound.bytes(),
+            ),
+            (
+                "fluvio_spu_partition_records_out_total",
+                "Records sent to consumers of partition",
+                |m| m.outbound.records(),
+            ),
+            (
+                "fluvio_spu_partition_bytes_out_total",
+                "Bytes sent to consumers of partition",
+                |m| m.outbound.bytes(),
+            ),
+        ];
+        for (name, help, value) in partition_counters {
+            write_header(&mut out, name, help, "counter");
+            for (key, metrics) in &partitions {
+                let _ = writeln!(
+                    out,
+                    "{name}{} {}",
+                    labels(&partition_pairs(key)),
+                    value(metrics)
+                );
+            }
+        }
+
+        write_header(
+            &mut out,
+            "fluvio_spu_produce_latency_seconds",
+            "Time to write produced records to partition",
+            "histogram",
+        );
+        for (key, metrics) in &partitions {
+            metrics.produce_latency.render(
+                &mut out,
+                "fluvio_spu_produce_latency_seconds",
+                &partition_pairs(key),
+            );
+        }
+        write_header(
+            &mut out,
+            "fluvio_spu_fetch_latency_seconds",
+            "Time to read and send records to consumer",
+            "histogram",
+        );
+        for (key, metrics) in &partitions {
+            metrics.fetch_latency.render(
+                &mut out,
+                "fluvio_spu_fetch_latency_seconds",
+                &partition_pairs(key),
+            );
+        }
+
+        let clients = self.clients.snapshot();
+        let client_counters: [(&str, &str, fn(&ClientMetrics) -> u64); 4] = [
+            (
+                "fluvio_spu_client_records_in_total",
+                "Records produced by client",
+                |m| m.inbound.records(),
+            ),
+            (
+                "fluvio_spu_client_bytes_in_total",
+                "Bytes produced by client",
+                |m| m.inbound.bytes(),
+            ),
+            (
+                "fluvio_spu_client_records_out_total",
+                "Records consumed by client",
+                |m| m.outbound.records(),
+            ),
+            (
+                "fluvio_spu_client_bytes_out_total",
+                "Bytes consumed by client",
+                |m| m.outbound.bytes(),
+            ),
+        ];
+        for (name, help, value) in client_counters {
+            write_header(&mut out, name, help, "counter");
+            for (client_id, metrics) in &clients {
+                let _ = writeln!(
+                    out,
+                    "{name}{} {}",
+                    labels(&[("client_id", client_id.clone())]),
+                    value(metrics)
+                );
+            }
+        }
+
+        let smartmodules = self.smartmodules.snapshot();
+        let smartmodule_counters: [(&str, &str, fn(&SmartModuleChainMetrics) -> u64); 4] = [
+            (
+                "fluvio_spu_smartmodule_bytes_in_total",
+                "Bytes processed by smartmodule",
+                |m| m.bytes_in(),
+            ),
+            (
+                "fluvio_spu_smartmodule_records_out_total",
+                "Records emitted by smartmodule",
+                |m| m.records_out(),
+            ),
+            (
+                "fluvio_spu_smartmodule_invocations_total",
+                "Smartmodule invocations",
+                |m| m.invocation_count(),
+            ),
+            (
+                "fluvio_spu_smartmodule_fuel_used_total",
+                "Fuel consumed by smartmodule",
+                |m| m.fuel_used(),
+            ),
+        ];
+        for (name, help, value) in smartmodule_counters {
+            write_header(&mut out, name, help, "counter");
+            for (smartmodule, metrics) in &smartmodules {
+                let _ = writeln!(
+                    out,
+                    "{name}{} {}",
+                    labels(&[("smartmodule", smartmodule.clone())]),
+                    value(metrics)
+                );
+            }
+        }
+
+        let replica_gauges: [(&str, &str, fn(&ReplicaStats) -> u64); 4] = [
+            (
+                "fluvio_spu_replica_leo",
+                "Log end offset of leader replica",
+                |r| r.leo.max(0) as u64,
+            ),
+            (
+                "fluvio_spu_replica_hw",
+                "High watermark of leader replica",
+                |r| r.hw.max(0) as u64,
+            ),
+            (
+                "fluvio_spu_replica_segments",
+                "Number of log segments of replica",
+                |r| r.segments as u64,
+            ),
+            (
+                "fluvio_spu_replica_size_bytes",
+                "Bytes on disk of replica",
+                |r| r.size,
+            ),
+        ];
+        for (name, help, value) in replica_gauges {
+            write_header(&mut out, name, help, "gauge");
+            for replica in replicas {
+                let _ = writeln!(
+                    out,
+                    "{name}{} {}",
+                    labels(&partition_pairs(&replica.replica)),
+                    value(replica)
+                );
+            }
+        }
+
+        write_header(
+            &mut out,
+            "fluvio_spu_replica_lag_records",
+            "Records of leader not yet replicated to follower",
+            "gauge",
+        );
+        for replica in replicas {
+            for (follower, follower_leo) in &replica.followers {
+                let mut pairs = partition_pairs(&replica.replica);
+                pairs.push(("follower", follower.to_string()));
+                let _ = writeln!(
+                    out,
+                    "fluvio_spu_replica_lag_records{} {}",
+                    labels(&pairs),
+                    (replica.leo - follower_leo).max(0)
+                );
+            }
+        }
+
+        out
+    }
+}
+
+/// point in time statistics of leader replica
+#[derive(Debug, Default)]
+pub(crate) struct ReplicaStats {
+    pub replica: ReplicaKey,
+    pub leo: Offset,
+    pub hw: Offset,
+    pub segments: usize,
+    pub size: u64,
+    /// follower id and its log end offset
+    pub followers: Vec<(SpuId, Offset)>,
+}
+
+#[derive(Default, Debug)]
+pub(crate) struct PartitionMetrics {
+    inbound: Record,
+    outbound: Record,
+    produce_latency: Histogram,
+    fetch_latency: Histogram,
+}
+
+#[derive(Default, Debug)]
+pub(crate) struct ClientMetrics {
+    inbound: Record,
+    outbound: Record,
+}
+
+/// metrics keyed by label, created on first use
+#[derive(Debug)]
+struct Labelled<K, V>(RwLock<HashMap<K, Arc<V>>>);
+
+impl<K, V> Default for Labelled<K, V> {
+    fn default() -> Self {
+        Self(RwLock::new(HashMap::new()))
+    }
+}
+
+impl<K, V> Labelled<K, V>
+where
+    K: Eq + Hash + Ord + Clone,
+    V: Default,
+{
+    fn get_or_default<Q>(&self, key: &Q) -> Arc<V>
+    where
+        K: std::borrow::Borrow<Q>,
+        Q: Eq + Hash + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(value) = self.0.read().ok().and_then(|map| map.get(key).cloned()) {
+            return value;
+        }
+        match self.0.write() {
+            Ok(mut map) => map.entry(key.to_owned()).or_default().clone(),
+            Err(_) => Arc::new(V::default()),
+        }
+    }
+
+    fn retain(&self, mut f: impl FnMut(&K) -> bool) {
+        if let Ok(mut map) = self.0.write() {
+            map.retain(|key, _| f(key));
+        }
+    }
+
+    /// entries sorted by key so output is stable
+    fn snapshot(&self) -> Vec<(K, Arc<V>)> {
+        let mut entries: Vec<(K, Arc<V>)> = self
+            .0
+            .read()
+            .map(|map| {
+                map.iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        entries
+    }
+}
+
+impl<V: Default> Labelled<String, V> {
+    /// get metrics of label set by client, labels are bounded so client can't exhaust memory
+    fn get_bounded(&self, label: &str) -> Arc<V> {
+        let label = match label.char_indices().nth(MAX_LABEL_LEN) {
+            Some((end, _)) => &label[..end],
+            None => label,
+        };
+        if let Some(value) = self.0.read().ok().and_then(|map| map.get(label).cloned()) {
+            return value;
+        }
+        match self.0.write() {
+            Ok(mut map) => {
+                let label = if map.len() >= MAX_CLIENT_LABELS && !map.contains_key(label) {
+                    OVERFLOW_LABEL
+                } else {
+                    label
+                };
+                map.entry(label.to_owned()).or_default().clone()
+            }
+            Err(_) => Arc::new(V::default()),
+        }
+    }
+}
+
+/// latency histogram with fixed buckets
+#[derive(Default, Debug)]
+pub(crate) struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    pub(crate) fn observe(&self, elapsed: Duration) {
+        let seconds = elapsed.as_secs_f64();
+        if let Some(index) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            self.buckets[index].fetch_add(1, Ordering::SeqCst);
+        }
+        self.count.fetch_add(1, Ordering::SeqCst);
+        self.sum_micros
+            .fetch_add(elapsed.as_micros() as u64, Ordering::SeqCst);
+    }
+
+    fn render(&self, out: &mut String, name: &str, label_pairs: &[LabelPair]) {
+        let mut cumulative = 0;
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(self.buckets.iter()) {
+            cumulative += bucket.load(Ordering::SeqCst);
+            let mut pairs = label_pairs.to_vec();
+            pairs.push(("le", bound.to_string()));
+            let _ = writeln!(out, "{name}_bucket{} {cumulative}", labels(&pairs));
+        }
+        let count = self.count.load(Ordering::SeqCst);
+        let mut pairs = label_pairs.to_vec();
+        pairs.push(("le", "+Inf".to_owned()));
+        let _ = writeln!(out, "{name}_bucket{} {count}", labels(&pairs));
+        let sum = self.sum_micros.load(Ordering::SeqCst) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{name}_sum{} {sum}", labels(label_pairs));
+        let _ = writeln!(out, "{name}_count{} {count}", labels(label_pairs));
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, ty: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {ty}");
+}
+
+type LabelPair = (&'static str, String);
+
+fn partition_pairs(replica: &ReplicaKey) -> Vec<LabelPair> {
+    vec![
+        ("topic", replica.topic.clone()),
+        ("partition", replica.partition.to_string()),
+    ]
+}
+
+fn labels(pairs: &[LabelPair]) -> String {
+    let pairs: Vec<String> = pairs
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{name}=\"{value}\"")
+        })
+        .collect();
+    format!("{{{}}}", pairs.join(","))
 }
 
 #[derive(Default, Debug, Serialize)]
@@ -44,6 +496,14 @@ impl Record {
         self.records.fetch_add(records, Ordering::SeqCst);
         self.bytes.fetch_add(bytes, Ordering::SeqCst);
     }
+
+    fn records(&self) -> u64 {
+        self.records.load(Ordering::SeqCst)
+    }
+
+    fn bytes(&self) -> u64 {
+        self.bytes.load(Ordering::SeqCst)
+    }
 }
 
 #[derive(Default, Debug, Serialize)]
@@ -52,7 +512,7 @@ pub struct Activity {
     client: Record,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Copy)]
 pub(crate) struct IncreaseValue {
     records: u64,
     bytes: u64,
@@ -93,6 +553,10 @@ impl IncreaseValue {
     pub(crate) fn new(records: u64, bytes: u64) -> Self {
         Self { records, bytes }
     }
+
+    pub(crate) fn bytes(&self) -> u64 {
+        self.bytes
+    }
 }
 
 // Measuring of serialized data. `bytes` is length of file slice, `records` is an offset's change
@@ -169,4 +633,118 @@ mod tests {
         assert_eq!(activity.connector.records.load(Ordering::SeqCst), 1);
         assert_eq!(activity.connector.bytes.load(Ordering::SeqCst), 123);
     }
+
+    #[test]
+    fn test_histogram_buckets() {
+        let histogram = Histogram::default();
+
+        histogram.observe(Duration::from_micros(500));
+        histogram.observe(Duration::from_millis(30));
+        histogram.observe(Duration::from_secs(10));
+
+        let mut out = String::new();
+        histogram.render(&mut out, "latency", &[("topic", "t".to_owned())]);
+
+        assert!(out.contains("latency_bucket{topic=\"t\",le=\"0.001\"} 1\n"));
+        assert!(out.contains("latency_bucket{topic=\"t\",le=\"0.05\"} 2\n"));
+        assert!(out.contains("latency_bucket{topic=\"t\",le=\"5\"} 2\n"));
+        assert!(out.contains("latency_bucket{topic=\"t\",le=\"+Inf\"} 3\n"));
+        assert!(out.contains("latency_count{topic=\"t\"} 3\n"));
+    }
+
+    #[test]
+    fn test_render_prometheus() {
+        //given
+        let metrics = SpuMetrics::new();
+        let replica = ReplicaKey::new("topic1", 0_u32);
+        metrics.record_produce(
+            &replica,
+            "producer",
+            false,
+            IncreaseValue::new(2, 100),
+            Duration::from_millis(2),
+        );
+        metrics.record_fetch(
+            &replica,
+            "consumer",
+            true,
+            IncreaseValue::new(1, 40),
+            Duration::from_millis(1),
+        );
+        let invocation = SmartModuleChainMetrics::default();
+        invocation.add_bytes_in(24);
+        invocation.add_records_out(1);
+        metrics.record_smartmodule("filter", &invocation);
+        let replicas = vec![ReplicaStats {
+            replica: replica.clone(),
+            leo: 10,
+            hw: 8,
+            segments: 2,
+            size: 2048,
+            followers: vec![(5001, 8), (5002, 6)],
+        }];
+
+        //when
+        let out = metrics.render_prometheus(&replicas);
+
+        //then
+        assert_eq!(metrics.inbound().client_records(), 2);
+        assert_eq!(metrics.outbound().connector_bytes(), 40);
+        assert_eq!(metrics.chain_metrics().invocation_count(), 1);
+        for line in [
+            "fluvio_spu_partition_records_in_total{topic=\"topic1\",partition=\"0\"} 2",
+            "fluvio_spu_partition_bytes_out_total{topic=\"topic1\",partition=\"0\"} 40",
+            "fluvio_spu_produce_latency_seconds_count{topic=\"topic1\",partition=\"0\"} 1",
+            "fluvio_spu_client_bytes_in_total{client_id=\"producer\"} 100",
+            "fluvio_spu_client_records_out_total{client_id=\"consumer\"} 1",
+            "fluvio_spu_smartmodule_bytes_in_total{smartmodule=\"filter\"} 24",
+            "fluvio_spu_replica_segments{topic=\"topic1\",partition=\"0\"} 2",
+            "fluvio_spu_replica_size_bytes{topic=\"topic1\",partition=\"0\"} 2048",
+            "fluvio_spu_replica_lag_records{topic=\"topic1\",partition=\"0\",follower=\"5002\"} 4",
+        ] {
+            assert!(out.lines().any(|l| l == line), "missing {line} in {out}");
+        }
+    }
+
+    #[test]
+    fn test_bounded_labels_and_removal() {
+        let metrics = SpuMetrics::new();
+        let replica = ReplicaKey::new("topic1", 0_u32);
+        let value = IncreaseValue::new(1, 10);
+        let elapsed = Duration::from_millis(1);
+        for n in 0..MAX_CLIENT_LABELS + 10 {
+            metrics.record_produce(&replica, &format!("client-{n}"), false, value, elapsed);
+        }
+        metrics.record_produce(&replica, &"x".repeat(1000), false, value, elapsed);
+        metrics.record_smartmodule("filter,map", &SmartModuleChainMetrics::default());
+        metrics.record_smartmodule("map", &SmartModuleChainMetrics::default());
+        metrics.record_smartmodule("aggregate", &SmartModuleChainMetrics::default());
+
+        let clients = metrics.clients.snapshot();
+        assert_eq!(clients.len(), MAX_CLIENT_LABELS + 1);
+        let (_, overflow) = clients
+            .iter()
+            .find(|(label, _)| label == OVERFLOW_LABEL)
+            .expect("overflow label");
+        assert_eq!(overflow.inbound.records(), 11);
+
+        metrics.remove_partition(&replica);
+        metrics.remove_smartmodule("map");
+        assert!(metrics.partitions.snapshot().is_empty());
+        let smartmodules: Vec<_> = metrics
+            .smartmodules
+            .snapshot()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert_eq!(smartmodules, vec!["aggregate".to_owned()]);
+    }
+
+    #[test]
+    fn test_label_escape() {
+        assert_eq!(
+            labels(&[("client_id", "a\"b\\c\nd".to_owned())]),
+            "{client_id=\"a\\\"b\\\\c\\nd\"}"
+        );
+    }
 }
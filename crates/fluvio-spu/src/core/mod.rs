@@ -5,9 +5,12 @@ mod leader_client;
 pub mod spus;
 pub mod replica;
 pub mod smartmodule;
+pub mod transform;
+pub mod quota;
 pub mod metrics;
 
 pub use self::global_context::{GlobalContext, ReplicaChange};
+pub use self::leader_client::LeaderConnections;
 pub use self::store::Spec;
 pub use self::store::LocalStore;
 pub use self::store::SpecChange;
@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tracing::debug;
+
+use fluvio_controlplane_metadata::quota::{QuotaSpec, QuotaTarget};
+
+use super::SharedQuotaLocalStore;
+
+/// usage ahead of quota rate client can burst before it is throttled
+const BURST: Duration = Duration::from_secs(1);
+
+/// number of tracked clients above which paid off usage is dropped
+const MAX_IDLE_USAGE: usize = 1024;
+
+/// traffic counted against byte rate of quota
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum QuotaTraffic {
+    Produce,
+    Fetch,
+}
+
+/// identity quota of client is looked up by
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct QuotaClient {
+    principal: Option<String>,
+    client_id: String,
+}
+
+impl QuotaClient {
+    pub(crate) fn new(principal: Option<&str>, client_id: &str) -> Self {
+        Self {
+            principal: principal.map(|principal| principal.to_owned()),
+            client_id: client_id.to_owned(),
+        }
+    }
+}
+
+/// Time at which usage recorded so far is paid off at quota rate.
+/// Client is throttled once it is more than burst ahead of now.
+#[derive(Debug, Default)]
+struct RateTracker {
+    paid_off_at: Option<Instant>,
+}
+
+impl RateTracker {
+    fn record(&mut self, amount: u64, rate: u64, now: Instant) -> Duration {
+        let start = self.paid_off_at.map_or(now, |at| at.max(now));
+        let paid_off_at = start + Duration::from_secs_f64(amount as f64 / rate.max(1) as f64);
+        self.paid_off_at = Some(paid_off_at);
+        paid_off_at.saturating_duration_since(now + BURST)
+    }
+
+    fn is_idle(&self, now: Instant) -> bool {
+        self.paid_off_at.map_or(true, |at| at <= now)
+    }
+}
+
+#[derive(Debug, Default)]
+struct ClientUsage {
+    produce_bytes: RateTracker,
+    fetch_bytes: RateTracker,
+    requests: RateTracker,
+}
+
+impl ClientUsage {
+    fn is_idle(&self, now: Instant) -> bool {
+        self.produce_bytes.is_idle(now)
+            && self.fetch_bytes.is_idle(now)
+            && self.requests.is_idle(now)
+    }
+}
+
+/// Tracks usage of clients against quotas sent by SC.
+/// Usage is kept per quota and client, so clients under default quota are limited separately.
+#[derive(Debug)]
+pub(crate) struct QuotaManager {
+    quotas: SharedQuotaLocalStore,
+    usage: Mutex<HashMap<(String, String), ClientUsage>>,
+}
+
+impl QuotaManager {
+    pub(crate) fn new(quotas: SharedQuotaLocalStore) -> Self {
+        Self {
+            quotas,
+            usage: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// record request of client, returns how long client should hold back to stay within quota
+    pub(crate) fn record(
+        &self,
+        client: &QuotaClient,
+        traffic: QuotaTraffic,
+        bytes: u64,
+    ) -> Duration {
+        self.record_at(client, traffic, bytes, Instant::now())
+    }
+
+    fn record_at(
+        &self,
+        client: &QuotaClient,
+        traffic: QuotaTraffic,
+        bytes: u64,
+        now: Instant,
+    ) -> Duration {
+        let (name, spec, client_key) = match self.find_quota(client) {
+            Some(quota) => quota,
+            None => return Duration::ZERO,
+        };
+        let byte_rate = match traffic {
+            QuotaTraffic::Produce => spec.produce_byte_rate,
+            QuotaTraffic::Fetch => spec.fetch_byte_rate,
+        };
+
+        let mut usage = match self.usage.lock() {
+            Ok(usage) => usage,
+            Err(_) => return Duration::ZERO,
+        };
+        if usage.len() > MAX_IDLE_USAGE {
+            usage.retain(|_, client_usage| !client_usage.is_idle(now));
+        }
+        let client_usage = usage.entry((name, client_key)).or_default();
+
+        let mut throttle = Duration::ZERO;
+        if let Some(rate) = byte_rate {
+            let bytes_tracker = match traffic {
+                QuotaTraffic::Produce => &mut client_usage.produce_bytes,
+                QuotaTraffic::Fetch => &mut client_usage.fetch_bytes,
+            };
+            throttle = throttle.max(bytes_tracker.record(bytes, rate, now));
+        }
+        if let Some(rate) = spec.request_rate {
+            throttle = throttle.max(client_usage.requests.record(1, rate, now));
+        }
+
+        if !throttle.is_zero() {
+            debug!(
+                ?client,
+                ?traffic,
+                throttle_ms = throttle.as_millis() as u64,
+                "client exceeded quota"
+            );
+        }
+        throttle
+    }
+
+    /// quota of principal takes precedence over quota of client id and default quota,
+    /// returns name of quota, its spec and key usage is tracked under
+    fn find_quota(&self, client: &QuotaClient) -> Option<(String, QuotaSpec, String)> {
+        let quotas = self.quotas.read();
+        let mut by_client_id = None;
+        let mut default = None;
+        for quota in quotas.values() {
+            match &quota.spec.target {
+                QuotaTarget::Principal { principal }
+                    if client.principal.as_ref() == Some(principal) =>
+                {
+                    return Some((quota.name.clone(), quota.spec.clone(), principal.clone()));
+                }
+                QuotaTarget::ClientId { client_id } if *client_id == client.client_id => {
+                    by_client_id = Some(quota);
+                }
+                QuotaTarget::Default => {
+                    default = Some(quota);
+                }
+                _ => {}
+            }
+        }
+
+        by_client_id
+            .map(|quota| {
+                (
+                    quota.name.clone(),
+                    quota.spec.clone(),
+                    client.client_id.clone(),
+                )
+            })
+            .or_else(|| {
+                default.map(|quota| {
+                    let client_key = client
+                        .principal
+                        .clone()
+                        .unwrap_or_else(|| client.client_id.clone());
+                    (quota.name.clone(), quota.spec.clone(), client_key)
+                })
+            })
+    }
+}
+
+/// throttle time sent back to client in response
+pub(crate) fn throttle_time_ms(throttle: Duration) -> i32 {
+    throttle.as_millis().min(i32::MAX as u128) as i32
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::time::{Duration, Instant};
+
+    use fluvio_controlplane_metadata::quota::{Quota, QuotaSpec, QuotaTarget};
+
+    use crate::core::quota::QuotaLocalStore;
+
+    use super::{QuotaManager, QuotaClient, QuotaTraffic};
+
+    fn manager(quotas: Vec<(&str, QuotaSpec)>) -> QuotaManager {
+        let store = QuotaLocalStore::new_shared();
+        store.sync_all(
+            quotas
+                .into_iter()
+                .map(|(name, spec)| Quota {
+                    name: name.to_owned(),
+                    spec,
+                })
+                .collect(),
+        );
+        QuotaManager::new(store)
+    }
+
+    #[test]
+    fn test_byte_rate_throttle() {
+        let quotas = manager(vec![(
+            "producer",
+            QuotaSpec::new(QuotaTarget::ClientId {
+                client_id: "producer".to_owned(),
+            })
+            .with_produce_byte_rate(1000),
+        )]);
+        let client = QuotaClient::new(None, "producer");
+        let now = Instant::now();
+
+        // one second of burst is allowed
+        assert!(quotas
+            .record_at(&client, QuotaTraffic::Produce, 1000, now)
+            .is_zero());
+        assert_eq!(
+            quotas.record_at(&client, QuotaTraffic::Produce, 500, now),
+            Duration::from_millis(500)
+        );
+
+        // fetch is not limited by produce rate
+        assert!(quotas
+            .record_at(&client, QuotaTraffic::Fetch, 10000, now)
+            .is_zero());
+
+        // usage is paid off over time
+        assert!(quotas
+            .record_at(
+                &client,
+                QuotaTraffic::Produce,
+                500,
+                now + Duration::from_secs(3)
+            )
+            .is_zero());
+
+        // other clients are not limited
+        assert!(quotas
+            .record_at(
+                &QuotaClient::new(None, "other"),
+                QuotaTraffic::Produce,
+                10000,
+                now
+            )
+            .is_zero());
+    }
+
+    #[test]
+    fn test_request_rate_throttle() {
+        let quotas = manager(vec![(
+            "default",
+            QuotaSpec::new(QuotaTarget::Default).with_request_rate(10),
+        )]);
+        let now = Instant::now();
+        let client1 = QuotaClient::new(None, "client1");
+        for _ in 0..10 {
+            assert!(quotas
+                .record_at(&client1, QuotaTraffic::Fetch, 0, now)
+                .is_zero());
+        }
+        assert_eq!(
+            quotas.record_at(&client1, QuotaTraffic::Fetch, 0, now),
+            Duration::from_millis(100)
+        );
+
+        // each client has own usage under default quota
+        assert!(quotas
+            .record_at(
+                &QuotaClient::new(None, "client2"),
+                QuotaTraffic::Fetch,
+                0,
+                now
+            )
+            .is_zero());
+    }
+
+    #[test]
+    fn test_quota_precedence() {
+        let quotas = manager(vec![
+            (
+                "default",
+                QuotaSpec::new(QuotaTarget::Default).with_produce_byte_rate(1),
+            ),
+            (
+                "client",
+                QuotaSpec::new(QuotaTarget::ClientId {
+                    client_id: "app".to_owned(),
+                })
+                .with_produce_byte_rate(10),
+            ),
+            (
+                "svc",
+                QuotaSpec::new(QuotaTarget::Principal {
+                    principal: "svc".to_owned(),
+                })
+                .with_produce_byte_rate(100),
+            ),
+        ]);
+
+        let (name, _, key) = quotas
+            .find_quota(&QuotaClient::new(Some("svc"), "app"))
+            .expect("quota");
+        assert_eq!((name.as_str(), key.as_str()), ("svc", "svc"));
+
+        let (name, _, key) = quotas
+            .find_quota(&QuotaClient::new(Some("other"), "app"))
+            .expect("quota");
+        assert_eq!((name.as_str(), key.as_str()), ("client", "app"));
+
+        let (name, _, key) = quotas
+            .find_quota(&QuotaClient::new(Some("other"), "cli"))
+            .expect("quota");
+        assert_eq!((name.as_str(), key.as_str()), ("default", "other"));
+    }
+}
@@ -0,0 +1,20 @@
+use fluvio_controlplane_metadata::transform::Quota;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+impl Spec for Quota {
+    const LABEL: &'static str = "Quota";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
+
+pub type QuotaLocalStore = LocalStore<Quota>;
@@ -0,0 +1,9 @@
+mod metadata;
+mod manager;
+
+pub use self::metadata::QuotaLocalStore;
+pub(crate) use self::manager::{QuotaManager, QuotaClient, QuotaTraffic, throttle_time_ms};
+
+use std::sync::Arc;
+
+pub type SharedQuotaLocalStore = Arc<QuotaLocalStore>;
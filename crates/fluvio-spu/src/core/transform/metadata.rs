@@ -0,0 +1,20 @@
+use fluvio_controlplane_metadata::transform::Transform;
+
+use crate::core::Spec;
+use crate::core::LocalStore;
+
+impl Spec for Transform {
+    const LABEL: &'static str = "Transform";
+
+    type Key = String;
+
+    fn key(&self) -> &Self::Key {
+        &self.name
+    }
+
+    fn key_owned(&self) -> Self::Key {
+        self.name.clone()
+    }
+}
+
+pub type TransformLocalStore = LocalStore<Transform>;
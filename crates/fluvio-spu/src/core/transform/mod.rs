@@ -0,0 +1,7 @@
+mod metadata;
+
+pub use self::metadata::TransformLocalStore;
+
+use std::sync::Arc;
+
+pub type SharedTransformLocalStore = Arc<TransformLocalStore>;
@@ -11,6 +11,7 @@ cfg_if::cfg_if! {
         mod storage;
         mod smartengine;
         mod monitoring;
+        mod transform;
         pub use start::main_loop;
     }
 }
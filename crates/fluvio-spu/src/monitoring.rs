@@ -1,15 +1,38 @@
-use std::io::Error as IoError;
+use std::io::{Error as IoError, ErrorKind};
+use std::time::Duration;
 
+use async_net::TcpListener;
+use async_net::TcpStream;
 use async_net::unix::UnixListener;
 
-use futures_util::{StreamExt, AsyncWriteExt};
+use futures_util::{StreamExt, AsyncReadExt, AsyncWriteExt};
 use fluvio_types::defaults::SPU_MONITORING_UNIX_SOCKET;
 use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_storage::ReplicaStorage;
+use tokio::select;
 use tracing::{error, info, debug};
 
-use crate::core::{DefaultSharedGlobalContext, metrics::SpuMetrics};
+use crate::core::{
+    DefaultSharedGlobalContext,
+    metrics::{SpuMetrics, ReplicaStats},
+};
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+const MAX_REQUEST_HEAD: usize = 8192;
+/// connection which doesn't send request in time is closed
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(10);
 
 pub(crate) fn init_monitoring(ctx: DefaultSharedGlobalContext) {
+    if let Some(addr) = ctx.config().metrics_endpoint.clone() {
+        let prometheus_ctx = ctx.clone();
+        spawn(async move {
+            if let Err(err) = start_prometheus(addr, prometheus_ctx).await {
+                error!("error running prometheus endpoint: {}", err);
+            }
+        });
+    }
+
     spawn(async move {
         if let Err(err) = start_monitoring(ctx).await {
             error!("error running monitoring: {}", err);
@@ -62,6 +85,101 @@ async fn start_monitoring(ctx: DefaultSharedGlobalContext) -> Result<(), IoError
         }
 
         info!("monitoring socket closed. Trying to reconnect in 5 seconds");
-        fluvio_future::timer::sleep(std::time::Duration::from_secs(5)).await;
+        sleep(Duration::from_secs(5)).await;
+    }
+}
+
+/// serve metrics in prometheus text format over http
+async fn start_prometheus(addr: String, ctx: DefaultSharedGlobalContext) -> Result<(), IoError> {
+    let listener = TcpListener::bind(&addr).await?;
+    info!(%addr, "prometheus endpoint started");
+
+    let mut incoming = listener.incoming();
+    while let Some(stream) = incoming.next().await {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                error!("error accepting connection: {}", err);
+                continue;
+            }
+        };
+        let ctx = ctx.clone();
+        spawn(async move {
+            if let Err(err) = respond_prometheus(stream, ctx).await {
+                debug!("error serving metrics: {}", err);
+            }
+        });
+    }
+    Ok(())
+}
+
+async fn respond_prometheus(
+    mut stream: TcpStream,
+    ctx: DefaultSharedGlobalContext,
+) -> Result<(), IoError> {
+    let head = select! {
+        head = read_request_head(&mut stream) => head?,
+        _ = sleep(REQUEST_READ_TIMEOUT) => {
+            return Err(IoError::new(ErrorKind::TimedOut, "timed out reading request"));
+        }
+    };
+
+    let request_line = String::from_utf8_lossy(&head);
+    let mut parts = request_line.split_whitespace();
+    let (status, body) = match (parts.next(), parts.next()) {
+        (Some("GET"), Some("/metrics")) => {
+            let replicas = replica_stats(&ctx).await;
+            ("200 OK", ctx.metrics().render_prometheus(&replicas))
+        }
+        _ => ("404 Not Found", String::new()),
+    };
+
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {PROMETHEUS_CONTENT_TYPE}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.flush().await
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> Result<Vec<u8>, IoError> {
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_HEAD {
+        let read = stream.read(&mut buf).await?;
+        if read == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..read]);
+    }
+    Ok(head)
+}
+
+/// collect offsets and storage usage of leader replicas
+async fn replica_stats(ctx: &DefaultSharedGlobalContext) -> Vec<ReplicaStats> {
+    let leaders: Vec<_> = ctx.leaders_state().read().await.values().cloned().collect();
+
+    let mut stats = Vec::with_capacity(leaders.len());
+    for leader in leaders {
+        let (segments, size) = {
+            let storage = leader.read().await;
+            (storage.segment_count().await, storage.get_partition_size())
+        };
+        let followers = leader
+            .followers_info()
+            .await
+            .into_iter()
+            .map(|(follower, info)| (follower, info.leo))
+            .collect();
+        stats.push(ReplicaStats {
+            replica: leader.id().clone(),
+            leo: leader.leo(),
+            hw: leader.hw(),
+            segments,
+            size,
+            followers,
+        });
     }
+    stats.sort_by(|a, b| a.replica.cmp(&b.replica));
+    stats
 }
@@ -139,7 +139,16 @@ impl FollowersState<FileReplica> {
         }
     }
 
-    pub async fn update_replica(&self, _replica: Replica) {}
+    /// apply storage configuration of updated replica
+    pub async fn update_replica(&self, ctx: &FileGlobalContext, replica: Replica) {
+        if let Some(state) = self.read().await.get(&replica.id) {
+            let mut replica_config: ReplicaConfig = ctx.config().into();
+            replica_config.update_from_replica(&replica);
+            state.update_config(&replica_config).await;
+        } else {
+            warn!(%replica, "follower replica not found for update");
+        }
+    }
 }
 
 /// State for Follower Replica Controller
@@ -0,0 +1,345 @@
+use std::collections::{BTreeMap, HashSet};
+use std::time::{Duration, Instant};
+
+use tracing::{info, warn, instrument};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::record::Offset;
+use fluvio_storage::OffsetInfo;
+use fluvio_types::SpuId;
+
+use crate::config::ReplicationConfig;
+use crate::core::DefaultSharedGlobalContext;
+
+/// how often lag of followers is checked when there are no offset updates
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Clone)]
+struct FollowerLag {
+    /// last time end offset of follower was same as leader's
+    caught_up_at: Instant,
+    in_sync: bool,
+    /// end offset last reported by follower
+    leo: Offset,
+    /// when follower last reported new end offset and leader's end offset at that time
+    last_fetch: Option<(Instant, Offset)>,
+}
+
+impl FollowerLag {
+    fn new(now: Instant) -> Self {
+        Self {
+            caught_up_at: now,
+            in_sync: true,
+            leo: -1,
+            last_fetch: None,
+        }
+    }
+
+    /// Follower which reaches leader's end offset as of its previous fetch was caught up
+    /// at time of that fetch, so follower keeping up with continuous produce stays in sync
+    /// even though it never reaches current end offset of leader.
+    fn update(&mut self, leo: Offset, leader_leo: Offset, now: Instant) {
+        if leo >= leader_leo {
+            self.caught_up_at = now;
+        }
+        if leo == self.leo {
+            return;
+        }
+        if let Some((fetched_at, fetch_leader_leo)) = self.last_fetch {
+            if leo >= fetch_leader_leo && fetched_at > self.caught_up_at {
+                self.caught_up_at = fetched_at;
+            }
+        }
+        self.leo = leo;
+        self.last_fetch = Some((now, leader_leo));
+    }
+}
+
+/// Followers in sync with leader, only they are waited for when high watermark is computed.
+/// Follower is removed when it hasn't caught up with leader's end offset for max lag time,
+/// where end offset of leader is taken at time of follower's previous fetch,
+/// or is behind by more than max lag offsets, and it is added back once it reaches leader's
+/// high watermark again. Followers start in sync, so records are not committed
+/// with fewer replicas than assigned until follower is known to lag.
+#[derive(Debug)]
+pub(crate) struct InSyncReplicas {
+    min_in_sync_replicas: u16,
+    max_lag: Duration,
+    max_lag_offsets: Option<u64>,
+    followers: BTreeMap<SpuId, FollowerLag>,
+}
+
+impl InSyncReplicas {
+    pub(crate) fn new(
+        follower_ids: impl IntoIterator<Item = SpuId>,
+        min_in_sync_replicas: Option<u16>,
+        config: &ReplicationConfig,
+    ) -> Self {
+        let now = Instant::now();
+        Self {
+            min_in_sync_replicas: min_in_sync_replicas.unwrap_or(config.min_in_sync_replicas),
+            max_lag: Duration::from_millis(config.replica_max_lag_ms),
+            max_lag_offsets: config.replica_max_lag_offsets,
+            followers: follower_ids
+                .into_iter()
+                .map(|id| (id, FollowerLag::new(now)))
+                .collect(),
+        }
+    }
+
+    /// sync with followers of updated replica, new followers start in sync
+    pub(crate) fn update_followers(
+        &mut self,
+        follower_ids: impl IntoIterator<Item = SpuId>,
+        min_in_sync_replicas: Option<u16>,
+        config: &ReplicationConfig,
+    ) {
+        let follower_ids: HashSet<SpuId> = follower_ids.into_iter().collect();
+        let now = Instant::now();
+        self.followers.retain(|id, _| follower_ids.contains(id));
+        for id in follower_ids {
+            self.followers
+                .entry(id)
+                .or_insert_with(|| FollowerLag::new(now));
+        }
+        self.min_in_sync_replicas = min_in_sync_replicas.unwrap_or(config.min_in_sync_replicas);
+    }
+
+    /// min in sync replicas required for produce with committed isolation
+    pub(crate) fn min_in_sync_replicas(&self) -> u16 {
+        self.min_in_sync_replicas
+    }
+
+    /// number of replicas in sync, including leader
+    pub(crate) fn count(&self) -> u16 {
+        1 + self.followers.values().filter(|lag| lag.in_sync).count() as u16
+    }
+
+    /// followers in sync
+    pub(crate) fn followers(&self) -> Vec<SpuId> {
+        self.followers
+            .iter()
+            .filter(|(_, lag)| lag.in_sync)
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// offsets of followers in sync
+    pub(crate) fn offsets(
+        &self,
+        offsets: &BTreeMap<SpuId, OffsetInfo>,
+    ) -> BTreeMap<SpuId, OffsetInfo> {
+        offsets
+            .iter()
+            .filter(|(id, _)| self.followers.get(id).map_or(false, |lag| lag.in_sync))
+            .map(|(id, info)| (*id, info.clone()))
+            .collect()
+    }
+
+    /// update lag of followers from their offsets,
+    /// return true if any follower was removed or added back
+    pub(crate) fn update(
+        &mut self,
+        leader: &OffsetInfo,
+        offsets: &BTreeMap<SpuId, OffsetInfo>,
+    ) -> bool {
+        self.update_at(leader, offsets, Instant::now())
+    }
+
+    fn update_at(
+        &mut self,
+        leader: &OffsetInfo,
+        offsets: &BTreeMap<SpuId, OffsetInfo>,
+        now: Instant,
+    ) -> bool {
+        let mut changed = false;
+        for (id, lag) in self.followers.iter_mut() {
+            let leo = offsets.get(id).map_or(-1, |info| info.leo);
+            lag.update(leo, leader.leo, now);
+            let lag_offsets = (leader.leo - leo).max(0) as u64;
+            let too_many_offsets = self
+                .max_lag_offsets
+                .map_or(false, |max_lag_offsets| lag_offsets > max_lag_offsets);
+            if lag.in_sync {
+                let lag_time = now.saturating_duration_since(lag.caught_up_at);
+                if lag_time > self.max_lag || too_many_offsets {
+                    warn!(
+                        follower = id,
+                        lag_ms = lag_time.as_millis() as u64,
+                        lag_offsets,
+                        "follower removed from in-sync replicas"
+                    );
+                    lag.in_sync = false;
+                    changed = true;
+                }
+            } else if leo >= 0 && leo >= leader.hw && !too_many_offsets {
+                info!(follower = id, leo, "follower back in sync");
+                lag.in_sync = true;
+                lag.caught_up_at = now;
+                changed = true;
+            }
+        }
+        changed
+    }
+}
+
+/// Checks lag of followers of all leaders periodically,
+/// so follower which stopped sending offsets is removed from in-sync replicas
+pub struct InSyncReplicasMonitor {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl InSyncReplicasMonitor {
+    pub fn start(ctx: DefaultSharedGlobalContext) {
+        let monitor = Self { ctx };
+        spawn(monitor.dispatch_loop());
+    }
+
+    async fn dispatch_loop(self) {
+        info!("starting in-sync replicas monitor");
+        loop {
+            self.check_leaders().await;
+            sleep(CHECK_INTERVAL).await;
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn check_leaders(&self) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for leader in leaders {
+            leader
+                .update_in_sync_replicas(self.ctx.follower_notifier())
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::collections::BTreeMap;
+    use std::time::{Duration, Instant};
+
+    use fluvio_storage::OffsetInfo;
+
+    use crate::config::ReplicationConfig;
+
+    use super::InSyncReplicas;
+
+    fn config(max_lag_offsets: Option<u64>) -> ReplicationConfig {
+        ReplicationConfig {
+            replica_max_lag_ms: 1000,
+            replica_max_lag_offsets: max_lag_offsets,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_follower_lag_time() {
+        let mut isr = InSyncReplicas::new([5001, 5002], Some(2), &config(None));
+        assert_eq!(isr.count(), 3);
+        assert_eq!(isr.min_in_sync_replicas(), 2);
+
+        let start = Instant::now();
+        let leader = OffsetInfo { hw: 5, leo: 10 };
+        let offsets: BTreeMap<_, _> = [
+            (5001, OffsetInfo { hw: 5, leo: 10 }),
+            (5002, OffsetInfo { hw: 5, leo: 5 }),
+        ]
+        .into_iter()
+        .collect();
+
+        // lagging follower is kept until max lag time passes
+        assert!(!isr.update_at(&leader, &offsets, start));
+        assert!(!isr.update_at(&leader, &offsets, start + Duration::from_millis(500)));
+        assert!(isr.update_at(&leader, &offsets, start + Duration::from_millis(1500)));
+        assert_eq!(isr.followers(), vec![5001]);
+        assert_eq!(isr.count(), 2);
+        assert_eq!(isr.offsets(&offsets).len(), 1);
+
+        // caught up follower stays in sync
+        assert!(!isr.update_at(&leader, &offsets, start + Duration::from_millis(3000)));
+        assert_eq!(isr.followers(), vec![5001]);
+
+        // follower is added back once it reaches high watermark
+        let leader = OffsetInfo { hw: 10, leo: 12 };
+        let offsets: BTreeMap<_, _> = [
+            (5001, OffsetInfo { hw: 10, leo: 12 }),
+            (5002, OffsetInfo { hw: 10, leo: 10 }),
+        ]
+        .into_iter()
+        .collect();
+        assert!(isr.update_at(&leader, &offsets, start + Duration::from_millis(3500)));
+        assert_eq!(isr.followers(), vec![5001, 5002]);
+    }
+
+    #[test]
+    fn test_follower_lag_continuous_produce() {
+        let mut isr = InSyncReplicas::new([5001], None, &config(None));
+
+        // follower always reaches end offset leader had at its previous fetch
+        let start = Instant::now();
+        for step in 0..10_i64 {
+            let leader = OffsetInfo {
+                hw: step * 10,
+                leo: (step + 1) * 10,
+            };
+            let offsets: BTreeMap<_, _> = [(
+                5001,
+                OffsetInfo {
+                    hw: 0,
+                    leo: step * 10,
+                },
+            )]
+            .into_iter()
+            .collect();
+            let now = start + Duration::from_millis(step as u64 * 400);
+            assert!(!isr.update_at(&leader, &offsets, now));
+            assert_eq!(isr.followers(), vec![5001]);
+        }
+
+        // follower which stops fetching falls out of sync while produce continues
+        let offsets: BTreeMap<_, _> = [(5001, OffsetInfo { hw: 0, leo: 90 })]
+            .into_iter()
+            .collect();
+        let leader = OffsetInfo { hw: 90, leo: 110 };
+        assert!(isr.update_at(&leader, &offsets, start + Duration::from_millis(5000)));
+        assert!(isr.followers().is_empty());
+    }
+
+    #[test]
+    fn test_follower_lag_offsets() {
+        let mut isr = InSyncReplicas::new([5001], None, &config(Some(100)));
+        assert_eq!(isr.min_in_sync_replicas(), 1);
+
+        let now = Instant::now();
+        let offsets: BTreeMap<_, _> = [(5001, OffsetInfo { hw: 0, leo: 50 })]
+            .into_iter()
+            .collect();
+        assert!(!isr.update_at(&OffsetInfo { hw: 0, leo: 150 }, &offsets, now));
+        assert!(isr.update_at(&OffsetInfo { hw: 0, leo: 151 }, &offsets, now));
+        assert_eq!(isr.count(), 1);
+
+        // follower at high watermark is not added back while too far behind end offset
+        assert!(!isr.update_at(&OffsetInfo { hw: 50, leo: 300 }, &offsets, now));
+        assert!(isr.update_at(&OffsetInfo { hw: 50, leo: 120 }, &offsets, now));
+        assert_eq!(isr.followers(), vec![5001]);
+    }
+
+    #[test]
+    fn test_update_followers() {
+        let config = config(None);
+        let mut isr = InSyncReplicas::new([5001, 5002], None, &config);
+        isr.update_followers([5002, 5003], Some(3), &config);
+        assert_eq!(isr.followers(), vec![5002, 5003]);
+        assert_eq!(isr.min_in_sync_replicas(), 3);
+    }
+}
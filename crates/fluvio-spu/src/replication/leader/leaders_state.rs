@@ -119,7 +119,7 @@ impl ReplicaLeadersState<FileReplica> {
     ) -> LeaderReplicaState<FileReplica> {
         let replica_id = replica.id.clone();
         let replica_storage = follower.inner_owned();
-        let leader = LeaderReplicaState::new(replica, config, status_update, replica_storage);
+        let leader = LeaderReplicaState::new(replica, config, status_update, replica_storage).await;
         self.insert_leader(replica_id, leader.clone()).await;
         leader
     }
@@ -6,6 +6,9 @@ mod peer_api;
 mod update_offsets;
 mod actions;
 mod spu;
+mod producer_state;
+mod in_sync;
+mod transaction;
 
 pub use self::leaders_state::{ReplicaLeadersState, SharedReplicaLeadersState};
 pub use self::replica_state::{SharedFileLeaderState, SharedLeaderState, LeaderReplicaState};
@@ -16,3 +19,5 @@ pub use self::update_offsets::UpdateOffsetRequest;
 pub use self::update_offsets::ReplicaOffsetRequest;
 pub use self::actions::FollowerOffsetUpdate;
 pub use self::spu::*;
+pub use self::in_sync::InSyncReplicasMonitor;
+pub use self::transaction::{TransactionMonitor, ConnectionId};
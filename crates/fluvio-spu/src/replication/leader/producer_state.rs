@@ -0,0 +1,239 @@
+use std::collections::{HashMap, VecDeque};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{BatchRecords, Offset, RecordSet};
+use fluvio_storage::ProducerBatch;
+
+/// number of recent batches remembered for each producer
+const MAX_TRACKED_BATCHES: usize = 5;
+
+/// Producer identity and sequence carried in batch header.
+/// Only first batch of record set is used
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct ProducerSequence {
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub first_sequence: i32,
+}
+
+impl ProducerSequence {
+    /// return sequence if record set is sent by idempotent producer
+    pub(crate) fn from_records<R: BatchRecords>(records: &RecordSet<R>) -> Option<Self> {
+        let header = records.batches.first()?.get_header();
+        if header.producer_id < 0 || header.first_sequence < 0 {
+            return None;
+        }
+        Some(Self {
+            producer_id: header.producer_id,
+            producer_epoch: header.producer_epoch,
+            first_sequence: header.first_sequence,
+        })
+    }
+}
+
+/// Result of checking sequence against producer state
+#[derive(Debug, Eq, PartialEq)]
+pub(crate) enum SequenceCheck {
+    /// batch has not been seen before and must be written
+    New,
+    /// batch was already written with base offset and leo
+    Duplicate(Offset, Offset),
+    Rejected(ErrorCode),
+}
+
+#[derive(Debug)]
+struct WrittenBatch {
+    first_sequence: i32,
+    base_offset: Offset,
+    leo: Offset,
+}
+
+#[derive(Debug)]
+struct ProducerEntry {
+    epoch: i16,
+    batches: VecDeque<WrittenBatch>,
+}
+
+/// Tracks recent batches written by idempotent producers, so retried batches are
+/// acknowledged with their original offsets instead of being written again.
+/// State is seeded from storage when leader is created.
+#[derive(Debug, Default)]
+pub(crate) struct ProducerStates {
+    producers: HashMap<i64, ProducerEntry>,
+}
+
+impl ProducerStates {
+    /// restore state from batches recovered by storage, in offset order
+    pub(crate) fn from_batches(batches: Vec<ProducerBatch>) -> Self {
+        let mut states = Self::default();
+        for batch in batches {
+            let sequence = ProducerSequence {
+                producer_id: batch.producer_id,
+                producer_epoch: batch.producer_epoch,
+                first_sequence: batch.first_sequence,
+            };
+            states.record(&sequence, batch.base_offset, batch.leo);
+        }
+        states
+    }
+
+    pub(crate) fn check(&self, sequence: &ProducerSequence) -> SequenceCheck {
+        let entry = match self.producers.get(&sequence.producer_id) {
+            Some(entry) => entry,
+            None => return SequenceCheck::New,
+        };
+
+        if sequence.producer_epoch < entry.epoch {
+            return SequenceCheck::Rejected(ErrorCode::InvalidProducerEpoch);
+        }
+        if sequence.producer_epoch > entry.epoch {
+            return SequenceCheck::New;
+        }
+
+        if let Some(batch) = entry
+            .batches
+            .iter()
+            .find(|batch| batch.first_sequence == sequence.first_sequence)
+        {
+            return SequenceCheck::Duplicate(batch.base_offset, batch.leo);
+        }
+
+        match entry.batches.back() {
+            Some(last) if sequence.first_sequence < last.first_sequence => {
+                SequenceCheck::Rejected(ErrorCode::OutOfOrderSequenceNumber)
+            }
+            _ => SequenceCheck::New,
+        }
+    }
+
+    /// remember batch which has been written
+    pub(crate) fn record(&mut self, sequence: &ProducerSequence, base_offset: Offset, leo: Offset) {
+        let entry = self
+            .producers
+            .entry(sequence.producer_id)
+            .or_insert_with(|| ProducerEntry {
+                epoch: sequence.producer_epoch,
+                batches: VecDeque::with_capacity(MAX_TRACKED_BATCHES),
+            });
+
+        if sequence.producer_epoch > entry.epoch {
+            entry.epoch = sequence.producer_epoch;
+            entry.batches.clear();
+        }
+
+        if entry.batches.len() == MAX_TRACKED_BATCHES {
+            entry.batches.pop_front();
+        }
+        entry.batches.push_back(WrittenBatch {
+            first_sequence: sequence.first_sequence,
+            base_offset,
+            leo,
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_storage::ProducerBatch;
+
+    use super::{ProducerStates, ProducerSequence, SequenceCheck, MAX_TRACKED_BATCHES};
+
+    fn sequence(producer_epoch: i16, first_sequence: i32) -> ProducerSequence {
+        ProducerSequence {
+            producer_id: 1,
+            producer_epoch,
+            first_sequence,
+        }
+    }
+
+    #[test]
+    fn test_duplicate_batch_detection() {
+        let mut states = ProducerStates::default();
+
+        assert_eq!(states.check(&sequence(0, 0)), SequenceCheck::New);
+        states.record(&sequence(0, 0), 0, 2);
+        assert_eq!(states.check(&sequence(0, 2)), SequenceCheck::New);
+        states.record(&sequence(0, 2), 2, 5);
+
+        // retried batches are acknowledged with original offsets
+        assert_eq!(
+            states.check(&sequence(0, 0)),
+            SequenceCheck::Duplicate(0, 2)
+        );
+        assert_eq!(
+            states.check(&sequence(0, 2)),
+            SequenceCheck::Duplicate(2, 5)
+        );
+
+        assert_eq!(
+            states.check(&sequence(0, 1)),
+            SequenceCheck::Rejected(ErrorCode::OutOfOrderSequenceNumber)
+        );
+    }
+
+    #[test]
+    fn test_restore_from_batches() {
+        let batches = vec![
+            ProducerBatch {
+                producer_id: 1,
+                producer_epoch: 0,
+                first_sequence: 0,
+                base_offset: 0,
+                leo: 2,
+            },
+            ProducerBatch {
+                producer_id: 1,
+                producer_epoch: 0,
+                first_sequence: 2,
+                base_offset: 2,
+                leo: 5,
+            },
+        ];
+        let states = ProducerStates::from_batches(batches);
+
+        assert_eq!(
+            states.check(&sequence(0, 2)),
+            SequenceCheck::Duplicate(2, 5)
+        );
+        assert_eq!(states.check(&sequence(0, 5)), SequenceCheck::New);
+    }
+
+    #[test]
+    fn test_producer_epoch() {
+        let mut states = ProducerStates::default();
+        states.record(&sequence(1, 0), 0, 1);
+
+        assert_eq!(
+            states.check(&sequence(0, 1)),
+            SequenceCheck::Rejected(ErrorCode::InvalidProducerEpoch)
+        );
+
+        // newer epoch resets sequence
+        assert_eq!(states.check(&sequence(2, 0)), SequenceCheck::New);
+        states.record(&sequence(2, 0), 1, 2);
+        assert_eq!(
+            states.check(&sequence(1, 5)),
+            SequenceCheck::Rejected(ErrorCode::InvalidProducerEpoch)
+        );
+    }
+
+    #[test]
+    fn test_tracked_batches_limit() {
+        let mut states = ProducerStates::default();
+        for i in 0..=MAX_TRACKED_BATCHES as i32 {
+            states.record(&sequence(0, i), i as i64, i as i64 + 1);
+        }
+
+        // oldest batch is no longer tracked
+        assert_eq!(
+            states.check(&sequence(0, 0)),
+            SequenceCheck::Rejected(ErrorCode::OutOfOrderSequenceNumber)
+        );
+        assert_eq!(
+            states.check(&sequence(0, 1)),
+            SequenceCheck::Duplicate(1, 2)
+        );
+    }
+}
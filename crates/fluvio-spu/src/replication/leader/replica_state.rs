@@ -3,6 +3,7 @@ use std::{
     collections::{BTreeMap, HashSet, BinaryHeap},
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
 };
 use std::iter::FromIterator;
 use std::fmt;
@@ -10,12 +11,17 @@ use std::fmt;
 use tracing::{debug, error, warn};
 use tracing::instrument;
 use async_rwlock::{RwLock};
+use async_lock::Mutex;
 use anyhow::Result;
 
 use fluvio_protocol::record::{RecordSet, Offset, ReplicaKey, BatchRecords};
+use fluvio_protocol::record::{Batch, ControlRecordType};
+use fluvio_protocol::link::ErrorCode;
 use fluvio_controlplane_metadata::partition::{Replica, ReplicaStatus, PartitionStatus};
 use fluvio_controlplane::LrsRequest;
-use fluvio_storage::{FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig};
+use fluvio_storage::{
+    FileReplica, ReplicaStorage, OffsetInfo, ReplicaStorageConfig, TransactionDecision,
+};
 use fluvio_types::{SpuId};
 use fluvio_spu_schema::Isolation;
 
@@ -27,6 +33,9 @@ use crate::replication::follower::sync::{PeerFileTopicResponse, PeerFilePartitio
 use crate::storage::SharableReplicaStorage;
 
 use super::{FollowerNotifier};
+use super::in_sync::InSyncReplicas;
+use super::producer_state::{ProducerStates, ProducerSequence, SequenceCheck};
+use super::transaction::{ConnectionId, TransactionOwners};
 
 pub type SharedLeaderState<S> = LeaderReplicaState<S>;
 pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
@@ -34,11 +43,13 @@ pub type SharedFileLeaderState = LeaderReplicaState<FileReplica>;
 #[derive(Debug)]
 pub struct LeaderReplicaState<S> {
     replica: Replica,
-    in_sync_replica: u16,
     storage: SharableReplicaStorage<S>,
     config: ReplicationConfig,
     followers: Arc<RwLock<BTreeMap<SpuId, OffsetInfo>>>,
+    in_sync_replicas: Arc<RwLock<InSyncReplicas>>,
     status_update: SharedStatusUpdate,
+    producers: Arc<Mutex<ProducerStates>>,
+    transaction_owners: Arc<Mutex<TransactionOwners>>,
 }
 
 impl<S> Clone for LeaderReplicaState<S> {
@@ -48,8 +59,10 @@ impl<S> Clone for LeaderReplicaState<S> {
             storage: self.storage.clone(),
             config: self.config.clone(),
             followers: self.followers.clone(),
-            in_sync_replica: self.in_sync_replica,
+            in_sync_replicas: self.in_sync_replicas.clone(),
             status_update: self.status_update.clone(),
+            producers: self.producers.clone(),
+            transaction_owners: self.transaction_owners.clone(),
         }
     }
 }
@@ -91,33 +104,44 @@ where
     S: ReplicaStorage,
 {
     /// create new state from existing storage
-    /// calculate default in_sync_replica from followers
-    pub fn new(
+    /// all followers are initially in sync
+    pub async fn new(
         replica: Replica,
         config: ReplicationConfig,
         status_update: SharedStatusUpdate,
         inner: SharableReplicaStorage<S>,
     ) -> Self {
         debug!(?replica, "replica storage");
-        let in_sync_replica = replica.replicas.len() as u16;
         let follower_ids = HashSet::from_iter(replica.replicas.clone());
         let followers = ids_to_map(replica.leader, follower_ids);
         debug!(?followers, "leader followers");
+        let in_sync_replicas = InSyncReplicas::new(
+            followers.keys().copied(),
+            replica.min_in_sync_replicas,
+            &config,
+        );
 
         debug!(
-            in_sync_replica,
+            in_sync_replicas = in_sync_replicas.count(),
             replica = %replica.id,
             follower = ?replica.replicas,
             "creating leader"
         );
 
+        inner.set_leader(true).await;
+
+        // retried batches written before load or promotion must still be detected
+        let producers = ProducerStates::from_batches(inner.read().await.producer_batches());
+
         Self {
             replica,
             storage: inner,
             config,
             followers: Arc::new(RwLock::new(followers)),
-            in_sync_replica,
+            in_sync_replicas: Arc::new(RwLock::new(in_sync_replicas)),
             status_update,
+            producers: Arc::new(Mutex::new(producers)),
+            transaction_owners: Arc::new(Mutex::new(TransactionOwners::default())),
         }
     }
 
@@ -134,11 +158,41 @@ where
         let mut replica_config: S::ReplicaConfig = config.into();
         replica_config.update_from_replica(&replica);
         let inner = SharableReplicaStorage::create(replica.id.clone(), replica_config).await?;
-        let leader_replica = Self::new(replica, config.into(), status_update, inner);
+        let leader_replica = Self::new(replica, config.into(), status_update, inner).await;
         leader_replica.update_status().await;
         Ok(leader_replica)
     }
 
+    /// apply storage configuration of updated replica to running storage
+    pub async fn update_storage_config<'a, C>(&self, config: &'a C, replica: &Replica)
+    where
+        S::ReplicaConfig: From<&'a C>,
+    {
+        let mut replica_config: S::ReplicaConfig = config.into();
+        replica_config.update_from_replica(replica);
+        self.storage.update_config(&replica_config).await;
+    }
+
+    /// sync followers with replicas of updated replica.
+    /// new followers start with unknown offsets until they report to leader
+    pub async fn update_followers(&self, replica: &Replica) {
+        let follower_ids: HashSet<SpuId> = HashSet::from_iter(replica.replicas.clone());
+        {
+            let mut followers = self.followers.write().await;
+            followers.retain(|id, _| follower_ids.contains(id));
+            for (id, info) in ids_to_map(replica.leader, follower_ids) {
+                followers.entry(id).or_insert(info);
+            }
+            self.in_sync_replicas.write().await.update_followers(
+                followers.keys().copied(),
+                replica.min_in_sync_replicas,
+                &self.config,
+            );
+            debug!(replica = %self.id(), followers = ?followers.keys(), "followers updated");
+        }
+        self.update_status().await;
+    }
+
     /// replica id
     pub fn id(&self) -> &ReplicaKey {
         &self.replica.id
@@ -149,10 +203,161 @@ where
         self.replica.leader
     }
 
-    /// override in sync replica
-    #[allow(unused)]
-    fn set_in_sync_replica(&mut self, replica_count: u16) {
-        self.in_sync_replica = replica_count;
+    /// leader and followers in sync with it
+    pub async fn in_sync_replicas(&self) -> Vec<SpuId> {
+        let mut replicas = vec![self.leader()];
+        replicas.extend(self.in_sync_replicas.read().await.followers());
+        replicas
+    }
+
+    /// produce with committed isolation requires min in sync replicas of topic or SPU default
+    pub async fn check_min_in_sync_replicas(&self) -> Result<(), ErrorCode> {
+        let in_sync_replicas = self.in_sync_replicas.read().await;
+        let in_sync = in_sync_replicas.count();
+        let min = in_sync_replicas.min_in_sync_replicas();
+        if in_sync < min {
+            Err(ErrorCode::NotEnoughInSyncReplicas { in_sync, min })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// update hw from offsets of followers in sync
+    async fn update_hw_from_followers(
+        &self,
+        leader_pos: &OffsetInfo,
+        followers: &BTreeMap<SpuId, OffsetInfo>,
+        in_sync_replicas: &InSyncReplicas,
+    ) {
+        let hw = if in_sync_replicas.count() == 1 {
+            // no follower in sync, leader alone commits
+            Some(leader_pos.leo).filter(|leo| *leo > leader_pos.hw)
+        } else {
+            compute_hw(
+                leader_pos,
+                in_sync_replicas.count(),
+                &in_sync_replicas.offsets(followers),
+            )
+        };
+        if let Some(hw) = hw {
+            debug!(hw, "updating hw");
+            if let Err(err) = self.update_hw(hw).await {
+                error!("error updating hw: {}", err);
+            }
+        } else {
+            debug!("no hw change");
+        }
+    }
+
+    /// remove followers lagging behind from in-sync replicas and add back ones caught up,
+    /// hw may advance once lagging follower is no longer waited for
+    pub async fn update_in_sync_replicas(&self, notifier: &FollowerNotifier) {
+        let leader_pos = self.as_offset();
+        let followers = self.followers.read().await;
+        let mut in_sync_replicas = self.in_sync_replicas.write().await;
+        if !in_sync_replicas.update(&leader_pos, &followers) {
+            return;
+        }
+        if !leader_pos.is_committed() {
+            self.update_hw_from_followers(&leader_pos, &followers, &in_sync_replicas)
+                .await;
+        }
+        drop(in_sync_replicas);
+        drop(followers);
+
+        self.notify_followers(notifier).await;
+        self.update_status().await;
+    }
+
+    /// abort transactions coordinated by this replica which are open for longer than
+    /// transaction timeout, so producer which crashed mid transaction doesn't hold back
+    /// committed reads. Abort becomes decision which is written to other partitions,
+    /// they never abort transaction on their own as it may have been committed already.
+    pub async fn abort_expired_transactions(&self, notifier: &FollowerNotifier) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_millis() as i64)
+            .unwrap_or_default();
+        let expired_before = now - self.config.transaction_timeout_ms as i64;
+        let expired: Vec<_> = self
+            .read()
+            .await
+            .open_transactions()
+            .into_iter()
+            .filter(|transaction| {
+                !transaction.partitions.is_empty() && transaction.started_at < expired_before
+            })
+            .collect();
+
+        for transaction in expired {
+            warn!(
+                replica = %self.id(),
+                producer_id = transaction.producer_id,
+                first_offset = transaction.first_offset,
+                "aborting expired transaction"
+            );
+            let mut records = RecordSet::default().add(Batch::control(
+                transaction.producer_id,
+                transaction.producer_epoch,
+                ControlRecordType::Abort,
+            ));
+            if let Err(err) = self.write_record_set(&mut records, notifier).await {
+                error!(replica = %self.id(), %err, "failed to abort transaction");
+            }
+        }
+    }
+
+    /// decisions of transactions coordinated by this replica not yet written to their partitions
+    pub async fn transaction_decisions(&self) -> Vec<TransactionDecision> {
+        self.read().await.transaction_decisions()
+    }
+
+    /// write marker of decision made by coordinator of transaction, nothing is written
+    /// if producer has already ended transaction in this replica or began newer one
+    pub async fn write_transaction_marker(
+        &self,
+        producer_id: i64,
+        producer_epoch: i16,
+        record_type: ControlRecordType,
+        notifier: &FollowerNotifier,
+    ) -> Result<(), ErrorCode> {
+        let open = self
+            .read()
+            .await
+            .open_transactions()
+            .iter()
+            .any(|transaction| {
+                transaction.producer_id == producer_id
+                    && transaction.producer_epoch == producer_epoch
+            });
+        if !open {
+            debug!(replica = %self.id(), producer_id, "transaction already ended");
+            return Ok(());
+        }
+        let mut records =
+            RecordSet::default().add(Batch::control(producer_id, producer_epoch, record_type));
+        match self.write_record_set(&mut records, notifier).await {
+            Ok(_) => Ok(()),
+            Err(err) => match err.downcast::<ErrorCode>() {
+                Ok(error_code) => Err(error_code),
+                Err(err) => Err(ErrorCode::Other(err.to_string())),
+            },
+        }
+    }
+
+    /// mark decision as written to all partitions of transaction
+    pub async fn complete_transaction(
+        &self,
+        decision: &TransactionDecision,
+        notifier: &FollowerNotifier,
+    ) -> Result<()> {
+        let mut records = RecordSet::default().add(Batch::control(
+            decision.producer_id,
+            decision.producer_epoch,
+            ControlRecordType::Complete,
+        ));
+        self.write_record_set(&mut records, notifier).await?;
+        Ok(())
     }
 
     /// update leader's state from follower's offset states
@@ -179,16 +384,12 @@ where
         let mut followers = self.followers.write().await;
         let update = if let Some(current_follow_info) = followers.get_mut(&follower_id) {
             if current_follow_info.update(&follower_pos) {
+                let mut in_sync_replicas = self.in_sync_replicas.write().await;
+                in_sync_replicas.update(&leader_pos, &followers);
                 // if our leo and hw is same there is no need to recompute hw
                 if !leader_pos.is_committed() {
-                    if let Some(hw) = compute_hw(&leader_pos, self.in_sync_replica, &followers) {
-                        debug!(hw, "updating hw");
-                        if let Err(err) = self.update_hw(hw).await {
-                            error!("error updating hw: {}", err);
-                        }
-                    } else {
-                        debug!("no hw change");
-                    }
+                    self.update_hw_from_followers(&leader_pos, &followers, &in_sync_replicas)
+                        .await;
                 } else {
                     debug!("leader is committed");
                 }
@@ -292,6 +493,7 @@ where
                 (*follower_id, follower_info.hw, follower_info.leo).into()
             })
             .collect();
+        let in_sync_replicas = self.in_sync_replicas().await;
         let storage_reader = self.storage.read().await;
         let size = storage_reader
             .get_partition_size()
@@ -299,6 +501,7 @@ where
             .unwrap_or(PartitionStatus::SIZE_ERROR);
 
         LrsRequest::new(self.id().to_owned(), leader, replicas, size)
+            .with_in_sync_replicas(in_sync_replicas)
     }
 
     #[instrument(skip(self))]
@@ -316,10 +519,57 @@ where
         records: &mut RecordSet<R>,
         notifiers: &FollowerNotifier,
     ) -> Result<(Offset, Offset, usize)> {
-        let offsets = self
-            .storage
-            .write_record_set(records, self.in_sync_replica == 1)
-            .await?;
+        self.write_records(records, notifiers, None).await
+    }
+
+    /// write records produced by client connection, transaction markers are accepted only
+    /// from connection which began transaction
+    pub async fn write_record_set_from<R: BatchRecords>(
+        &self,
+        records: &mut RecordSet<R>,
+        notifiers: &FollowerNotifier,
+        connection: ConnectionId,
+    ) -> Result<(Offset, Offset, usize)> {
+        self.write_records(records, notifiers, Some(connection))
+            .await
+    }
+
+    async fn write_records<R: BatchRecords>(
+        &self,
+        records: &mut RecordSet<R>,
+        notifiers: &FollowerNotifier,
+        connection: Option<ConnectionId>,
+    ) -> Result<(Offset, Offset, usize)> {
+        // hold owners lock while writing so transaction can't be ended concurrently
+        let mut owners = self.transaction_owners.lock().await;
+        if let Some(connection) = connection {
+            owners.check(records, connection)?;
+        }
+        // records are committed right away if no follower is in sync
+        let hw_update = self.in_sync_replicas.read().await.count() == 1;
+        let offsets = match ProducerSequence::from_records(records) {
+            Some(sequence) => {
+                // hold producer lock while writing so retried batch can't be written twice
+                let mut producers = self.producers.lock().await;
+                match producers.check(&sequence) {
+                    SequenceCheck::Duplicate(base_offset, leo) => {
+                        debug!(?sequence, base_offset, "duplicate batch");
+                        return Ok((base_offset, leo, 0));
+                    }
+                    SequenceCheck::Rejected(error_code) => {
+                        warn!(?sequence, %error_code, "batch rejected");
+                        return Err(error_code.into());
+                    }
+                    SequenceCheck::New => {}
+                }
+                let offsets = self.storage.write_record_set(records, hw_update).await?;
+                producers.record(&sequence, offsets.0, offsets.1);
+                offsets
+            }
+            None => self.storage.write_record_set(records, hw_update).await?,
+        };
+        owners.update(records, connection);
+        drop(owners);
 
         self.notify_followers(notifiers).await;
         self.update_status().await;
@@ -349,8 +599,7 @@ where
         self.followers.read().await.keys().cloned().collect()
     }
 
-    // get copy of followers_info for debugging
-    #[allow(unused)]
+    // get copy of followers_info
     pub async fn followers_info(&self) -> BTreeMap<SpuId, OffsetInfo> {
         self.followers.read().await.clone()
     }
@@ -764,6 +1013,29 @@ mod test_leader {
             Ok(true)
         }
 
+        async fn find_offset_by_timestamp(
+            &self,
+            _timestamp: fluvio_types::Timestamp,
+        ) -> Result<Option<Offset>, ErrorCode> {
+            Ok(None)
+        }
+
+        fn update_config(&self, _replica_config: &Self::ReplicaConfig) {}
+
+        fn set_leader(&self, _leader: bool) {}
+
+        fn open_transactions(&self) -> Vec<fluvio_storage::OpenTransaction> {
+            vec![]
+        }
+
+        fn producer_batches(&self) -> Vec<fluvio_storage::ProducerBatch> {
+            vec![]
+        }
+
+        fn transaction_decisions(&self) -> Vec<fluvio_storage::TransactionDecision> {
+            vec![]
+        }
+
         type ReplicaConfig = MockConfig;
 
         fn get_log_start_offset(&self) -> Offset {
@@ -792,7 +1064,50 @@ mod test_leader {
         .await
         .expect("state");
 
-        assert_eq!(state.in_sync_replica, 1);
+        assert_eq!(state.in_sync_replicas.read().await.count(), 1);
+        assert_eq!(state.in_sync_replicas().await, vec![5000]);
+    }
+
+    #[fluvio_future::test]
+    async fn test_leader_min_in_sync_replicas() {
+        let mut leader_config = SpuConfig {
+            id: 5000,
+            ..Default::default()
+        };
+        leader_config.replication.replica_max_lag_offsets = Some(5);
+        let notifier = FollowerNotifier::shared();
+
+        let mut replica = Replica::new(("test", 1), 5000, vec![5000, 5001]);
+        replica.min_in_sync_replicas = Some(2);
+        let state: LeaderReplicaState<MockStorage> =
+            LeaderReplicaState::create(replica, &leader_config, StatusMessageSink::shared())
+                .await
+                .expect("state");
+        assert!(state.check_min_in_sync_replicas().await.is_ok());
+
+        state
+            .write_record_set(&mut create_recordset(10), &notifier)
+            .await
+            .expect("write");
+        assert_eq!(state.hw(), 0);
+
+        // follower is too far behind, leader commits alone
+        state.update_in_sync_replicas(&notifier).await;
+        assert_eq!(state.in_sync_replicas().await, vec![5000]);
+        assert_eq!(state.hw(), 10);
+        assert_eq!(
+            state.check_min_in_sync_replicas().await,
+            Err(ErrorCode::NotEnoughInSyncReplicas { in_sync: 1, min: 2 })
+        );
+
+        // follower caught up
+        assert!(
+            state
+                .update_states_from_followers(5001, OffsetInfo { leo: 10, hw: 10 }, &notifier)
+                .await
+        );
+        assert_eq!(state.in_sync_replicas().await, vec![5000, 5001]);
+        assert!(state.check_min_in_sync_replicas().await.is_ok());
     }
 
     #[fluvio_future::test]
@@ -0,0 +1,273 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+
+use tracing::{debug, error, info, instrument, warn};
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::RequestMessage;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{BatchRecords, ControlRecordType, RecordSet, ReplicaKey};
+use fluvio_socket::FluvioSocket;
+use fluvio_storage::TransactionDecision;
+use fluvio_types::SpuId;
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::internal::WriteTxnMarkersRequest;
+
+/// how often transactions are checked for timeout and pending decisions
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Runs coordinator side of transactions for all leaders.
+/// Aborts transactions which are not ended within transaction timeout, producer may have
+/// crashed before committing or aborting them. Decision recorded in coordinator partition
+/// is rolled forward to every partition of transaction until all of them have marker.
+pub struct TransactionMonitor {
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl TransactionMonitor {
+    pub fn start(ctx: DefaultSharedGlobalContext) {
+        let monitor = Self { ctx };
+        spawn(monitor.dispatch_loop());
+    }
+
+    async fn dispatch_loop(self) {
+        info!("starting transaction monitor");
+        loop {
+            sleep(CHECK_INTERVAL).await;
+            self.check_leaders().await;
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn check_leaders(&self) {
+        let leaders: Vec<_> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .values()
+            .cloned()
+            .collect();
+        for leader in leaders {
+            leader
+                .abort_expired_transactions(self.ctx.follower_notifier())
+                .await;
+            for decision in leader.transaction_decisions().await {
+                if self.roll_forward(leader.id(), &decision).await {
+                    if let Err(err) = leader
+                        .complete_transaction(&decision, self.ctx.follower_notifier())
+                        .await
+                    {
+                        error!(%err, "failed to complete transaction");
+                    }
+                }
+            }
+        }
+    }
+
+    /// write decision to all partitions of transaction, true if all of them have it
+    #[instrument(skip(self, decision), fields(producer_id = decision.producer_id))]
+    async fn roll_forward(&self, coordinator: &ReplicaKey, decision: &TransactionDecision) -> bool {
+        let local_spu = self.ctx.local_spu_id();
+        let mut remote: BTreeMap<SpuId, Vec<ReplicaKey>> = BTreeMap::new();
+        let mut completed = true;
+        for replica in decision.partitions.iter().filter(|p| *p != coordinator) {
+            let leader = match self.ctx.replica_localstore().spec(replica) {
+                Some(spec) => spec.leader,
+                None => {
+                    // partition has been deleted, nothing to write
+                    debug!(%replica, "partition of transaction no longer exists");
+                    continue;
+                }
+            };
+            if leader != local_spu {
+                remote.entry(leader).or_default().push(replica.clone());
+                continue;
+            }
+            let written = match self.ctx.leaders_state().get(replica).await {
+                Some(leader) => leader
+                    .write_transaction_marker(
+                        decision.producer_id,
+                        decision.producer_epoch,
+                        decision.record_type,
+                        self.ctx.follower_notifier(),
+                    )
+                    .await
+                    .is_ok(),
+                None => false,
+            };
+            if !written {
+                debug!(%replica, "transaction marker not written, retrying later");
+                completed = false;
+            }
+        }
+
+        for (spu, partitions) in remote {
+            if let Err(err) = self.write_remote_markers(spu, decision, partitions).await {
+                warn!(spu, %err, "failed to write transaction markers, retrying later");
+                completed = false;
+            }
+        }
+        completed
+    }
+
+    /// send markers to partitions led by other SPU
+    async fn write_remote_markers(
+        &self,
+        spu: SpuId,
+        decision: &TransactionDecision,
+        partitions: Vec<ReplicaKey>,
+    ) -> anyhow::Result<()> {
+        let spec = self
+            .ctx
+            .spu_localstore()
+            .spec(&spu)
+            .ok_or_else(|| anyhow::anyhow!("unknown spu: {spu}"))?;
+        let mut socket = FluvioSocket::connect(&spec.private_endpoint.to_string()).await?;
+        let request = WriteTxnMarkersRequest {
+            producer_id: decision.producer_id,
+            producer_epoch: decision.producer_epoch,
+            record_type: decision.record_type,
+            partitions,
+        };
+        let response = socket.send(&RequestMessage::new_request(request)).await?;
+        for result in response.response.partitions {
+            if result.error_code.is_error() {
+                return Err(anyhow::anyhow!(
+                    "partition {}: {}",
+                    result.replica,
+                    result.error_code
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// id of public connection, unique within SPU process
+pub type ConnectionId = u64;
+
+/// Connections which began open transactions of replica. Only connection which began
+/// transaction can end it, markers written by SPU itself are always accepted.
+/// Owners are not persisted, so transaction which was open before SPU restarted
+/// can't be ended by client and is aborted by its coordinator once it times out.
+#[derive(Debug, Default)]
+pub(crate) struct TransactionOwners {
+    owners: HashMap<i64, ConnectionId>,
+}
+
+impl TransactionOwners {
+    /// check that control batches come from connection which began their transaction
+    pub(crate) fn check<R: BatchRecords>(
+        &self,
+        records: &RecordSet<R>,
+        connection: ConnectionId,
+    ) -> Result<(), ErrorCode> {
+        let mut started = HashMap::new();
+        for batch in &records.batches {
+            let header = batch.get_header();
+            if !header.is_transactional() {
+                continue;
+            }
+            let owner = started
+                .get(&header.producer_id)
+                .or_else(|| self.owners.get(&header.producer_id))
+                .copied();
+            let control_type = batch.control_type().map_err(|_| ErrorCode::CorruptBatch)?;
+            if control_type == Some(ControlRecordType::Complete) {
+                // only coordinator SPU completes transaction
+                debug!(producer_id = header.producer_id, "complete from client");
+                return Err(ErrorCode::InvalidTxnState);
+            }
+            if control_type.map_or(false, |record_type| record_type.is_marker()) {
+                if owner != Some(connection) {
+                    debug!(
+                        producer_id = header.producer_id,
+                        ?owner,
+                        connection,
+                        "transaction not owned by connection"
+                    );
+                    return Err(ErrorCode::InvalidTxnState);
+                }
+                started.remove(&header.producer_id);
+            } else if owner.is_none() {
+                started.insert(header.producer_id, connection);
+            }
+        }
+        Ok(())
+    }
+
+    /// update owners from written batches, connection is none if SPU wrote them
+    pub(crate) fn update<R: BatchRecords>(
+        &mut self,
+        records: &RecordSet<R>,
+        connection: Option<ConnectionId>,
+    ) {
+        for batch in &records.batches {
+            let header = batch.get_header();
+            if !header.is_transactional() {
+                continue;
+            }
+            let is_marker = matches!(
+                batch.control_type(),
+                Ok(Some(record_type)) if record_type.is_marker()
+            );
+            if is_marker {
+                self.owners.remove(&header.producer_id);
+            } else if let Some(connection) = connection {
+                self.owners.entry(header.producer_id).or_insert(connection);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_protocol::record::{Batch, ControlRecordType, Record, RecordSet};
+
+    use super::TransactionOwners;
+
+    fn data(producer_id: i64) -> RecordSet {
+        let mut batch = Batch::from(vec![Record::new("value")]);
+        batch.get_mut_header().producer_id = producer_id;
+        batch.get_mut_header().set_transactional(true);
+        RecordSet::default().add(batch)
+    }
+
+    fn commit(producer_id: i64) -> RecordSet {
+        RecordSet::default().add(Batch::control(producer_id, 0, ControlRecordType::Commit))
+    }
+
+    #[test]
+    fn test_transaction_owners() {
+        let mut owners = TransactionOwners::default();
+        assert!(owners.check(&data(1), 1).is_ok());
+        owners.update(&data(1), Some(1));
+
+        assert_eq!(owners.check(&commit(1), 2), Err(ErrorCode::InvalidTxnState));
+        assert!(owners.check(&commit(1), 1).is_ok());
+
+        // marker written by SPU ends transaction of any connection
+        owners.update(&commit(1), None);
+        assert_eq!(owners.check(&commit(1), 1), Err(ErrorCode::InvalidTxnState));
+
+        // transaction begun and ended in same request
+        let mut records = data(2);
+        records.batches.extend(commit(2).batches);
+        assert!(owners.check(&records, 3).is_ok());
+
+        // partitions added by client begin transaction, complete is written only by SPU
+        let add_partitions =
+            RecordSet::default().add(Batch::add_partitions(3, 0, vec![("topic", 0).into()]));
+        assert!(owners.check(&add_partitions, 4).is_ok());
+        owners.update(&add_partitions, Some(4));
+        assert_eq!(owners.check(&commit(3), 5), Err(ErrorCode::InvalidTxnState));
+        let complete = RecordSet::default().add(Batch::control(3, 0, ControlRecordType::Complete));
+        assert_eq!(owners.check(&complete, 4), Err(ErrorCode::InvalidTxnState));
+        assert!(owners.check(&commit(3), 4).is_ok());
+    }
+}
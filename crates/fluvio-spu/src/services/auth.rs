@@ -0,0 +1,202 @@
+//!
+//! # Data Plane Authorization
+//!
+//! Checks produce and consume of SPU public connections against the data policy
+//! distributed by SC. If authorization is configured, everything is denied until
+//! SC sends a policy, otherwise everything is allowed.
+//!
+
+use async_trait::async_trait;
+use tracing::debug;
+
+use fluvio_auth::{AuthContext, AuthError, DataAction, InstanceAction, TypeAction};
+use fluvio_auth::x509::X509Identity;
+use fluvio_controlplane_metadata::extended::ObjectType;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_socket::FluvioSocket;
+
+use crate::core::DefaultSharedGlobalContext;
+
+/// authorization context of single public connection
+#[derive(Debug, Clone)]
+pub struct SpuAuthContext {
+    identity: Option<X509Identity>,
+    internal: bool,
+    ctx: DefaultSharedGlobalContext,
+}
+
+impl SpuAuthContext {
+    pub(crate) fn new(identity: Option<X509Identity>, ctx: DefaultSharedGlobalContext) -> Self {
+        Self {
+            identity,
+            internal: false,
+            ctx,
+        }
+    }
+
+    /// peer SPU connected to private endpoint, which is not exposed to clients
+    pub(crate) fn internal(ctx: DefaultSharedGlobalContext) -> Self {
+        Self {
+            identity: None,
+            internal: true,
+            ctx,
+        }
+    }
+
+    pub(crate) fn is_internal(&self) -> bool {
+        self.internal
+    }
+
+    /// identity is sent by TLS proxy only if authorization scopes are configured,
+    /// or negotiated with client if SASL is enabled, which accepts identity of proxy as well
+    pub(crate) async fn create(
+        socket: &mut FluvioSocket,
+        ctx: DefaultSharedGlobalContext,
+    ) -> Result<Self, AuthError> {
+        let identity = if let Some(sasl) = ctx.sasl() {
+            Some(sasl.create_identity(socket).await?)
+        } else if ctx.config().x509_auth_scopes.is_some() {
+            Some(X509Identity::create_from_connection(socket).await?)
+        } else {
+            None
+        };
+        Ok(Self::new(identity, ctx))
+    }
+
+    /// authenticated principal of connection, quotas of principal take precedence over client id
+    pub(crate) fn principal(&self) -> Option<&str> {
+        self.identity
+            .as_ref()
+            .map(|identity| identity.principal.as_str())
+    }
+
+    /// check data action, returning error code to send back to client if not allowed
+    pub(crate) async fn authorize(&self, action: DataAction, topic: &str) -> Result<(), ErrorCode> {
+        match self.allow_data_action(action, topic).await {
+            Ok(true) => Ok(()),
+            Ok(false) => {
+                debug!(?action, topic, "data action not authorized");
+                Err(ErrorCode::PermissionDenied)
+            }
+            Err(err) => Err(ErrorCode::Other(format!("authorization error: {err}"))),
+        }
+    }
+}
+
+#[async_trait]
+impl AuthContext for SpuAuthContext {
+    /// admin operations are only served by SC
+    async fn allow_type_action(
+        &self,
+        _ty: ObjectType,
+        _action: TypeAction,
+    ) -> Result<bool, AuthError> {
+        Ok(false)
+    }
+
+    async fn allow_instance_action(
+        &self,
+        _ty: ObjectType,
+        _action: InstanceAction,
+        _key: &str,
+    ) -> Result<bool, AuthError> {
+        Ok(false)
+    }
+
+    async fn allow_data_action(&self, action: DataAction, topic: &str) -> Result<bool, AuthError> {
+        if self.internal {
+            return Ok(true);
+        }
+        let policy = match self.ctx.data_policy() {
+            Some(policy) => policy,
+            None => return Ok(!authorization_enabled(&self.ctx)),
+        };
+
+        // connection without identity can't match any role
+        Ok(self
+            .identity
+            .as_ref()
+            .map(|identity| policy.evaluate(action, topic, identity))
+            .unwrap_or(false))
+    }
+}
+
+/// connections are authenticated by SASL or TLS proxy
+fn authorization_enabled(ctx: &DefaultSharedGlobalContext) -> bool {
+    ctx.sasl().is_some() || ctx.config().x509_auth_scopes.is_some()
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_auth::{DataAction, DataPolicy, TopicPermission};
+    use fluvio_auth::x509::X509Identity;
+    use fluvio_protocol::link::ErrorCode;
+
+    use crate::config::SpuConfig;
+    use crate::core::GlobalContext;
+
+    use super::SpuAuthContext;
+
+    #[fluvio_future::test]
+    async fn test_spu_data_authorization() {
+        let ctx = GlobalContext::new_shared_context(SpuConfig::default());
+        let identity = X509Identity::new("svc".to_owned(), vec!["reader".to_owned()]);
+        let reader = SpuAuthContext::new(Some(identity), ctx.clone());
+        let anonymous = SpuAuthContext::new(None, ctx.clone());
+
+        // no policy from sc, everything allowed
+        assert!(reader
+            .authorize(DataAction::Produce, "topic1")
+            .await
+            .is_ok());
+        assert!(anonymous
+            .authorize(DataAction::Consume, "topic1")
+            .await
+            .is_ok());
+
+        let mut policy = DataPolicy::root();
+        policy.0.insert(
+            "reader".to_owned(),
+            vec![TopicPermission::new("topic1", vec![DataAction::Consume])],
+        );
+        ctx.set_data_policy(Some(policy));
+
+        assert!(reader
+            .authorize(DataAction::Consume, "topic1")
+            .await
+            .is_ok());
+        assert_eq!(
+            reader.authorize(DataAction::Produce, "topic1").await,
+            Err(ErrorCode::PermissionDenied)
+        );
+        assert_eq!(
+            reader.authorize(DataAction::Consume, "topic2").await,
+            Err(ErrorCode::PermissionDenied)
+        );
+        assert_eq!(
+            anonymous.authorize(DataAction::Consume, "topic1").await,
+            Err(ErrorCode::PermissionDenied)
+        );
+    }
+
+    #[fluvio_future::test]
+    async fn test_spu_data_authorization_without_policy() {
+        let config = SpuConfig {
+            x509_auth_scopes: Some("scopes.json".into()),
+            ..Default::default()
+        };
+        let ctx = GlobalContext::new_shared_context(config);
+        let identity = X509Identity::new("svc".to_owned(), vec!["Root".to_owned()]);
+        let root = SpuAuthContext::new(Some(identity), ctx.clone());
+
+        // authorization configured, nothing allowed until sc sends policy
+        assert_eq!(
+            root.authorize(DataAction::Produce, "topic1").await,
+            Err(ErrorCode::PermissionDenied)
+        );
+
+        ctx.set_data_policy(Some(DataPolicy::root()));
+        assert!(root.authorize(DataAction::Produce, "topic1").await.is_ok());
+    }
+}
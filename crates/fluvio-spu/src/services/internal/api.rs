@@ -8,12 +8,18 @@ use fluvio_protocol::{Encoder, Decoder};
 use fluvio_protocol::api::{RequestMessage, ApiMessage, RequestHeader};
 
 use super::fetch_stream_request::FetchStreamRequest;
+use super::txn_markers::WriteTxnMarkersRequest;
+use super::peer_produce::PeerProduceRequest;
+use super::replicate_checkpoint::ReplicateCheckpointRequest;
 
 #[repr(u16)]
 #[derive(Eq, PartialEq, Debug, Encoder, Decoder, Clone, Copy)]
 #[fluvio(encode_discriminant)]
 pub enum SPUPeerApiEnum {
     FetchStream = 0,
+    WriteTxnMarkers = 1,
+    Produce = 2,
+    ReplicateCheckpoint = 3,
 }
 
 impl Default for SPUPeerApiEnum {
@@ -26,6 +32,12 @@ impl Default for SPUPeerApiEnum {
 pub enum SpuPeerRequest {
     #[fluvio(tag = 0)]
     FetchStream(RequestMessage<FetchStreamRequest>),
+    #[fluvio(tag = 1)]
+    WriteTxnMarkers(RequestMessage<WriteTxnMarkersRequest>),
+    #[fluvio(tag = 2)]
+    Produce(RequestMessage<PeerProduceRequest>),
+    #[fluvio(tag = 3)]
+    ReplicateCheckpoint(RequestMessage<ReplicateCheckpointRequest>),
 }
 
 impl Default for SpuPeerRequest {
@@ -50,6 +62,19 @@ impl ApiMessage for SpuPeerRequest {
                 header,
                 FetchStreamRequest::decode_from(src, version)?,
             ))),
+            SPUPeerApiEnum::WriteTxnMarkers => Ok(SpuPeerRequest::WriteTxnMarkers(
+                RequestMessage::new(header, WriteTxnMarkersRequest::decode_from(src, version)?),
+            )),
+            SPUPeerApiEnum::Produce => Ok(SpuPeerRequest::Produce(RequestMessage::new(
+                header,
+                PeerProduceRequest::decode_from(src, version)?,
+            ))),
+            SPUPeerApiEnum::ReplicateCheckpoint => {
+                Ok(SpuPeerRequest::ReplicateCheckpoint(RequestMessage::new(
+                    header,
+                    ReplicateCheckpointRequest::decode_from(src, version)?,
+                )))
+            }
         }
     }
 }
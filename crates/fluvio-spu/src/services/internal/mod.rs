@@ -1,6 +1,9 @@
 mod api;
 mod service_impl;
 mod fetch_stream_request;
+mod txn_markers;
+mod peer_produce;
+mod replicate_checkpoint;
 
 use tracing::info;
 
@@ -11,6 +14,9 @@ use crate::core::DefaultSharedGlobalContext;
 
 pub use self::fetch_stream_request::FetchStreamRequest;
 pub use self::fetch_stream_request::FetchStreamResponse;
+pub use self::txn_markers::{WriteTxnMarkersRequest, WriteTxnMarkersResponse, TxnMarkerResult};
+pub use self::peer_produce::PeerProduceRequest;
+pub use self::replicate_checkpoint::{ReplicateCheckpointRequest, ReplicateCheckpointResponse};
 pub use self::api::SPUPeerApiEnum;
 pub use self::api::SpuPeerRequest;
 
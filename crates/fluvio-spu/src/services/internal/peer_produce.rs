@@ -0,0 +1,36 @@
+#![allow(clippy::assign_op_pattern)]
+
+use tracing::debug;
+
+use fluvio_protocol::api::{Request, RequestMessage, ResponseMessage};
+use fluvio_protocol::derive::{Decoder, Encoder};
+use fluvio_spu_schema::produce::{DefaultProduceRequest, ProduceResponse};
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::services::public::{handle_produce_request, ConnectionContext};
+
+use super::SPUPeerApiEnum;
+
+/// Records produced by SPU to partition led by peer, e.g. output of transform.
+/// It is sent to private endpoint, so it doesn't need client identity.
+#[derive(Decoder, Encoder, Debug, Default)]
+pub struct PeerProduceRequest {
+    pub request: DefaultProduceRequest,
+}
+
+impl Request for PeerProduceRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::Produce as u16;
+    const DEFAULT_API_VERSION: i16 = DefaultProduceRequest::DEFAULT_API_VERSION;
+    type Response = ProduceResponse;
+}
+
+/// write records of peer same way as records of client
+pub(crate) async fn handle_peer_produce(
+    request: RequestMessage<PeerProduceRequest>,
+    ctx: DefaultSharedGlobalContext,
+    peer_ctx: &ConnectionContext,
+) -> anyhow::Result<ResponseMessage<ProduceResponse>> {
+    let (header, request) = request.get_header_request();
+    debug!(client = %header.client_id(), "received produce from peer");
+    handle_produce_request(RequestMessage::new(header, request.request), ctx, peer_ctx).await
+}
@@ -0,0 +1,58 @@
+#![allow(clippy::assign_op_pattern)]
+
+use tracing::{debug, warn};
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::derive::{Decoder, Encoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ReplicaKey;
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::smartengine::checkpoint::{CheckpointStore, SmartModuleCheckpoint};
+
+use super::SPUPeerApiEnum;
+
+/// Checkpoint of named stream or transform saved by leader of partition.
+/// It is replicated to followers so stream resumes from it after failover
+#[derive(Decoder, Encoder, Debug, Default)]
+pub struct ReplicateCheckpointRequest {
+    pub name: String,
+    pub replica: ReplicaKey,
+    pub transform: bool,
+    pub checkpoint: SmartModuleCheckpoint,
+}
+
+impl Request for ReplicateCheckpointRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::ReplicateCheckpoint as u16;
+    type Response = ReplicateCheckpointResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct ReplicateCheckpointResponse {
+    pub error_code: ErrorCode,
+}
+
+/// save checkpoint of partition followed by this SPU
+pub(crate) async fn handle_replicate_checkpoint(
+    request: &ReplicateCheckpointRequest,
+    ctx: &DefaultSharedGlobalContext,
+) -> ReplicateCheckpointResponse {
+    if ctx.followers_state().get(&request.replica).await.is_none() {
+        debug!(replica = %request.replica, "not follower, ignoring checkpoint");
+        return ReplicateCheckpointResponse {
+            error_code: ErrorCode::PartitionNotFound,
+        };
+    }
+    let error_code =
+        match CheckpointStore::replicated(&ctx.config().smartmodule_checkpoint_dir(), request) {
+            Ok(store) => match store.write(&request.checkpoint).await {
+                Ok(()) => ErrorCode::None,
+                Err(err) => {
+                    warn!(%err, name = %request.name, "unable to save replicated checkpoint");
+                    ErrorCode::Other(err.to_string())
+                }
+            },
+            Err(error_code) => error_code,
+        };
+    ReplicateCheckpointResponse { error_code }
+}
@@ -1,17 +1,23 @@
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use tracing::{debug, warn, instrument};
+use futures_util::stream::StreamExt;
+use tracing::{debug, trace, warn, instrument};
 use anyhow::Result;
 
-use fluvio_service::{wait_for_request, FluvioService, ConnectInfo};
+use fluvio_service::{FluvioService, ConnectInfo};
 use fluvio_socket::FluvioSocket;
 
 use crate::core::DefaultSharedGlobalContext;
 use crate::replication::leader::FollowerHandler;
+use crate::services::auth::SpuAuthContext;
+use crate::services::public::ConnectionContext;
 use super::SpuPeerRequest;
 use super::SPUPeerApiEnum;
 use super::FetchStreamResponse;
+use super::txn_markers::handle_write_txn_markers;
+use super::peer_produce::handle_peer_produce;
+use super::replicate_checkpoint::handle_replicate_checkpoint;
 
 #[derive(Debug)]
 pub struct InternalService {}
@@ -36,34 +42,59 @@ impl FluvioService for InternalService {
     ) -> Result<()> {
         let (mut sink, mut stream) = socket.split();
         let mut api_stream = stream.api_stream::<SpuPeerRequest, SPUPeerApiEnum>();
+        let peer_ctx = ConnectionContext::new(SpuAuthContext::internal(ctx.clone()));
 
-        // register follower
-        let (follower_id, spu_update) = wait_for_request!(
-            api_stream,
-
-            SpuPeerRequest::FetchStream(req_msg) => {
+        // register follower, coordinator of transaction sends markers, peers running
+        // transforms produce records and leaders replicate SmartModule checkpoints instead
+        let (follower_id, spu_update) = loop {
+            let req_message = match api_stream.next().await {
+                Some(Ok(req_message)) => req_message,
+                Some(Err(err)) => {
+                    debug!(%err, "error decoding request, end of connection");
+                    return Ok(());
+                }
+                None => {
+                    trace!("peer connection terminated");
+                    return Ok(());
+                }
+            };
 
-                let request = &req_msg.request;
-                let follower_id = request.spu_id;
-                debug!(
-                    follower_id,
-                    "received fetch stream"
-                );
-                // check if follower_id is valid
-                if let Some(spu_update) = ctx.follower_notifier().get(&follower_id).await {
-                    let response = FetchStreamResponse::new(follower_id);
+            match req_message {
+                SpuPeerRequest::FetchStream(req_msg) => {
+                    let request = &req_msg.request;
+                    let follower_id = request.spu_id;
+                    debug!(follower_id, "received fetch stream");
+                    // check if follower_id is valid
+                    if let Some(spu_update) = ctx.follower_notifier().get(&follower_id).await {
+                        let response = FetchStreamResponse::new(follower_id);
+                        let res_msg = req_msg.new_response(response);
+                        sink.send_response(&res_msg, req_msg.header.api_version())
+                            .await?;
+                        break (follower_id, spu_update);
+                    } else {
+                        warn!(follower_id, "unknown spu, dropping connection");
+                        return Ok(());
+                    }
+                }
+                SpuPeerRequest::WriteTxnMarkers(req_msg) => {
+                    let response = handle_write_txn_markers(&req_msg.request, &ctx).await;
                     let res_msg = req_msg.new_response(response);
-                    sink
-                        .send_response(&res_msg, req_msg.header.api_version())
+                    sink.send_response(&res_msg, req_msg.header.api_version())
+                        .await?;
+                }
+                SpuPeerRequest::Produce(req_msg) => {
+                    let version = req_msg.header.api_version();
+                    let res_msg = handle_peer_produce(req_msg, ctx.clone(), &peer_ctx).await?;
+                    sink.send_response(&res_msg, version).await?;
+                }
+                SpuPeerRequest::ReplicateCheckpoint(req_msg) => {
+                    let response = handle_replicate_checkpoint(&req_msg.request, &ctx).await;
+                    let res_msg = req_msg.new_response(response);
+                    sink.send_response(&res_msg, req_msg.header.api_version())
                         .await?;
-                    (follower_id,spu_update)
-                } else {
-                    warn!(follower_id, "unknown spu, dropping connection");
-                    return Ok(())
                 }
-
             }
-        );
+        };
 
         drop(api_stream);
 
@@ -0,0 +1,69 @@
+#![allow(clippy::assign_op_pattern)]
+
+use tracing::debug;
+
+use fluvio_protocol::api::Request;
+use fluvio_protocol::derive::{Decoder, Encoder};
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{ControlRecordType, ReplicaKey};
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::SPUPeerApiEnum;
+
+/// Sent by coordinator of transaction to leader of its other partitions,
+/// to write decision of transaction to them
+#[derive(Decoder, Encoder, Debug, Default)]
+pub struct WriteTxnMarkersRequest {
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub record_type: ControlRecordType,
+    pub partitions: Vec<ReplicaKey>,
+}
+
+impl Request for WriteTxnMarkersRequest {
+    const API_KEY: u16 = SPUPeerApiEnum::WriteTxnMarkers as u16;
+    type Response = WriteTxnMarkersResponse;
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct WriteTxnMarkersResponse {
+    pub partitions: Vec<TxnMarkerResult>,
+}
+
+#[derive(Decoder, Encoder, Default, Debug)]
+pub struct TxnMarkerResult {
+    pub replica: ReplicaKey,
+    pub error_code: ErrorCode,
+}
+
+/// write decision of transaction to partitions led by this SPU
+pub(crate) async fn handle_write_txn_markers(
+    request: &WriteTxnMarkersRequest,
+    ctx: &DefaultSharedGlobalContext,
+) -> WriteTxnMarkersResponse {
+    let mut response = WriteTxnMarkersResponse::default();
+    for replica in &request.partitions {
+        let error_code = match ctx.leaders_state().get(replica).await {
+            Some(leader) => match leader
+                .write_transaction_marker(
+                    request.producer_id,
+                    request.producer_epoch,
+                    request.record_type,
+                    ctx.follower_notifier(),
+                )
+                .await
+            {
+                Ok(()) => ErrorCode::None,
+                Err(error_code) => error_code,
+            },
+            None => ErrorCode::NotLeaderForPartition,
+        };
+        debug!(%replica, %error_code, "transaction marker written");
+        response.partitions.push(TxnMarkerResult {
+            replica: replica.clone(),
+            error_code,
+        });
+    }
+    response
+}
@@ -1,4 +1,5 @@
 pub(crate) mod public;
+pub(crate) mod auth;
 
 pub mod internal;
 
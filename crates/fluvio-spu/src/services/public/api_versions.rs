@@ -29,8 +29,8 @@ pub async fn handle_api_version_request(
     ));
     response.api_keys.push(make_version_key(
         SpuServerApiKey::FetchOffsets,
-        FetchOffsetsRequest::DEFAULT_API_VERSION,
-        FetchOffsetsRequest::DEFAULT_API_VERSION,
+        FetchOffsetsRequest::MIN_API_VERSION,
+        FetchOffsetsRequest::MAX_API_VERSION,
     ));
     response.api_keys.push(make_version_key(
         SpuServerApiKey::StreamFetch,
@@ -1,15 +1,55 @@
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::replication::leader::ConnectionId;
+use crate::services::auth::SpuAuthContext;
 use crate::services::public::StreamPublishers;
 
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(0);
+
 #[derive(Debug)]
 pub(crate) struct ConnectionContext {
+    id: ConnectionId,
     stream_publishers: StreamPublishers,
+    auth: SpuAuthContext,
+    produce_throttled_until: Mutex<Option<Instant>>,
 }
 
 impl ConnectionContext {
-    pub(crate) fn new() -> Self {
+    pub(crate) fn new(auth: SpuAuthContext) -> Self {
         Self {
+            id: NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
             stream_publishers: StreamPublishers::new(),
+            auth,
+            produce_throttled_until: Mutex::new(None),
+        }
+    }
+
+    pub(crate) fn id(&self) -> ConnectionId {
+        self.id
+    }
+
+    pub(crate) fn auth(&self) -> &SpuAuthContext {
+        &self.auth
+    }
+
+    /// hold back next produce request of this connection for throttle time
+    pub(crate) fn throttle_produce(&self, throttle: Duration) {
+        if throttle.is_zero() {
+            return;
         }
+        let until = Instant::now() + throttle;
+        if let Ok(mut throttled_until) = self.produce_throttled_until.lock() {
+            *throttled_until = Some(throttled_until.map_or(until, |current| current.max(until)));
+        }
+    }
+
+    pub(crate) fn take_produce_throttle(&self) -> Option<Instant> {
+        self.produce_throttled_until
+            .lock()
+            .ok()
+            .and_then(|mut throttled_until| throttled_until.take())
     }
 
     pub(crate) fn stream_publishers(&self) -> &StreamPublishers {
@@ -1,22 +1,30 @@
+use std::time::Instant;
+
 use tracing::{debug, trace, instrument};
 use anyhow::Result;
 
 use fluvio_spu_schema::file::FileRecordSet;
 use fluvio_socket::ExclusiveFlvSink;
 use fluvio_socket::SocketError;
-use fluvio_protocol::{link::ErrorCode, api::RequestMessage};
+use fluvio_protocol::{
+    link::ErrorCode,
+    api::{RequestHeader, RequestMessage},
+};
 use fluvio_spu_schema::fetch::{
     FileFetchResponse, FileFetchRequest, FilePartitionResponse, FileTopicResponse,
     FetchablePartitionResponse, FetchPartition, FetchableTopic, FetchableTopicResponse,
 };
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_auth::DataAction;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::metrics::IncreaseValue;
+use crate::services::auth::SpuAuthContext;
 use crate::traffic::TrafficType;
 
 /// perform log fetch request using zero copy write
 #[instrument(
-    skip(request, ctx, sink),
+    skip(request, ctx, auth, sink),
     fields(
         max_bytes = request.request.max_bytes,
     ),
@@ -24,6 +32,7 @@ use crate::traffic::TrafficType;
 pub async fn handle_fetch_request(
     request: RequestMessage<FileFetchRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &SpuAuthContext,
     sink: ExclusiveFlvSink,
 ) -> Result<()> {
     let (header, fetch_request) = request.get_header_request();
@@ -32,7 +41,7 @@ pub async fn handle_fetch_request(
 
     for topic_request in &fetch_request.topics {
         let topic_response =
-            handle_fetch_topic(&ctx, &fetch_request, topic_request, header.is_connector()).await?;
+            handle_fetch_topic(&ctx, &fetch_request, topic_request, &header, auth).await?;
         fetch_response.topics.push(topic_response);
     }
 
@@ -52,14 +61,15 @@ pub async fn handle_fetch_request(
 }
 
 #[instrument(
-    skip(ctx, fetch_request, topic_request),
+    skip(ctx, fetch_request, topic_request, header, auth),
     fields(topic = %topic_request.name),
 )]
 async fn handle_fetch_topic(
     ctx: &DefaultSharedGlobalContext,
     fetch_request: &FileFetchRequest,
     topic_request: &FetchableTopic,
-    is_connector: bool,
+    header: &RequestHeader,
+    auth: &SpuAuthContext,
 ) -> Result<FetchableTopicResponse<FileRecordSet>> {
     let topic = &topic_request.name;
 
@@ -68,16 +78,22 @@ async fn handle_fetch_topic(
         ..Default::default()
     };
 
+    if let Err(error_code) = auth.authorize(DataAction::Consume, topic).await {
+        for partition_request in &topic_request.fetch_partitions {
+            topic_response.partitions.push(FilePartitionResponse {
+                partition_index: partition_request.partition_index,
+                error_code: error_code.clone(),
+                ..Default::default()
+            });
+        }
+        return Ok(topic_response);
+    }
+
     for partition_request in &topic_request.fetch_partitions {
         let replica_id = ReplicaKey::new(topic.clone(), partition_request.partition_index);
-        let partition_response = handle_fetch_partition(
-            ctx,
-            replica_id,
-            fetch_request,
-            partition_request,
-            is_connector,
-        )
-        .await?;
+        let partition_response =
+            handle_fetch_partition(ctx, replica_id, fetch_request, partition_request, header)
+                .await?;
         topic_response.partitions.push(partition_response);
     }
 
@@ -85,7 +101,7 @@ async fn handle_fetch_topic(
 }
 
 #[instrument(
-skip(ctx, replica_id, partition_request),
+skip(ctx, replica_id, partition_request, header),
     fields(%replica_id)
 )]
 async fn handle_fetch_partition(
@@ -93,7 +109,7 @@ async fn handle_fetch_partition(
     replica_id: ReplicaKey,
     fetch_request: &FileFetchRequest,
     partition_request: &FetchPartition,
-    is_connector: bool,
+    header: &RequestHeader,
 ) -> Result<FetchablePartitionResponse<FileRecordSet>, SocketError> {
     trace!("Fetching partition:");
     let fetch_offset = partition_request.fetch_offset;
@@ -112,8 +128,7 @@ async fn handle_fetch_partition(
         }
     };
 
-    let metrics = ctx.metrics();
-
+    let now = Instant::now();
     match leader_state
         .read_records(
             fetch_offset,
@@ -125,12 +140,17 @@ async fn handle_fetch_partition(
         Ok(slice) => {
             partition_response.high_watermark = slice.end.hw;
             partition_response.log_start_offset = slice.start;
+            if !slice.aborted.is_empty() {
+                partition_response.aborted = Some(slice.aborted);
+            }
 
             if let Some(file_slice) = slice.file_slice {
-                metrics.outbound().increase(
-                    is_connector,
-                    (slice.end.hw - slice.start) as u64,
-                    file_slice.len(),
+                ctx.metrics().record_fetch(
+                    &replica_id,
+                    header.client_id(),
+                    header.is_connector(),
+                    IncreaseValue::new((slice.end.hw - slice.start) as u64, file_slice.len()),
+                    now.elapsed(),
                 );
                 partition_response.records = file_slice.into();
             }
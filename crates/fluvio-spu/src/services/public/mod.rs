@@ -22,13 +22,14 @@ use fluvio_spu_schema::server::SpuServerApiKey;
 use fluvio_types::event::StickyEvent;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::services::auth::SpuAuthContext;
 use self::api_versions::handle_api_version_request;
-use self::produce_handler::handle_produce_request;
+pub(crate) use self::produce_handler::handle_produce_request;
 use self::fetch_handler::handle_fetch_request;
 use self::offset_request::handle_offset_request;
 use self::offset_update::handle_offset_update;
 use self::stream_fetch::{StreamFetchHandler, publishers::StreamPublishers};
-use self::conn_context::ConnectionContext;
+pub(crate) use self::conn_context::ConnectionContext;
 
 pub(crate) type SpuPublicServer =
     FluvioApiServer<SpuServerRequest, SpuServerApiKey, DefaultSharedGlobalContext, PublicService>;
@@ -63,16 +64,18 @@ impl FluvioService for PublicService {
     async fn respond(
         self: Arc<Self>,
         context: DefaultSharedGlobalContext,
-        socket: FluvioSocket,
+        mut socket: FluvioSocket,
         _connection: ConnectInfo,
     ) -> Result<()> {
+        let auth = SpuAuthContext::create(&mut socket, context.clone()).await?;
+
         let (sink, mut stream) = socket.split();
 
         let mut shared_sink = sink.as_shared();
         let api_stream = stream.api_stream::<SpuServerRequest, SpuServerApiKey>();
         let shutdown = StickyEvent::shared();
         let mut event_stream = api_stream.take_until(shutdown.listen_pinned());
-        let mut conn_ctx = ConnectionContext::new();
+        let mut conn_ctx = ConnectionContext::new(auth);
 
         loop {
             let event = event_stream.next().await;
@@ -94,17 +97,22 @@ impl FluvioService for PublicService {
                         ),
                         SpuServerRequest::ProduceRequest(request) => call_service!(
                             request,
-                            handle_produce_request(request, context.clone()),
+                            handle_produce_request(request, context.clone(), &conn_ctx),
                             shared_sink,
                             "ProduceRequest"
                         ),
                         SpuServerRequest::FileFetchRequest(request) => {
-                            handle_fetch_request(request, context.clone(), shared_sink.clone())
-                                .await?
+                            handle_fetch_request(
+                                request,
+                                context.clone(),
+                                conn_ctx.auth(),
+                                shared_sink.clone(),
+                            )
+                            .await?
                         }
                         SpuServerRequest::FetchOffsetsRequest(request) => call_service!(
                             request,
-                            handle_offset_request(request, context.clone()),
+                            handle_offset_request(request, context.clone(), conn_ctx.auth()),
                             shared_sink,
                             "FetchOffsetsRequest"
                         ),
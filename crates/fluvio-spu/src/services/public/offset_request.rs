@@ -1,6 +1,6 @@
 use std::io::Error as IoError;
 
-use tracing::{trace, instrument};
+use tracing::{trace, error, instrument};
 
 use fluvio_protocol::api::{RequestMessage, ResponseMessage};
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
@@ -9,13 +9,16 @@ use fluvio_spu_schema::server::fetch_offset::FetchOffsetsResponse;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
+use fluvio_auth::DataAction;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::services::auth::SpuAuthContext;
 
-#[instrument(skip(req_msg, ctx))]
+#[instrument(skip(req_msg, ctx, auth))]
 pub async fn handle_offset_request(
     req_msg: RequestMessage<FetchOffsetsRequest>,
     ctx: DefaultSharedGlobalContext,
+    auth: &SpuAuthContext,
 ) -> Result<ResponseMessage<FetchOffsetsResponse>, IoError> {
     let request = req_msg.request();
     trace!("handling flv fetch request: {:#?}", request);
@@ -30,6 +33,20 @@ pub async fn handle_offset_request(
             ..Default::default()
         };
 
+        if let Err(error_code) = auth.authorize(DataAction::Consume, topic).await {
+            for partition_req in &topic_request.partitions {
+                topic_response
+                    .partitions
+                    .push(FetchOffsetPartitionResponse {
+                        partition_index: partition_req.partition_index,
+                        error_code: error_code.clone(),
+                        ..Default::default()
+                    });
+            }
+            response.topics.push(topic_response);
+            continue;
+        }
+
         for partition_req in &topic_request.partitions {
             let partition = &partition_req.partition_index;
             let mut partition_response = FetchOffsetPartitionResponse {
@@ -43,6 +60,19 @@ pub async fn handle_offset_request(
                 partition_response.error_code = ErrorCode::None;
                 partition_response.start_offset = start_offset;
                 partition_response.last_stable_offset = hw;
+                if let Some(timestamp) = partition_req.timestamp {
+                    match replica.find_offset_by_timestamp(timestamp).await {
+                        Ok(offset) => {
+                            let offset = offset.map(|offset| offset.min(hw)).unwrap_or(hw);
+                            trace!(timestamp, offset, "resolved timestamp offset");
+                            partition_response.timestamp_offset = Some(offset);
+                        }
+                        Err(err) => {
+                            error!(%err, timestamp, "failed to resolve timestamp");
+                            partition_response.error_code = err;
+                        }
+                    }
+                }
             } else {
                 trace!("offset fetch request is not found: {}", rep_id);
                 partition_response.error_code = ErrorCode::PartitionNotLeader;
@@ -8,6 +8,8 @@ use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::api::{ResponseMessage, RequestMessage};
 use crate::services::public::conn_context::ConnectionContext;
 
+/// session ids are scoped to connection, so only streams authorized by
+/// stream fetch of this connection can be updated
 #[instrument(skip(conn_ctx, request))]
 pub(crate) async fn handle_offset_update(
     request: RequestMessage<UpdateOffsetsRequest>,
@@ -1,5 +1,5 @@
 use std::io::{Error, ErrorKind};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use tokio::select;
 use tracing::warn;
@@ -7,8 +7,8 @@ use tracing::{debug, trace, error};
 use tracing::instrument;
 use anyhow::{anyhow, Result};
 
-use fluvio_smartengine::SmartModuleChainInstance;
-use fluvio_protocol::api::RequestKind;
+use fluvio_smartengine::metrics::SmartModuleChainMetrics;
+use fluvio_protocol::api::{RequestHeader, RequestKind};
 use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{BatchRecords, Offset, Batch, RawRecords};
 use fluvio::Compression;
@@ -23,15 +23,21 @@ use fluvio_protocol::{api::RequestMessage, link::ErrorCode};
 use fluvio_protocol::api::ResponseMessage;
 use fluvio_protocol::record::RecordSet;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_auth::DataAction;
 
 use fluvio_future::timer::sleep;
 
 use crate::core::DefaultSharedGlobalContext;
+use crate::core::metrics::IncreaseValue;
+use crate::core::quota::{QuotaClient, QuotaTraffic, throttle_time_ms};
+use crate::replication::leader::{ConnectionId, SharedFileLeaderState};
 use crate::smartengine::context::SmartModuleContext;
 use crate::smartengine::produce_batch::ProduceBatchIterator;
 use crate::smartengine::batch::process_batch;
 use crate::traffic::TrafficType;
 
+use super::conn_context::ConnectionContext;
+
 struct TopicWriteResult {
     topic: String,
     partitions: Vec<PartitionWriteResult>,
@@ -46,7 +52,7 @@ struct PartitionWriteResult {
 }
 
 #[instrument(
-    skip(request,ctx),
+    skip(request,ctx,conn_ctx),
     fields(
         id = request.header.correlation_id(),
         client = %request.header.client_id()
@@ -55,11 +61,41 @@ struct PartitionWriteResult {
 pub async fn handle_produce_request(
     request: RequestMessage<DefaultProduceRequest>,
     ctx: DefaultSharedGlobalContext,
+    conn_ctx: &ConnectionContext,
 ) -> Result<ResponseMessage<ProduceResponse>> {
+    let auth = conn_ctx.auth();
     let (header, produce_request) = request.get_header_request();
     trace!("Handling ProduceRequest: {:#?}", produce_request);
 
-    let mut sm_chain_instance =
+    // producer which ignores throttle time is held back here
+    if let Some(throttled_until) = conn_ctx.take_produce_throttle() {
+        let wait = throttled_until.saturating_duration_since(Instant::now());
+        if !wait.is_zero() {
+            debug!(wait_ms = wait.as_millis() as u64, "throttling producer");
+            sleep(wait).await;
+        }
+    }
+
+    // records are written when client is over quota, its next request is held back instead
+    let request_bytes: u64 = produce_request
+        .topics
+        .iter()
+        .flat_map(|topic| topic.partitions.iter())
+        .flat_map(|partition| partition.records.batches.iter())
+        .map(|batch| batch.batch_len() as u64)
+        .sum();
+    // records of peer SPUs, e.g. from transforms, are not subject to client quotas
+    let throttle = if auth.is_internal() {
+        Duration::ZERO
+    } else {
+        ctx.quotas().record(
+            &QuotaClient::new(auth.principal(), header.client_id()),
+            QuotaTraffic::Produce,
+            request_bytes,
+        )
+    };
+
+    let mut sm_ctx =
         smartmodule_chain(produce_request.smartmodules, header.api_version(), &ctx).await?;
 
     let mut topic_results = Vec::with_capacity(produce_request.topics.len());
@@ -67,8 +103,10 @@ pub async fn handle_produce_request(
         let topic_result = handle_produce_topic(
             &ctx,
             topic_request,
-            header.is_connector(),
-            sm_chain_instance.as_mut(),
+            &header,
+            produce_request.isolation,
+            conn_ctx,
+            sm_ctx.as_mut(),
         )
         .await?;
         topic_results.push(topic_result);
@@ -80,20 +118,24 @@ pub async fn handle_produce_request(
         &ctx,
     )
     .await;
-    let response = into_response(topic_results);
+    let mut response = into_response(topic_results);
+    response.throttle_time_ms = throttle_time_ms(throttle);
+    conn_ctx.throttle_produce(throttle);
     trace!("Returning ProduceResponse: {:#?}", &response);
     Ok(RequestMessage::<DefaultProduceRequest>::response_with_header(&header, response))
 }
 
 #[instrument(
-    skip(ctx, topic_request, sm_chain_instance),
+    skip(ctx, topic_request, header, isolation, conn_ctx, sm_ctx),
     fields(topic = %topic_request.name),
 )]
 async fn handle_produce_topic(
     ctx: &DefaultSharedGlobalContext,
     topic_request: DefaultTopicRequest,
-    is_connector: bool,
-    mut sm_chain_instance: Option<&mut SmartModuleChainInstance>,
+    header: &RequestHeader,
+    isolation: Isolation,
+    conn_ctx: &ConnectionContext,
+    mut sm_ctx: Option<&mut SmartModuleContext>,
 ) -> Result<TopicWriteResult> {
     let topic = &topic_request.name;
 
@@ -104,21 +146,56 @@ async fn handle_produce_topic(
         partitions: vec![],
     };
 
-    for mut partition_request in topic_request.partitions.into_iter() {
-        if let Some(sm_chain_instance) = &mut sm_chain_instance {
-            apply_smartmodules_for_partition_request(
-                &mut partition_request,
-                sm_chain_instance,
-                ctx,
-            )?;
+    if let Err(error_code) = conn_ctx.auth().authorize(DataAction::Produce, topic).await {
+        for partition_request in topic_request.partitions {
+            let replica_id = ReplicaKey::new(topic.clone(), partition_request.partition_index);
+            topic_result
+                .partitions
+                .push(PartitionWriteResult::error(replica_id, error_code.clone()));
         }
+        return Ok(topic_result);
+    }
 
+    for mut partition_request in topic_request.partitions.into_iter() {
         let replica_id = ReplicaKey::new(topic.clone(), partition_request.partition_index);
 
+        // batches corrupted on the way are rejected before they are transformed or stored
+        if !partition_request
+            .records
+            .batches
+            .iter()
+            .all(|batch| batch.validate_crc())
+        {
+            error!(%replica_id, "Batch CRC doesn't match its content");
+            topic_result.partitions.push(PartitionWriteResult::error(
+                replica_id,
+                ErrorCode::CorruptBatch,
+            ));
+            continue;
+        }
+
+        // transaction markers are stored as they are, SmartModules only transform records
+        let is_control = partition_request
+            .records
+            .batches
+            .iter()
+            .any(|batch| batch.get_header().is_control());
+        if let Some(sm_ctx) = sm_ctx.as_mut().filter(|_| !is_control) {
+            apply_smartmodules_for_partition_request(&mut partition_request, sm_ctx, ctx)?;
+        }
+
         let partition_response = if partition_request.records.total_records() == 0 {
             PartitionWriteResult::filtered(replica_id)
         } else {
-            handle_produce_partition(ctx, replica_id, partition_request, is_connector).await
+            handle_produce_partition(
+                ctx,
+                replica_id,
+                partition_request,
+                header,
+                isolation,
+                conn_ctx.id(),
+            )
+            .await
         };
 
         topic_result.partitions.push(partition_response);
@@ -127,14 +204,16 @@ async fn handle_produce_topic(
 }
 
 #[instrument(
-    skip(ctx, replica_id, partition_request),
+    skip(ctx, replica_id, partition_request, header, isolation, connection),
     fields(%replica_id),
 )]
 async fn handle_produce_partition<R: BatchRecords>(
     ctx: &DefaultSharedGlobalContext,
     replica_id: ReplicaKey,
     partition_request: PartitionProduceData<RecordSet<R>>,
-    is_connector: bool,
+    header: &RequestHeader,
+    isolation: Isolation,
+    connection: ConnectionId,
 ) -> PartitionWriteResult {
     trace!("Handling produce request for partition:");
 
@@ -154,6 +233,14 @@ async fn handle_produce_partition<R: BatchRecords>(
         }
     };
 
+    // committed records must be replicated to enough replicas, so they are rejected early
+    if isolation == Isolation::ReadCommitted {
+        if let Err(error_code) = leader_state.check_min_in_sync_replicas().await {
+            warn!(%replica_id, %error_code, "Not enough in-sync replicas");
+            return PartitionWriteResult::error(replica_id, error_code);
+        }
+    }
+
     let mut records = partition_request.records;
 
     if validate_records(&records, replica_metadata.compression_type).is_err() {
@@ -161,16 +248,20 @@ async fn handle_produce_partition<R: BatchRecords>(
         return PartitionWriteResult::error(replica_id, ErrorCode::CompressionError);
     }
 
+    let now = Instant::now();
     let write_result = leader_state
-        .write_record_set(&mut records, ctx.follower_notifier())
+        .write_record_set_from(&mut records, ctx.follower_notifier(), connection)
         .await;
 
-    let metrics = ctx.metrics();
     match write_result {
         Ok((base_offset, leo, bytes)) => {
-            metrics
-                .inbound()
-                .increase(is_connector, (leo - base_offset) as u64, bytes as u64);
+            ctx.metrics().record_produce(
+                &replica_id,
+                header.client_id(),
+                header.is_connector(),
+                IncreaseValue::new((leo - base_offset) as u64, bytes as u64),
+                now.elapsed(),
+            );
 
             PartitionWriteResult::ok(replica_id, base_offset, leo)
         }
@@ -179,6 +270,13 @@ async fn handle_produce_partition<R: BatchRecords>(
                 error!(%replica_id, "Batch is too big: {:#?}", err);
                 PartitionWriteResult::error(replica_id, ErrorCode::MessageTooLarge)
             }
+            _ if err.is::<ErrorCode>() => {
+                let error_code = err
+                    .downcast::<ErrorCode>()
+                    .unwrap_or(ErrorCode::StorageError);
+                error!(%replica_id, %error_code, "Batch rejected");
+                PartitionWriteResult::error(replica_id, error_code)
+            }
             _ => {
                 error!(%replica_id, "Error writing to replica: {:#?}", err);
                 PartitionWriteResult::error(replica_id, ErrorCode::StorageError)
@@ -191,51 +289,60 @@ async fn smartmodule_chain(
     sm_invocations: Vec<SmartModuleInvocation>,
     api_version: i16,
     ctx: &DefaultSharedGlobalContext,
-) -> Result<Option<SmartModuleChainInstance>> {
-    let sm_ctx = match SmartModuleContext::try_from(sm_invocations, api_version, ctx).await {
-        Ok(ctx) => ctx,
+) -> Result<Option<SmartModuleContext>> {
+    match SmartModuleContext::try_from(sm_invocations, api_version, ctx).await {
+        Ok(sm_ctx) => Ok(sm_ctx),
         Err(error_code) => {
             warn!("smartmodule context init failed: {:?}", error_code);
-            return Err(anyhow!("smartmodule context init failed: {}", error_code));
+            Err(anyhow!("smartmodule context init failed: {}", error_code))
         }
-    };
-
-    if let Some(ctx) = sm_ctx {
-        Ok(Some(ctx.chain))
-    } else {
-        Ok(None)
     }
 }
 
 fn apply_smartmodules_for_partition_request(
     partition_request: &mut PartitionProduceData<RecordSet<RawRecords>>,
-    sm_chain_instance: &mut SmartModuleChainInstance,
+    sm_ctx: &mut SmartModuleContext,
     ctx: &DefaultSharedGlobalContext,
 ) -> Result<()> {
     let records = &partition_request.records;
     let batches = &records.batches;
 
-    let mut batches = ProduceBatchIterator::new(batches);
+    let mut batch_iter = ProduceBatchIterator::new(batches);
 
-    let sm_result = match process_batch(
-        sm_chain_instance,
-        &mut batches,
+    let chain_metrics = SmartModuleChainMetrics::default();
+    let processed = process_batch(
+        &mut sm_ctx.chain,
+        &mut batch_iter,
         std::usize::MAX,
-        ctx.metrics().chain_metrics(),
-    ) {
-        Ok((result, sm_runtime_error)) => {
-            if let Some(error) = sm_runtime_error {
+        &chain_metrics,
+    );
+    ctx.metrics()
+        .record_smartmodule(&sm_ctx.name, &chain_metrics);
+
+    let sm_result = match processed {
+        Ok(processed) => {
+            if let Some(error) = processed.error {
                 return Err(anyhow!("smartmodule runtime error: {error}"));
             } else {
-                result
+                processed.batch
             }
         }
         Err(general_error) => return Err(anyhow!("smartmodule chain failed: {general_error}")),
     };
 
-    let smartmoduled_records = Batch::<RawRecords>::try_from(sm_result)
+    let mut smartmoduled_records = Batch::<RawRecords>::try_from(sm_result)
         .map_err(|e| Error::new(ErrorKind::Other, format!("Compression Error: {:?}", e)))?;
 
+    // keep producer identity so idempotent producer batches can still be deduplicated
+    // and transactional batches still belong to their transaction
+    if let Some(first) = batches.first() {
+        let header = smartmoduled_records.get_mut_header();
+        header.producer_id = first.header.producer_id;
+        header.producer_epoch = first.header.producer_epoch;
+        header.first_sequence = first.header.first_sequence;
+        header.set_transactional(first.header.is_transactional());
+    }
+
     partition_request.records = RecordSet {
         batches: vec![smartmoduled_records],
     };
@@ -270,6 +377,8 @@ fn validate_records<R: BatchRecords>(
 /// For isolation = ReadCommitted wait until the replica's `hw` includes written records offsets or
 /// until `timeout` passes. In case of timeout, the partition response returns `RequestTimedOut`
 /// error code. The timeout is not shared between partitions.
+/// If in-sync replicas dropped below min in-sync replicas while waiting, records may be committed
+/// by fewer replicas than required, so `NotEnoughInSyncReplicas` is returned after the append.
 ///
 /// For isolation = ReadUncommitted - it's no op.
 async fn wait_for_acks(
@@ -297,6 +406,7 @@ async fn wait_for_acks(
                 let leo = partition.leo;
                 if leader_state.hw().ge(&leo) {
                     trace!(?partition.replica_id, %leo, "batch already committed, skip waiting");
+                    check_in_sync_after_append(partition, &leader_state).await;
                     continue;
                 }
 
@@ -313,6 +423,7 @@ async fn wait_for_acks(
                 select! {
                     _ = wait_future => {
                         trace!(?partition.replica_id, "waiting for acks completed");
+                        check_in_sync_after_append(partition, &leader_state).await;
                     },
                     _ = timer => {
                         debug!(?partition.replica_id, "response timeout exceeded");
@@ -328,6 +439,17 @@ async fn wait_for_acks(
     };
 }
 
+/// records are acknowledged only if they were committed by min in-sync replicas
+async fn check_in_sync_after_append(
+    partition: &mut PartitionWriteResult,
+    leader_state: &SharedFileLeaderState,
+) {
+    if let Err(error_code) = leader_state.check_min_in_sync_replicas().await {
+        warn!(%partition.replica_id, %error_code, "Not enough in-sync replicas after append");
+        partition.error_code = error_code;
+    }
+}
+
 impl From<TopicWriteResult> for TopicProduceResponse {
     fn from(write_result: TopicWriteResult) -> Self {
         Self {
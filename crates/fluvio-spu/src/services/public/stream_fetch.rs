@@ -1,37 +1,45 @@
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use tracing::{debug, error, instrument, trace, warn};
 use tokio::select;
 
-use fluvio_smartengine::SmartModuleChainInstance;
+use fluvio_smartengine::metrics::SmartModuleChainMetrics;
+use fluvio_smartmodule::dataplane::smartmodule::SmartModuleErrorPolicy;
 use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_auth::DataAction;
 use fluvio_types::event::{StickyEvent, offsets::OffsetPublisher};
 use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
 use fluvio_socket::{ExclusiveFlvSink, SocketError};
 use fluvio_protocol::{
     api::{RequestMessage, RequestHeader},
-    record::{RecordSet, Offset, RawRecords},
+    record::{RecordSet, Offset, RawRecords, RECORD_HEADERS_VERSION},
 };
 use fluvio_protocol::link::{ErrorCode, smartmodule::SmartModuleTransformRuntimeError};
 use fluvio_compression::CompressionError;
 use fluvio_spu_schema::{
     server::stream_fetch::{
         DefaultStreamFetchRequest, FileStreamFetchRequest, StreamFetchRequest, StreamFetchResponse,
+        RECORD_HEADERS_API,
     },
-    fetch::{FilePartitionResponse, FetchablePartitionResponse},
+    fetch::{AbortedTransactionFilter, FilePartitionResponse, FetchablePartitionResponse},
     Isolation,
     file::FileRecordSet,
 };
 use fluvio_types::event::offsets::OffsetChangeListener;
 use fluvio_protocol::record::Batch;
 
-use crate::core::{DefaultSharedGlobalContext, metrics::IncreaseValue};
+use crate::core::{DefaultSharedGlobalContext, LeaderConnections, metrics::IncreaseValue};
+use crate::core::quota::{QuotaManager, QuotaClient, QuotaTraffic, throttle_time_ms};
 use crate::replication::leader::SharedFileLeaderState;
+use crate::services::auth::SpuAuthContext;
 use crate::services::public::conn_context::ConnectionContext;
 use crate::services::public::stream_fetch::publishers::INIT_OFFSET;
 use crate::smartengine::context::SmartModuleContext;
-use crate::smartengine::batch::process_batch;
+use crate::smartengine::checkpoint::{CheckpointStore, SmartModuleCheckpoint};
+use crate::smartengine::dead_letter::send_dead_letters;
+use crate::smartengine::batch::{process_batch, SmartModuleInputBatch};
 use crate::smartengine::file_batch::FileBatchIterator;
 use crate::core::metrics::SpuMetrics;
 use crate::traffic::TrafficType;
@@ -49,6 +57,14 @@ pub struct StreamFetchHandler {
     leader_state: SharedFileLeaderState,
     stream_id: u32,
     metrics: Arc<SpuMetrics>,
+    checkpoint: Option<CheckpointStore>,
+    leaders: Arc<LeaderConnections>,
+    quotas: Arc<QuotaManager>,
+    quota_client: QuotaClient,
+    /// dead letters are produced on behalf of consumer, so they are authorized as its produce
+    auth: SpuAuthContext,
+    /// records are not sent before this time if consumer exceeded its quota
+    throttled_until: Option<Instant>,
 }
 
 impl StreamFetchHandler {
@@ -63,6 +79,29 @@ impl StreamFetchHandler {
         let (header, msg) = request.get_header_request();
         let replica = ReplicaKey::new(msg.topic.clone(), msg.partition);
 
+        if let Err(error_code) = conn_ctx
+            .auth()
+            .authorize(DataAction::Consume, &replica.topic)
+            .await
+        {
+            send_back_error(&sink, &replica, &header, 0, error_code).await?;
+            return Ok(());
+        }
+
+        // failed records are produced to dead-letter topics on behalf of consumer
+        for invocation in &msg.smartmodules {
+            if let SmartModuleErrorPolicy::DeadLetter(topic) = &invocation.error_policy {
+                if let Err(error_code) = conn_ctx.auth().authorize(DataAction::Produce, topic).await
+                {
+                    send_back_error(&sink, &replica, &header, 0, error_code).await?;
+                    return Ok(());
+                }
+            }
+        }
+
+        let quota_client = QuotaClient::new(conn_ctx.auth().principal(), header.client_id());
+        let auth = conn_ctx.auth().clone();
+
         if let Some(leader_state) = ctx.leaders_state().get(&replica).await {
             let (stream_id, offset_publisher) = conn_ctx
                 .stream_publishers_mut()
@@ -81,6 +120,8 @@ impl StreamFetchHandler {
                     replica,
                     consumer_offset_listener,
                     msg,
+                    quota_client,
+                    auth,
                 )
                 .await
                 {
@@ -98,6 +139,7 @@ impl StreamFetchHandler {
                     error_code: ErrorCode::NotLeaderForPartition,
                     ..Default::default()
                 },
+                throttle_time_ms: 0,
             };
 
             let response_msg =
@@ -116,7 +158,7 @@ impl StreamFetchHandler {
 
     #[allow(clippy::too_many_arguments)]
     #[instrument(
-        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener),
+        skip(ctx,replica,end_event,leader_state,header,msg,consumer_offset_listener,quota_client,auth),
         fields(
             replica = %replica,
             sink = sink.id()
@@ -132,11 +174,13 @@ impl StreamFetchHandler {
         replica: ReplicaKey,
         consumer_offset_listener: OffsetChangeListener,
         msg: StreamFetchRequest<FileRecordSet>,
+        quota_client: QuotaClient,
+        auth: SpuAuthContext,
     ) -> Result<(), SocketError> {
         debug!("request: {:#?}", msg);
         let version = header.api_version();
 
-        let derivedstream_ctx =
+        let mut derivedstream_ctx =
             match SmartModuleContext::try_from(msg.smartmodules, version, &ctx).await {
                 Ok(ctx) => ctx,
                 Err(error_code) => {
@@ -146,6 +190,42 @@ impl StreamFetchHandler {
                 }
             };
 
+        let mut starting_offset = msg.fetch_offset;
+
+        // only SmartModule chains have state to checkpoint
+        let checkpoint = match (&msg.checkpoint, derivedstream_ctx.as_mut()) {
+            (Some(name), Some(sm_ctx)) => {
+                let store = match CheckpointStore::stream(
+                    &ctx.config().smartmodule_checkpoint_dir(),
+                    name,
+                    &replica,
+                ) {
+                    Ok(store) => store,
+                    Err(error_code) => {
+                        send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
+                        return Ok(());
+                    }
+                };
+                if let Some(saved) = store.load().await {
+                    match sm_ctx.chain.restore_state(saved.state) {
+                        Ok(()) => {
+                            debug!(
+                                %name,
+                                offset = saved.offset,
+                                "resuming stream from checkpoint"
+                            );
+                            starting_offset = saved.offset;
+                        }
+                        Err(err) => {
+                            warn!(%name, %err, "ignoring checkpoint of different SmartModule chain")
+                        }
+                    }
+                }
+                Some(store)
+            }
+            _ => None,
+        };
+
         let max_bytes = msg.max_bytes as u32;
         // compute max fetch bytes depends on smart stream
         let max_fetch_bytes = if derivedstream_ctx.is_some() {
@@ -154,7 +234,6 @@ impl StreamFetchHandler {
             max_bytes
         };
 
-        let starting_offset = msg.fetch_offset;
         let isolation = msg.isolation;
 
         debug!(
@@ -178,9 +257,18 @@ impl StreamFetchHandler {
             leader_state,
             max_fetch_bytes,
             metrics: ctx.metrics(),
+            checkpoint,
+            leaders: ctx.leaders(),
+            quotas: ctx.quotas(),
+            quota_client,
+            auth,
+            throttled_until: None,
         };
 
-        if let Err(err) = handler.process(starting_offset, derivedstream_ctx).await {
+        if let Err(err) = handler
+            .process(msg.fetch_offset, starting_offset, derivedstream_ctx)
+            .await
+        {
             match err {
                 StreamFetchError::Fetch(error_code) => {
                     send_back_error(&sink, &replica, &header, stream_id, error_code).await?;
@@ -207,18 +295,29 @@ impl StreamFetchHandler {
 
     async fn process(
         mut self,
+        fetch_offset: Offset,
         starting_offset: Offset,
-        sm_ctx: Option<SmartModuleContext>,
+        mut sm_ctx: Option<SmartModuleContext>,
     ) -> Result<(), StreamFetchError> {
-        let mut smartmodule_instance = if let Some(ctx) = sm_ctx {
-            let SmartModuleContext { chain: st } = ctx;
-            Some(st)
-        } else {
-            None
-        };
+        // stream resumed from checkpoint may start at other offset than consumer requested,
+        // so consumer is told where stream starts before any records are sent
+        if self.checkpoint.is_some() {
+            self.send_start_offset(starting_offset).await?;
+            // consumer acknowledges start, records are sent after that so they are not sent twice
+            if starting_offset > 0 && !self.wait_start_ack().await {
+                debug!("end event has been received before start was acknowledged");
+                return Ok(());
+            }
+        }
+        if fetch_offset != starting_offset {
+            debug!(
+                fetch_offset,
+                starting_offset, "stream starts from checkpoint"
+            );
+        }
 
         let (mut last_partition_offset, consumer_wait) = self
-            .send_back_records(starting_offset, smartmodule_instance.as_mut())
+            .send_back_records(starting_offset, sm_ctx.as_mut())
             .await?;
 
         let mut leader_offset_receiver = self.leader_state.offset_listener(&self.isolation);
@@ -267,7 +366,7 @@ impl StreamFetchHandler {
                         last_partition_offset,
                         "Consumer offset updated and is behind, need to send records",
                     );
-                    let (offset, wait) = self.send_back_records(consumer_offset_update, smartmodule_instance.as_mut()).await?;
+                    let (offset, wait) = self.send_back_records(consumer_offset_update, sm_ctx.as_mut()).await?;
                     last_partition_offset = offset;
                     if wait {
                         last_known_consumer_offset = None;
@@ -308,7 +407,7 @@ impl StreamFetchHandler {
 
                     // We need to send the consumer all records since the last consumer offset
                     debug!(partition_offset_update, last_consumer_offset, "reading offset event");
-                    let (offset, wait) = self.send_back_records(last_consumer_offset, smartmodule_instance.as_mut()).await?;
+                    let (offset, wait) = self.send_back_records(last_consumer_offset, sm_ctx.as_mut()).await?;
                     last_partition_offset = offset;
                     if wait {
                         last_known_consumer_offset = None;
@@ -331,18 +430,75 @@ impl StreamFetchHandler {
         Ok(())
     }
 
+    /// send response without records which tells consumer offset where stream starts
+    async fn send_start_offset(&self, starting_offset: Offset) -> Result<(), StreamFetchError> {
+        type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
+
+        let (log_start_offset, _) = self.leader_state.start_offset_info().await;
+        let partition_response = DefaultPartitionResponse {
+            partition_index: self.replica.partition,
+            high_watermark: self.leader_state.hw(),
+            log_start_offset,
+            next_filter_offset: starting_offset,
+            ..Default::default()
+        };
+
+        let stream_response = StreamFetchResponse {
+            topic: self.replica.topic.clone(),
+            stream_id: self.stream_id,
+            partition: partition_response,
+            throttle_time_ms: 0,
+        };
+
+        let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
+            &self.header,
+            stream_response,
+        );
+
+        debug!(starting_offset, "sending start offset to consumer");
+        let mut inner_sink = self.sink.lock().await;
+        inner_sink
+            .send_response(&response_msg, self.header.api_version())
+            .await?;
+        Ok(())
+    }
+
+    /// wait until consumer acknowledges start offset, returns false if stream ended before
+    async fn wait_start_ack(&mut self) -> bool {
+        loop {
+            select! {
+                _ = self.end_event.listen() => return false,
+                consumer_offset_update = self.consumer_offset_listener.listen() => {
+                    if consumer_offset_update != INIT_OFFSET {
+                        debug!(consumer_offset_update, "consumer acknowledged start offset");
+                        return true;
+                    }
+                }
+            }
+        }
+    }
+
     /// send back records back to consumer
     /// return (next offset, consumer wait)
     //  consumer wait flag tells that there are records send back to consumer
     #[instrument(
-        skip(self, sm_chain),
+        skip(self, sm_ctx),
         fields(stream_id = self.stream_id)
     )]
     async fn send_back_records(
         &mut self,
         starting_offset: Offset,
-        sm_chain: Option<&mut SmartModuleChainInstance>,
+        sm_ctx: Option<&mut SmartModuleContext>,
     ) -> Result<(Offset, bool), StreamFetchError> {
+        // consumer which ignores throttle time is held back here
+        if let Some(throttled_until) = self.throttled_until.take() {
+            let wait = throttled_until.saturating_duration_since(Instant::now());
+            if !wait.is_zero() {
+                debug!(wait_ms = wait.as_millis() as u64, "throttling consumer");
+                sleep(wait).await;
+            }
+        }
+
         let now = Instant::now();
 
         let mut file_partition_response = FilePartitionResponse {
@@ -353,7 +509,7 @@ impl StreamFetchHandler {
         // Read records from the leader starting from `offset`
         // Returns with the HW/LEO of the latest records available in the leader
         // This describes the range of records that can be read in this request
-        let read_end_offset = match self
+        let (read_end_offset, aborted) = match self
             .leader_state
             .read_records(starting_offset, self.max_fetch_bytes, self.isolation)
             .await
@@ -365,7 +521,7 @@ impl StreamFetchHandler {
                 if let Some(file_slice) = slice.file_slice {
                     file_partition_response.records = file_slice.into();
                 }
-                slice.end
+                (slice.end, slice.aborted)
             }
             Err(err) => {
                 debug!(%err,"error reading records from leader");
@@ -391,8 +547,8 @@ impl StreamFetchHandler {
             return Ok((starting_offset, false));
         }
 
-        let (offset, wait, metrics_update) = match sm_chain {
-            Some(chain) => {
+        let (offset, wait, metrics_update) = match sm_ctx {
+            Some(sm_ctx) => {
                 // If a SmartModule is provided, we need to read records from file to memory
                 // In-memory records are then processed by SmartModule and returned to consumer
 
@@ -400,36 +556,112 @@ impl StreamFetchHandler {
                 let mut file_batch_iterator =
                     FileBatchIterator::from_raw_slice(records.raw_slice());
 
-                let (batch, smartmodule_error) = process_batch(
-                    chain,
-                    &mut file_batch_iterator,
+                // transaction markers and batches of aborted transactions are not processed
+                let mut transaction_filter = AbortedTransactionFilter::default();
+                transaction_filter.add(aborted);
+
+                // offset after last batch processed by chain
+                let mut processed_offset = None;
+                let mut processed_batches = file_batch_iterator
+                    .by_ref()
+                    .filter(|batch| match batch {
+                        Ok(batch) => transaction_filter.retain(&batch.batch),
+                        Err(_) => true,
+                    })
+                    .inspect(|batch| {
+                        if let Ok(batch) = batch {
+                            processed_offset =
+                                Some(batch.base_offset() + batch.offset_delta() as Offset + 1);
+                        }
+                    });
+
+                let chain_metrics = SmartModuleChainMetrics::default();
+                let processed = process_batch(
+                    &mut sm_ctx.chain,
+                    &mut processed_batches,
                     self.max_bytes as usize,
-                    self.metrics.chain_metrics(),
-                )
-                .map_err(|err| {
+                    &chain_metrics,
+                );
+                self.metrics
+                    .record_smartmodule(&sm_ctx.name, &chain_metrics);
+                let processed = processed.map_err(|err| {
                     StreamFetchError::Fetch(ErrorCode::Other(format!("SmartModule err {err}")))
                 })?;
-                let metrics_update = IncreaseValue::from(&batch);
+
+                // dead letters are sent before checkpoint, so they are not lost on failure
+                let dead_letters = sm_ctx.chain.take_dead_letters();
+                if !dead_letters.is_empty() {
+                    send_dead_letters(&self.leaders, &self.replica, dead_letters, Some(&self.auth))
+                        .await
+                        .map_err(|err| {
+                            StreamFetchError::Fetch(ErrorCode::Other(format!(
+                                "unable to send dead letters: {err}"
+                            )))
+                        })?;
+                }
+
+                // chain state covers every batch it processed, so it is checkpointed only when
+                // all of them are sent: batch dropped by max bytes or failed is processed again
+                let checkpoint = match processed.included_offset {
+                    Some(offset)
+                        if self.checkpoint.is_some()
+                            && processed.error.is_none()
+                            && processed_offset == Some(offset) =>
+                    {
+                        Some(SmartModuleCheckpoint {
+                            offset,
+                            state: sm_ctx.chain.state(),
+                        })
+                    }
+                    _ => None,
+                };
+                let metrics_update = IncreaseValue::from(&processed.batch);
+                let throttle = self.record_quota(metrics_update.bytes());
 
                 let (offset, wait) = self
                     .send_processed_response(
                         file_partition_response,
                         next_offset,
-                        batch,
-                        smartmodule_error,
+                        processed.batch,
+                        processed.error,
+                        throttle,
                     )
                     .await?;
+
+                if let (Some(store), Some(checkpoint)) = (&mut self.checkpoint, checkpoint) {
+                    match store.save(&checkpoint).await {
+                        Ok(true) => {
+                            // followers keep checkpoint so stream resumes from it after failover
+                            let followers = self
+                                .leader_state
+                                .followers_info()
+                                .await
+                                .into_keys()
+                                .collect::<Vec<_>>();
+                            self.leaders
+                                .replicate_checkpoint(&followers, store.replication(checkpoint))
+                                .await;
+                        }
+                        Ok(false) => {}
+                        Err(err) => error!(%err, "unable to checkpoint SmartModule state"),
+                    }
+                }
                 (offset, wait, metrics_update)
             }
             None => {
                 // If no SmartModule is provided, respond using raw file records
                 debug!("No SmartModule, sending back entire log");
+                if !aborted.is_empty() {
+                    file_partition_response.aborted = Some(aborted);
+                }
                 let metrics_update = IncreaseValue::from(&file_partition_response);
+                let throttle = self.record_quota(metrics_update.bytes());
 
                 let response = StreamFetchResponse {
                     topic: self.replica.topic.clone(),
                     stream_id: self.stream_id,
                     partition: file_partition_response,
+                    throttle_time_ms: throttle_time_ms(throttle),
                 };
 
                 let response_msg = RequestMessage::<FileStreamFetchRequest>::response_with_header(
@@ -455,12 +687,27 @@ impl StreamFetchHandler {
                 )
             }
         };
-        self.metrics
-            .outbound()
-            .increase_by_value(self.header.is_connector(), metrics_update);
+        self.metrics.record_fetch(
+            &self.replica,
+            self.header.client_id(),
+            self.header.is_connector(),
+            metrics_update,
+            now.elapsed(),
+        );
         Ok((offset, wait))
     }
 
+    /// record records sent to consumer against its quota, returns throttle time for consumer
+    fn record_quota(&mut self, bytes: u64) -> Duration {
+        let throttle = self
+            .quotas
+            .record(&self.quota_client, QuotaTraffic::Fetch, bytes);
+        if !throttle.is_zero() {
+            self.throttled_until = Some(Instant::now() + throttle);
+        }
+        throttle
+    }
+
     #[instrument(skip(self, file_partition_response, batch, smartmodule_error))]
     async fn send_processed_response(
         &self,
@@ -468,6 +715,7 @@ impl StreamFetchHandler {
         next_offset: Offset,
         batch: Batch,
         smartmodule_error: Option<SmartModuleTransformRuntimeError>,
+        throttle: Duration,
     ) -> Result<(Offset, bool), StreamFetchError> {
         type DefaultPartitionResponse = FetchablePartitionResponse<RecordSet<RawRecords>>;
 
@@ -500,13 +748,19 @@ impl StreamFetchHandler {
 
         //trace!("batch: {:#?}",batch);
 
-        let records = RecordSet::default().add(batch);
+        // consumer before record headers can't skip them
+        let record_version = if self.header.api_version() >= RECORD_HEADERS_API {
+            RECORD_HEADERS_VERSION
+        } else {
+            0
+        };
+        let records = RecordSet::default().add(batch.try_into_raw(record_version)?);
         let partition_response = DefaultPartitionResponse {
             partition_index: self.replica.partition,
             error_code,
             high_watermark: file_partition_response.high_watermark,
             log_start_offset: file_partition_response.log_start_offset,
-            records: records.try_into()?,
+            records,
             next_filter_offset,
             // we mark last offset in the response that we should sync up
             ..Default::default()
@@ -516,6 +770,7 @@ impl StreamFetchHandler {
             topic: self.replica.topic.clone(),
             stream_id: self.stream_id,
             partition: partition_response,
+            throttle_time_ms: throttle_time_ms(throttle),
         };
 
         let response_msg = RequestMessage::<DefaultStreamFetchRequest>::response_with_header(
@@ -552,6 +807,7 @@ async fn send_back_error(
         topic: replica.topic.clone(),
         stream_id,
         partition: partition_response,
+        throttle_time_ms: 0,
     };
 
     let response_msg =
@@ -492,3 +492,82 @@ async fn test_produce_metrics() {
     server_end_event.notify();
     debug!("terminated controller");
 }
+
+#[fluvio_future::test(ignore)]
+async fn test_produce_quota_throttle() {
+    use fluvio_controlplane_metadata::quota::{Quota, QuotaSpec, QuotaTarget};
+
+    let test_path = temp_dir().join("produce_quota_throttle");
+    ensure_clean_dir(&test_path);
+    let port = portpicker::pick_unused_port().expect("No free ports left");
+
+    let addr = format!("127.0.0.1:{port}");
+    let mut spu_config = SpuConfig::default();
+    spu_config.log.base_dir = test_path;
+    let ctx = GlobalContext::new_shared_context(spu_config);
+
+    let server_end_event = create_public_server(addr.to_owned(), ctx.clone()).run();
+
+    // wait for stream controller async to start
+    sleep(Duration::from_millis(100)).await;
+
+    let client_socket =
+        MultiplexerSocket::new(FluvioSocket::connect(&addr).await.expect("connect"));
+    let topic = "test_produce";
+    let test = Replica::new((topic, 0), 5001, vec![5001]);
+    let test_id = test.id.clone();
+    ctx.replica_localstore().sync_all(vec![test.clone()]);
+
+    let replica = LeaderReplicaState::create(test, ctx.config(), ctx.status_update_owned())
+        .await
+        .expect("replica");
+    ctx.leaders_state().insert(test_id, replica.clone()).await;
+
+    ctx.quota_localstore().sync_all(vec![Quota {
+        name: "slow-producer".to_owned(),
+        spec: QuotaSpec::new(QuotaTarget::ClientId {
+            client_id: "slow".to_owned(),
+        })
+        .with_produce_byte_rate(100),
+    }]);
+
+    let produce = |client_id: &str| {
+        let records = create_filter_records(10)
+            .try_into()
+            .expect("filter records");
+        let mut produce_request = DefaultProduceRequest::default();
+        produce_request.topics.push(TopicProduceData {
+            name: topic.to_owned(),
+            partitions: vec![DefaultPartitionRequest {
+                partition_index: 0,
+                records,
+            }],
+            ..Default::default()
+        });
+        RequestMessage::new_request(produce_request).set_client_id(client_id)
+    };
+
+    // records are written even if client is over quota
+    let response = client_socket
+        .send_and_receive(produce("slow"))
+        .await
+        .expect("produce");
+    let partition_response = response
+        .find_partition_response(topic, 0)
+        .expect("partition");
+    assert_eq!(partition_response.error_code, ErrorCode::None);
+    assert!(response.throttle_time_ms > 0);
+    let throttle = Duration::from_millis(response.throttle_time_ms as u64);
+
+    // connection which ignores throttle time is held back, clients without quota are not throttled
+    let start = std::time::Instant::now();
+    let response = client_socket
+        .send_and_receive(produce("fast"))
+        .await
+        .expect("produce");
+    assert!(start.elapsed() >= throttle / 2);
+    assert_eq!(response.throttle_time_ms, 0);
+
+    server_end_event.notify();
+    debug!("terminated controller");
+}
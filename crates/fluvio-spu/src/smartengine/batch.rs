@@ -12,6 +12,7 @@ use fluvio_protocol::{
     link::smartmodule::SmartModuleTransformRuntimeError,
 };
 use fluvio_smartengine::SmartModuleChainInstance;
+use fluvio_types::Timestamp;
 use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
 
 pub(crate) trait SmartModuleInputBatch {
@@ -21,21 +22,33 @@ pub(crate) trait SmartModuleInputBatch {
 
     fn offset_delta(&self) -> i32;
 
+    fn base_timestamp(&self) -> Timestamp;
+
     fn get_compression(&self) -> Result<Compression, CompressionError>;
 }
 
+/// Output of SmartModule chain for input batches
+pub(crate) struct ProcessedBatch {
+    pub(crate) batch: Batch,
+    pub(crate) error: Option<SmartModuleTransformRuntimeError>,
+    /// offset after last input batch whose output is fully in batch. Input batch dropped because
+    /// of max bytes or failed in SmartModule is not included although chain has processed it
+    pub(crate) included_offset: Option<Offset>,
+}
+
 #[instrument(skip(sm_chain_instance, input_batches, max_bytes, metric))]
 pub(crate) fn process_batch<R: SmartModuleInputBatch>(
     sm_chain_instance: &mut SmartModuleChainInstance,
     input_batches: &mut impl Iterator<Item = Result<R, IoError>>,
     max_bytes: usize,
     metric: &SmartModuleChainMetrics,
-) -> Result<(Batch, Option<SmartModuleTransformRuntimeError>), Error> {
+) -> Result<ProcessedBatch, Error> {
     let mut smartmodule_batch = Batch::<MemoryRecords>::default();
     smartmodule_batch.base_offset = -1; // indicate this is uninitialized
     smartmodule_batch.set_offset_delta(-1); // make add_to_offset_delta correctly
 
     let mut total_bytes = 0;
+    let mut included_offset = None;
 
     for batch_result in input_batches {
         let input_batch = batch_result?;
@@ -51,7 +64,9 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
 
         let now = Instant::now();
 
-        let input = SmartModuleInput::new(input_batch.records().clone(), input_batch.base_offset());
+        let mut input =
+            SmartModuleInput::new(input_batch.records().clone(), input_batch.base_offset());
+        input.set_base_timestamp(input_batch.base_timestamp());
 
         let output = sm_chain_instance.process(input, metric)?;
 
@@ -90,7 +105,11 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
                     total_bytes = total_bytes + record_bytes,
                     max_bytes, "Total SmartModuleInstance bytes reached"
                 );
-                return Ok((smartmodule_batch, maybe_error));
+                return Ok(ProcessedBatch {
+                    batch: smartmodule_batch,
+                    error: maybe_error,
+                    included_offset,
+                });
             }
 
             total_bytes += record_bytes;
@@ -113,8 +132,14 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
 
         // If we had a processing error, return current batch and error
         if maybe_error.is_some() {
-            return Ok((smartmodule_batch, maybe_error));
+            return Ok(ProcessedBatch {
+                batch: smartmodule_batch,
+                error: maybe_error,
+                included_offset,
+            });
         }
+        included_offset =
+            Some(input_batch.base_offset() + input_batch.offset_delta() as Offset + 1);
     }
 
     debug!(
@@ -122,7 +147,11 @@ pub(crate) fn process_batch<R: SmartModuleInputBatch>(
         "No more batches, SmartModuleInstance end"
     );
 
-    Ok((smartmodule_batch, None))
+    Ok(ProcessedBatch {
+        batch: smartmodule_batch,
+        error: None,
+        included_offset,
+    })
 }
 
 fn set_compression(
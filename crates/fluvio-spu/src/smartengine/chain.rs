@@ -2,7 +2,7 @@ use tracing::{debug, error};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_smartengine::{
     SmartEngine, SmartModuleChainBuilder, SmartModuleChainInstance, SmartModuleConfig,
-    SmartModuleInitialData,
+    SmartModuleInitialData, SmartModuleLimits,
 };
 use fluvio_spu_schema::server::smartmodule::{
     SmartModuleContextData, SmartModuleInvocation, SmartModuleKind,
@@ -12,6 +12,7 @@ pub(crate) fn build_chain(
     invocations: Vec<SmartModuleInvocation>,
     version: i16,
     engine: SmartEngine,
+    limits: SmartModuleLimits,
 ) -> Result<SmartModuleChainInstance, ErrorCode> {
     let mut chain_builder = SmartModuleChainBuilder::default();
     for invocation in invocations {
@@ -32,6 +33,9 @@ pub(crate) fn build_chain(
             SmartModuleKind::Generic(SmartModuleContextData::Aggregate { ref accumulator }) => {
                 SmartModuleInitialData::with_aggregate(accumulator.clone())
             }
+            SmartModuleKind::Generic(SmartModuleContextData::Window(ref config)) => {
+                SmartModuleInitialData::with_window(config.clone())
+            }
             _ => SmartModuleInitialData::default(),
         };
 
@@ -41,6 +45,8 @@ pub(crate) fn build_chain(
                 .params(invocation.params)
                 .version(version)
                 .initial_data(initial_data)
+                .limits(limits)
+                .error_policy(invocation.error_policy)
                 .build()
                 .map_err(|err| ErrorCode::SmartModuleInvalid {
                     error: err.to_string(),
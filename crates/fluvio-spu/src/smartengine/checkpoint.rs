@@ -0,0 +1,210 @@
+use std::fs;
+use std::io::{Cursor, Error as IoError};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use blocking::unblock;
+use tracing::{debug, warn};
+
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::Offset;
+use fluvio_protocol::{Decoder, Encoder};
+
+use crate::services::internal::ReplicateCheckpointRequest;
+
+/// checkpoints of transforms are kept apart from named streams so names don't clash
+const TRANSFORM_SUBDIR: &str = "transforms";
+
+/// checkpoint is saved at most once per interval unless offset advanced by `CHECKPOINT_MAX_RECORDS`.
+/// Records processed since last checkpoint are processed again after restart
+const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(1);
+const CHECKPOINT_MAX_RECORDS: Offset = 10_000;
+
+/// State of SmartModule chain together with offset where processing continues
+#[derive(Debug, Default, Clone, PartialEq, Eq, Encoder, Decoder)]
+pub struct SmartModuleCheckpoint {
+    /// offset of next record to process
+    pub offset: Offset,
+    /// state of each SmartModule in chain, none for stateless ones
+    pub state: Vec<Option<Vec<u8>>>,
+}
+
+/// Checkpoints of named stream or transform of single partition, kept on SPU disk.
+/// Leader replicates saved checkpoints to followers, so new leader resumes from them
+#[derive(Debug, Clone)]
+pub(crate) struct CheckpointStore {
+    name: String,
+    replica: ReplicaKey,
+    transform: bool,
+    path: PathBuf,
+    /// time and offset of last checkpoint saved by this store
+    last_saved: Option<(Instant, Offset)>,
+}
+
+impl CheckpointStore {
+    /// checkpoints of named stream
+    pub(crate) fn stream(dir: &Path, name: &str, replica: &ReplicaKey) -> Result<Self, ErrorCode> {
+        Self::new(dir, name, replica, false)
+    }
+
+    /// checkpoints of transform
+    pub(crate) fn transform(
+        dir: &Path,
+        name: &str,
+        replica: &ReplicaKey,
+    ) -> Result<Self, ErrorCode> {
+        Self::new(dir, name, replica, true)
+    }
+
+    /// store of checkpoint replicated by leader
+    pub(crate) fn replicated(
+        dir: &Path,
+        request: &ReplicateCheckpointRequest,
+    ) -> Result<Self, ErrorCode> {
+        Self::new(dir, &request.name, &request.replica, request.transform)
+    }
+
+    fn new(
+        dir: &Path,
+        name: &str,
+        replica: &ReplicaKey,
+        transform: bool,
+    ) -> Result<Self, ErrorCode> {
+        let valid = !name.is_empty()
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(ErrorCode::Other(format!("invalid checkpoint name: {name}")));
+        }
+        let dir = if transform {
+            dir.join(TRANSFORM_SUBDIR)
+        } else {
+            dir.to_path_buf()
+        };
+        Ok(Self {
+            name: name.to_owned(),
+            replica: replica.clone(),
+            transform,
+            path: dir
+                .join(name)
+                .join(format!("{}-{}", replica.topic, replica.partition)),
+            last_saved: None,
+        })
+    }
+
+    /// last checkpoint, none if stream was never checkpointed
+    pub(crate) async fn load(&self) -> Option<SmartModuleCheckpoint> {
+        let path = self.path.clone();
+        let bytes = unblock(move || fs::read(path)).await.ok()?;
+        match SmartModuleCheckpoint::decode_from(&mut Cursor::new(bytes), 0) {
+            Ok(checkpoint) => {
+                debug!(path = %self.path.display(), offset = checkpoint.offset, "loaded checkpoint");
+                Some(checkpoint)
+            }
+            Err(err) => {
+                warn!(path = %self.path.display(), %err, "discarding invalid checkpoint");
+                None
+            }
+        }
+    }
+
+    /// save checkpoint unless one was saved recently, returns true if it was saved
+    pub(crate) async fn save(
+        &mut self,
+        checkpoint: &SmartModuleCheckpoint,
+    ) -> Result<bool, IoError> {
+        if let Some((saved_at, saved_offset)) = self.last_saved {
+            if saved_at.elapsed() < CHECKPOINT_INTERVAL
+                && checkpoint.offset - saved_offset < CHECKPOINT_MAX_RECORDS
+            {
+                return Ok(false);
+            }
+        }
+        self.write(checkpoint).await?;
+        self.last_saved = Some((Instant::now(), checkpoint.offset));
+        Ok(true)
+    }
+
+    /// write checkpoint to disk
+    pub(crate) async fn write(&self, checkpoint: &SmartModuleCheckpoint) -> Result<(), IoError> {
+        let mut bytes = vec![];
+        checkpoint.encode(&mut bytes, 0)?;
+        let path = self.path.clone();
+        unblock(move || {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            // write to temporary file first so partial checkpoint is never loaded
+            let tmp = path.with_extension("tmp");
+            fs::write(&tmp, bytes)?;
+            fs::rename(&tmp, &path)
+        })
+        .await?;
+        debug!(path = %self.path.display(), offset = checkpoint.offset, "saved checkpoint");
+        Ok(())
+    }
+
+    /// request to replicate checkpoint to followers of partition
+    pub(crate) fn replication(
+        &self,
+        checkpoint: SmartModuleCheckpoint,
+    ) -> ReplicateCheckpointRequest {
+        ReplicateCheckpointRequest {
+            name: self.name.clone(),
+            replica: self.replica.clone(),
+            transform: self.transform,
+            checkpoint,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_controlplane_metadata::partition::ReplicaKey;
+
+    use super::{CheckpointStore, SmartModuleCheckpoint};
+
+    #[fluvio_future::test]
+    async fn test_checkpoint_store() {
+        let dir = std::env::temp_dir().join("fluvio-spu-checkpoint-test");
+        let _ = std::fs::remove_dir_all(&dir);
+        let replica = ReplicaKey::new("totals", 0);
+
+        let mut store = CheckpointStore::stream(&dir, "daily-sum", &replica).expect("store");
+        assert!(store.load().await.is_none());
+
+        let checkpoint = SmartModuleCheckpoint {
+            offset: 42,
+            state: vec![None, Some(b"100".to_vec())],
+        };
+        assert!(store.save(&checkpoint).await.expect("save"));
+        assert_eq!(store.load().await, Some(checkpoint.clone()));
+
+        // checkpoint right after previous one is skipped
+        let next = SmartModuleCheckpoint {
+            offset: 43,
+            ..checkpoint.clone()
+        };
+        assert!(!store.save(&next).await.expect("save"));
+        assert_eq!(store.load().await, Some(checkpoint.clone()));
+
+        // other partition and transform of same name have own checkpoints
+        let other = CheckpointStore::stream(&dir, "daily-sum", &ReplicaKey::new("totals", 1))
+            .expect("store");
+        assert!(other.load().await.is_none());
+        let transform = CheckpointStore::transform(&dir, "daily-sum", &replica).expect("store");
+        assert!(transform.load().await.is_none());
+
+        // replicated checkpoint is written to same place as on leader
+        let replicated =
+            CheckpointStore::replicated(&dir, &store.replication(next.clone())).expect("store");
+        replicated.write(&next).await.expect("write");
+        assert_eq!(store.load().await, Some(next));
+
+        assert!(CheckpointStore::stream(&dir, "../escape", &replica).is_err());
+        assert!(CheckpointStore::stream(&dir, "", &replica).is_err());
+    }
+}
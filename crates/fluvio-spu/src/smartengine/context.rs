@@ -9,6 +9,8 @@ use crate::smartengine::chain;
 
 pub struct SmartModuleContext {
     pub chain: SmartModuleChainInstance,
+    /// name of chain used to label metrics
+    pub name: String,
 }
 
 impl SmartModuleContext {
@@ -30,17 +32,36 @@ impl SmartModuleContext {
             return Ok(None);
         }
 
+        let name = chain_name(&invocations);
         let mut fetched_invocations = Vec::with_capacity(invocations.len());
         for invocation in invocations {
             fetched_invocations.push(resolve_invocation(invocation, ctx)?)
         }
 
         Ok(Some(Self {
-            chain: chain::build_chain(fetched_invocations, version, ctx.smartengine_owned())?,
+            chain: chain::build_chain(
+                fetched_invocations,
+                version,
+                ctx.smartengine_owned(),
+                ctx.config().smartmodule_limits,
+            )?,
+            name,
         }))
     }
 }
 
+/// predefined smartmodules are named by their name, ad-hoc ones as `adhoc`
+fn chain_name(invocations: &[SmartModuleInvocation]) -> String {
+    invocations
+        .iter()
+        .map(|invocation| match &invocation.wasm {
+            SmartModuleInvocationWasm::Predefined(name) => name.as_str(),
+            SmartModuleInvocationWasm::AdHoc(_) => "adhoc",
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
 fn resolve_invocation(
     invocation: SmartModuleInvocation,
     ctx: &DefaultSharedGlobalContext,
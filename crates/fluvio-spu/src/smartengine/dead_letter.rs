@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use anyhow::{anyhow, Result};
+use tracing::debug;
+
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_protocol::record::{Batch, Record};
+use fluvio_smartengine::SmartModuleDeadLetter;
+
+use fluvio_auth::DataAction;
+
+use crate::core::LeaderConnections;
+use crate::services::auth::SpuAuthContext;
+
+/// headers of dead-lettered record, describing why and where from it was dead-lettered
+pub(crate) const ERROR_HEADER: &str = "smartmodule-error";
+pub(crate) const SOURCE_TOPIC_HEADER: &str = "source-topic";
+pub(crate) const SOURCE_PARTITION_HEADER: &str = "source-partition";
+pub(crate) const SOURCE_OFFSET_HEADER: &str = "source-offset";
+
+/// produce records failed with dead-letter policy to their topics.
+/// Records of source partition are produced to same mapped partition so they keep their order.
+/// Peer leaders trust records sent to their private endpoint, so records produced on behalf
+/// of consumer are authorized against its connection here, as policy may change during stream.
+pub(crate) async fn send_dead_letters(
+    leaders: &LeaderConnections,
+    source: &ReplicaKey,
+    dead_letters: Vec<SmartModuleDeadLetter>,
+    consumer: Option<&SpuAuthContext>,
+) -> Result<()> {
+    let mut by_topic: BTreeMap<String, Vec<Record>> = BTreeMap::new();
+    for dead_letter in dead_letters {
+        by_topic
+            .entry(dead_letter.topic.clone())
+            .or_default()
+            .push(dead_letter_record(source, dead_letter));
+    }
+
+    if let Some(auth) = consumer {
+        for topic in by_topic.keys() {
+            auth.authorize(DataAction::Produce, topic)
+                .await
+                .map_err(|error_code| anyhow!("dead-letter topic {topic}: {error_code}"))?;
+        }
+    }
+
+    for (topic, records) in by_topic {
+        let target = leaders
+            .mapped_partition(&topic, source.partition)
+            .ok_or_else(|| anyhow!("dead-letter topic {topic} not found"))?;
+        debug!(%target, records = records.len(), "sending dead letters");
+        leaders.produce(&target, Batch::from(records)).await?;
+    }
+    Ok(())
+}
+
+fn dead_letter_record(source: &ReplicaKey, dead_letter: SmartModuleDeadLetter) -> Record {
+    let error = dead_letter.error;
+    Record {
+        key: error.record_key,
+        value: error.record_value,
+        ..Default::default()
+    }
+    .with_header(ERROR_HEADER, error.hint)
+    .with_header(SOURCE_TOPIC_HEADER, source.topic.clone())
+    .with_header(SOURCE_PARTITION_HEADER, source.partition.to_string())
+    .with_header(SOURCE_OFFSET_HEADER, error.offset.to_string())
+}
+
+#[cfg(test)]
+mod test {
+
+    use fluvio_controlplane_metadata::partition::ReplicaKey;
+    use fluvio_protocol::link::smartmodule::{SmartModuleKind, SmartModuleTransformRuntimeError};
+    use fluvio_smartengine::SmartModuleDeadLetter;
+
+    use super::{
+        dead_letter_record, ERROR_HEADER, SOURCE_OFFSET_HEADER, SOURCE_PARTITION_HEADER,
+        SOURCE_TOPIC_HEADER,
+    };
+
+    #[test]
+    fn test_dead_letter_record() {
+        let error = SmartModuleTransformRuntimeError {
+            hint: "invalid value".to_string(),
+            offset: 40,
+            kind: SmartModuleKind::Map,
+            record_key: Some("key".into()),
+            record_value: "bad".into(),
+            ..Default::default()
+        };
+        let record = dead_letter_record(
+            &ReplicaKey::new("orders", 2),
+            SmartModuleDeadLetter {
+                topic: "orders-errors".to_string(),
+                error,
+            },
+        );
+
+        assert_eq!(record.value.as_ref(), b"bad");
+        assert_eq!(
+            record.key.as_ref().map(|key| key.as_ref()),
+            Some(&b"key"[..])
+        );
+        assert_eq!(
+            record
+                .header(SOURCE_TOPIC_HEADER)
+                .map(|value| value.as_ref()),
+            Some(&b"orders"[..])
+        );
+        assert_eq!(
+            record
+                .header(SOURCE_PARTITION_HEADER)
+                .map(|value| value.as_ref()),
+            Some(&b"2"[..])
+        );
+        assert_eq!(
+            record
+                .header(SOURCE_OFFSET_HEADER)
+                .map(|value| value.as_ref()),
+            Some(&b"40"[..])
+        );
+        assert_eq!(
+            record.header(ERROR_HEADER).map(|value| value.as_ref()),
+            Some(&b"invalid value"[..])
+        );
+    }
+}
@@ -7,6 +7,7 @@ use nix::sys::uio::pread;
 use fluvio_protocol::record::{Batch, Offset, BATCH_FILE_HEADER_SIZE, BATCH_HEADER_SIZE};
 use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_compression::{Compression, CompressionError};
+use fluvio_types::Timestamp;
 
 use super::batch::SmartModuleInputBatch;
 
@@ -29,6 +30,10 @@ impl SmartModuleInputBatch for FileBatch {
         self.batch.header.last_offset_delta
     }
 
+    fn base_timestamp(&self) -> Timestamp {
+        self.batch.header.first_timestamp
+    }
+
     fn get_compression(&self) -> Result<Compression, CompressionError> {
         self.batch.get_compression()
     }
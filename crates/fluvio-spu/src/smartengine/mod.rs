@@ -2,4 +2,6 @@ pub(crate) mod batch;
 pub(crate) mod file_batch;
 pub(crate) mod produce_batch;
 pub(crate) mod context;
+pub(crate) mod checkpoint;
+pub(crate) mod dead_letter;
 mod chain;
@@ -3,6 +3,7 @@ use fluvio_protocol::record::{Batch, RawRecords, Offset};
 
 use super::batch::SmartModuleInputBatch;
 use fluvio_compression::{Compression, CompressionError};
+use fluvio_types::Timestamp;
 
 #[derive(Debug)]
 pub struct ProduceBatch<'a> {
@@ -29,6 +30,10 @@ impl<'a> SmartModuleInputBatch for ProduceBatch<'a> {
         self.batch.header.last_offset_delta
     }
 
+    fn base_timestamp(&self) -> Timestamp {
+        self.batch.header.first_timestamp
+    }
+
     fn get_compression(&self) -> Result<Compression, CompressionError> {
         self.batch.get_compression()
     }
@@ -7,6 +7,9 @@ use crate::services::public::{SpuPublicServer, create_public_server};
 use crate::core::DefaultSharedGlobalContext;
 use crate::core::GlobalContext;
 use crate::control_plane::ScDispatcher;
+use crate::transform::TransformController;
+use crate::replication::leader::InSyncReplicasMonitor;
+use crate::replication::leader::TransactionMonitor;
 
 type FileReplicaContext = GlobalContext<FileReplica>;
 
@@ -91,6 +94,10 @@ pub fn create_services(
     let sc_dispatcher = ScDispatcher::new(ctx.clone());
     sc_dispatcher.run();
 
+    TransformController::start(ctx.clone());
+    InSyncReplicasMonitor::start(ctx.clone());
+    TransactionMonitor::start(ctx.clone());
+
     (ctx, internal_server, public_server)
 }
 
@@ -102,15 +109,25 @@ mod proxy {
 
     use flv_util::print_cli_err;
     use fluvio_future::openssl::TlsAcceptor;
+    use fluvio_auth::x509::X509Authenticator;
     use crate::config::SpuConfig;
-    use flv_tls_proxy::start as proxy_start;
+    use flv_tls_proxy::{
+        start as proxy_start, start_with_authenticator as proxy_start_with_authenticator,
+    };
 
     pub async fn start_proxy(config: SpuConfig, acceptor: (TlsAcceptor, String)) {
         let (tls_acceptor, proxy_addr) = acceptor;
         let target = config.public_endpoint;
         info!("starting TLS proxy: {}", proxy_addr);
 
-        if let Err(err) = proxy_start(&proxy_addr, tls_acceptor, target).await {
+        let result = if let Some(x509_auth_scopes) = config.x509_auth_scopes {
+            let authenticator = Box::new(X509Authenticator::new(&x509_auth_scopes));
+            proxy_start_with_authenticator(&proxy_addr, tls_acceptor, target, authenticator).await
+        } else {
+            proxy_start(&proxy_addr, tls_acceptor, target).await
+        };
+
+        if let Err(err) = result {
             print_cli_err!(err);
             process::exit(-1);
         } else {
@@ -14,6 +14,7 @@ use fluvio_protocol::record::{Offset, RecordSet};
 use fluvio_protocol::link::ErrorCode;
 use fluvio_storage::{ReplicaStorage, StorageError, OffsetInfo, ReplicaSlice};
 use fluvio_types::{event::offsets::OffsetChangeListener};
+use fluvio_types::Timestamp;
 use fluvio_types::event::offsets::OffsetPublisher;
 
 pub const REMOVAL_START: Offset = -1000; // indicate that storage about to be removed
@@ -102,6 +103,25 @@ where
         (reader.get_log_start_offset(), reader.get_hw())
     }
 
+    /// find first offset with records at or after timestamp
+    pub async fn find_offset_by_timestamp(
+        &self,
+        timestamp: Timestamp,
+    ) -> Result<Option<Offset>, ErrorCode> {
+        let reader = self.read().await;
+        reader.find_offset_by_timestamp(timestamp).await
+    }
+
+    /// apply updated configuration to storage
+    pub async fn update_config(&self, config: &S::ReplicaConfig) {
+        self.read().await.update_config(config);
+    }
+
+    /// mark storage as leader's, so it uploads segments to remote tier
+    pub async fn set_leader(&self, leader: bool) {
+        self.read().await.set_leader(leader);
+    }
+
     /// read records into partition response
     /// return leo and hw
     #[instrument(skip(self, offset, max_len, isolation))]
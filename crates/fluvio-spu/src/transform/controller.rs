@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use tracing::{debug, info, instrument};
+
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::transform::TransformSpec;
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_types::event::StickyEvent;
+
+use crate::core::DefaultSharedGlobalContext;
+
+use super::task::TransformTask;
+
+/// how often running tasks are reconciled with transforms and leaders of this SPU
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
+/// transform task is keyed by transform name and source partition
+type TaskKey = (String, ReplicaKey);
+
+struct RunningTask {
+    spec: TransformSpec,
+    end_event: Arc<StickyEvent>,
+}
+
+/// Runs transform task for each source partition led by this SPU.
+/// Tasks are stopped when transform is changed or deleted or leadership moves to other SPU.
+pub struct TransformController {
+    ctx: DefaultSharedGlobalContext,
+    tasks: HashMap<TaskKey, RunningTask>,
+}
+
+impl TransformController {
+    pub fn start(ctx: DefaultSharedGlobalContext) {
+        let controller = Self {
+            ctx,
+            tasks: HashMap::new(),
+        };
+        spawn(controller.dispatch_loop());
+    }
+
+    async fn dispatch_loop(mut self) {
+        info!("starting transform controller");
+        loop {
+            self.sync_tasks().await;
+            sleep(SYNC_INTERVAL).await;
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn sync_tasks(&mut self) {
+        let leaders: Vec<ReplicaKey> = self
+            .ctx
+            .leaders_state()
+            .read()
+            .await
+            .keys()
+            .cloned()
+            .collect();
+
+        let mut desired: HashMap<TaskKey, TransformSpec> = HashMap::new();
+        for transform in self.ctx.transform_localstore().all_values() {
+            for replica in leaders
+                .iter()
+                .filter(|replica| replica.topic == transform.spec.source)
+            {
+                desired.insert(
+                    (transform.name.clone(), replica.clone()),
+                    transform.spec.clone(),
+                );
+            }
+        }
+
+        self.tasks.retain(|(name, replica), task| {
+            let keep = desired.get(&(name.clone(), replica.clone())) == Some(&task.spec);
+            if !keep {
+                info!(%name, %replica, "stopping transform");
+                task.end_event.notify();
+            }
+            keep
+        });
+
+        for ((name, replica), spec) in desired {
+            let key = (name, replica);
+            if self.tasks.contains_key(&key) {
+                continue;
+            }
+            let (name, replica) = key.clone();
+            info!(%name, %replica, "starting transform");
+            let end_event = StickyEvent::shared();
+            TransformTask::start(
+                self.ctx.clone(),
+                name,
+                spec.clone(),
+                replica,
+                end_event.clone(),
+            );
+            self.tasks.insert(key, RunningTask { spec, end_event });
+        }
+
+        debug!(tasks = self.tasks.len(), "synced transform tasks");
+    }
+}
@@ -0,0 +1,12 @@
+//!
+//! # Transforms
+//!
+//! Transform continuously reads records of source topic, runs them through SmartModule chain
+//! and produces output into target topic. Each source partition is processed by its leader,
+//! which checkpoints offset and state of chain so processing resumes after restart.
+//!
+
+mod controller;
+mod task;
+
+pub use controller::TransformController;
@@ -0,0 +1,270 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use tokio::select;
+use tracing::{debug, error, info, instrument, warn};
+
+use fluvio_controlplane::TransformOffset;
+use fluvio_controlplane_metadata::partition::ReplicaKey;
+use fluvio_controlplane_metadata::transform::{TransformSpec, TransformStep};
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::api::Request;
+use fluvio_protocol::record::{Batch, Offset};
+use fluvio_smartengine::metrics::SmartModuleChainMetrics;
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::fetch::AbortedTransactionFilter;
+use fluvio_spu_schema::server::smartmodule::{
+    SmartModuleContextData, SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind,
+};
+use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchRequest;
+use fluvio_types::event::StickyEvent;
+
+use crate::core::DefaultSharedGlobalContext;
+use crate::replication::leader::SharedFileLeaderState;
+use crate::smartengine::batch::{process_batch, SmartModuleInputBatch};
+use crate::smartengine::checkpoint::{CheckpointStore, SmartModuleCheckpoint};
+use crate::smartengine::context::SmartModuleContext;
+use crate::smartengine::dead_letter::send_dead_letters;
+use crate::smartengine::file_batch::FileBatchIterator;
+
+/// wait before restarting task which failed, e.g. SmartModule or target leader is not available yet
+const RETRY_INTERVAL: Duration = Duration::from_secs(5);
+
+/// max bytes of source records read at once
+const MAX_READ_BYTES: u32 = 1_048_576;
+
+/// Runs transform for single source partition led by this SPU
+pub(crate) struct TransformTask {
+    ctx: DefaultSharedGlobalContext,
+    name: String,
+    spec: TransformSpec,
+    replica: ReplicaKey,
+    end_event: Arc<StickyEvent>,
+}
+
+impl TransformTask {
+    pub(crate) fn start(
+        ctx: DefaultSharedGlobalContext,
+        name: String,
+        spec: TransformSpec,
+        replica: ReplicaKey,
+        end_event: Arc<StickyEvent>,
+    ) {
+        let task = Self {
+            ctx,
+            name,
+            spec,
+            replica,
+            end_event,
+        };
+        spawn(task.dispatch_loop());
+    }
+
+    #[instrument(skip(self), fields(name = %self.name, replica = %self.replica))]
+    async fn dispatch_loop(self) {
+        while !self.end_event.is_set() {
+            match self.process().await {
+                Ok(()) => break,
+                Err(err) => {
+                    error!(%err, "transform failed, retrying");
+                    select! {
+                        _ = self.end_event.listen() => break,
+                        _ = sleep(RETRY_INTERVAL) => {}
+                    }
+                }
+            }
+        }
+        info!("transform task terminated");
+    }
+
+    /// transform records until end event is received
+    async fn process(&self) -> Result<()> {
+        let leader = self
+            .ctx
+            .leaders_state()
+            .get(&self.replica)
+            .await
+            .ok_or_else(|| anyhow!("not leader of {}", self.replica))?;
+
+        let invocations = self.spec.transforms.iter().map(invocation).collect();
+        let mut sm_ctx = SmartModuleContext::try_from(
+            invocations,
+            DefaultStreamFetchRequest::DEFAULT_API_VERSION,
+            &self.ctx,
+        )
+        .await?
+        .ok_or_else(|| anyhow!("transform has no SmartModule"))?;
+
+        // checkpoint holds SmartModule state and is replicated to followers, offset is also
+        // committed to SC so that new leader of source partition resumes from it
+        let mut checkpoint = CheckpointStore::transform(
+            &self.ctx.config().smartmodule_checkpoint_dir(),
+            &self.name,
+            &self.replica,
+        )?;
+        let committed = self.committed_offset();
+        let mut offset = match checkpoint.load().await {
+            // sc offset lags behind checkpoint saved on this spu
+            Some(saved) if committed.map_or(true, |committed| saved.offset >= committed) => {
+                if let Err(err) = sm_ctx.chain.restore_state(saved.state) {
+                    // records before checkpoint were already produced, so only state is reset
+                    warn!(%err, "discarding state of different SmartModule chain");
+                }
+                saved.offset
+            }
+            saved => {
+                if saved.is_some() {
+                    // checkpoint of other leader was not replicated here, its state is not available
+                    warn!(
+                        ?committed,
+                        "local checkpoint is behind committed offset, resetting state"
+                    );
+                }
+                match committed {
+                    Some(committed) => committed,
+                    None => leader.start_offset_info().await.0,
+                }
+            }
+        };
+        debug!(offset, ?committed, "starting transform");
+
+        let mut leader_offset_listener = leader.offset_listener(&Isolation::ReadCommitted);
+        loop {
+            let next_offset = self
+                .transform_records(&leader, &mut sm_ctx, &mut checkpoint, offset)
+                .await?;
+
+            if next_offset == offset {
+                select! {
+                    _ = self.end_event.listen() => return Ok(()),
+                    leader_offset = leader_offset_listener.listen() => {
+                        debug!(leader_offset, "received leader update");
+                    }
+                }
+            } else if self.end_event.is_set() {
+                return Ok(());
+            }
+            offset = next_offset;
+        }
+    }
+
+    /// transform records from offset and produce them to target, returns offset to continue from
+    async fn transform_records(
+        &self,
+        leader: &SharedFileLeaderState,
+        sm_ctx: &mut SmartModuleContext,
+        checkpoint: &mut CheckpointStore,
+        offset: Offset,
+    ) -> Result<Offset> {
+        let slice = leader
+            .read_records(offset, MAX_READ_BYTES, Isolation::ReadCommitted)
+            .await?;
+        let file_slice = match slice.file_slice {
+            Some(file_slice) => file_slice,
+            None => return Ok(offset),
+        };
+
+        let mut file_batch_iterator = FileBatchIterator::from_raw_slice(file_slice);
+        let mut transaction_filter = AbortedTransactionFilter::default();
+        transaction_filter.add(slice.aborted);
+        let mut processed_offset = None;
+        let mut processed_batches = file_batch_iterator
+            .by_ref()
+            .inspect(|batch| {
+                if let Ok(batch) = batch {
+                    processed_offset =
+                        Some(batch.base_offset() + batch.offset_delta() as Offset + 1);
+                }
+            })
+            .filter(|batch| match batch {
+                Ok(batch) => transaction_filter.retain(&batch.batch),
+                Err(_) => true,
+            });
+
+        let chain_metrics = SmartModuleChainMetrics::default();
+        let processed = process_batch(
+            &mut sm_ctx.chain,
+            &mut processed_batches,
+            usize::MAX,
+            &chain_metrics,
+        )?;
+        self.ctx
+            .metrics()
+            .record_smartmodule(&sm_ctx.name, &chain_metrics);
+        // records failed with skip or dead-letter policy are already dropped by chain, so error
+        // here is from fail policy: nothing is produced and task is retried from checkpoint
+        if let Some(err) = processed.error {
+            return Err(err.into());
+        }
+        let batch = processed.batch;
+
+        let next_offset = match processed_offset {
+            Some(next_offset) => next_offset,
+            None => return Ok(offset),
+        };
+
+        if !batch.records().is_empty() {
+            self.produce(batch).await?;
+        }
+        let dead_letters = sm_ctx.chain.take_dead_letters();
+        if !dead_letters.is_empty() {
+            // dead-letter topics of transform are part of its spec, created by admin through SC
+            send_dead_letters(&self.ctx.leaders(), &self.replica, dead_letters, None).await?;
+        }
+
+        // checkpoint only after records are produced, so they are produced at least once
+        let saved = SmartModuleCheckpoint {
+            offset: next_offset,
+            state: sm_ctx.chain.state(),
+        };
+        if checkpoint.save(&saved).await? {
+            let followers = leader
+                .followers_info()
+                .await
+                .into_keys()
+                .collect::<Vec<_>>();
+            self.ctx
+                .leaders()
+                .replicate_checkpoint(&followers, checkpoint.replication(saved))
+                .await;
+            self.ctx
+                .transform_offsets()
+                .send(TransformOffset::new(
+                    &self.name,
+                    self.replica.partition,
+                    next_offset,
+                ))
+                .await;
+        }
+        debug!(next_offset, "transformed records");
+        Ok(next_offset)
+    }
+
+    /// offset committed to sc by leaders of source partition
+    fn committed_offset(&self) -> Option<Offset> {
+        self.ctx
+            .transform_localstore()
+            .spec(&self.name)
+            .and_then(|transform| transform.status.offset(self.replica.partition))
+    }
+
+    async fn produce(&self, batch: Batch) -> Result<()> {
+        let leaders = self.ctx.leaders();
+        let target = leaders
+            .mapped_partition(&self.spec.target, self.replica.partition)
+            .ok_or_else(|| anyhow!("target topic {} not found", self.spec.target))?;
+        leaders.produce(&target, batch).await
+    }
+}
+
+/// SmartModule of transform is invoked same way as in consumer chain
+fn invocation(step: &TransformStep) -> SmartModuleInvocation {
+    SmartModuleInvocation {
+        wasm: SmartModuleInvocationWasm::Predefined(step.uses.clone()),
+        kind: SmartModuleKind::Generic(SmartModuleContextData::None),
+        params: step.with.clone().into(),
+        error_policy: step.on_error.clone(),
+    }
+}
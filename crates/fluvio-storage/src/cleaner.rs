@@ -1,21 +1,25 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::time::Duration;
 use std::ops::Div;
 use std::ops::Rem;
 
-use tracing::{debug, info, instrument};
+use tracing::{debug, error, info, instrument};
 
 use fluvio_future::task::spawn;
 use fluvio_future::timer::sleep;
 use fluvio_types::event::StickyEvent;
 
+use crate::compaction::Compactor;
 use crate::config::{SharedReplicaConfig, StorageConfig};
 use crate::replica::ReplicaSize;
 use crate::segments::SharedSegments;
 
 /// Replica cleaner. This is a background task that periodically checks for expired segments and
 /// removes them. It also enforces max partition size by removing first segments if replica size is
-/// exceeded. In the future, this may be done by a central cleaner pool instead of per a replica.
+/// exceeded. If compaction is enabled, closed segments are compacted by key.
+/// If remote tier is configured, aged segments are offloaded to it instead of being kept locally.
+/// In the future, this may be done by a central cleaner pool instead of per a replica.
 #[derive(Debug)]
 pub(crate) struct Cleaner {
     config: Arc<StorageConfig>,
@@ -23,6 +27,8 @@ pub(crate) struct Cleaner {
     segments: Arc<SharedSegments>,
     replica_size: Arc<ReplicaSize>,
     end_event: Arc<StickyEvent>,
+    compacted_end_offset: AtomicI64,
+    pending_tombstones: AtomicBool,
 }
 
 impl Cleaner {
@@ -39,6 +45,8 @@ impl Cleaner {
             segments,
             replica_size,
             end_event,
+            compacted_end_offset: AtomicI64::new(-1),
+            pending_tombstones: AtomicBool::new(false),
         });
 
         let cleaner_ref = cleaner.clone();
@@ -72,8 +80,10 @@ impl Cleaner {
                     break;
                 },
                 _ = sleep(sleep_period) => {
+                    self.enforce_offload().await;
                     self.enforce_size().await;
                     self.enforce_ttl().await;
+                    self.enforce_compaction().await;
                 }
             }
         }
@@ -103,7 +113,14 @@ impl Cleaner {
                 .read()
                 .await
                 .find_first(count_to_remove as usize);
-            self.segments.remove_segments(&segments_to_remove).await;
+            if self.segments.has_remote() {
+                // with remote tier, size only limits local disk usage
+                if let Err(err) = self.segments.offload_segments(&segments_to_remove).await {
+                    error!(%err, "offloading segments failed");
+                }
+            } else {
+                self.segments.remove_segments(&segments_to_remove).await;
+            }
 
             let read = self.segments.read().await;
             self.replica_size.store_prev(read.occupied_memory());
@@ -115,7 +132,8 @@ impl Cleaner {
         let retention_secs =
             Duration::from_secs(self.replica_config.retention_seconds.get() as u64);
         let read = self.segments.read().await;
-        let expired_segments = read.find_expired_segments(&retention_secs);
+        let mut expired_segments = read.find_expired_segments(&retention_secs);
+        expired_segments.extend(read.find_expired_remote_segments(&retention_secs));
         let total = read.len();
         drop(read);
         debug!(
@@ -130,6 +148,76 @@ impl Cleaner {
             self.replica_size.store_prev(read.occupied_memory());
         }
     }
+
+    /// offload segments older than remote age to remote tier and remove them locally.
+    /// Segments fetched back from remote tier are evicted after same age.
+    #[instrument(skip(self))]
+    async fn enforce_offload(&self) {
+        if !self.segments.has_remote() {
+            return;
+        }
+        if let Err(err) = self.segments.list_remote_segments().await {
+            error!(%err, "failed to list remote segments");
+        }
+
+        let remote_age = Duration::from_secs(self.replica_config.remote_after_seconds.get() as u64);
+        self.segments.evict_fetched_segments(&remote_age).await;
+
+        let aged_segments = self
+            .segments
+            .read()
+            .await
+            .find_expired_segments(&remote_age);
+        debug!(
+            seconds = remote_age.as_secs(),
+            aged = aged_segments.len(),
+            "segments to offload"
+        );
+        if aged_segments.is_empty() {
+            return;
+        }
+        if let Err(err) = self.segments.offload_segments(&aged_segments).await {
+            error!(%err, "offloading segments failed");
+        }
+        let read = self.segments.read().await;
+        self.replica_size.store_prev(read.occupied_memory());
+    }
+
+    /// compact closed segments, this only runs when new segments are closed or tombstones are pending
+    #[instrument(skip(self))]
+    async fn enforce_compaction(&self) {
+        if !self.replica_config.compact.get() {
+            return;
+        }
+
+        let end_offset = self.segments.read().await.max_offset();
+        if end_offset <= self.compacted_end_offset.load(Ordering::Acquire)
+            && !self.pending_tombstones.load(Ordering::Acquire)
+        {
+            debug!(end_offset, "no new segments to compact");
+            return;
+        }
+
+        match Compactor::new(&self.replica_config, &self.segments)
+            .compact()
+            .await
+        {
+            Ok(result) => {
+                debug!(?result, "compaction done");
+                self.compacted_end_offset
+                    .store(result.end_offset, Ordering::Release);
+                self.pending_tombstones
+                    .store(result.pending_tombstones > 0, Ordering::Release);
+                if result.removed > 0 {
+                    let read = self.segments.read().await;
+                    self.replica_size.store_prev(read.occupied_memory());
+                }
+            }
+            Err(err) => {
+                error!(%err, "compaction failed");
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -138,6 +226,7 @@ mod tests {
     use std::env::temp_dir;
     use std::ops::AddAssign;
     use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, AtomicI64};
     use std::time::Duration;
 
     use anyhow::Result;
@@ -287,6 +376,8 @@ mod tests {
             segments,
             replica_size,
             end_event: StickyEvent::shared(),
+            compacted_end_offset: AtomicI64::new(-1),
+            pending_tombstones: AtomicBool::new(false),
         }
     }
 }
@@ -0,0 +1,318 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::{debug, info, instrument};
+use anyhow::Result;
+
+use fluvio_future::fs::{create_dir_all, remove_dir_all};
+use fluvio_protocol::record::{Batch, Offset, RawRecords, Record, RecordData};
+
+use crate::batch::FileBatchStream;
+use crate::config::SharedReplicaConfig;
+use crate::index::EXTENSION as INDEX_EXTENSION;
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::time_index::EXTENSION as TIME_INDEX_EXTENSION;
+use crate::segment::{MutableSegment, ReadSegment};
+use crate::segments::SharedSegments;
+use crate::util::generate_file_name;
+
+/// directory under replica where compacted segments are staged before replacing originals
+const COMPACTION_DIR: &str = ".compaction";
+
+/// Outcome of compaction run
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub(crate) struct CompactionResult {
+    /// end offset of last closed segment which was considered
+    pub end_offset: Offset,
+    /// number of records removed
+    pub removed: usize,
+    /// tombstones that are still in grace period
+    pub pending_tombstones: usize,
+}
+
+/// Compacts closed segments of a replica.
+/// Only latest record for each key is kept. Records without key are never removed.
+/// Tombstones (records with key and empty value) are removed once they are older than tombstone retention.
+/// Offsets of remaining records are preserved. Last record of each segment is always kept
+/// so segment offset range doesn't change. Transaction markers are never removed.
+pub(crate) struct Compactor<'a> {
+    option: &'a Arc<SharedReplicaConfig>,
+    segments: &'a SharedSegments,
+}
+
+struct SegmentInfo {
+    base_offset: Offset,
+    end_offset: Offset,
+    tombstone_expired: bool,
+}
+
+impl<'a> Compactor<'a> {
+    pub(crate) fn new(option: &'a Arc<SharedReplicaConfig>, segments: &'a SharedSegments) -> Self {
+        Self { option, segments }
+    }
+
+    #[instrument(skip(self), fields(base_dir = ?self.option.base_dir))]
+    pub(crate) async fn compact(&self) -> Result<CompactionResult> {
+        let tombstone_retention =
+            Duration::from_secs(self.option.tombstone_retention_seconds.get() as u64);
+
+        let closed: Vec<SegmentInfo> = {
+            let read = self.segments.read().await;
+            read.offset_ranges()
+                .into_iter()
+                .filter_map(|(base_offset, end_offset)| {
+                    read.find_segment(base_offset)
+                        .map(|(_, segment)| SegmentInfo {
+                            base_offset,
+                            end_offset,
+                            tombstone_expired: segment.is_expired(&tombstone_retention),
+                        })
+                })
+                .collect()
+        };
+
+        let mut result = CompactionResult {
+            end_offset: closed.last().map(|info| info.end_offset).unwrap_or(-1),
+            ..Default::default()
+        };
+
+        if closed.is_empty() {
+            return Ok(result);
+        }
+
+        // first pass: find latest offset of each key
+        let mut latest: HashMap<RecordData, Offset> = HashMap::new();
+        for info in &closed {
+            let mut stream = self.open_stream(info.base_offset).await?;
+            while let Some(batch_pos) = stream.try_next().await? {
+                let batch = batch_pos.inner();
+                if batch.get_header().is_control() {
+                    continue;
+                }
+                let base_offset = batch.get_base_offset();
+                for record in batch.memory_records()? {
+                    if let Some(key) = record.key() {
+                        latest.insert(key.clone(), base_offset + record.preamble.offset_delta());
+                    }
+                }
+            }
+        }
+        debug!(keys = latest.len(), "found keys");
+
+        let staging_dir = self.option.base_dir.join(COMPACTION_DIR);
+        let now = now_ms();
+
+        // second pass: rewrite segments which have removable records
+        for info in &closed {
+            let tombstone_expired = |record: &Record, timestamp_base: i64| -> bool {
+                if timestamp_base > 0 {
+                    now - (timestamp_base + record.timestamp_delta())
+                        > tombstone_retention.as_millis() as i64
+                } else {
+                    info.tombstone_expired
+                }
+            };
+
+            let mut batches: Vec<Batch> = vec![];
+            let mut removed = 0;
+            let mut stream = self.open_stream(info.base_offset).await?;
+            while let Some(batch_pos) = stream.try_next().await? {
+                let batch = batch_pos.inner();
+                let base_offset = batch.get_base_offset();
+                let timestamp_base = batch.get_header().first_timestamp;
+                let control = batch.get_header().is_control();
+
+                let mut retained = vec![];
+                for record in batch.memory_records()? {
+                    let offset = base_offset + record.preamble.offset_delta();
+                    let keep = match record.key() {
+                        _ if control || offset == info.end_offset - 1 => true,
+                        None => true,
+                        Some(key) if latest.get(key) != Some(&offset) => false,
+                        Some(_) if record.value().is_empty() => {
+                            if tombstone_expired(&record, timestamp_base) {
+                                false
+                            } else {
+                                result.pending_tombstones += 1;
+                                true
+                            }
+                        }
+                        Some(_) => true,
+                    };
+                    if keep {
+                        retained.push(record);
+                    } else {
+                        removed += 1;
+                    }
+                }
+
+                if let Some(last) = retained.last() {
+                    let mut compacted = Batch::default();
+                    compacted.header = batch.header.clone();
+                    compacted.set_base_offset(base_offset);
+                    compacted.set_offset_delta(last.preamble.offset_delta() as i32);
+                    *compacted.mut_records() = retained;
+                    batches.push(compacted);
+                }
+            }
+
+            if removed == 0 {
+                debug!(base_offset = info.base_offset, "nothing to compact");
+                continue;
+            }
+
+            self.rewrite_segment(info, batches, &staging_dir).await?;
+            info!(base_offset = info.base_offset, removed, "segment compacted");
+            result.removed += removed;
+        }
+
+        Ok(result)
+    }
+
+    async fn open_stream(&self, base_offset: Offset) -> Result<FileBatchStream<RawRecords>> {
+        let path = generate_file_name(&self.option.base_dir, base_offset, MESSAGE_LOG_EXTENSION);
+        let stream: FileBatchStream<RawRecords> = FileBatchStream::open(&path).await?;
+        Ok(stream)
+    }
+
+    /// write batches into staging segment and replace original segment with it
+    async fn rewrite_segment(
+        &self,
+        info: &SegmentInfo,
+        batches: Vec<Batch>,
+        staging_dir: &Path,
+    ) -> Result<()> {
+        // always start with clean staging area
+        if staging_dir.exists() {
+            remove_dir_all(staging_dir).await?;
+        }
+        create_dir_all(staging_dir).await?;
+
+        let staging_option = self.option.with_base_dir(staging_dir.to_path_buf());
+        staging_option.segment_max_bytes.set(u32::MAX);
+
+        let mut segment =
+            MutableSegment::create(info.base_offset, Arc::new(staging_option)).await?;
+        for batch in batches {
+            let raw: Batch<RawRecords> = batch.try_into()?;
+            segment.append_batch_at_offset(&raw).await?;
+        }
+        segment.roll_over().await?;
+        drop(segment);
+
+        // replace while holding write lock so no reader can mix old index with new log
+        let mut write = self.segments.write().await;
+        for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION, TIME_INDEX_EXTENSION] {
+            std::fs::rename(
+                generate_file_name(staging_dir, info.base_offset, extension),
+                generate_file_name(&self.option.base_dir, info.base_offset, extension),
+            )?;
+        }
+        let compacted =
+            ReadSegment::open_for_read(info.base_offset, info.end_offset, self.option.clone())
+                .await?;
+        write.add_segment(compacted);
+        drop(write);
+
+        remove_dir_all(staging_dir).await?;
+        Ok(())
+    }
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+    use std::io::Cursor;
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::Decoder;
+    use fluvio_protocol::record::{Batch, MemoryRecords, Record};
+    use fluvio_protocol::fixture::read_bytes_from_file;
+
+    use crate::config::ReplicaConfig;
+    use crate::segment::MutableSegment;
+    use crate::segments::{SegmentList, SharedSegments};
+    use crate::util::generate_file_name;
+
+    use super::Compactor;
+
+    fn key_value_batch(records: &[(&str, &str)]) -> Batch {
+        let mut batch = Batch::default();
+        for (key, value) in records {
+            batch.add_record(Record::new_key_value(*key, *value));
+        }
+        batch
+    }
+
+    #[fluvio_future::test]
+    async fn test_compact_keeps_latest_per_key() {
+        let rep_dir = temp_dir().join("compaction-latest-key");
+        ensure_new_dir(&rep_dir).expect("new");
+        let option = ReplicaConfig {
+            base_dir: rep_dir.clone(),
+            segment_max_bytes: 10000,
+            index_max_bytes: 1000,
+            index_max_interval_bytes: 0,
+            compact: true,
+            tombstone_retention_seconds: 0,
+            ..Default::default()
+        }
+        .shared();
+
+        let mut segment = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        segment
+            .append_batch(&mut key_value_batch(&[("a", "1"), ("b", "1")]))
+            .await
+            .expect("append");
+        segment
+            .append_batch(&mut key_value_batch(&[("a", "2"), ("c", "")]))
+            .await
+            .expect("append");
+        segment
+            .append_batch(&mut key_value_batch(&[("b", "2"), ("d", "1")]))
+            .await
+            .expect("append");
+        let segment = segment.convert_to_segment().await.expect("convert");
+        assert_eq!(segment.get_end_offset(), 6);
+
+        let segments = SharedSegments::from(SegmentList::new());
+        segments.add_segment(segment).await;
+
+        let result = Compactor::new(&option, &segments)
+            .compact()
+            .await
+            .expect("compact");
+
+        // a:1, b:1 are overwritten and c is expired tombstone
+        assert_eq!(result.removed, 3);
+        assert_eq!(result.end_offset, 6);
+
+        let read = segments.read().await;
+        let (_, compacted) = read.find_segment(0).expect("segment");
+        assert_eq!(compacted.get_end_offset(), 6);
+        drop(read);
+
+        let bytes = read_bytes_from_file(generate_file_name(&rep_dir, 0, "log")).expect("read");
+        let mut cursor = Cursor::new(bytes);
+        let first = Batch::<MemoryRecords>::decode_from(&mut cursor, 0).expect("decode");
+        let second = Batch::<MemoryRecords>::decode_from(&mut cursor, 0).expect("decode");
+        assert_eq!(first.get_base_offset(), 2);
+        assert_eq!(first.records().len(), 1);
+        assert_eq!(first.get_last_offset(), 2);
+        assert_eq!(second.get_base_offset(), 4);
+        assert_eq!(second.records().len(), 2);
+        assert_eq!(second.get_last_offset(), 5);
+    }
+}
@@ -2,7 +2,7 @@ use std::default::Default;
 use std::path::PathBuf;
 use std::fmt;
 use std::sync::Arc;
-use std::sync::atomic::{AtomicU32, AtomicU64};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64};
 
 use derive_builder::Builder;
 use fluvio_controlplane_metadata::partition::Replica;
@@ -12,12 +12,14 @@ use serde::Deserialize;
 use fluvio_types::defaults::{
     SPU_LOG_INDEX_MAX_BYTES, SPU_LOG_BASE_DIR, STORAGE_FLUSH_WRITE_COUNT, STORAGE_FLUSH_IDLE_MSEC,
     STORAGE_MAX_BATCH_SIZE, STORAGE_RETENTION_SECONDS, SPU_PARTITION_MAX_BYTES,
+    STORAGE_TOMBSTONE_RETENTION_SECONDS, STORAGE_REMOTE_AFTER_SECONDS,
 };
 use fluvio_types::defaults::SPU_LOG_INDEX_MAX_INTERVAL_BYTES;
 use fluvio_types::defaults::SPU_LOG_SEGMENT_MAX_BYTES;
 use fluvio_protocol::record::{Size, Size64};
 
 use crate::{ReplicaStorageConfig};
+use crate::tiered::{RemoteStore, RemoteStoreConfig};
 
 // Replica specific config
 #[derive(Builder, Clone, Debug, Eq, PartialEq, Deserialize)]
@@ -53,6 +55,18 @@ pub struct ReplicaConfig {
     #[builder(default = "default_max_partition_size()")]
     #[serde(default = "default_max_partition_size")]
     pub max_partition_size: Size64,
+    #[builder(default)]
+    #[serde(default)]
+    pub compact: bool, // if true, closed segments are compacted by key
+    #[builder(default = "default_tombstone_retention_seconds()")]
+    #[serde(default = "default_tombstone_retention_seconds")]
+    pub tombstone_retention_seconds: Size,
+    #[builder(default)]
+    #[serde(default)]
+    pub remote: Option<RemoteStoreConfig>, // if set, closed segments are offloaded to remote store
+    #[builder(default = "default_remote_after_seconds()")]
+    #[serde(default = "default_remote_after_seconds")]
+    pub remote_after_seconds: Size,
 }
 
 impl fmt::Display for ReplicaConfig {
@@ -67,6 +81,12 @@ impl ReplicaStorageConfig for ReplicaConfig {
             match policy {
                 CleanupPolicy::Segment(segment) => {
                     self.retention_seconds = segment.retention_secs();
+                    self.compact = false;
+                }
+                CleanupPolicy::Compact(compact) => {
+                    self.retention_seconds = compact.retention_secs();
+                    self.tombstone_retention_seconds = compact.tombstone_retention_secs();
+                    self.compact = true;
                 }
             }
         }
@@ -128,6 +148,14 @@ const fn default_max_partition_size() -> Size64 {
     SPU_PARTITION_MAX_BYTES
 }
 
+const fn default_tombstone_retention_seconds() -> Size {
+    STORAGE_TOMBSTONE_RETENTION_SECONDS
+}
+
+const fn default_remote_after_seconds() -> Size {
+    STORAGE_REMOTE_AFTER_SECONDS
+}
+
 impl ReplicaConfig {
     // Used to get a [`ConfigOptionBuilder`].
     pub fn builder() -> ReplicaConfigBuilder {
@@ -152,6 +180,10 @@ impl Default for ReplicaConfig {
             retention_seconds: default_retention_seconds(),
             max_partition_size: default_max_partition_size(),
             update_hw: true,
+            compact: false,
+            tombstone_retention_seconds: default_tombstone_retention_seconds(),
+            remote: None,
+            remote_after_seconds: default_remote_after_seconds(),
         }
     }
 }
@@ -212,8 +244,25 @@ impl SharedConfigValue<AtomicU64> {
     }
 }
 
+impl SharedConfigValue<AtomicBool> {
+    pub fn new(value: bool) -> Self {
+        SharedConfigValue(AtomicBool::new(value))
+    }
+
+    #[inline(always)]
+    pub fn get(&self) -> bool {
+        self.0.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn set(&self, value: bool) {
+        self.0.store(value, std::sync::atomic::Ordering::Relaxed)
+    }
+}
+
 pub type SharedConfigU32Value = SharedConfigValue<AtomicU32>;
 pub type SharedConfigU64Value = SharedConfigValue<AtomicU64>;
+pub type SharedConfigBoolValue = SharedConfigValue<AtomicBool>;
 
 /// Config that can be shared updated
 #[derive(Debug)]
@@ -228,6 +277,10 @@ pub struct SharedReplicaConfig {
     pub update_hw: bool, // if true, enable hw update
     pub retention_seconds: SharedConfigU32Value,
     pub max_partition_size: SharedConfigU64Value,
+    pub compact: SharedConfigBoolValue,
+    pub tombstone_retention_seconds: SharedConfigU32Value,
+    pub remote: Option<Arc<dyn RemoteStore>>,
+    pub remote_after_seconds: SharedConfigU32Value,
 }
 
 impl From<ReplicaConfig> for SharedReplicaConfig {
@@ -243,6 +296,49 @@ impl From<ReplicaConfig> for SharedReplicaConfig {
             update_hw: config.update_hw,
             retention_seconds: SharedConfigU32Value::new(config.retention_seconds),
             max_partition_size: SharedConfigU64Value::new(config.max_partition_size),
+            compact: SharedConfigBoolValue::new(config.compact),
+            tombstone_retention_seconds: SharedConfigU32Value::new(
+                config.tombstone_retention_seconds,
+            ),
+            remote: config.remote.as_ref().map(RemoteStoreConfig::build),
+            remote_after_seconds: SharedConfigU32Value::new(config.remote_after_seconds),
+        }
+    }
+}
+
+impl SharedReplicaConfig {
+    /// update values which can be changed while replica is running
+    pub fn update(&self, config: &ReplicaConfig) {
+        self.segment_max_bytes.set(config.segment_max_bytes);
+        self.retention_seconds.set(config.retention_seconds);
+        self.max_partition_size.set(config.max_partition_size);
+        self.compact.set(config.compact);
+        self.tombstone_retention_seconds
+            .set(config.tombstone_retention_seconds);
+        self.remote_after_seconds.set(config.remote_after_seconds);
+    }
+
+    /// snapshot of current values using different base directory
+    pub(crate) fn with_base_dir(&self, base_dir: PathBuf) -> Self {
+        SharedReplicaConfig {
+            base_dir,
+            index_max_bytes: SharedConfigU32Value::new(self.index_max_bytes.get()),
+            index_max_interval_bytes: SharedConfigU32Value::new(
+                self.index_max_interval_bytes.get(),
+            ),
+            segment_max_bytes: SharedConfigU32Value::new(self.segment_max_bytes.get()),
+            flush_write_count: SharedConfigU32Value::new(self.flush_write_count.get()),
+            flush_idle_msec: SharedConfigU32Value::new(self.flush_idle_msec.get()),
+            max_batch_size: SharedConfigU32Value::new(self.max_batch_size.get()),
+            update_hw: self.update_hw,
+            retention_seconds: SharedConfigU32Value::new(self.retention_seconds.get()),
+            max_partition_size: SharedConfigU64Value::new(self.max_partition_size.get()),
+            compact: SharedConfigBoolValue::new(self.compact.get()),
+            tombstone_retention_seconds: SharedConfigU32Value::new(
+                self.tombstone_retention_seconds.get(),
+            ),
+            remote: self.remote.clone(),
+            remote_after_seconds: SharedConfigU32Value::new(self.remote_after_seconds.get()),
         }
     }
 }
@@ -270,4 +366,26 @@ mod tests {
 
         assert_eq!(ReplicaConfig::default(), config);
     }
+
+    #[test]
+    fn test_update_from_compact_replica() {
+        use fluvio_controlplane_metadata::topic::{CompactPolicy, SegmentBasedPolicy};
+
+        let mut config = ReplicaConfig::default();
+        let replica = Replica {
+            cleanup_policy: Some(CleanupPolicy::Compact(CompactPolicy {
+                tombstone_retention_secs: 60,
+                delete: Some(SegmentBasedPolicy {
+                    time_in_seconds: 3600,
+                }),
+            })),
+            ..Default::default()
+        };
+
+        config.update_from_replica(&replica);
+
+        assert!(config.compact);
+        assert_eq!(config.tombstone_retention_seconds, 60);
+        assert_eq!(config.retention_seconds, 3600);
+    }
 }
@@ -17,12 +17,19 @@ pub mod config;
 #[cfg(feature = "fixture")]
 pub mod fixture;
 mod cleaner;
+mod compaction;
+mod time_index;
+mod transaction;
+mod producer;
+pub mod tiered;
 
 pub use crate::error::StorageError;
 pub use crate::records::FileRecordsSlice;
 pub use crate::index::LogIndex;
 pub use crate::index::OffsetPosition;
 pub use crate::replica::FileReplica;
+pub use crate::transaction::{OpenTransaction, TransactionDecision};
+pub use crate::producer::ProducerBatch;
 
 pub use inner::*;
 mod inner {
@@ -37,6 +44,8 @@ mod inner {
     use fluvio_protocol::record::RecordSet;
     use fluvio_controlplane_metadata::partition::Replica;
     use fluvio_future::file_slice::AsyncFileSlice;
+    use fluvio_spu_schema::fetch::AbortedTransaction;
+    use fluvio_types::Timestamp;
 
     #[derive(Debug, Clone, Eq, PartialEq)]
     pub struct OffsetInfo {
@@ -116,6 +125,8 @@ mod inner {
         pub start: Offset,   // start offset
         pub end: OffsetInfo, // end offset
         pub file_slice: Option<AsyncFileSlice>,
+        /// transactions aborted with batches in slice, only for committed reads
+        pub aborted: Vec<AbortedTransaction>,
     }
 
     /// some storage configuration
@@ -163,6 +174,32 @@ mod inner {
 
         async fn update_high_watermark(&mut self, offset: Offset) -> Result<bool, StorageError>;
 
+        /// find first offset of batch containing records at or after timestamp
+        /// return None if all records are older than timestamp
+        async fn find_offset_by_timestamp(
+            &self,
+            timestamp: Timestamp,
+        ) -> Result<Option<Offset>, ErrorCode>;
+
+        /// apply updated configuration to running replica.
+        /// location of replica can't be changed
+        fn update_config(&self, replica_config: &Self::ReplicaConfig);
+
+        /// leader uploads closed segments to remote tier,
+        /// followers only remove local copies of segments leader has uploaded
+        fn set_leader(&self, leader: bool);
+
+        /// transactions which have written batches but are not committed or aborted yet
+        fn open_transactions(&self) -> Vec<crate::OpenTransaction>;
+
+        /// decisions of transactions coordinated by replica which are not yet written
+        /// to all partitions of transaction
+        fn transaction_decisions(&self) -> Vec<crate::TransactionDecision>;
+
+        /// recent batches of idempotent producers, so retries of batches written before
+        /// leader was loaded are detected
+        fn producer_batches(&self) -> Vec<crate::ProducerBatch>;
+
         /// permanently remove
         async fn remove(&self) -> Result<(), StorageError>;
     }
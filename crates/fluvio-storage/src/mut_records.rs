@@ -44,7 +44,7 @@ pub struct MutFileRecords {
     base_offset: Offset,
     file: File,
     len: u32,
-    max_len: u32,
+    option: Arc<SharedReplicaConfig>,
     _flush_policy: FlushPolicy,
     write_count: u64,
     flush_count: Arc<AtomicU32>,
@@ -83,8 +83,7 @@ impl MutFileRecords {
         option: Arc<SharedReplicaConfig>,
     ) -> Result<MutFileRecords, BoundedFileSinkError> {
         let log_path = generate_file_name(&option.base_dir, base_offset, MESSAGE_LOG_EXTENSION);
-        let max_len = option.segment_max_bytes.get();
-        debug!(log_path = ?log_path, max_len = "creating log at");
+        debug!(log_path = ?log_path, max_len = option.segment_max_bytes.get(), "creating log at");
         let file = fluvio_future::fs::util::open_read_append(log_path.clone()).await?;
         let metadata = file.metadata().await?;
         let len = metadata.len() as u32;
@@ -93,12 +92,12 @@ impl MutFileRecords {
             base_offset,
             file,
             len,
-            max_len,
             _flush_policy: get_flush_policy_from_config(&option),
             write_count: 0,
             flush_count: Arc::new(AtomicU32::new(0)),
             path: log_path.to_owned(),
             _flush_time_tx: None,
+            option,
         })
     }
 
@@ -141,7 +140,8 @@ impl MutFileRecords {
         let batch_len = batch.write_size(0);
         debug!(batch_len, "writing batch of size",);
 
-        if (batch_len as u32 + self.len) <= self.max_len {
+        // segment size can be changed while segment is active
+        if (batch_len as u32 + self.len) <= self.option.segment_max_bytes.get() {
             let mut buffer: Vec<u8> = Vec::with_capacity(batch_len);
             batch.encode(&mut buffer, 0)?;
             assert_eq!(buffer.len(), batch_len);
@@ -0,0 +1,97 @@
+use std::collections::{HashMap, VecDeque};
+
+use fluvio_protocol::record::{Batch, BatchRecords, Offset};
+
+/// number of recent batches kept for each idempotent producer
+const MAX_BATCHES_PER_PRODUCER: usize = 5;
+
+/// Batch written by idempotent producer
+#[derive(Default, Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ProducerBatch {
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub first_sequence: i32,
+    pub base_offset: Offset,
+    /// offset after last record of batch
+    pub leo: Offset,
+}
+
+/// Recent batches of idempotent producers, so leader can detect retried batches
+/// written before it was loaded or promoted.
+/// Like transaction index, it is rebuilt from batches of local segments when replica is loaded.
+#[derive(Debug, Default)]
+pub(crate) struct ProducerBatchIndex {
+    producers: HashMap<i64, VecDeque<ProducerBatch>>,
+}
+
+impl ProducerBatchIndex {
+    /// update from batch written at its base offset
+    pub(crate) fn update<R: BatchRecords>(&mut self, batch: &Batch<R>) {
+        let header = batch.get_header();
+        if header.producer_id < 0 || header.first_sequence < 0 {
+            return;
+        }
+        let batches = self.producers.entry(header.producer_id).or_default();
+        // newer epoch starts sequence again
+        if matches!(batches.back(), Some(last) if last.producer_epoch < header.producer_epoch) {
+            batches.clear();
+        }
+        if batches.len() == MAX_BATCHES_PER_PRODUCER {
+            batches.pop_front();
+        }
+        batches.push_back(ProducerBatch {
+            producer_id: header.producer_id,
+            producer_epoch: header.producer_epoch,
+            first_sequence: header.first_sequence,
+            base_offset: batch.get_base_offset(),
+            leo: batch.get_last_offset() + 1,
+        });
+    }
+
+    /// batches of all producers, in order they were written
+    pub(crate) fn batches(&self) -> Vec<ProducerBatch> {
+        let mut batches: Vec<ProducerBatch> = self.producers.values().flatten().copied().collect();
+        batches.sort_by_key(|batch| batch.base_offset);
+        batches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::record::{Batch, Offset, Record};
+
+    use super::{ProducerBatchIndex, MAX_BATCHES_PER_PRODUCER};
+
+    fn batch(base_offset: Offset, producer_epoch: i16, first_sequence: i32) -> Batch {
+        let mut batch = Batch::from(vec![Record::new("value"), Record::new("value")]);
+        batch.set_base_offset(base_offset);
+        let header = batch.get_mut_header();
+        header.producer_id = 1;
+        header.producer_epoch = producer_epoch;
+        header.first_sequence = first_sequence;
+        batch
+    }
+
+    #[test]
+    fn test_producer_batch_index() {
+        let mut index = ProducerBatchIndex::default();
+
+        // batches without producer are not tracked
+        index.update(&Batch::from(vec![Record::new("value")]));
+        assert!(index.batches().is_empty());
+
+        for i in 0..=MAX_BATCHES_PER_PRODUCER as i32 {
+            index.update(&batch(i as Offset * 2, 0, i * 2));
+        }
+        let batches = index.batches();
+        assert_eq!(batches.len(), MAX_BATCHES_PER_PRODUCER);
+        assert_eq!(batches[0].first_sequence, 2);
+        assert_eq!(batches[0].base_offset, 2);
+        assert_eq!(batches[0].leo, 4);
+
+        // newer epoch replaces batches of older one
+        index.update(&batch(20, 1, 0));
+        assert_eq!(index.batches().len(), 1);
+    }
+}
@@ -6,6 +6,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use tracing::{debug, trace, warn, instrument, info};
 use async_trait::async_trait;
 use anyhow::Result;
+use chrono::Utc;
 
 use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_protocol::Encoder;
@@ -15,6 +16,7 @@ use fluvio_spu_schema::Isolation;
 use fluvio_protocol::record::{Offset, ReplicaKey, Size, Size64};
 use fluvio_protocol::record::{Batch, BatchRecords};
 use fluvio_protocol::record::RecordSet;
+use fluvio_types::Timestamp;
 
 use crate::{OffsetInfo, checkpoint::CheckPoint};
 use crate::segments::SharedSegments;
@@ -23,6 +25,9 @@ use crate::config::{ReplicaConfig, SharedReplicaConfig, StorageConfig};
 use crate::ReplicaSlice;
 use crate::{StorageError, ReplicaStorage};
 use crate::cleaner::Cleaner;
+use crate::transaction::TransactionIndex;
+use crate::producer::ProducerBatchIndex;
+use crate::{OpenTransaction, ProducerBatch, TransactionDecision};
 
 /// Replica is public abstraction for commit log which are distributed.
 /// Internally it is stored as list of segments.  Each segment contains finite sets of record batches.
@@ -39,6 +44,8 @@ pub struct FileReplica {
     commit_checkpoint: CheckPoint<Offset>,
     cleaner: Arc<Cleaner>,
     size: Arc<ReplicaSize>,
+    transactions: TransactionIndex,
+    producers: ProducerBatchIndex,
 }
 
 #[derive(Debug, Default)]
@@ -103,8 +110,14 @@ impl ReplicaStorage for FileReplica {
     ) -> Result<ReplicaSlice, ErrorCode> {
         match isolation {
             Isolation::ReadCommitted => {
-                self.read_records(offset, Some(self.get_hw()), max_len)
-                    .await
+                // records of open transactions are not visible until transaction ends,
+                // consumer may already be past last stable offset if it started at hw
+                let hw = self.get_hw();
+                let lso = self.transactions.last_stable_offset(hw).max(offset.min(hw));
+                let mut slice = self.read_records(offset, Some(lso), max_len).await?;
+                slice.end.hw = lso;
+                slice.aborted = self.transactions.aborted(offset, lso);
+                Ok(slice)
             }
             Isolation::ReadUncommitted => self.read_records(offset, None, max_len).await,
         }
@@ -142,9 +155,14 @@ impl ReplicaStorage for FileReplica {
                 return Err(StorageError::BatchTooBig(max_batch_size).into());
             }
         }
+        self.transactions.validate(&records.batches)?;
 
+        self.transactions.truncate(self.get_log_start_offset());
+        let now = Utc::now().timestamp_millis();
         for batch in &mut records.batches {
             self.write_batch(batch).await?;
+            self.transactions.update(batch, now);
+            self.producers.update(batch);
         }
 
         if update_highwatermark {
@@ -175,8 +193,52 @@ impl ReplicaStorage for FileReplica {
         }
     }
 
+    #[instrument(skip(self))]
+    async fn find_offset_by_timestamp(
+        &self,
+        timestamp: Timestamp,
+    ) -> Result<Option<Offset>, ErrorCode> {
+        let offset = match self
+            .prev_segments
+            .find_offset_by_timestamp(timestamp)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("time index error: {err:#?}")))?
+        {
+            Some(offset) => Some(offset),
+            None => self
+                .active_segment
+                .find_offset_by_timestamp(timestamp)
+                .await
+                .map_err(|err| ErrorCode::Other(format!("time index error: {err:#?}")))?,
+        };
+        debug!(timestamp, ?offset, "found offset by timestamp");
+        Ok(offset)
+    }
+
+    fn update_config(&self, replica_config: &Self::ReplicaConfig) {
+        debug!(?replica_config, "updating replica config");
+        self.option.update(replica_config);
+    }
+
+    fn set_leader(&self, leader: bool) {
+        self.prev_segments.set_leader(leader);
+    }
+
+    fn open_transactions(&self) -> Vec<OpenTransaction> {
+        self.transactions.open_transactions()
+    }
+
+    fn transaction_decisions(&self) -> Vec<TransactionDecision> {
+        self.transactions.decisions()
+    }
+
+    fn producer_batches(&self) -> Vec<ProducerBatch> {
+        self.producers.batches()
+    }
+
     #[instrument(skip(self))]
     async fn remove(&self) -> Result<(), StorageError> {
+        self.prev_segments.remove_remote_segments().await;
         remove_dir_all(&self.option.base_dir)
             .await
             .map_err(StorageError::Io)?;
@@ -186,6 +248,12 @@ impl ReplicaStorage for FileReplica {
     }
 }
 
+impl Drop for FileReplica {
+    fn drop(&mut self) {
+        self.cleaner.shutdown();
+    }
+}
+
 impl FileReplica {
     pub const PREFER_MAX_LEN: u32 = 1000000; // 1MB as limit
 
@@ -260,6 +328,24 @@ impl FileReplica {
             commit_checkpoint.write(leo).await?;
         }
 
+        // transactions are only tracked for segments on local disk
+        let mut base_offsets: Vec<Offset> = segments
+            .read()
+            .await
+            .offset_ranges()
+            .into_iter()
+            .map(|(base_offset, _)| base_offset)
+            .collect();
+        base_offsets.push(active_segment.get_base_offset());
+        let mut producers = ProducerBatchIndex::default();
+        let transactions = TransactionIndex::rebuild(
+            &shared_config.base_dir,
+            &base_offsets,
+            Utc::now().timestamp_millis(),
+            &mut producers,
+        )
+        .await?;
+
         let size = Arc::new(ReplicaSize::default());
         let cleaner = Cleaner::start_new(
             storage_config,
@@ -277,6 +363,8 @@ impl FileReplica {
             commit_checkpoint,
             cleaner,
             size,
+            transactions,
+            producers,
         })
     }
 
@@ -293,6 +381,11 @@ impl FileReplica {
         }
     }
 
+    /// number of segments including active segment
+    pub async fn segment_count(&self) -> usize {
+        self.prev_segments.read().await.len() + 1
+    }
+
     /// update high watermark to end
     #[instrument(skip(self))]
     pub async fn update_high_watermark_to_end(&mut self) -> Result<bool, StorageError> {
@@ -450,8 +543,9 @@ mod tests {
     use std::time::Duration;
 
     use fluvio_spu_schema::Isolation;
+    use fluvio_protocol::link::ErrorCode;
     use fluvio_protocol::record::Batch;
-    use fluvio_protocol::record::Offset;
+    use fluvio_protocol::record::{ControlRecordType, Offset};
     use fluvio_protocol::{Decoder, Encoder};
     use fluvio_protocol::record::{Record, RecordSet};
     use fluvio_protocol::record::MemoryRecords;
@@ -673,7 +767,8 @@ mod tests {
         assert_eq!(replica.get_log_start_offset(), START_OFFSET);
         let replica_dir = &option.base_dir.join("test-1");
         let dir_contents = fs::read_dir(replica_dir).expect("read_dir");
-        assert_eq!(dir_contents.count(), 5, "should be 5 files");
+        // log, index and time index of each segment and replication checkpoint
+        assert_eq!(dir_contents.count(), 7, "should be 7 files");
 
         let seg2_file = replica_dir.join(TEST_SE2_NAME);
         let bytes = read_bytes_from_file(seg2_file).expect("file read");
@@ -828,6 +923,95 @@ mod tests {
         assert_eq!(slice.file_slice.unwrap().len() as usize, batch_len);
     }
 
+    /// committed fetch stops at first batch of open transaction
+    #[fluvio_future::test]
+    async fn test_transaction_fetch() {
+        let option = base_option("test_transaction_fetch");
+        let mut replica = create_replica("test", 0, option.clone()).await;
+
+        let mut transactional = create_batch();
+        transactional.get_mut_header().producer_id = 1;
+        transactional.get_mut_header().set_transactional(true);
+        let mut records = RecordSet::default().add(create_batch()).add(transactional);
+        replica
+            .write_recordset(&mut records, true)
+            .await
+            .expect("write");
+        assert_eq!(replica.get_hw(), 4);
+
+        let slice = replica
+            .read_partition_slice(0, FileReplica::PREFER_MAX_LEN, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.end.hw, 2);
+        assert_eq!(
+            slice.file_slice.unwrap().len() as usize,
+            create_batch().write_size(0)
+        );
+        assert_eq!(replica.open_transactions().len(), 1);
+
+        // commit without transaction is rejected
+        let mut records = RecordSet::default().add(Batch::control(2, 0, ControlRecordType::Commit));
+        let err = replica
+            .write_recordset(&mut records, true)
+            .await
+            .expect_err("commit");
+        assert_eq!(
+            err.downcast::<ErrorCode>().expect("error code"),
+            ErrorCode::InvalidTxnState
+        );
+
+        let mut records = RecordSet::default().add(Batch::control(1, 0, ControlRecordType::Abort));
+        replica
+            .write_recordset(&mut records, true)
+            .await
+            .expect("abort");
+        assert!(replica.open_transactions().is_empty());
+
+        let slice = replica
+            .read_partition_slice(0, FileReplica::PREFER_MAX_LEN, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.end.hw, 5);
+        assert_eq!(slice.aborted.len(), 1);
+        assert_eq!(slice.aborted[0].first_offset, 2);
+
+        // aborted transaction is restored after restart
+        drop(replica);
+        let replica = create_replica("test", 0, option).await;
+        let slice = replica
+            .read_partition_slice(0, FileReplica::PREFER_MAX_LEN, Isolation::ReadCommitted)
+            .await
+            .expect("read");
+        assert_eq!(slice.aborted.len(), 1);
+        assert!(replica.open_transactions().is_empty());
+    }
+
+    /// batches of idempotent producers are restored after restart
+    #[fluvio_future::test]
+    async fn test_producer_batches_restored() {
+        let option = base_option("test_producer_batches_restored");
+        let mut replica = create_replica("test", 0, option.clone()).await;
+
+        let mut idempotent = create_batch();
+        idempotent.get_mut_header().producer_id = 1;
+        idempotent.get_mut_header().first_sequence = 0;
+        let mut records = RecordSet::default().add(create_batch()).add(idempotent);
+        replica
+            .write_recordset(&mut records, true)
+            .await
+            .expect("write");
+        assert_eq!(replica.producer_batches().len(), 1);
+
+        drop(replica);
+        let replica = create_replica("test", 0, option).await;
+        let batches = replica.producer_batches();
+        assert_eq!(batches.len(), 1);
+        assert_eq!(batches[0].producer_id, 1);
+        assert_eq!(batches[0].base_offset, 2);
+        assert_eq!(batches[0].leo, 4);
+    }
+
     #[fluvio_future::test]
     async fn test_replica_delete() {
         let mut option = base_option("test_delete");
@@ -939,6 +1123,108 @@ mod tests {
         assert_eq!(segment.get_end_offset(), 4);
     }
 
+    /// segment size change is applied to running replica
+    #[fluvio_future::test]
+    async fn test_replica_update_config() {
+        let mut option = base_option("test_replica_update_config");
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut new_replica = create_replica("test", 0, option.clone()).await;
+        for _ in 0..3 {
+            new_replica
+                .write_batch(&mut producer.generate_batch())
+                .await
+                .expect("write");
+        }
+        assert_eq!(new_replica.prev_segments.read().await.len(), 0);
+
+        option.segment_max_bytes = 160;
+        option.compact = true;
+        new_replica.update_config(&option);
+        assert!(new_replica.option.compact.get());
+
+        for _ in 0..2 {
+            new_replica
+                .write_batch(&mut producer.generate_batch())
+                .await
+                .expect("write");
+        }
+        assert_eq!(new_replica.prev_segments.read().await.len(), 1);
+    }
+
+    /// find offsets by timestamp across segments, also after reload
+    #[fluvio_future::test]
+    async fn test_replica_find_offset_by_timestamp() {
+        let mut option = base_option("test_find_by_timestamp");
+        // enough for 2 batch (2 records per batch)
+        option.segment_max_bytes = 160;
+        option.index_max_interval_bytes = 50;
+
+        let producer = BatchProducer::builder()
+            .records(2u16)
+            .record_generator(Arc::new(|_, _| Record::new("1")))
+            .build()
+            .expect("batch");
+
+        let mut new_replica = create_replica("test", 0, option.clone()).await;
+        for timestamp in [1000, 2000, 3000] {
+            let mut batch = producer.generate_batch();
+            batch.header.first_timestamp = timestamp;
+            batch.header.max_time_stamp = timestamp + 10;
+            new_replica.write_batch(&mut batch).await.expect("write");
+        }
+        assert_eq!(new_replica.prev_segments.read().await.len(), 1);
+
+        assert_eq!(
+            new_replica.find_offset_by_timestamp(0).await.expect("find"),
+            Some(0)
+        );
+        assert_eq!(
+            new_replica
+                .find_offset_by_timestamp(1500)
+                .await
+                .expect("find"),
+            Some(2)
+        );
+        assert_eq!(
+            new_replica
+                .find_offset_by_timestamp(3010)
+                .await
+                .expect("find"),
+            Some(4)
+        );
+        assert_eq!(
+            new_replica
+                .find_offset_by_timestamp(3011)
+                .await
+                .expect("find"),
+            None
+        );
+        drop(new_replica);
+
+        // time index is loaded from persisted entries and last block of segments
+        let old_replica = create_replica("test", 0, option.clone()).await;
+        assert_eq!(
+            old_replica
+                .find_offset_by_timestamp(2500)
+                .await
+                .expect("find"),
+            Some(4)
+        );
+        assert_eq!(
+            old_replica
+                .find_offset_by_timestamp(1010)
+                .await
+                .expect("find"),
+            Some(0)
+        );
+    }
+
     /// test replica with purging segments
     #[fluvio_future::test]
     async fn test_replica_segment_purge() {
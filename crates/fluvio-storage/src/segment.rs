@@ -1,17 +1,21 @@
 use std::fmt;
 use std::io::Error as IoError;
+use std::io::ErrorKind;
 use std::ops::Deref;
+use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use tracing::{debug, trace, instrument, info, error};
 use anyhow::{Result};
+use async_lock::Mutex;
 
 use fluvio_future::fs::remove_file;
 use fluvio_future::file_slice::AsyncFileSlice;
 use fluvio_protocol::record::{Batch, BatchRecords};
 use fluvio_protocol::record::{Offset, Size, Size64};
 use fluvio_protocol::link::ErrorCode;
+use fluvio_types::Timestamp;
 
 use crate::batch_header::{BatchHeaderStream, FileEmptyRecords};
 use crate::mut_index::MutLogIndex;
@@ -25,6 +29,9 @@ use crate::StorageError;
 use crate::batch::{FileBatchStream};
 use crate::index::OffsetPosition;
 use crate::validator::LogValidationError;
+use crate::time_index::TimeIndex;
+use crate::time_index::EXTENSION as TIME_INDEX_EXTENSION;
+use crate::util::generate_file_name;
 
 pub type MutableSegment = Segment<MutLogIndex, MutFileRecords>;
 pub type ReadSegment = Segment<LogIndex, FileRecordsSlice>;
@@ -41,6 +48,9 @@ pub struct Segment<I, L> {
     index: I,
     base_offset: Offset,
     end_offset: Offset,
+    /// loaded on first lookup by timestamp, active segment loads it when opened
+    /// and maintains it as batches are appended
+    time_index: Arc<Mutex<Option<TimeIndex>>>,
 }
 
 impl<I, L> fmt::Debug for Segment<I, L> {
@@ -226,6 +236,30 @@ where
         Ok(None)
     }
 
+    /// find base offset of first batch which contains records at or after timestamp
+    #[instrument(skip(self))]
+    pub(crate) async fn find_offset_by_timestamp(
+        &self,
+        timestamp: Timestamp,
+    ) -> Result<Option<Offset>> {
+        self.timestamp_search().find_offset(timestamp).await
+    }
+
+    /// search by timestamp which can be used without borrowing segment
+    pub(crate) fn timestamp_search(&self) -> TimestampSearch {
+        TimestampSearch {
+            time_index: self.time_index.clone(),
+            log_path: self.msg_log.get_path().to_path_buf(),
+            index_path: generate_file_name(
+                &self.option.base_dir,
+                self.base_offset,
+                TIME_INDEX_EXTENSION,
+            ),
+            log_len: self.msg_log.len() as Size,
+            interval: self.option.index_max_interval_bytes.get(),
+        }
+    }
+
     pub(crate) fn occupied_memory(&self) -> Size64 {
         self.index.len() + self.msg_log.len()
     }
@@ -250,6 +284,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
             option,
             base_offset,
             end_offset,
+            time_index: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -277,6 +312,7 @@ impl Segment<LogIndex, FileRecordsSlice> {
                     option,
                     base_offset,
                     end_offset: val.leo(),
+                    time_index: Arc::new(Mutex::new(None)),
                 })
             }
             Err(err) => {
@@ -290,12 +326,28 @@ impl Segment<LogIndex, FileRecordsSlice> {
         self.msg_log.is_expired(expired_duration)
     }
 
+    /// time of newest record in segment, time of last write if records have no timestamp
+    pub(crate) async fn max_timestamp(&self) -> Result<SystemTime> {
+        Ok(self
+            .timestamp_search()
+            .read_index(TimeIndex::max_timestamp)
+            .await?
+            .flatten()
+            .map(|timestamp| UNIX_EPOCH + Duration::from_millis(timestamp as u64))
+            .unwrap_or_else(|| self.msg_log.last_modified_time()))
+    }
+
     pub(crate) async fn remove(self) -> Result<(), StorageError> {
+        let time_index_path = self.timestamp_search().index_path;
         self.msg_log.remove().await?;
         let index_file_path = self.index.clean();
         info!(index_path = %index_file_path.display(),"removing index file");
         remove_file(&index_file_path).await?;
-        Ok(())
+        // segments written before time index was persisted don't have the file
+        match remove_file(&time_index_path).await {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        }
     }
 }
 
@@ -312,12 +364,22 @@ impl Segment<MutLogIndex, MutFileRecords> {
 
         let index = MutLogIndex::create(base_offset, option.clone()).await?;
 
+        let mut time_index = TimeIndex::default();
+        time_index
+            .open_file(&generate_file_name(
+                &option.base_dir,
+                base_offset,
+                TIME_INDEX_EXTENSION,
+            ))
+            .await?;
+
         Ok(MutableSegment {
             option: option.to_owned(),
             msg_log,
             index,
             base_offset,
             end_offset: base_offset,
+            time_index: Arc::new(Mutex::new(Some(time_index))),
         })
     }
 
@@ -341,6 +403,7 @@ impl Segment<MutLogIndex, MutFileRecords> {
             index,
             base_offset,
             end_offset: base_offset,
+            time_index: Arc::new(Mutex::new(None)),
         })
     }
 
@@ -376,6 +439,9 @@ impl Segment<MutLogIndex, MutFileRecords> {
             }
         }
         self.end_offset = leo;
+        // time index is loaded after repair, so entries of truncated batches are discarded
+        let time_index = self.timestamp_search().load(true).await?;
+        *self.time_index.lock().await = Some(time_index);
         Ok(self.end_offset)
     }
 
@@ -393,7 +459,12 @@ impl Segment<MutLogIndex, MutFileRecords> {
     /// convert to immutable segment
     #[allow(clippy::wrong_self_convention)]
     pub async fn as_segment(self) -> Result<ReadSegment> {
-        Segment::open_for_read(self.get_base_offset(), self.end_offset, self.option.clone()).await
+        let segment =
+            Segment::open_for_read(self.get_base_offset(), self.end_offset, self.option.clone())
+                .await?;
+        let time_index = self.time_index.lock().await.take();
+        *segment.time_index.lock().await = time_index.map(TimeIndex::close_file);
+        Ok(segment)
     }
 
     /// use only in test
@@ -446,6 +517,8 @@ impl Segment<MutLogIndex, MutFileRecords> {
                 )
                 .await?;
             self.end_offset = next_end_offset + 1;
+            self.index_batch_time(start_file_pos, batch.get_header().max_time_stamp)
+                .await?;
             debug!(end_offset = self.end_offset, "updated leo");
             Ok(true)
         } else {
@@ -453,12 +526,139 @@ impl Segment<MutLogIndex, MutFileRecords> {
         }
     }
 
+    /// Append batch while keeping its base offset.
+    /// Unlike `append_batch`, offsets don't need to be contiguous, but batch must not start before end offset.
+    /// This is used when rewriting existing segment such as compaction.
+    #[instrument(skip(batch))]
+    pub(crate) async fn append_batch_at_offset<R: BatchRecords>(
+        &mut self,
+        batch: &Batch<R>,
+    ) -> Result<bool> {
+        if batch.records_len() == 0 {
+            return Err(StorageError::EmptyBatch.into());
+        }
+
+        if batch.get_base_offset() < self.end_offset {
+            return Err(LogValidationError::InvalidBaseOffsetMinimum {
+                invalid_batch_offset: batch.get_base_offset(),
+            }
+            .into());
+        }
+
+        let relative_offset_in_segment = (batch.get_base_offset() - self.base_offset) as i32;
+        let start_file_pos = self.msg_log.get_pos();
+
+        let (write_success, batch_len, end_file_pos) = self.msg_log.write_batch(batch).await?;
+        debug!(
+            write_success,
+            batch_len,
+            end_file_pos,
+            base_offset = batch.get_base_offset(),
+            "batch written at offset"
+        );
+        if write_success {
+            self.index
+                .write_index(
+                    relative_offset_in_segment as u32,
+                    start_file_pos,
+                    batch_len as u32,
+                )
+                .await?;
+            self.end_offset = batch.get_last_offset() + 1;
+            self.index_batch_time(start_file_pos, batch.get_header().max_time_stamp)
+                .await?;
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// add appended batch to time index
+    async fn index_batch_time(&mut self, pos: Size, max_timestamp: Timestamp) -> Result<()> {
+        let interval = self.option.index_max_interval_bytes.get();
+        if let Some(time_index) = self.time_index.lock().await.as_mut() {
+            time_index.add_batch(pos, max_timestamp, interval).await?;
+        }
+        Ok(())
+    }
+
     #[allow(unused)]
     pub async fn flush(&mut self) -> Result<(), StorageError> {
         self.msg_log.flush().await.map_err(|err| err.into())
     }
 }
 
+/// Search of segment by timestamp. It only shares time index and file paths of segment,
+/// so segment list doesn't have to be locked while batches are scanned
+pub(crate) struct TimestampSearch {
+    time_index: Arc<Mutex<Option<TimeIndex>>>,
+    log_path: PathBuf,
+    index_path: PathBuf,
+    log_len: Size,
+    interval: Size,
+}
+
+impl TimestampSearch {
+    /// find base offset of first batch which contains records at or after timestamp
+    pub(crate) async fn find_offset(&self, timestamp: Timestamp) -> Result<Option<Offset>> {
+        let start_pos = match self.read_index(|index| index.lookup(timestamp)).await? {
+            Some(Some(pos)) => pos,
+            _ => return Ok(None),
+        };
+        let mut header_stream = match self.open_log(start_pos).await? {
+            Some(stream) => stream,
+            None => return Ok(None),
+        };
+        while let Some(batch_pos) = header_stream.try_next().await? {
+            let batch = batch_pos.get_batch();
+            if batch.get_header().max_time_stamp >= timestamp {
+                return Ok(Some(batch.get_base_offset()));
+            }
+        }
+        Ok(None)
+    }
+
+    /// read time index, loading it first if needed
+    async fn read_index<T>(&self, read: impl FnOnce(&TimeIndex) -> T) -> Result<Option<T>> {
+        let mut time_index = self.time_index.lock().await;
+        if time_index.is_none() {
+            *time_index = Some(self.load(false).await?);
+        }
+        Ok(time_index.as_ref().map(read))
+    }
+
+    /// load persisted entries of time index, then add batches of last block which is not persisted
+    async fn load(&self, writable: bool) -> Result<TimeIndex> {
+        let mut time_index = TimeIndex::load(&self.index_path, self.log_len).await?;
+        if writable {
+            time_index.open_file(&self.index_path).await?;
+        }
+        if time_index.tail_start() < self.log_len {
+            if let Some(mut header_stream) = self.open_log(time_index.tail_start()).await? {
+                while let Some(batch_pos) = header_stream.try_next().await? {
+                    let max_timestamp = batch_pos.get_batch().get_header().max_time_stamp;
+                    time_index
+                        .add_batch(batch_pos.get_pos(), max_timestamp, self.interval)
+                        .await?;
+                }
+            }
+        }
+        debug!(path = %self.index_path.display(), "time index loaded");
+        Ok(time_index)
+    }
+
+    /// open log at position, None if segment has been removed since search was created
+    async fn open_log(&self, pos: Size) -> Result<Option<BatchHeaderStream>> {
+        let mut header_stream = match BatchHeaderStream::open(&self.log_path).await {
+            Ok(stream) => stream,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        header_stream.set_absolute(pos).await?;
+        Ok(Some(header_stream))
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
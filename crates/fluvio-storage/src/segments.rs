@@ -2,10 +2,10 @@ use std::collections::BTreeMap;
 use std::ops::Bound::Included;
 use std::ffi::OsStr;
 use std::sync::Arc;
-use std::sync::atomic::AtomicI64;
+use std::sync::atomic::{AtomicBool, AtomicI64};
 use std::time::Duration;
 
-use async_lock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use async_lock::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use tracing::{debug, trace, error, instrument, info};
 use anyhow::Result;
 
@@ -13,9 +13,11 @@ use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Size64;
 use fluvio_protocol::record::Offset;
 use fluvio_future::file_slice::AsyncFileSlice;
+use fluvio_types::Timestamp;
 
 use crate::config::SharedReplicaConfig;
 use crate::segment::ReadSegment;
+use crate::tiered::{RemoteSegment, RemoteTier};
 use crate::util::log_path_get_offset;
 
 const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqCst;
@@ -26,14 +28,34 @@ const MEM_ORDER: std::sync::atomic::Ordering = std::sync::atomic::Ordering::SeqC
 pub(crate) struct SharedSegments {
     inner: Arc<RwLock<SegmentList>>,
     min_offset: AtomicI64,
+    remote: Option<RemoteTier>,
+    /// base offset of active segment at open while remote segments are not listed yet.
+    /// Remote segments at or after it are still local
+    unlisted_remote_before: Mutex<Option<Offset>>,
+    /// replicas of partition share remote segments, only leader uploads and deletes them
+    leader: AtomicBool,
 }
 
 impl SharedSegments {
     pub(crate) fn from(list: SegmentList) -> Arc<Self> {
+        Self::with_remote(list, None, None)
+    }
+
+    fn with_remote(
+        list: SegmentList,
+        remote: Option<RemoteTier>,
+        active_offset: Option<Offset>,
+    ) -> Arc<Self> {
         let min = list.min_offset;
+        let unlisted_remote_before = remote
+            .as_ref()
+            .map(|_| active_offset.unwrap_or(Offset::MAX));
         Arc::new(Self {
             inner: Arc::new(RwLock::new(list)),
             min_offset: AtomicI64::new(min),
+            remote,
+            unlisted_remote_before: Mutex::new(unlisted_remote_before),
+            leader: AtomicBool::new(false),
         })
     }
 
@@ -87,11 +109,84 @@ impl SharedSegments {
             }
         }
 
-        let shared_segments = SharedSegments::from(segments);
+        let remote = option
+            .remote
+            .clone()
+            .map(|store| RemoteTier::new(store, option.clone()));
+        if let Some(remote) = &remote {
+            remote.clear_cache().await;
+        }
+
+        let shared_segments = SharedSegments::with_remote(segments, remote, last_offset);
+        // replica is usable with local segments while remote store is unreachable
+        if let Err(err) = shared_segments.list_remote_segments().await {
+            error!(%err, "failed to list remote segments, retrying later");
+        }
 
         Ok((shared_segments, last_offset))
     }
 
+    /// add segments found in remote tier, does nothing once they are listed
+    #[instrument(skip(self))]
+    pub(crate) async fn list_remote_segments(&self) -> Result<()> {
+        let remote = match &self.remote {
+            Some(remote) => remote,
+            None => return Ok(()),
+        };
+        let mut unlisted_remote_before = self.unlisted_remote_before.lock().await;
+        let active_offset = match *unlisted_remote_before {
+            Some(active_offset) => active_offset,
+            None => return Ok(()),
+        };
+        let listed = remote.list_segments().await?;
+
+        let mut write = self.write().await;
+        for segment in listed {
+            // segment which is still local was not removed after upload
+            if write.segments.contains_key(&segment.base_offset)
+                || segment.base_offset >= active_offset
+            {
+                debug!(base_offset = segment.base_offset, "segment is also local");
+                continue;
+            }
+            let min_offset = write.add_remote_segment(segment);
+            self.min_offset.store(min_offset, MEM_ORDER);
+            debug!(min_offset, "adding remote segment");
+        }
+        *unlisted_remote_before = None;
+        Ok(())
+    }
+
+    /// add segments uploaded by leader since they were listed. Segment is added only once
+    /// records up to its end are in closed local segments, so it never overlaps active one
+    async fn refresh_remote_segments(&self) -> Result<()> {
+        let remote = match &self.remote {
+            Some(remote) => remote,
+            None => return Ok(()),
+        };
+        let listed = remote.list_segments().await?;
+
+        let mut write = self.write().await;
+        let local_end = write.local_end_offset();
+        for segment in listed {
+            if write.remote.contains_key(&segment.base_offset) || segment.end_offset > local_end {
+                continue;
+            }
+            let min_offset = write.add_remote_segment(segment);
+            self.min_offset.store(min_offset, MEM_ORDER);
+            debug!(min_offset, "adding remote segment uploaded by leader");
+        }
+        Ok(())
+    }
+
+    pub(crate) fn set_leader(&self, leader: bool) {
+        self.leader.store(leader, MEM_ORDER);
+    }
+
+    pub(crate) fn is_leader(&self) -> bool {
+        self.leader.load(MEM_ORDER)
+    }
+
     pub async fn read(&self) -> RwLockReadGuard<'_, SegmentList> {
         self.inner.read().await
     }
@@ -118,8 +213,93 @@ impl SharedSegments {
         }
     }
 
+    /// is remote tier configured
+    pub(crate) fn has_remote(&self) -> bool {
+        self.remote.is_some()
+    }
+
+    /// upload segments to remote tier and remove them from local disk.
+    /// Segments already in remote tier are only removed, other segments are kept on followers
+    /// until leader uploads them
+    #[instrument(skip(self))]
+    pub(crate) async fn offload_segments(&self, base_offsets: &[Offset]) -> Result<()> {
+        let remote = match &self.remote {
+            Some(remote) => remote,
+            None => return Ok(()),
+        };
+        if !self.is_leader() {
+            self.refresh_remote_segments().await?;
+        }
+        for base_offset in base_offsets {
+            let read = self.read().await;
+            let remote_segment = match read.segments.get(base_offset) {
+                Some(segment) => RemoteSegment {
+                    base_offset: *base_offset,
+                    end_offset: segment.get_end_offset(),
+                    max_timestamp: segment.max_timestamp().await?,
+                },
+                None => continue,
+            };
+            let uploaded =
+                read.remote_covers(remote_segment.base_offset, remote_segment.end_offset);
+            drop(read);
+
+            if uploaded {
+                debug!(base_offset, "segment is already in remote tier");
+            } else if self.is_leader() {
+                remote.upload(&remote_segment).await?;
+                info!(
+                    base_offset,
+                    end_offset = remote_segment.end_offset,
+                    "segment offloaded"
+                );
+            } else {
+                debug!(base_offset, "waiting for leader to upload segment");
+                continue;
+            }
+
+            let mut write = self.write().await;
+            if !uploaded {
+                write.add_remote_segment(remote_segment);
+            }
+            if let Some((old_segment, min_offset)) = write.remove_segment(base_offset) {
+                drop(write);
+                self.min_offset.store(min_offset, MEM_ORDER);
+                if let Err(err) = old_segment.remove().await {
+                    error!("failed to remove offloaded segment: {:#?}", err);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// remove segments fetched from remote tier which are older than expired duration
+    #[instrument(skip(self))]
+    pub(crate) async fn evict_fetched_segments(&self, expired_duration: &Duration) {
+        let expired = self.write().await.take_expired_fetched(expired_duration);
+        for segment in expired {
+            debug!(
+                base_offset = segment.get_base_offset(),
+                "evicting fetched segment"
+            );
+            if let Err(err) = segment.remove().await {
+                error!("failed to remove fetched segment: {:#?}", err);
+            }
+        }
+    }
+
+    /// remove all segments from remote tier
+    #[instrument(skip(self))]
+    pub(crate) async fn remove_remote_segments(&self) {
+        if let Err(err) = self.list_remote_segments().await {
+            error!(%err, "failed to list remote segments, they are not removed");
+        }
+        let base_offsets: Vec<Offset> = self.read().await.remote.keys().copied().collect();
+        self.remove_segments(&base_offsets).await;
+    }
+
     /// find slice in the segments
-    /// if not found, return OutOfRange error
+    /// segments offloaded to remote tier are fetched first
     pub async fn find_slice(
         &self,
         start_offset: Offset,
@@ -127,20 +307,69 @@ impl SharedSegments {
     ) -> Result<AsyncFileSlice, ErrorCode> {
         let reader = self.read().await;
         if let Some((_offset, segment)) = reader.find_segment(start_offset) {
-            if let Some(slice) = segment.records_slice(start_offset, max_offset).await? {
-                Ok(slice)
-            } else {
-                Err(ErrorCode::Other(format!(
-                    "slice not found in start_offset: {start_offset}, segment: {segment:#?} "
-                )))
-            }
-        } else {
-            Err(ErrorCode::Other(format!(
+            return segment_slice(segment, start_offset, max_offset).await;
+        }
+        if let Some(segment) = reader.find_fetched_segment(start_offset) {
+            return segment_slice(segment, start_offset, max_offset).await;
+        }
+        let (remote, remote_segment) =
+            match (&self.remote, reader.find_remote_segment(start_offset)) {
+                (Some(remote), Some(remote_segment)) => (remote, remote_segment.clone()),
+                _ => {
+                    return Err(ErrorCode::Other(format!(
+                        "Segment not found for start_offset: {start_offset}"
+                    )))
+                }
+            };
+        drop(reader);
+
+        self.fetch_segment(remote, &remote_segment)
+            .await
+            .map_err(|err| ErrorCode::Other(format!("remote segment fetch error: {err:#?}")))?;
+        let reader = self.read().await;
+        match reader.find_fetched_segment(start_offset) {
+            Some(segment) => segment_slice(segment, start_offset, max_offset).await,
+            None => Err(ErrorCode::Other(format!(
                 "Segment not found for start_offset: {start_offset}"
-            )))
+            ))),
         }
     }
 
+    /// download remote segment unless it is already fetched
+    async fn fetch_segment(&self, remote: &RemoteTier, segment: &RemoteSegment) -> Result<()> {
+        let _guard = remote.fetch_lock().lock().await;
+        if self.read().await.fetched.contains_key(&segment.base_offset) {
+            return Ok(());
+        }
+        let fetched = remote.fetch(segment).await?;
+        self.write()
+            .await
+            .fetched
+            .insert(segment.base_offset, fetched);
+        Ok(())
+    }
+
+    /// find first offset with records at or after timestamp, segments are searched from oldest
+    pub(crate) async fn find_offset_by_timestamp(
+        &self,
+        timestamp: Timestamp,
+    ) -> Result<Option<Offset>> {
+        // segments are not locked while they are searched, so writers are not blocked by scans
+        let searches: Vec<_> = self
+            .read()
+            .await
+            .segments
+            .values()
+            .map(ReadSegment::timestamp_search)
+            .collect();
+        for search in searches {
+            if let Some(offset) = search.find_offset(timestamp).await? {
+                return Ok(Some(offset));
+            }
+        }
+        Ok(None)
+    }
+
     #[instrument(skip(self))]
     async fn remove_segment(&self, base_offset: &Offset) {
         let mut write = self.write().await;
@@ -151,13 +380,47 @@ impl SharedSegments {
             if let Err(err) = old_segment.remove().await {
                 error!("failed to remove segment: {:#?}", err);
             }
+        } else if let Some((remote_segment, fetched, min_offset)) =
+            write.remove_remote_segment(base_offset)
+        {
+            drop(write);
+            self.min_offset.store(min_offset, MEM_ORDER);
+            if let Some(fetched) = fetched {
+                if let Err(err) = fetched.remove().await {
+                    error!("failed to remove fetched segment: {:#?}", err);
+                }
+            }
+            match &self.remote {
+                Some(remote) if self.is_leader() => {
+                    if let Err(err) = remote.delete(&remote_segment).await {
+                        error!("failed to remove remote segment: {:#?}", err);
+                    }
+                }
+                _ => {}
+            }
         }
     }
 }
 
+async fn segment_slice(
+    segment: &ReadSegment,
+    start_offset: Offset,
+    max_offset: Option<Offset>,
+) -> Result<AsyncFileSlice, ErrorCode> {
+    if let Some(slice) = segment.records_slice(start_offset, max_offset).await? {
+        Ok(slice)
+    } else {
+        Err(ErrorCode::Other(format!(
+            "slice not found in start_offset: {start_offset}, segment: {segment:#?} "
+        )))
+    }
+}
+
 #[derive(Debug)]
 pub struct SegmentList {
     segments: BTreeMap<Offset, ReadSegment>, // max base offset of all segments
+    remote: BTreeMap<Offset, RemoteSegment>, // segments offloaded to remote tier
+    fetched: BTreeMap<Offset, ReadSegment>,  // remote segments cached locally
     min_offset: Offset,
     max_offset: Offset,
 }
@@ -166,6 +429,8 @@ impl SegmentList {
     pub fn new() -> Self {
         SegmentList {
             segments: BTreeMap::new(),
+            remote: BTreeMap::new(),
+            fetched: BTreeMap::new(),
             max_offset: 0,
             min_offset: -1,
         }
@@ -176,6 +441,11 @@ impl SegmentList {
         self.segments.len()
     }
 
+    /// end offset of last segment
+    pub(crate) fn max_offset(&self) -> Offset {
+        self.max_offset
+    }
+
     pub fn occupied_memory(&self) -> Size64 {
         self.segments
             .values()
@@ -183,8 +453,9 @@ impl SegmentList {
             .sum()
     }
 
+    /// add segment, existing segment with same base offset is replaced
     #[instrument(skip(self, segment))]
-    fn add_segment(&mut self, segment: ReadSegment) -> Offset {
+    pub(crate) fn add_segment(&mut self, segment: ReadSegment) -> Offset {
         debug!(
             base_offset = segment.get_base_offset(),
             end_offset = segment.get_end_offset(),
@@ -195,12 +466,30 @@ impl SegmentList {
         self.min_offset
     }
 
+    /// add segment offloaded to remote tier
+    pub(crate) fn add_remote_segment(&mut self, segment: RemoteSegment) -> Offset {
+        debug!(
+            base_offset = segment.base_offset,
+            end_offset = segment.end_offset,
+            "inserting remote"
+        );
+        self.remote.insert(segment.base_offset, segment);
+        self.update_min_max();
+        self.min_offset
+    }
+
     fn update_min_max(&mut self) {
         let mut max_offset = 0;
         let mut min_offset = -1;
-        self.segments.values().for_each(|segment| {
-            let base_offset = segment.get_base_offset();
-            let end_offset = segment.get_end_offset();
+        let local = self
+            .segments
+            .values()
+            .map(|segment| (segment.get_base_offset(), segment.get_end_offset()));
+        let remote = self
+            .remote
+            .values()
+            .map(|segment| (segment.base_offset, segment.end_offset));
+        local.chain(remote).for_each(|(base_offset, end_offset)| {
             if end_offset > max_offset {
                 max_offset = end_offset;
             }
@@ -222,6 +511,71 @@ impl SegmentList {
         }
     }
 
+    /// remove remote segment together with its fetched copy and return min offset
+    fn remove_remote_segment(
+        &mut self,
+        offset: &Offset,
+    ) -> Option<(RemoteSegment, Option<ReadSegment>, Offset)> {
+        if let Some(segment) = self.remote.remove(offset) {
+            let fetched = self.fetched.remove(offset);
+            self.update_min_max();
+            Some((segment, fetched, self.min_offset))
+        } else {
+            None
+        }
+    }
+
+    /// end offset of last local segment
+    fn local_end_offset(&self) -> Offset {
+        self.segments
+            .values()
+            .map(ReadSegment::get_end_offset)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// are all records in offset range in remote segments
+    fn remote_covers(&self, base_offset: Offset, end_offset: Offset) -> bool {
+        let mut covered = base_offset;
+        for segment in self.remote.values() {
+            if segment.base_offset <= covered && covered < segment.end_offset {
+                covered = segment.end_offset;
+            }
+        }
+        covered >= end_offset
+    }
+
+    /// remote segment containing offset
+    fn find_remote_segment(&self, offset: Offset) -> Option<&RemoteSegment> {
+        self.remote
+            .range(..=offset)
+            .next_back()
+            .map(|(_, segment)| segment)
+            .filter(|segment| offset < segment.end_offset)
+    }
+
+    /// fetched remote segment containing offset
+    fn find_fetched_segment(&self, offset: Offset) -> Option<&ReadSegment> {
+        self.fetched
+            .range(..=offset)
+            .next_back()
+            .map(|(_, segment)| segment)
+            .filter(|segment| offset < segment.get_end_offset())
+    }
+
+    fn take_expired_fetched(&mut self, expired_duration: &Duration) -> Vec<ReadSegment> {
+        let expired: Vec<Offset> = self
+            .fetched
+            .iter()
+            .filter(|(_, segment)| segment.is_expired(expired_duration))
+            .map(|(base_offset, _)| *base_offset)
+            .collect();
+        expired
+            .iter()
+            .filter_map(|base_offset| self.fetched.remove(base_offset))
+            .collect()
+    }
+
     #[cfg(test)]
     #[cfg(feature = "fixture")]
     pub fn get_segment(&self, offset: Offset) -> Option<&ReadSegment> {
@@ -260,6 +614,23 @@ impl SegmentList {
             .collect()
     }
 
+    /// base and end offsets of all segments
+    pub(crate) fn offset_ranges(&self) -> Vec<(Offset, Offset)> {
+        self.segments
+            .values()
+            .map(|segment| (segment.get_base_offset(), segment.get_end_offset()))
+            .collect()
+    }
+
+    /// remote segments which were uploaded before expired duration
+    pub(crate) fn find_expired_remote_segments(&self, expired_duration: &Duration) -> Vec<Offset> {
+        self.remote
+            .iter()
+            .filter(|(_, segment)| segment.is_expired(expired_duration))
+            .map(|(base_offset, _)| *base_offset)
+            .collect()
+    }
+
     #[instrument(skip(self))]
     pub(crate) fn find_first(&self, count: usize) -> Vec<Offset> {
         self.segments.keys().take(count).copied().collect()
@@ -0,0 +1,100 @@
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+use anyhow::Result;
+use blocking::unblock;
+
+use super::{RemoteObject, RemoteStore};
+
+/// suffix of files being written, they are not listed
+const TMP_SUFFIX: &str = ".tmp";
+
+/// Remote store in local directory, useful for testing or with network volume
+#[derive(Debug, Clone)]
+pub struct LocalDirStore {
+    dir: PathBuf,
+}
+
+impl LocalDirStore {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+}
+
+#[async_trait]
+impl RemoteStore for LocalDirStore {
+    async fn put(&self, key: &str, path: &Path) -> Result<()> {
+        let target = self.dir.join(key);
+        let source = path.to_path_buf();
+        unblock(move || {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // copy to temporary file first so partial object is never listed
+            let mut tmp = target.clone().into_os_string();
+            tmp.push(TMP_SUFFIX);
+            fs::copy(source, &tmp)?;
+            fs::rename(&tmp, target)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn get(&self, key: &str, path: &Path) -> Result<()> {
+        let source = self.dir.join(key);
+        let target = path.to_path_buf();
+        unblock(move || {
+            fs::copy(source, target)?;
+            Ok(())
+        })
+        .await
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        let path = self.dir.join(key);
+        unblock(move || match fs::remove_file(path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err.into()),
+            _ => Ok(()),
+        })
+        .await
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<RemoteObject>> {
+        let (dir_prefix, name_prefix) = prefix.rsplit_once('/').unwrap_or(("", prefix));
+        let dir = self.dir.join(dir_prefix);
+        let dir_prefix = dir_prefix.to_owned();
+        let name_prefix = name_prefix.to_owned();
+        unblock(move || {
+            let entries = match fs::read_dir(dir) {
+                Ok(entries) => entries,
+                Err(err) if err.kind() == ErrorKind::NotFound => return Ok(vec![]),
+                Err(err) => return Err(err.into()),
+            };
+            let mut objects = vec![];
+            for entry in entries {
+                let entry = entry?;
+                let metadata = entry.metadata()?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if !metadata.is_file()
+                    || !name.starts_with(&name_prefix)
+                    || name.ends_with(TMP_SUFFIX)
+                {
+                    continue;
+                }
+                let key = if dir_prefix.is_empty() {
+                    name
+                } else {
+                    format!("{dir_prefix}/{name}")
+                };
+                objects.push(RemoteObject {
+                    key,
+                    modified: metadata.modified()?,
+                });
+            }
+            Ok(objects)
+        })
+        .await
+    }
+}
@@ -0,0 +1,472 @@
+//! Remote tier of replica storage.
+//!
+//! Closed segments are uploaded to object store once they are older than configured age and
+//! then removed from local disk. When records of offloaded segment are read, segment is fetched
+//! back into local cache directory of replica, where it is kept for same age.
+//! Replicas of partition share its remote segments: only leader uploads and deletes them,
+//! followers drop local copies of segments once leader has uploaded them.
+
+mod local;
+mod s3;
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_lock::Mutex;
+use async_trait::async_trait;
+use anyhow::Result;
+use serde::Deserialize;
+use tracing::{debug, error};
+
+use fluvio_future::fs::{create_dir_all, remove_dir_all};
+use fluvio_protocol::record::Offset;
+
+use crate::config::SharedReplicaConfig;
+use crate::index::EXTENSION as INDEX_EXTENSION;
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::segment::ReadSegment;
+use crate::util::generate_file_name;
+
+pub use local::LocalDirStore;
+pub use s3::{S3Config, S3Store};
+
+/// directory under replica where fetched remote segments are cached
+const REMOTE_CACHE_DIR: &str = ".remote-cache";
+
+/// Object stored in remote store
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RemoteObject {
+    pub key: String,
+    /// time when object was stored
+    pub modified: SystemTime,
+}
+
+/// Object store which closed segments are offloaded to.
+/// Keys are relative paths separated by `/`.
+#[async_trait]
+pub trait RemoteStore: fmt::Debug + Send + Sync {
+    /// store content of local file under key, existing object is replaced
+    async fn put(&self, key: &str, path: &Path) -> Result<()>;
+
+    /// write content of object to local file
+    async fn get(&self, key: &str, path: &Path) -> Result<()>;
+
+    /// remove object, removing object which doesn't exist is not an error
+    async fn delete(&self, key: &str) -> Result<()>;
+
+    /// all objects with key starting with prefix
+    async fn list(&self, prefix: &str) -> Result<Vec<RemoteObject>>;
+}
+
+/// Remote store used by replicas
+#[derive(Debug, Clone, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteStoreConfig {
+    /// directory, for example on network volume
+    Local { dir: PathBuf },
+    /// S3 compatible object store
+    S3(S3Config),
+}
+
+impl RemoteStoreConfig {
+    pub fn build(&self) -> Arc<dyn RemoteStore> {
+        match self {
+            Self::Local { dir } => Arc::new(LocalDirStore::new(dir.clone())),
+            Self::S3(config) => Arc::new(S3Store::new(config.clone())),
+        }
+    }
+}
+
+/// Segment which was offloaded to remote store
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(crate) struct RemoteSegment {
+    pub base_offset: Offset,
+    pub end_offset: Offset,
+    /// time of newest record, age of remote segment is counted from it same as for local one
+    pub max_timestamp: SystemTime,
+}
+
+impl RemoteSegment {
+    pub(crate) fn is_expired(&self, expired_duration: &Duration) -> bool {
+        self.max_timestamp
+            .elapsed()
+            .map(|elapsed| elapsed > *expired_duration)
+            .unwrap_or(false)
+    }
+}
+
+/// Segments of single replica in remote store.
+/// Each segment is stored as log and index objects under prefix unique to partition, offset range
+/// and max timestamp of segment are part of key so segments can be listed without fetching them.
+#[derive(Debug)]
+pub(crate) struct RemoteTier {
+    store: Arc<dyn RemoteStore>,
+    option: Arc<SharedReplicaConfig>,
+    prefix: String,
+    cache_dir: PathBuf,
+    /// only one segment is fetched at time so same segment is never downloaded twice
+    fetch_lock: Mutex<()>,
+}
+
+impl RemoteTier {
+    pub(crate) fn new(store: Arc<dyn RemoteStore>, option: Arc<SharedReplicaConfig>) -> Self {
+        Self {
+            store,
+            prefix: remote_prefix(&option.base_dir),
+            cache_dir: option.base_dir.join(REMOTE_CACHE_DIR),
+            option,
+            fetch_lock: Mutex::new(()),
+        }
+    }
+
+    pub(crate) fn fetch_lock(&self) -> &Mutex<()> {
+        &self.fetch_lock
+    }
+
+    fn key(&self, segment: &RemoteSegment, extension: &str) -> String {
+        let max_timestamp = segment
+            .max_timestamp
+            .duration_since(UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_millis())
+            .unwrap_or_default();
+        format!(
+            "{}/{:020}-{:020}-{max_timestamp:020}.{extension}",
+            self.prefix, segment.base_offset, segment.end_offset
+        )
+    }
+
+    /// segments in remote store, segment is listed only if its upload was completed
+    pub(crate) async fn list_segments(&self) -> Result<Vec<RemoteSegment>> {
+        let objects = self.store.list(&format!("{}/", self.prefix)).await?;
+        Ok(objects
+            .into_iter()
+            .filter_map(|object| parse_log_key(&object.key))
+            .collect())
+    }
+
+    /// upload local segment
+    pub(crate) async fn upload(&self, segment: &RemoteSegment) -> Result<()> {
+        // log is uploaded last as its presence marks complete segment
+        for extension in [INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+            let path = generate_file_name(&self.option.base_dir, segment.base_offset, extension);
+            self.store.put(&self.key(segment, extension), &path).await?;
+        }
+        debug!(
+            base_offset = segment.base_offset,
+            end_offset = segment.end_offset,
+            "segment uploaded"
+        );
+        Ok(())
+    }
+
+    /// download remote segment into cache directory and open it
+    pub(crate) async fn fetch(&self, segment: &RemoteSegment) -> Result<ReadSegment> {
+        create_dir_all(&self.cache_dir).await?;
+        for extension in [INDEX_EXTENSION, MESSAGE_LOG_EXTENSION] {
+            let path = generate_file_name(&self.cache_dir, segment.base_offset, extension);
+            self.store.get(&self.key(segment, extension), &path).await?;
+        }
+        debug!(base_offset = segment.base_offset, "segment fetched");
+        let cache_option = self.option.with_base_dir(self.cache_dir.clone());
+        ReadSegment::open_for_read(
+            segment.base_offset,
+            segment.end_offset,
+            Arc::new(cache_option),
+        )
+        .await
+    }
+
+    pub(crate) async fn delete(&self, segment: &RemoteSegment) -> Result<()> {
+        // log is removed first so partially removed segment is not listed
+        for extension in [MESSAGE_LOG_EXTENSION, INDEX_EXTENSION] {
+            self.store.delete(&self.key(segment, extension)).await?;
+        }
+        Ok(())
+    }
+
+    /// remove segments fetched before restart
+    pub(crate) async fn clear_cache(&self) {
+        if self.cache_dir.exists() {
+            if let Err(err) = remove_dir_all(&self.cache_dir).await {
+                error!(%err, dir = %self.cache_dir.display(), "failed to clear remote cache");
+            }
+        }
+    }
+}
+
+/// prefix of partition in remote store is `topic/partition`, taken from replica directory,
+/// so it is same on every SPU hosting replica of partition
+fn remote_prefix(base_dir: &Path) -> String {
+    let name = base_dir
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.rsplit_once('-') {
+        Some((topic, partition)) => format!("{topic}/{partition}"),
+        None => name,
+    }
+}
+
+/// segment from key of its log
+fn parse_log_key(key: &str) -> Option<RemoteSegment> {
+    let name = key.rsplit('/').next()?;
+    let mut parts = name
+        .strip_suffix(MESSAGE_LOG_EXTENSION)?
+        .strip_suffix('.')?
+        .split('-');
+    let base_offset = parts.next()?.parse().ok()?;
+    let end_offset = parts.next()?.parse().ok()?;
+    let max_timestamp = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(RemoteSegment {
+        base_offset,
+        end_offset,
+        max_timestamp: UNIX_EPOCH + Duration::from_millis(max_timestamp),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+    use std::ffi::OsStr;
+    use std::path::Path;
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+    use flv_util::fixture::ensure_new_dir;
+    use fluvio_protocol::fixture::create_batch;
+
+    use crate::config::ReplicaConfig;
+    use crate::segment::MutableSegment;
+    use crate::segments::SharedSegments;
+
+    use super::{parse_log_key, remote_prefix, RemoteSegment, RemoteStoreConfig};
+
+    #[test]
+    fn test_remote_keys() {
+        assert_eq!(
+            remote_prefix(Path::new("/var/fluvio/spu-logs-5001/orders-0")),
+            "orders/0"
+        );
+        assert_eq!(
+            remote_prefix(Path::new("/var/fluvio/spu-logs-5002/user-events-12")),
+            "user-events/12"
+        );
+        assert_eq!(
+            parse_log_key(
+                "orders/0/00000000000000000100-00000000000000000600-00000001700000000000.log"
+            ),
+            Some(RemoteSegment {
+                base_offset: 100,
+                end_offset: 600,
+                max_timestamp: UNIX_EPOCH + Duration::from_millis(1_700_000_000_000),
+            })
+        );
+        assert!(parse_log_key(
+            "orders/0/00000000000000000100-00000000000000000600-00000001700000000000.index"
+        )
+        .is_none());
+        assert!(parse_log_key("orders/0/00000000000000000100-00000000000000000600.log").is_none());
+    }
+
+    #[fluvio_future::test]
+    async fn test_offload_and_fetch_segment() {
+        let rep_dir = temp_dir().join("tiered-offload").join("orders-0");
+        let remote_dir = temp_dir().join("tiered-offload-remote");
+        ensure_new_dir(&rep_dir).expect("new");
+        ensure_new_dir(&remote_dir).expect("new");
+
+        let option = ReplicaConfig {
+            base_dir: rep_dir.clone(),
+            segment_max_bytes: 1000,
+            index_max_bytes: 1000,
+            index_max_interval_bytes: 0,
+            remote: Some(RemoteStoreConfig::Local {
+                dir: remote_dir.clone(),
+            }),
+            ..Default::default()
+        }
+        .shared();
+
+        // closed segment with 2 records written 2 hours ago and active one
+        let max_timestamp = SystemTime::now() - Duration::from_secs(7200);
+        let max_timestamp_ms = max_timestamp
+            .duration_since(UNIX_EPOCH)
+            .expect("epoch")
+            .as_millis() as i64;
+        let remote_log = format!(
+            "orders/0/00000000000000000000-00000000000000000002-{max_timestamp_ms:020}.log"
+        );
+        let mut batch = create_batch();
+        batch.get_mut_header().first_timestamp = max_timestamp_ms;
+        batch.get_mut_header().max_time_stamp = max_timestamp_ms;
+        let mut segment = MutableSegment::create(0, option.clone())
+            .await
+            .expect("create");
+        segment.append_batch(&mut batch).await.expect("append");
+        let closed = segment.convert_to_segment().await.expect("convert");
+        MutableSegment::create(2, option.clone())
+            .await
+            .expect("create");
+
+        let (segments, last_offset) = SharedSegments::from_dir(option.clone())
+            .await
+            .expect("from");
+        assert_eq!(last_offset, Some(2));
+        assert_eq!(segments.read().await.len(), 1);
+        drop(closed);
+
+        segments.set_leader(true);
+        segments.offload_segments(&[0]).await.expect("offload");
+        assert_eq!(segments.read().await.len(), 0);
+        assert_eq!(segments.min_offset(), 0);
+        assert!(!rep_dir.join("00000000000000000000.log").exists());
+        assert!(remote_dir.join(&remote_log).exists());
+
+        // remote segment age is counted from its records, not from upload
+        let read = segments.read().await;
+        assert_eq!(
+            read.find_expired_remote_segments(&Duration::from_secs(3600)),
+            vec![0]
+        );
+        assert!(read
+            .find_expired_remote_segments(&Duration::from_secs(3 * 3600))
+            .is_empty());
+        drop(read);
+
+        // segment is fetched on read
+        let slice = segments.find_slice(1, None).await.expect("slice");
+        assert!(slice.len() > 0);
+        assert!(rep_dir
+            .join(".remote-cache/00000000000000000000.log")
+            .exists());
+
+        // remote segment is found after restart
+        let (segments, _) = SharedSegments::from_dir(option).await.expect("from");
+        assert_eq!(segments.read().await.len(), 0);
+        assert_eq!(segments.min_offset(), 0);
+        assert!(!rep_dir.join(".remote-cache").exists());
+
+        segments.set_leader(true);
+        segments.remove_segments(&[0]).await;
+        assert_eq!(segments.min_offset(), -1);
+        assert!(!remote_dir.join(&remote_log).exists());
+    }
+
+    #[fluvio_future::test]
+    async fn test_follower_offload() {
+        let leader_dir = temp_dir().join("tiered-leader").join("orders-0");
+        let follower_dir = temp_dir().join("tiered-follower").join("orders-0");
+        let remote_dir = temp_dir().join("tiered-follower-remote");
+        ensure_new_dir(&remote_dir).expect("new");
+
+        // leader and follower have same closed segment with 2 records and active one
+        let mut replicas = vec![];
+        for rep_dir in [&leader_dir, &follower_dir] {
+            ensure_new_dir(rep_dir).expect("new");
+            let option = ReplicaConfig {
+                base_dir: rep_dir.clone(),
+                segment_max_bytes: 1000,
+                index_max_bytes: 1000,
+                index_max_interval_bytes: 0,
+                remote: Some(RemoteStoreConfig::Local {
+                    dir: remote_dir.clone(),
+                }),
+                ..Default::default()
+            }
+            .shared();
+            let mut segment = MutableSegment::create(0, option.clone())
+                .await
+                .expect("create");
+            segment
+                .append_batch(&mut create_batch())
+                .await
+                .expect("append");
+            drop(segment.convert_to_segment().await.expect("convert"));
+            MutableSegment::create(2, option.clone())
+                .await
+                .expect("create");
+            let (segments, _) = SharedSegments::from_dir(option).await.expect("from");
+            replicas.push(segments);
+        }
+        let follower = replicas.pop().expect("follower");
+        let leader = replicas.pop().expect("leader");
+        leader.set_leader(true);
+
+        // follower keeps segment until leader uploads it
+        follower.offload_segments(&[0]).await.expect("offload");
+        assert_eq!(follower.read().await.len(), 1);
+        assert!(std::fs::read_dir(&remote_dir)
+            .expect("read")
+            .next()
+            .is_none());
+
+        leader.offload_segments(&[0]).await.expect("offload");
+        assert_eq!(leader.read().await.len(), 0);
+
+        // uploaded segment is only removed from follower disk
+        follower.offload_segments(&[0]).await.expect("offload");
+        assert_eq!(follower.read().await.len(), 0);
+        assert_eq!(follower.min_offset(), 0);
+        assert!(!follower_dir.join("00000000000000000000.log").exists());
+        let slice = follower.find_slice(1, None).await.expect("slice");
+        assert!(slice.len() > 0);
+
+        // follower doesn't remove segment shared with leader
+        follower.remove_segments(&[0]).await;
+        assert_eq!(follower.min_offset(), -1);
+        assert_eq!(leader.min_offset(), 0);
+        let remote_logs = std::fs::read_dir(remote_dir.join("orders/0"))
+            .expect("read")
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension() == Some(OsStr::new("log")))
+            .count();
+        assert_eq!(remote_logs, 1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_remote_listed_once_reachable() {
+        let rep_dir = temp_dir().join("tiered-unlisted").join("orders-0");
+        let remote_dir = temp_dir().join("tiered-unlisted-remote");
+        ensure_new_dir(&rep_dir).expect("new");
+        ensure_new_dir(&remote_dir.join("orders")).expect("new");
+        let remote_replica_dir = remote_dir.join("orders/0");
+
+        let option = ReplicaConfig {
+            base_dir: rep_dir,
+            remote: Some(RemoteStoreConfig::Local {
+                dir: remote_dir.clone(),
+            }),
+            ..Default::default()
+        }
+        .shared();
+
+        // listing fails as replica prefix is not directory
+        std::fs::write(&remote_replica_dir, b"").expect("write");
+        let (segments, _) = SharedSegments::from_dir(option).await.expect("from");
+        assert_eq!(segments.min_offset(), -1);
+        assert!(segments.list_remote_segments().await.is_err());
+
+        std::fs::remove_file(&remote_replica_dir).expect("remove");
+        ensure_new_dir(&remote_replica_dir).expect("new");
+        std::fs::write(
+            remote_replica_dir
+                .join("00000000000000000000-00000000000000000002-00000001700000000000.log"),
+            b"",
+        )
+        .expect("write");
+        segments.list_remote_segments().await.expect("list");
+        assert_eq!(segments.min_offset(), 0);
+        assert_eq!(
+            segments
+                .read()
+                .await
+                .find_expired_remote_segments(&Duration::from_secs(3600)),
+            vec![0]
+        );
+    }
+}
@@ -0,0 +1,321 @@
+use std::fmt;
+use std::path::Path;
+use std::time::SystemTime;
+
+use async_trait::async_trait;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use futures_lite::io::{copy, BufReader};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use surf::http::Method;
+use surf::{Body, Client, Request, Response, Url};
+use tracing::debug;
+
+use fluvio_future::fs::File;
+
+use super::{RemoteObject, RemoteStore};
+
+/// payload is not signed, so segments can be streamed without hashing them first
+const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
+
+const SIGNED_HEADERS: &str = "host;x-amz-content-sha256;x-amz-date";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Location and credentials of S3 compatible store
+#[derive(Clone, Eq, PartialEq, Deserialize)]
+pub struct S3Config {
+    /// url of store, buckets are addressed by path, e.g. `https://s3.us-east-1.amazonaws.com`
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl fmt::Debug for S3Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("S3Config")
+            .field("endpoint", &self.endpoint)
+            .field("region", &self.region)
+            .field("bucket", &self.bucket)
+            .field("access_key_id", &self.access_key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Remote store using S3 API, requests are signed with AWS signature version 4
+pub struct S3Store {
+    config: S3Config,
+    client: Client,
+}
+
+impl fmt::Debug for S3Store {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "S3Store({}/{})",
+            self.config.endpoint, self.config.bucket
+        )
+    }
+}
+
+impl S3Store {
+    pub fn new(config: S3Config) -> Self {
+        Self {
+            config,
+            client: Client::new(),
+        }
+    }
+
+    /// signed request for object key, or for bucket if key is empty
+    fn request(&self, method: Method, key: &str, query: &[(&str, &str)]) -> Result<Request> {
+        let mut path = format!("/{}", uri_encode(&self.config.bucket, false));
+        if !key.is_empty() {
+            path.push('/');
+            path.push_str(&uri_encode(key, false));
+        }
+        let mut query: Vec<String> = query
+            .iter()
+            .map(|(name, value)| format!("{}={}", uri_encode(name, true), uri_encode(value, true)))
+            .collect();
+        query.sort();
+        let query = query.join("&");
+
+        let mut url = Url::parse(&self.config.endpoint)?;
+        url.set_path(&path);
+        if !query.is_empty() {
+            url.set_query(Some(&query));
+        }
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_owned(),
+            (None, _) => return Err(anyhow!("invalid S3 endpoint: {}", self.config.endpoint)),
+        };
+
+        let now = Utc::now();
+        let authorization = self.authorization(&method, &path, &query, &host, now);
+        let mut request = Request::new(method, url);
+        request.set_header("host", host);
+        request.set_header("x-amz-content-sha256", UNSIGNED_PAYLOAD);
+        request.set_header("x-amz-date", amz_date(now));
+        request.set_header("authorization", authorization);
+        Ok(request)
+    }
+
+    fn authorization(
+        &self,
+        method: &Method,
+        path: &str,
+        query: &str,
+        host: &str,
+        now: DateTime<Utc>,
+    ) -> String {
+        let amz_date = amz_date(now);
+        let date = now.format("%Y%m%d").to_string();
+        let canonical_request = format!(
+            "{method}\n{path}\n{query}\nhost:{host}\nx-amz-content-sha256:{UNSIGNED_PAYLOAD}\nx-amz-date:{amz_date}\n\n{SIGNED_HEADERS}\n{UNSIGNED_PAYLOAD}"
+        );
+        let scope = format!("{date}/{}/s3/aws4_request", self.config.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let mut key = hmac(
+            format!("AWS4{}", self.config.secret_access_key).as_bytes(),
+            date.as_bytes(),
+        );
+        for part in [self.config.region.as_str(), "s3", "aws4_request"] {
+            key = hmac(&key, part.as_bytes());
+        }
+        let signature = hex(&hmac(&key, string_to_sign.as_bytes()));
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={SIGNED_HEADERS}, Signature={signature}",
+            self.config.access_key_id
+        )
+    }
+
+    async fn send(&self, request: Request) -> Result<Response> {
+        let mut response = self
+            .client
+            .send(request)
+            .await
+            .map_err(|err| anyhow!("S3 request failed: {err}"))?;
+        if response.status().is_success() {
+            Ok(response)
+        } else {
+            let body = response.body_string().await.unwrap_or_default();
+            Err(anyhow!("S3 request failed: {}, {body}", response.status()))
+        }
+    }
+}
+
+#[async_trait]
+impl RemoteStore for S3Store {
+    async fn put(&self, key: &str, path: &Path) -> Result<()> {
+        let file = File::open(path).await?;
+        let len = file.metadata().await?.len() as usize;
+        let mut request = self.request(Method::Put, key, &[])?;
+        request.set_body(Body::from_reader(BufReader::new(file), Some(len)));
+        self.send(request).await?;
+        debug!(key, len, "object stored");
+        Ok(())
+    }
+
+    async fn get(&self, key: &str, path: &Path) -> Result<()> {
+        let response = self.send(self.request(Method::Get, key, &[])?).await?;
+        let mut file = File::create(path).await?;
+        copy(response, &mut file).await?;
+        file.sync_all().await?;
+        Ok(())
+    }
+
+    async fn delete(&self, key: &str) -> Result<()> {
+        self.send(self.request(Method::Delete, key, &[])?).await?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<RemoteObject>> {
+        let mut objects = vec![];
+        let mut continuation_token: Option<String> = None;
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", prefix)];
+            if let Some(token) = &continuation_token {
+                query.push(("continuation-token", token.as_str()));
+            }
+            let mut response = self.send(self.request(Method::Get, "", &query)?).await?;
+            let body = response
+                .body_string()
+                .await
+                .map_err(|err| anyhow!("invalid S3 list response: {err}"))?;
+            let (page, next) = parse_list_response(&body)?;
+            objects.extend(page);
+            match next {
+                Some(token) => continuation_token = Some(token),
+                None => return Ok(objects),
+            }
+        }
+    }
+}
+
+/// objects of ListObjectsV2 response, with continuation token if response is truncated
+fn parse_list_response(body: &str) -> Result<(Vec<RemoteObject>, Option<String>)> {
+    let mut objects = vec![];
+    for contents in xml_elements(body, "Contents") {
+        let key = xml_elements(contents, "Key")
+            .next()
+            .ok_or_else(|| anyhow!("S3 object without key"))?;
+        let modified = xml_elements(contents, "LastModified")
+            .next()
+            .map(DateTime::parse_from_rfc3339)
+            .transpose()?
+            .map(SystemTime::from)
+            .unwrap_or_else(SystemTime::now);
+        objects.push(RemoteObject {
+            key: key.to_owned(),
+            modified,
+        });
+    }
+    let truncated = xml_elements(body, "IsTruncated").next() == Some("true");
+    let next = xml_elements(body, "NextContinuationToken")
+        .next()
+        .filter(|_| truncated)
+        .map(str::to_owned);
+    Ok((objects, next))
+}
+
+/// content of all elements with tag, elements are not expected to be nested
+fn xml_elements<'a>(xml: &'a str, tag: &str) -> impl Iterator<Item = &'a str> {
+    let open = format!("<{tag}>");
+    let close = format!("</{tag}>");
+    let mut rest = xml;
+    std::iter::from_fn(move || {
+        let start = rest.find(&open)? + open.len();
+        let end = start + rest[start..].find(&close)?;
+        let content = &rest[start..end];
+        rest = &rest[end + close.len()..];
+        Some(content)
+    })
+}
+
+fn amz_date(now: DateTime<Utc>) -> String {
+    now.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("hmac accepts any key length");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// percent encoding required by signature, slash is kept in paths
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::{parse_list_response, uri_encode};
+
+    #[test]
+    fn test_uri_encode() {
+        assert_eq!(
+            uri_encode("spu-logs-0/orders-0/", false),
+            "spu-logs-0/orders-0/"
+        );
+        assert_eq!(
+            uri_encode("spu-logs-0/orders-0/", true),
+            "spu-logs-0%2Forders-0%2F"
+        );
+        assert_eq!(uri_encode("a b+c", true), "a%20b%2Bc");
+    }
+
+    #[test]
+    fn test_parse_list_response() {
+        let body = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>fluvio</Name>
+  <Prefix>spu-logs-0/orders-0/</Prefix>
+  <KeyCount>2</KeyCount>
+  <IsTruncated>true</IsTruncated>
+  <NextContinuationToken>token-1</NextContinuationToken>
+  <Contents>
+    <Key>spu-logs-0/orders-0/00000000000000000000-00000000000000000100.index</Key>
+    <LastModified>2023-03-01T10:00:00.000Z</LastModified>
+    <Size>80</Size>
+  </Contents>
+  <Contents>
+    <Key>spu-logs-0/orders-0/00000000000000000000-00000000000000000100.log</Key>
+    <LastModified>2023-03-01T10:00:01.000Z</LastModified>
+    <Size>4096</Size>
+  </Contents>
+</ListBucketResult>"#;
+
+        let (objects, next) = parse_list_response(body).expect("parse");
+        assert_eq!(objects.len(), 2);
+        assert_eq!(
+            objects[1].key,
+            "spu-logs-0/orders-0/00000000000000000000-00000000000000000100.log"
+        );
+        assert!(objects[0].modified < objects[1].modified);
+        assert_eq!(next.as_deref(), Some("token-1"));
+    }
+}
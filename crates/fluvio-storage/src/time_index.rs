@@ -0,0 +1,201 @@
+use std::io::{Error as IoError, ErrorKind, SeekFrom};
+use std::path::Path;
+
+use futures_lite::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use fluvio_future::fs::File;
+use fluvio_future::fs::util;
+use fluvio_protocol::record::Size;
+use fluvio_types::Timestamp;
+
+pub const EXTENSION: &str = "timeindex";
+
+/// timestamp and file position
+const ENTRY_SIZE: usize = 12;
+
+const NO_TIMESTAMP: Timestamp = -1;
+
+/// Sparse time index of segment.
+/// Log is divided into blocks of at least `index_max_interval_bytes`. Each entry consist of
+/// pair of (max timestamp up to end of block, file position where block ends).
+/// Because max timestamp includes all previous blocks, entries are sorted by both
+/// timestamp and position even if producers clocks are not in sync.
+/// Entries of completed blocks are persisted in time index file of segment,
+/// last block is only kept in memory and rebuilt by scanning its batches.
+#[derive(Debug)]
+pub(crate) struct TimeIndex {
+    entries: Vec<(Timestamp, Size)>,
+    /// position where last block starts
+    tail_start: Size,
+    max_timestamp: Timestamp,
+    /// time index file, only open for active segment
+    file: Option<File>,
+}
+
+impl Default for TimeIndex {
+    fn default() -> Self {
+        Self {
+            entries: vec![],
+            tail_start: 0,
+            max_timestamp: NO_TIMESTAMP,
+            file: None,
+        }
+    }
+}
+
+impl TimeIndex {
+    /// load persisted entries. Entries beyond log length are discarded,
+    /// so index stays consistent with log truncated after crash
+    pub(crate) async fn load(path: &Path, log_len: Size) -> Result<Self, IoError> {
+        let mut index = Self::default();
+        let mut contents = Vec::new();
+        match File::open(path).await {
+            Ok(mut file) => {
+                file.read_to_end(&mut contents).await?;
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(index),
+            Err(err) => return Err(err),
+        }
+
+        for entry in contents.chunks_exact(ENTRY_SIZE) {
+            let mut timestamp = [0; 8];
+            timestamp.copy_from_slice(&entry[..8]);
+            let mut pos = [0; 4];
+            pos.copy_from_slice(&entry[8..]);
+            let timestamp = Timestamp::from_be_bytes(timestamp);
+            let pos = Size::from_be_bytes(pos);
+            if pos <= index.tail_start || pos > log_len {
+                break;
+            }
+            index.entries.push((timestamp, pos));
+            index.tail_start = pos;
+            index.max_timestamp = timestamp;
+        }
+        Ok(index)
+    }
+
+    /// open time index file so completed blocks are persisted as batches are added.
+    /// Entries discarded while loading are removed from file
+    pub(crate) async fn open_file(&mut self, path: &Path) -> Result<(), IoError> {
+        let mut file = util::open_read_write(path).await?;
+        let len = (self.entries.len() * ENTRY_SIZE) as u64;
+        file.set_len(len).await?;
+        file.seek(SeekFrom::Start(len)).await?;
+        self.file = Some(file);
+        Ok(())
+    }
+
+    /// close time index file once segment is no longer written
+    pub(crate) fn close_file(mut self) -> Self {
+        self.file = None;
+        self
+    }
+
+    /// position from which batches are not covered by persisted entries
+    pub(crate) fn tail_start(&self) -> Size {
+        self.tail_start
+    }
+
+    /// add batch starting at file position, batches must be added in position order
+    pub(crate) async fn add_batch(
+        &mut self,
+        pos: Size,
+        max_timestamp: Timestamp,
+        interval: Size,
+    ) -> Result<(), IoError> {
+        // complete last block before this batch
+        if pos > self.tail_start && pos - self.tail_start >= interval {
+            let entry = (self.max_timestamp, pos);
+            if let Some(file) = &mut self.file {
+                let mut contents = Vec::with_capacity(ENTRY_SIZE);
+                contents.extend_from_slice(&entry.0.to_be_bytes());
+                contents.extend_from_slice(&entry.1.to_be_bytes());
+                file.write_all(&contents).await?;
+                file.flush().await?;
+            }
+            self.entries.push(entry);
+            self.tail_start = pos;
+        }
+        self.max_timestamp = self.max_timestamp.max(max_timestamp);
+        Ok(())
+    }
+
+    /// max timestamp of all batches
+    pub(crate) fn max_timestamp(&self) -> Option<Timestamp> {
+        (self.max_timestamp >= 0).then_some(self.max_timestamp)
+    }
+
+    /// position of block which contains first batch with records at or after timestamp.
+    /// Batches of block must be scanned to find batch. None if all records are older
+    pub(crate) fn lookup(&self, timestamp: Timestamp) -> Option<Size> {
+        if self.max_timestamp < timestamp {
+            return None;
+        }
+        let index = self
+            .entries
+            .partition_point(|(max_timestamp, _)| *max_timestamp < timestamp);
+        match index.checked_sub(1) {
+            Some(previous) => self.entries.get(previous).map(|(_, pos)| *pos),
+            None => Some(0),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use std::env::temp_dir;
+
+    use flv_util::fixture::ensure_new_dir;
+
+    use super::TimeIndex;
+
+    #[fluvio_future::test]
+    async fn test_time_index_lookup() {
+        let mut index = TimeIndex::default();
+        index.add_batch(0, 100, 50).await.expect("add");
+        index.add_batch(20, 200, 50).await.expect("add");
+        // first block ends before this batch
+        index.add_batch(60, 250, 50).await.expect("add");
+        // out of order timestamp doesn't raise max
+        index.add_batch(80, 150, 50).await.expect("add");
+        index.add_batch(100, -1, 50).await.expect("add");
+        index.add_batch(120, 300, 50).await.expect("add");
+
+        assert_eq!(index.entries, vec![(200, 60), (250, 120)]);
+        assert_eq!(index.max_timestamp(), Some(300));
+        assert_eq!(index.lookup(50), Some(0));
+        assert_eq!(index.lookup(200), Some(0));
+        assert_eq!(index.lookup(201), Some(60));
+        assert_eq!(index.lookup(250), Some(60));
+        assert_eq!(index.lookup(251), Some(120));
+        assert_eq!(index.lookup(301), None);
+    }
+
+    #[fluvio_future::test]
+    async fn test_time_index_persisted() {
+        let test_dir = temp_dir().join("time_index_persisted");
+        ensure_new_dir(&test_dir).expect("dir");
+        let path = test_dir.join("00000000000000000000.timeindex");
+
+        let mut index = TimeIndex::default();
+        index.open_file(&path).await.expect("open");
+        for (pos, timestamp) in [(0, 100), (60, 200), (120, 300), (180, 400)] {
+            index.add_batch(pos, timestamp, 50).await.expect("add");
+        }
+        drop(index);
+
+        // last block is not persisted
+        let loaded = TimeIndex::load(&path, 240).await.expect("load");
+        assert_eq!(loaded.entries, vec![(100, 60), (200, 120), (300, 180)]);
+        assert_eq!(loaded.tail_start(), 180);
+
+        // entries beyond truncated log are discarded
+        let mut loaded = TimeIndex::load(&path, 150).await.expect("load");
+        assert_eq!(loaded.entries, vec![(100, 60), (200, 120)]);
+        loaded.open_file(&path).await.expect("open");
+        drop(loaded);
+        let loaded = TimeIndex::load(&path, 240).await.expect("load");
+        assert_eq!(loaded.entries.len(), 2);
+    }
+}
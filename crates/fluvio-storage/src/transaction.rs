@@ -0,0 +1,399 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use tracing::{debug, warn};
+
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::{Batch, BatchRecords, ControlRecordType, Offset, RawRecords, ReplicaKey};
+use fluvio_spu_schema::fetch::AbortedTransaction;
+use fluvio_types::Timestamp;
+
+use crate::batch::FileBatchStream;
+use crate::producer::ProducerBatchIndex;
+use crate::records::MESSAGE_LOG_EXTENSION;
+use crate::util::generate_file_name;
+
+/// Transaction which has written batches but has no marker yet
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct OpenTransaction {
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    /// offset of first batch of transaction
+    pub first_offset: Offset,
+    /// time in millis when first batch was written or replica was loaded
+    pub started_at: Timestamp,
+    /// partitions registered in transaction if replica is its coordinator, empty otherwise
+    pub partitions: Vec<ReplicaKey>,
+}
+
+/// Commit or abort marker written to coordinator of transaction,
+/// which is not yet written to all partitions of transaction
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+pub struct TransactionDecision {
+    pub producer_id: i64,
+    pub producer_epoch: i16,
+    pub record_type: ControlRecordType,
+    pub partitions: Vec<ReplicaKey>,
+}
+
+#[derive(Default, Debug, Clone, Eq, PartialEq)]
+struct AbortedRange {
+    producer_id: i64,
+    first_offset: Offset,
+    /// offset of abort marker
+    last_offset: Offset,
+}
+
+/// Open and aborted transactions of replica, tracked from batches as they are written.
+/// Open transactions hold committed reads back at last stable offset, aborted ones are
+/// reported to consumers so they can skip their batches.
+/// Nothing is persisted separately, index is rebuilt from batches of local segments when
+/// replica is loaded, so it is always consistent with log.
+#[derive(Debug, Default)]
+pub(crate) struct TransactionIndex {
+    open: Vec<OpenTransaction>,
+    aborted: Vec<AbortedRange>,
+    /// decisions of transactions coordinated by replica, until they are complete
+    decisions: Vec<TransactionDecision>,
+    /// latest epoch of each transactional producer
+    producer_epochs: HashMap<i64, i16>,
+}
+
+impl TransactionIndex {
+    /// scan segments in order of their base offsets, open transactions found
+    /// are timed out from now on. Batches of idempotent producers are recovered in same scan
+    pub(crate) async fn rebuild(
+        dir: &Path,
+        base_offsets: &[Offset],
+        now: Timestamp,
+        producers: &mut ProducerBatchIndex,
+    ) -> Result<Self> {
+        let mut index = Self::default();
+        for base_offset in base_offsets {
+            let path = generate_file_name(dir, *base_offset, MESSAGE_LOG_EXTENSION);
+            let mut stream: FileBatchStream<RawRecords> = FileBatchStream::open(&path).await?;
+            while let Some(batch_pos) = stream.try_next().await? {
+                index.update(batch_pos.get_batch(), now);
+                producers.update(batch_pos.get_batch());
+            }
+        }
+        debug!(
+            open = index.open.len(),
+            aborted = index.aborted.len(),
+            "rebuilt transaction index"
+        );
+        Ok(index)
+    }
+
+    /// offset before which all transactions have ended, committed reads stop at it
+    pub(crate) fn last_stable_offset(&self, hw: Offset) -> Offset {
+        self.open
+            .iter()
+            .map(|transaction| transaction.first_offset)
+            .fold(hw, Offset::min)
+    }
+
+    pub(crate) fn open_transactions(&self) -> Vec<OpenTransaction> {
+        self.open.clone()
+    }
+
+    pub(crate) fn decisions(&self) -> Vec<TransactionDecision> {
+        self.decisions.clone()
+    }
+
+    /// transactions aborted with batches between start and end offset
+    pub(crate) fn aborted(&self, start: Offset, end: Offset) -> Vec<AbortedTransaction> {
+        self.aborted
+            .iter()
+            .filter(|range| range.first_offset < end && range.last_offset >= start)
+            .map(|range| AbortedTransaction {
+                producer_id: range.producer_id,
+                first_offset: range.first_offset,
+            })
+            .collect()
+    }
+
+    /// batches of producer with older epoch than last seen one are fenced off, they come
+    /// from producer instance which has been replaced. Commit marker is rejected if producer
+    /// has no open transaction, it may have been aborted because it timed out
+    pub(crate) fn validate<R: BatchRecords>(&self, batches: &[Batch<R>]) -> Result<(), ErrorCode> {
+        let mut open: HashMap<i64, i16> = self
+            .open
+            .iter()
+            .map(|transaction| (transaction.producer_id, transaction.producer_epoch))
+            .collect();
+        let mut epochs: HashMap<i64, i16> = HashMap::new();
+        for batch in batches {
+            let header = batch.get_header();
+            if !header.is_transactional() || header.producer_id < 0 {
+                continue;
+            }
+            let producer_id = header.producer_id;
+            let epoch = header.producer_epoch;
+            let last_epoch = epochs
+                .get(&producer_id)
+                .or_else(|| self.producer_epochs.get(&producer_id));
+            if matches!(last_epoch, Some(last_epoch) if epoch < *last_epoch) {
+                return Err(ErrorCode::InvalidProducerEpoch);
+            }
+            epochs.insert(producer_id, epoch);
+
+            if let Err(err) = batch.transaction_partitions() {
+                warn!(%err, "invalid control batch");
+                return Err(ErrorCode::CorruptBatch);
+            }
+            match batch.control_type() {
+                // newer producer instance may abort transaction of replaced one, but not commit it
+                Ok(Some(record_type)) if record_type.is_marker() => match open.remove(&producer_id)
+                {
+                    Some(open_epoch)
+                        if open_epoch != epoch && record_type == ControlRecordType::Commit =>
+                    {
+                        return Err(ErrorCode::InvalidProducerEpoch);
+                    }
+                    None if record_type == ControlRecordType::Commit => {
+                        return Err(ErrorCode::InvalidTxnState);
+                    }
+                    _ => {}
+                },
+                Ok(Some(ControlRecordType::Complete)) => {}
+                // transaction of replaced producer instance must end first
+                Ok(_) => match open.get(&producer_id) {
+                    Some(open_epoch) if *open_epoch != epoch => {
+                        return Err(ErrorCode::InvalidTxnState);
+                    }
+                    Some(_) => {}
+                    None => {
+                        open.insert(producer_id, epoch);
+                    }
+                },
+                Err(err) => {
+                    warn!(%err, "invalid control batch");
+                    return Err(ErrorCode::CorruptBatch);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// update from batch written at its base offset
+    pub(crate) fn update<R: BatchRecords>(&mut self, batch: &Batch<R>, now: Timestamp) {
+        let header = batch.get_header();
+        if !header.is_transactional() || header.producer_id < 0 {
+            return;
+        }
+        let epoch = self
+            .producer_epochs
+            .entry(header.producer_id)
+            .or_insert(header.producer_epoch);
+        *epoch = header.producer_epoch.max(*epoch);
+
+        match batch.control_type() {
+            Ok(Some(record_type)) if record_type.is_marker() => {
+                let position = match self
+                    .open
+                    .iter()
+                    .position(|transaction| transaction.producer_id == header.producer_id)
+                {
+                    Some(position) => position,
+                    None => return,
+                };
+                let transaction = self.open.remove(position);
+                debug!(?transaction, ?record_type, "transaction ended");
+                if record_type == ControlRecordType::Abort {
+                    self.aborted.push(AbortedRange {
+                        producer_id: transaction.producer_id,
+                        first_offset: transaction.first_offset,
+                        last_offset: batch.get_base_offset(),
+                    });
+                }
+                if !transaction.partitions.is_empty() {
+                    self.decisions.push(TransactionDecision {
+                        producer_id: transaction.producer_id,
+                        producer_epoch: header.producer_epoch,
+                        record_type,
+                        partitions: transaction.partitions,
+                    });
+                }
+            }
+            Ok(Some(ControlRecordType::Complete)) => {
+                self.decisions
+                    .retain(|decision| decision.producer_id != header.producer_id);
+            }
+            Ok(_) => {
+                let partitions = batch.transaction_partitions().unwrap_or_default();
+                let transaction = match self
+                    .open
+                    .iter_mut()
+                    .find(|transaction| transaction.producer_id == header.producer_id)
+                {
+                    Some(transaction) => transaction,
+                    None => {
+                        let transaction = OpenTransaction {
+                            producer_id: header.producer_id,
+                            producer_epoch: header.producer_epoch,
+                            first_offset: batch.get_base_offset(),
+                            started_at: now,
+                            partitions: vec![],
+                        };
+                        debug!(?transaction, "transaction started");
+                        self.open.push(transaction);
+                        self.open.last_mut().expect("transaction added")
+                    }
+                };
+                for partition in partitions {
+                    if !transaction.partitions.contains(&partition) {
+                        transaction.partitions.push(partition);
+                    }
+                }
+            }
+            Err(err) => {
+                warn!(%err, "invalid control batch");
+            }
+        }
+    }
+
+    /// forget aborted transactions which have been removed from log
+    pub(crate) fn truncate(&mut self, log_start_offset: Offset) {
+        self.aborted
+            .retain(|range| range.last_offset >= log_start_offset);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_protocol::record::{Batch, ControlRecordType, Offset, Record, ReplicaKey};
+
+    use super::TransactionIndex;
+
+    fn data(base_offset: Offset, producer_id: i64) -> Batch {
+        let mut batch = Batch::from(vec![Record::new("value"), Record::new("value")]);
+        batch.set_base_offset(base_offset);
+        batch.get_mut_header().producer_id = producer_id;
+        batch.get_mut_header().set_transactional(true);
+        batch
+    }
+
+    fn marker(base_offset: Offset, producer_id: i64, record_type: ControlRecordType) -> Batch {
+        fenced_marker(base_offset, producer_id, 0, record_type)
+    }
+
+    fn fenced_marker(
+        base_offset: Offset,
+        producer_id: i64,
+        producer_epoch: i16,
+        record_type: ControlRecordType,
+    ) -> Batch {
+        let mut batch = Batch::control(producer_id, producer_epoch, record_type);
+        batch.set_base_offset(base_offset);
+        batch
+    }
+
+    #[test]
+    fn test_transaction_index() {
+        let mut index = TransactionIndex::default();
+        assert_eq!(index.last_stable_offset(10), 10);
+
+        // non transactional batches are not tracked
+        index.update(&Batch::from(vec![Record::new("value")]), 0);
+        assert!(index.open_transactions().is_empty());
+
+        index.update(&data(0, 1), 0);
+        index.update(&data(2, 2), 0);
+        index.update(&data(4, 1), 0);
+        assert_eq!(index.last_stable_offset(6), 0);
+        assert_eq!(index.open_transactions().len(), 2);
+
+        index.update(&marker(6, 1, ControlRecordType::Abort), 0);
+        assert_eq!(index.last_stable_offset(7), 2);
+        index.update(&marker(7, 2, ControlRecordType::Commit), 0);
+        assert_eq!(index.last_stable_offset(8), 8);
+
+        assert_eq!(index.aborted(0, 8).len(), 1);
+        assert_eq!(index.aborted(6, 8)[0].first_offset, 0);
+        assert!(index.aborted(7, 8).is_empty());
+
+        // commit of producer without open transaction is rejected
+        assert_eq!(
+            index.validate(&[marker(8, 1, ControlRecordType::Commit)]),
+            Err(ErrorCode::InvalidTxnState)
+        );
+        assert!(index
+            .validate(&[data(8, 1), marker(10, 1, ControlRecordType::Commit)])
+            .is_ok());
+        assert!(index
+            .validate(&[marker(8, 1, ControlRecordType::Abort)])
+            .is_ok());
+
+        index.truncate(7);
+        assert!(index.aborted(0, 10).is_empty());
+    }
+
+    #[test]
+    fn test_transaction_decision() {
+        let mut index = TransactionIndex::default();
+        let partitions = vec![ReplicaKey::new("orders", 0u32)];
+        let mut add = Batch::add_partitions(1, 0, partitions.clone());
+        add.set_base_offset(0);
+        assert!(index.validate(&[add.clone()]).is_ok());
+        index.update(&add, 0);
+        index.update(&data(1, 1), 0);
+        let mut add = Batch::add_partitions(1, 0, vec![ReplicaKey::new("payments", 1u32)]);
+        add.set_base_offset(3);
+        index.update(&add, 0);
+        assert_eq!(index.last_stable_offset(4), 0);
+        assert_eq!(index.open_transactions()[0].partitions.len(), 2);
+        assert!(index.decisions().is_empty());
+
+        index.update(&marker(4, 1, ControlRecordType::Commit), 0);
+        assert!(index.open_transactions().is_empty());
+        let decisions = index.decisions();
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].record_type, ControlRecordType::Commit);
+        assert_eq!(decisions[0].partitions[0], partitions[0]);
+
+        index.update(&marker(5, 1, ControlRecordType::Complete), 0);
+        assert!(index.decisions().is_empty());
+
+        // transaction which only wrote to replica has no decision to roll forward
+        index.update(&data(6, 2), 0);
+        index.update(&marker(8, 2, ControlRecordType::Abort), 0);
+        assert!(index.decisions().is_empty());
+    }
+
+    #[test]
+    fn test_transaction_producer_epoch() {
+        let mut index = TransactionIndex::default();
+        let mut newer = data(0, 1);
+        newer.get_mut_header().producer_epoch = 1;
+        assert!(index.validate(&[newer.clone()]).is_ok());
+        index.update(&newer, 0);
+
+        // batches of replaced producer instance are fenced
+        assert_eq!(
+            index.validate(&[data(2, 1)]),
+            Err(ErrorCode::InvalidProducerEpoch)
+        );
+        assert_eq!(
+            index.validate(&[marker(2, 1, ControlRecordType::Commit)]),
+            Err(ErrorCode::InvalidProducerEpoch)
+        );
+
+        // newer instance can't continue transaction of older one, only abort it
+        let mut index = TransactionIndex::default();
+        index.update(&data(0, 1), 0);
+        let mut newer = data(2, 1);
+        newer.get_mut_header().producer_epoch = 1;
+        assert_eq!(index.validate(&[newer]), Err(ErrorCode::InvalidTxnState));
+        assert_eq!(
+            index.validate(&[fenced_marker(2, 1, 1, ControlRecordType::Commit)]),
+            Err(ErrorCode::InvalidProducerEpoch)
+        );
+        assert!(index
+            .validate(&[fenced_marker(2, 1, 1, ControlRecordType::Abort)])
+            .is_ok());
+    }
+}
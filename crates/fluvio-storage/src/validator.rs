@@ -3,7 +3,7 @@ use std::path::Path;
 use std::path::PathBuf;
 use std::time::Duration;
 
-use fluvio_protocol::record::BatchRecords;
+use fluvio_protocol::record::RawRecords;
 use tracing::error;
 use tracing::info;
 use tracing::instrument;
@@ -16,7 +16,6 @@ use fluvio_protocol::record::Offset;
 use crate::batch::BatchHeaderError;
 use crate::batch::FileBatchStream;
 use crate::batch::StorageBytesIterator;
-use crate::batch_header::FileEmptyRecords;
 use crate::file::FileBytesIterator;
 use crate::index::Index;
 use crate::util::log_path_get_offset;
@@ -29,6 +28,8 @@ pub enum LogValidationError {
     BatchDecoding(#[from] BatchHeaderError),
     #[error("batch offset is less than base offset: {invalid_batch_offset}")]
     InvalidBaseOffsetMinimum { invalid_batch_offset: Offset },
+    #[error("batch crc doesn't match its content at offset: {offset}")]
+    InvalidCrc { offset: Offset },
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -55,11 +56,10 @@ pub struct LogValidator {
 }
 
 impl LogValidator {
-    async fn validate_core<I, S, R>(path: impl AsRef<Path>, index: Option<&I>) -> Result<Self>
+    async fn validate_core<I, S>(path: impl AsRef<Path>, index: Option<&I>) -> Result<Self>
     where
         I: Index,
         S: StorageBytesIterator,
-        R: BatchRecords + Default + std::fmt::Debug,
     {
         let file_path = path.as_ref().to_path_buf();
         let mut val = Self {
@@ -76,16 +76,16 @@ impl LogValidator {
         );
 
         let start_time = std::time::Instant::now();
-        let batch_stream: FileBatchStream<R, S> = match FileBatchStream::open(&val.file_path).await
-        {
-            Ok(batch_stream) => batch_stream,
-            Err(err) => match err.kind() {
-                ErrorKind::UnexpectedEof => {
-                    return Err(anyhow!("empty file with base offset: {}", val.base_offset))
-                }
-                _ => return Err(err.into()),
-            },
-        };
+        let batch_stream: FileBatchStream<RawRecords, S> =
+            match FileBatchStream::open(&val.file_path).await {
+                Ok(batch_stream) => batch_stream,
+                Err(err) => match err.kind() {
+                    ErrorKind::UnexpectedEof => {
+                        return Err(anyhow!("empty file with base offset: {}", val.base_offset))
+                    }
+                    _ => return Err(err.into()),
+                },
+            };
 
         // find recoverable error
 
@@ -109,15 +109,14 @@ impl LogValidator {
 
     /// validate log file
     #[instrument(skip(self, index, batch_stream))]
-    async fn validate_with_stream<I, R, S>(
+    async fn validate_with_stream<I, S>(
         &mut self,
-        mut batch_stream: FileBatchStream<R, S>,
+        mut batch_stream: FileBatchStream<RawRecords, S>,
         index: Option<&I>,
     ) -> Result<()>
     where
         I: Index,
         S: StorageBytesIterator,
-        R: BatchRecords + Default + std::fmt::Debug,
     {
         let mut last_index_pos = 0;
 
@@ -145,6 +144,20 @@ impl LogValidator {
                 return Ok(());
             }
 
+            // batch corrupted on disk, batches before it are still valid
+            if !current_batch.validate_crc() {
+                error!(
+                    last_valid_offset = self.last_valid_offset,
+                    last_valid_pos = self.last_valid_batch_pos,
+                    current_batch_offset,
+                    "found batch with invalid crc, aborting"
+                );
+                self.error = Some(LogValidationError::InvalidCrc {
+                    offset: current_batch_offset,
+                });
+                return Ok(());
+            }
+
             // set high watermark for validating batches
             self.last_valid_offset = current_batch_offset + offset_delta as Offset;
             self.last_valid_batch_pos = current_batch_pos;
@@ -245,7 +258,7 @@ impl LogValidator {
         I: Index,
         S: StorageBytesIterator,
     {
-        Self::validate_core::<I, S, FileEmptyRecords>(path, index).await
+        Self::validate_core::<I, S>(path, index).await
     }
 
     #[instrument(skip(index, path))]
@@ -431,6 +444,64 @@ mod tests {
         let err = validator.error.expect("error");
         assert!(matches!(err, LogValidationError::BatchDecoding(_)));
     }
+
+    #[fluvio_future::test]
+    async fn test_validating_corrupted_batch() {
+        const OFFSET: i64 = 701;
+
+        let test_dir = temp_dir().join("validate_corrupted_batch");
+        ensure_new_dir(&test_dir).expect("new");
+
+        let options = ReplicaConfig {
+            base_dir: test_dir,
+            segment_max_bytes: 1000,
+            ..Default::default()
+        }
+        .shared();
+
+        let mut msg_sink = MutFileRecords::create(OFFSET, options)
+            .await
+            .expect("record created");
+
+        let mut builder = BatchProducer::builder()
+            .base_offset(OFFSET)
+            .build()
+            .expect("build");
+
+        msg_sink
+            .write_batch(&builder.batch_records(3))
+            .await
+            .expect("write");
+        msg_sink.flush().await.expect("flush");
+        let first_batch_len = msg_sink.get_pos();
+        msg_sink
+            .write_batch(&builder.batch_records(3))
+            .await
+            .expect("write");
+        msg_sink.flush().await.expect("flush");
+        let test_fs_path = msg_sink.get_path().to_owned();
+        drop(msg_sink);
+
+        // flip bit in last record of second batch
+        let mut contents = std::fs::read(&test_fs_path).expect("read");
+        let last = contents.len() - 1;
+        contents[last] ^= 0x01;
+        std::fs::write(&test_fs_path, contents).expect("write");
+
+        let validator = LogValidator::default_validate::<LogIndex>(&test_fs_path, None)
+            .await
+            .expect("validate");
+
+        // only first batch is valid
+        assert_eq!(validator.leo(), OFFSET + 3);
+        assert_eq!(validator.last_valid_file_pos, first_batch_len);
+        assert_eq!(validator.batches, 1);
+        let err = validator.error.expect("error");
+        assert!(matches!(
+            err,
+            LogValidationError::InvalidCrc { offset } if offset == OFFSET + 3
+        ));
+    }
 }
 
 #[cfg(test)]
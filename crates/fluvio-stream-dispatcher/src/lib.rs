@@ -1,6 +1,7 @@
 pub mod store;
 pub mod dispatcher;
 pub mod actions;
+pub mod metadata;
 
 mod error;
 
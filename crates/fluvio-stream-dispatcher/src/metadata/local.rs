@@ -0,0 +1,868 @@
+//!
+//! # Local Metadata Client
+//!
+//! `MetadataClient` backed by JSON files so SC can run without Kubernetes.
+//! Each object is stored at `<root>/<plural>.<group>/<namespace>/<name>.json`.
+//! Resource versions come from a single counter shared by all kinds and
+//! watch streams replay events newer than requested version.
+//!
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::fs;
+use std::io::Error as IoError;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use async_lock::{Mutex, MutexGuard};
+use async_trait::async_trait;
+use blocking::unblock;
+use event_listener::Event;
+use futures_util::future::{BoxFuture, FutureExt};
+use futures_util::stream::{self, BoxStream, StreamExt};
+use once_cell::sync::Lazy;
+use serde_json::{json, Error as SerdeJsonError, Value};
+use tracing::{debug, trace};
+
+use k8_diff::DiffError;
+use k8_metadata_client::{
+    ListArg, MetadataClient, MetadataClientError, NameSpace, PatchMergeType, TokenStreamResult,
+};
+use k8_types::options::DeleteOptions;
+use k8_types::{DeleteStatus, InputK8Obj, K8List, K8Meta, K8Obj, K8Watch, Spec, UpdateK8ObjStatus};
+
+/// max watch events kept per kind, older watchers have to re-list
+const MAX_WATCH_EVENTS: usize = 1000;
+
+const OBJECT_EXT: &str = "json";
+
+#[derive(Debug)]
+pub enum LocalMetadataError {
+    NotFound(String),
+    AlreadyExists(String),
+    Conflict(String),
+    PatchError,
+    IoError(IoError),
+    JsonError(SerdeJsonError),
+    DiffError(DiffError),
+}
+
+impl fmt::Display for LocalMetadataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NotFound(name) => write!(f, "{name} not found"),
+            Self::AlreadyExists(name) => write!(f, "{name} already exists"),
+            Self::Conflict(name) => write!(f, "{name} has been modified"),
+            Self::PatchError => write!(f, "patch error"),
+            Self::IoError(err) => write!(f, "{err}"),
+            Self::JsonError(err) => write!(f, "{err}"),
+            Self::DiffError(err) => write!(f, "{err:?}"),
+        }
+    }
+}
+
+impl std::error::Error for LocalMetadataError {}
+
+impl From<IoError> for LocalMetadataError {
+    fn from(error: IoError) -> Self {
+        Self::IoError(error)
+    }
+}
+
+impl From<SerdeJsonError> for LocalMetadataError {
+    fn from(error: SerdeJsonError) -> Self {
+        Self::JsonError(error)
+    }
+}
+
+impl From<DiffError> for LocalMetadataError {
+    fn from(error: DiffError) -> Self {
+        Self::DiffError(error)
+    }
+}
+
+impl MetadataClientError for LocalMetadataError {
+    fn not_founded(&self) -> bool {
+        matches!(self, Self::NotFound(_))
+    }
+
+    fn patch_error() -> Self {
+        Self::PatchError
+    }
+}
+
+type ObjectKey = (String, String);
+
+#[derive(Debug, Clone, Copy)]
+enum WatchType {
+    Added,
+    Modified,
+    Deleted,
+}
+
+#[derive(Debug, Default)]
+struct KindStore {
+    objects: BTreeMap<ObjectKey, Value>,
+    events: VecDeque<(u64, WatchType, Value)>,
+    /// version of latest event dropped from history
+    compacted: u64,
+}
+
+impl KindStore {
+    fn push_event(&mut self, version: u64, ty: WatchType, obj: Value) {
+        if self.events.len() >= MAX_WATCH_EVENTS {
+            if let Some((dropped, _, _)) = self.events.pop_front() {
+                self.compacted = dropped;
+            }
+        }
+        self.events.push_back((version, ty, obj));
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    version: u64,
+    kinds: HashMap<String, KindStore>,
+}
+
+#[derive(Debug)]
+struct Inner {
+    root: PathBuf,
+    state: Mutex<State>,
+    event: Event,
+}
+
+/// Metadata client storing objects in local files
+#[derive(Debug, Clone)]
+pub struct LocalMetadataClient {
+    inner: Arc<Inner>,
+}
+
+impl LocalMetadataClient {
+    /// open store at root, loading all previously stored objects
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self, IoError> {
+        let root = root.into();
+        fs::create_dir_all(&root)?;
+        let state = load_state(&root)?;
+        debug!(root = %root.display(), version = state.version, "opened local metadata");
+        Ok(Self {
+            inner: Arc::new(Inner {
+                root,
+                state: Mutex::new(state),
+                event: Event::new(),
+            }),
+        })
+    }
+
+    async fn lock(&self) -> MutexGuard<'_, State> {
+        self.inner.state.lock().await
+    }
+
+    fn object_path(&self, kind: &str, key: &ObjectKey) -> PathBuf {
+        self.inner
+            .root
+            .join(kind)
+            .join(&key.0)
+            .join(format!("{}.{OBJECT_EXT}", key.1))
+    }
+
+    async fn write_object(&self, kind: &str, key: &ObjectKey, obj: &Value) -> Result<(), IoError> {
+        let path = self.object_path(kind, key);
+        let content = serde_json::to_vec_pretty(obj)?;
+        unblock(move || {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            // write then rename so object is never half written
+            let tmp = path.with_extension("tmp");
+            fs::write(&tmp, content)?;
+            fs::rename(tmp, path)
+        })
+        .await
+    }
+
+    async fn remove_object(&self, kind: &str, key: &ObjectKey) -> Result<(), IoError> {
+        let path = self.object_path(kind, key);
+        unblock(move || match fs::remove_file(path) {
+            Err(err) if err.kind() != ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        })
+        .await
+    }
+
+    /// store object with new version and publish watch event.
+    /// state stays locked while file is written, so files are written in version order
+    async fn store(
+        &self,
+        state: &mut State,
+        kind: &str,
+        key: ObjectKey,
+        mut obj: Value,
+        ty: WatchType,
+    ) -> Result<Value, LocalMetadataError> {
+        state.version += 1;
+        let version = state.version;
+        obj["metadata"]["resourceVersion"] = Value::String(version.to_string());
+
+        match ty {
+            WatchType::Deleted => self.remove_object(kind, &key).await?,
+            _ => self.write_object(kind, &key, &obj).await?,
+        }
+        trace!(kind, name = %key.1, version, ?ty, "stored");
+
+        let kind_store = state.kinds.entry(kind.to_owned()).or_default();
+        match ty {
+            WatchType::Deleted => {
+                kind_store.objects.remove(&key);
+            }
+            _ => {
+                kind_store.objects.insert(key, obj.clone());
+            }
+        }
+        kind_store.push_event(version, ty, obj.clone());
+        self.inner.event.notify(usize::MAX);
+        Ok(obj)
+    }
+
+    /// delete object and its dependents.
+    /// object with finalizers is only marked for deletion
+    fn delete_object<'a>(
+        &'a self,
+        state: &'a mut State,
+        kind: &'a str,
+        key: ObjectKey,
+    ) -> BoxFuture<'a, Result<(Value, bool), LocalMetadataError>> {
+        async move {
+            let obj = current(state, kind, &key)?;
+
+            // there is no garbage collector, remove dependents here
+            if let Some(uid) = obj["metadata"]["uid"].as_str() {
+                for (child_kind, child_key) in dependents(state, uid) {
+                    self.delete_object(state, &child_kind, child_key).await?;
+                }
+            }
+
+            if has_finalizers(&obj) {
+                if !obj["metadata"]["deletionTimestamp"].is_string() {
+                    let mut marked = obj;
+                    marked["metadata"]["deletionTimestamp"] = Value::String(now());
+                    let marked = self
+                        .store(state, kind, key, marked, WatchType::Modified)
+                        .await?;
+                    return Ok((marked, false));
+                }
+                return Ok((obj, false));
+            }
+
+            let deleted = self
+                .store(state, kind, key, obj, WatchType::Deleted)
+                .await?;
+            Ok((deleted, true))
+        }
+        .boxed()
+    }
+
+    async fn list<S: Spec>(&self, namespace: &NameSpace) -> Result<K8List<S>, LocalMetadataError> {
+        let state = self.lock().await;
+        let items: Vec<&Value> = state
+            .kinds
+            .get(&kind_key::<S>())
+            .map(|kind_store| {
+                kind_store
+                    .objects
+                    .iter()
+                    .filter(|((ns, _), _)| in_namespace(namespace, ns))
+                    .map(|(_, obj)| obj)
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(serde_json::from_value(json!({
+            "apiVersion": S::api_version(),
+            "kind": format!("{}List", S::kind()),
+            "metadata": {
+                "resourceVersion": state.version.to_string(),
+            },
+            "items": items,
+        }))?)
+    }
+
+    /// events of kind after version, None if history no longer has them
+    async fn events_since<S>(
+        &self,
+        namespace: &NameSpace,
+        version: u64,
+    ) -> Option<Vec<(u64, WatchType, Value)>>
+    where
+        S: Spec,
+    {
+        let state = self.lock().await;
+        let kind_store = match state.kinds.get(&kind_key::<S>()) {
+            Some(kind_store) => kind_store,
+            None => return Some(vec![]),
+        };
+        if version < kind_store.compacted {
+            return None;
+        }
+        Some(
+            kind_store
+                .events
+                .iter()
+                .filter(|(event_version, _, obj)| {
+                    *event_version > version
+                        && in_namespace(
+                            namespace,
+                            obj["metadata"]["namespace"].as_str().unwrap_or_default(),
+                        )
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+#[async_trait]
+impl MetadataClient for LocalMetadataClient {
+    type MetadataClientError = LocalMetadataError;
+
+    async fn retrieve_item<S, M>(&self, metadata: &M) -> Result<K8Obj<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+        M: K8Meta + Send + Sync,
+    {
+        let key = object_key(metadata);
+        let obj = self
+            .lock()
+            .await
+            .kinds
+            .get(&kind_key::<S>())
+            .and_then(|kind_store| kind_store.objects.get(&key))
+            .cloned()
+            .ok_or(LocalMetadataError::NotFound(key.1))?;
+        Ok(serde_json::from_value(obj)?)
+    }
+
+    async fn retrieve_items_with_option<S, N>(
+        &self,
+        namespace: N,
+        _option: Option<ListArg>,
+    ) -> Result<K8List<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+        N: Into<NameSpace> + Send + Sync,
+    {
+        self.list(&namespace.into()).await
+    }
+
+    fn retrieve_items_in_chunks<'a, S, N>(
+        self: Arc<Self>,
+        namespace: N,
+        _limit: u32,
+        _option: Option<ListArg>,
+    ) -> BoxStream<'a, K8List<S>>
+    where
+        S: Spec + 'static,
+        N: Into<NameSpace> + Send + Sync + 'static,
+    {
+        // everything is in memory, so single chunk is enough
+        let namespace = namespace.into();
+        stream::once(async move { self.list(&namespace).await.ok() })
+            .filter_map(|list| async move { list })
+            .boxed()
+    }
+
+    async fn delete_item_with_option<S, M>(
+        &self,
+        metadata: &M,
+        _option: Option<DeleteOptions>,
+    ) -> Result<DeleteStatus<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+        M: K8Meta + Send + Sync,
+    {
+        let key = object_key(metadata);
+        debug!(kind = S::kind(), name = %key.1, "deleting");
+        let (obj, deleted) = {
+            let mut state = self.lock().await;
+            self.delete_object(&mut state, &kind_key::<S>(), key)
+                .await?
+        };
+
+        if deleted {
+            Ok(serde_json::from_value(json!({
+                "apiVersion": "v1",
+                "kind": "Status",
+                "status": "Success",
+                "details": {
+                    "name": obj["metadata"]["name"],
+                    "kind": S::kind(),
+                },
+            }))?)
+        } else {
+            Ok(DeleteStatus::ForegroundDelete(serde_json::from_value(obj)?))
+        }
+    }
+
+    async fn create_item<S>(
+        &self,
+        value: InputK8Obj<S>,
+    ) -> Result<K8Obj<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+    {
+        let kind = kind_key::<S>();
+        let key = object_key(&value.metadata);
+        let mut obj = serde_json::to_value(&value)?;
+        obj["metadata"]["uid"] = Value::String(uid());
+        obj["metadata"]["creationTimestamp"] = Value::String(now());
+        obj["status"] = serde_json::to_value(S::Status::default())?;
+
+        let created = {
+            let mut state = self.lock().await;
+            if state
+                .kinds
+                .get(&kind)
+                .map(|kind_store| kind_store.objects.contains_key(&key))
+                .unwrap_or_default()
+            {
+                return Err(LocalMetadataError::AlreadyExists(key.1));
+            }
+            self.store(&mut state, &kind, key, obj, WatchType::Added)
+                .await?
+        };
+        Ok(serde_json::from_value(created)?)
+    }
+
+    async fn update_status<S>(
+        &self,
+        value: &UpdateK8ObjStatus<S>,
+    ) -> Result<K8Obj<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+    {
+        let input = serde_json::to_value(value)?;
+        let namespace = input["metadata"]["namespace"].as_str().unwrap_or_default();
+        let name = input["metadata"]["name"].as_str().unwrap_or_default();
+        let key = (namespace.to_owned(), name.to_owned());
+        let kind = kind_key::<S>();
+
+        let updated = {
+            let mut state = self.lock().await;
+            let mut obj = current(&state, &kind, &key)?;
+            // same optimistic concurrency as K8 api server
+            if let Some(version) = input["metadata"]["resourceVersion"].as_str() {
+                if !version.is_empty() && obj["metadata"]["resourceVersion"] != version {
+                    return Err(LocalMetadataError::Conflict(key.1));
+                }
+            }
+            obj["status"] = input["status"].clone();
+            self.store(&mut state, &kind, key, obj, WatchType::Modified)
+                .await?
+        };
+        Ok(serde_json::from_value(updated)?)
+    }
+
+    async fn patch<S, M>(
+        &self,
+        metadata: &M,
+        patch: &Value,
+        _merge_type: PatchMergeType,
+    ) -> Result<K8Obj<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+        M: K8Meta + fmt::Display + Send + Sync,
+    {
+        self.patch_object::<S>(object_key(metadata), patch, false)
+            .await
+    }
+
+    async fn patch_status<S, M>(
+        &self,
+        metadata: &M,
+        patch: &Value,
+        _merge_type: PatchMergeType,
+    ) -> Result<K8Obj<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+        M: K8Meta + fmt::Display + Send + Sync,
+    {
+        self.patch_object::<S>(object_key(metadata), patch, true)
+            .await
+    }
+
+    async fn patch_subresource<S, M>(
+        &self,
+        metadata: &M,
+        subresource: String,
+        patch: &Value,
+        _merge_type: PatchMergeType,
+    ) -> Result<K8Obj<S>, Self::MetadataClientError>
+    where
+        S: Spec,
+        M: K8Meta + fmt::Display + Send + Sync,
+    {
+        // only status sub resource exists for fluvio objects
+        if subresource != "status" {
+            return Err(LocalMetadataError::PatchError);
+        }
+        self.patch_object::<S>(object_key(metadata), patch, true)
+            .await
+    }
+
+    fn watch_stream_since<S, N>(
+        &self,
+        namespace: N,
+        resource_version: Option<String>,
+    ) -> BoxStream<'_, TokenStreamResult<S, Self::MetadataClientError>>
+    where
+        S: Spec + 'static,
+        N: Into<NameSpace>,
+    {
+        let namespace = namespace.into();
+        let version: Option<u64> = resource_version.and_then(|version| version.parse().ok());
+
+        stream::unfold(version, move |version| {
+            let namespace = namespace.clone();
+            async move {
+                let version = match version {
+                    Some(version) => version,
+                    None => self.lock().await.version,
+                };
+                loop {
+                    let listener = self.inner.event.listen();
+                    let events = match self.events_since::<S>(&namespace, version).await {
+                        Some(events) => events,
+                        None => {
+                            // history is gone, end stream so caller re-lists
+                            debug!(kind = S::kind(), version, "watch version too old");
+                            return None;
+                        }
+                    };
+                    if let Some((last, _, _)) = events.last() {
+                        let last = *last;
+                        let tokens = events
+                            .into_iter()
+                            .map(|(_, ty, obj)| to_watch(ty, obj))
+                            .collect();
+                        return Some((Ok(tokens), Some(last)));
+                    }
+                    listener.await;
+                }
+            }
+        })
+        .boxed()
+    }
+}
+
+impl LocalMetadataClient {
+    /// apply json merge patch, only status is changed for status patch
+    async fn patch_object<S: Spec>(
+        &self,
+        key: ObjectKey,
+        patch: &Value,
+        status_only: bool,
+    ) -> Result<K8Obj<S>, LocalMetadataError> {
+        if !patch.is_object() {
+            return Err(LocalMetadataError::PatchError);
+        }
+        let kind = kind_key::<S>();
+
+        let patched = {
+            let mut state = self.lock().await;
+            let obj = current(&state, &kind, &key)?;
+            let mut patched = obj.clone();
+            merge_patch(&mut patched, patch);
+            if status_only {
+                let mut status_patched = obj.clone();
+                status_patched["status"] = patched["status"].take();
+                patched = status_patched;
+            } else {
+                // identity is owned by store
+                patched["metadata"]["uid"] = obj["metadata"]["uid"].clone();
+                patched["metadata"]["resourceVersion"] = obj["metadata"]["resourceVersion"].clone();
+            }
+
+            if patched == obj {
+                trace!(name = %key.1, "patch has no changes");
+                patched
+            } else if patched["metadata"]["deletionTimestamp"].is_string()
+                && !has_finalizers(&patched)
+            {
+                // last finalizer is removed
+                self.store(&mut state, &kind, key, patched, WatchType::Deleted)
+                    .await?
+            } else {
+                self.store(&mut state, &kind, key, patched, WatchType::Modified)
+                    .await?
+            }
+        };
+        Ok(serde_json::from_value(patched)?)
+    }
+}
+
+fn load_state(root: &Path) -> Result<State, IoError> {
+    let mut state = State::default();
+    for kind_dir in fs::read_dir(root)? {
+        let kind_dir = kind_dir?;
+        if !kind_dir.file_type()?.is_dir() {
+            continue;
+        }
+        let kind = kind_dir.file_name().to_string_lossy().to_string();
+        let mut kind_store = KindStore::default();
+        for ns_dir in fs::read_dir(kind_dir.path())? {
+            let ns_dir = ns_dir?;
+            if !ns_dir.file_type()?.is_dir() {
+                continue;
+            }
+            let namespace = ns_dir.file_name().to_string_lossy().to_string();
+            for file in fs::read_dir(ns_dir.path())? {
+                let path = file?.path();
+                if path.extension().and_then(|ext| ext.to_str()) != Some(OBJECT_EXT) {
+                    continue;
+                }
+                let obj: Value = serde_json::from_slice(&fs::read(&path)?)?;
+                let name = match obj["metadata"]["name"].as_str() {
+                    Some(name) => name.to_owned(),
+                    None => {
+                        debug!(path = %path.display(), "skipping object without name");
+                        continue;
+                    }
+                };
+                if let Some(version) = obj["metadata"]["resourceVersion"]
+                    .as_str()
+                    .and_then(|version| version.parse::<u64>().ok())
+                {
+                    state.version = state.version.max(version);
+                }
+                kind_store.objects.insert((namespace.clone(), name), obj);
+            }
+        }
+        debug!(kind, count = kind_store.objects.len(), "loaded");
+        state.kinds.insert(kind, kind_store);
+    }
+    // events before restart are lost
+    for kind_store in state.kinds.values_mut() {
+        kind_store.compacted = state.version;
+    }
+    Ok(state)
+}
+
+fn kind_key<S: Spec>() -> String {
+    let crd = S::metadata();
+    format!("{}.{}", crd.names.plural, crd.group)
+}
+
+fn object_key<M: K8Meta>(metadata: &M) -> ObjectKey {
+    (metadata.namespace().to_owned(), metadata.name().to_owned())
+}
+
+fn current(state: &State, kind: &str, key: &ObjectKey) -> Result<Value, LocalMetadataError> {
+    state
+        .kinds
+        .get(kind)
+        .and_then(|kind_store| kind_store.objects.get(key))
+        .cloned()
+        .ok_or_else(|| LocalMetadataError::NotFound(key.1.clone()))
+}
+
+fn in_namespace(namespace: &NameSpace, ns: &str) -> bool {
+    match namespace {
+        NameSpace::All => true,
+        NameSpace::Named(name) => name == ns,
+    }
+}
+
+fn has_finalizers(obj: &Value) -> bool {
+    obj["metadata"]["finalizers"]
+        .as_array()
+        .map(|finalizers| !finalizers.is_empty())
+        .unwrap_or_default()
+}
+
+/// objects of any kind owned by uid
+fn dependents(state: &State, uid: &str) -> Vec<(String, ObjectKey)> {
+    let mut children = vec![];
+    for (kind, kind_store) in &state.kinds {
+        for (key, obj) in &kind_store.objects {
+            let owned = obj["metadata"]["ownerReferences"]
+                .as_array()
+                .map(|refs| refs.iter().any(|owner| owner["uid"] == uid))
+                .unwrap_or_default();
+            if owned {
+                children.push((kind.clone(), key.clone()));
+            }
+        }
+    }
+    children
+}
+
+fn to_watch<S, E>(ty: WatchType, obj: Value) -> Result<K8Watch<S>, E>
+where
+    S: Spec,
+    E: From<SerdeJsonError>,
+{
+    let obj: K8Obj<S> = serde_json::from_value(obj)?;
+    Ok(match ty {
+        WatchType::Added => K8Watch::ADDED(obj),
+        WatchType::Modified => K8Watch::MODIFIED(obj),
+        WatchType::Deleted => K8Watch::DELETED(obj),
+    })
+}
+
+/// RFC 7386 json merge patch
+fn merge_patch(target: &mut Value, patch: &Value) {
+    match patch {
+        Value::Object(patch_map) => {
+            if !target.is_object() {
+                *target = Value::Object(Default::default());
+            }
+            if let Value::Object(target_map) = target {
+                for (key, value) in patch_map {
+                    if value.is_null() {
+                        target_map.remove(key);
+                    } else {
+                        merge_patch(target_map.entry(key.as_str()).or_insert(Value::Null), value);
+                    }
+                }
+            }
+        }
+        _ => *target = patch.clone(),
+    }
+}
+
+fn now() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// counter keeps uid unique within process, start time keeps it unique across restarts
+fn uid() -> String {
+    static START: Lazy<u128> = Lazy::new(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_nanos())
+            .unwrap_or_default()
+    });
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    format!("local-{:x}-{count:x}", *START)
+}
+
+#[cfg(test)]
+mod test {
+
+    use std::sync::Arc;
+
+    use futures_util::StreamExt;
+
+    use k8_metadata_client::MetadataClient;
+    use k8_metadata_client::PatchMergeType::JsonMerge;
+    use k8_types::core::namespace::NamespaceSpec;
+    use k8_types::{InputK8Obj, InputObjectMeta, K8Watch};
+
+    use super::LocalMetadataClient;
+
+    fn input(name: &str) -> InputK8Obj<NamespaceSpec> {
+        InputK8Obj::new(
+            NamespaceSpec::default(),
+            InputObjectMeta {
+                name: name.to_owned(),
+                namespace: "default".to_owned(),
+                ..Default::default()
+            },
+        )
+    }
+
+    #[fluvio_future::test]
+    async fn test_local_metadata_persist() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let client = LocalMetadataClient::open(dir.path()).expect("open");
+
+        let created = client.create_item(input("ns1")).await.expect("create");
+        assert_eq!(created.metadata.resource_version, "1");
+        assert!(client.create_item(input("ns1")).await.is_err());
+        client.create_item(input("ns2")).await.expect("create");
+
+        let reopened = LocalMetadataClient::open(dir.path()).expect("reopen");
+        let items = reopened
+            .retrieve_items::<NamespaceSpec, _>("default")
+            .await
+            .expect("list");
+        assert_eq!(items.items.len(), 2);
+        assert_eq!(items.metadata.resource_version, "2");
+
+        reopened
+            .delete_item::<NamespaceSpec, _>(&created.metadata)
+            .await
+            .expect("delete");
+        let items = LocalMetadataClient::open(dir.path())
+            .expect("reopen")
+            .retrieve_items::<NamespaceSpec, _>("default")
+            .await
+            .expect("list");
+        assert_eq!(items.items.len(), 1);
+    }
+
+    #[fluvio_future::test]
+    async fn test_local_metadata_finalizer() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let client = LocalMetadataClient::open(dir.path()).expect("open");
+
+        let mut with_finalizer = input("ns1");
+        with_finalizer.metadata.finalizers = vec!["test".to_owned()];
+        let created = client.create_item(with_finalizer).await.expect("create");
+
+        client
+            .delete_item::<NamespaceSpec, _>(&created.metadata)
+            .await
+            .expect("delete");
+        let marked = client
+            .retrieve_item::<NamespaceSpec, _>(&created.metadata)
+            .await
+            .expect("still exists");
+        assert!(marked.metadata.deletion_timestamp.is_some());
+
+        let remove_finalizers = serde_json::json!({ "metadata": { "finalizers": null } });
+        client
+            .patch::<NamespaceSpec, _>(&created.metadata.as_input(), &remove_finalizers, JsonMerge)
+            .await
+            .expect("patch");
+        assert!(client
+            .retrieve_item::<NamespaceSpec, _>(&created.metadata)
+            .await
+            .is_err());
+    }
+
+    #[fluvio_future::test]
+    async fn test_local_metadata_watch() {
+        let dir = tempfile::tempdir().expect("temp dir");
+        let client = Arc::new(LocalMetadataClient::open(dir.path()).expect("open"));
+
+        let created = client.create_item(input("ns1")).await.expect("create");
+        let mut stream = client.watch_stream_since::<NamespaceSpec, _>(
+            "default",
+            Some(created.metadata.resource_version.clone()),
+        );
+
+        client.create_item(input("ns2")).await.expect("create");
+        client
+            .delete_item::<NamespaceSpec, _>(&created.metadata)
+            .await
+            .expect("delete");
+
+        let mut names = vec![];
+        while names.len() < 2 {
+            let events = stream.next().await.expect("events").expect("ok");
+            for event in events {
+                match event.expect("event") {
+                    K8Watch::ADDED(obj) => names.push(format!("+{}", obj.metadata.name)),
+                    K8Watch::MODIFIED(obj) => names.push(format!("~{}", obj.metadata.name)),
+                    K8Watch::DELETED(obj) => names.push(format!("-{}", obj.metadata.name)),
+                }
+            }
+        }
+        assert_eq!(names, vec!["+ns2", "-ns1"]);
+    }
+}
@@ -32,6 +32,8 @@ pub const SPU_PRIVATE_HOSTNAME: &str = "0.0.0.0";
 pub const SPU_CREDENTIALS_FILE: &str = "/etc/fluvio/.credentials/token_secret";
 pub const SPU_RETRY_SC_TIMEOUT_MS: u16 = 3000;
 pub const SPU_MIN_IN_SYNC_REPLICAS: u16 = 1;
+pub const SPU_REPLICA_MAX_LAG_MS: u64 = 30000;
+pub const SPU_TRANSACTION_TIMEOUT_MS: u64 = 60000;
 pub const SPU_LOG_BASE_DIR: &str = "/var/lib/fluvio/data";
 pub const SPU_LOG_SIZE: &str = "10Gi";
 pub const SPU_LOG_INDEX_MAX_BYTES: u32 = 10485760;
@@ -47,6 +49,8 @@ pub const SPU_LOG_LOG_SEGMENT_MAX_BYTE_MIN: u32 = 1024; // crd
 pub const STORAGE_RETENTION_SECONDS: u32 = 7 * 24 * 3600;
 
 pub const STORAGE_RETENTION_SECONDS_MIN: u32 = 10; // crd
+pub const STORAGE_TOMBSTONE_RETENTION_SECONDS: u32 = 24 * 3600;
+pub const STORAGE_REMOTE_AFTER_SECONDS: u32 = 24 * 3600;
 pub const STORAGE_FLUSH_WRITE_COUNT: u32 = 1;
 pub const STORAGE_FLUSH_IDLE_MSEC: u32 = 0;
 pub const STORAGE_MAX_BATCH_SIZE: u32 = 33_554_432;
@@ -12,11 +12,15 @@ use fluvio_protocol::api::{Request, RequestMessage};
 use fluvio_future::net::DomainConnector;
 use fluvio_sc_schema::objects::{
     DeleteRequest, ObjectApiCreateRequest, ObjectApiDeleteRequest, ObjectApiListRequest,
-    ObjectApiWatchRequest, Metadata, ListFilter, WatchRequest, WatchResponse, CreateRequest,
-    CommonCreateRequest,
+    ObjectApiUpdateRequest, ObjectApiWatchRequest, Metadata, ListFilter, WatchRequest,
+    WatchResponse, CreateRequest, CommonCreateRequest, UpdateRequest,
+};
+use fluvio_sc_schema::reassignment::{DrainSpuRequest, ReassignPartitionRequest};
+use fluvio_sc_schema::{
+    AdminSpec, DeletableAdminSpec, CreatableAdminSpec, UpdatableAdminSpec, TryEncodableFrom,
 };
-use fluvio_sc_schema::{AdminSpec, DeletableAdminSpec, CreatableAdminSpec, TryEncodableFrom};
 use fluvio_socket::{ClientConfig, VersionedSerialSocket, SerialFrame, MultiplexerSocket};
+use fluvio_types::{PartitionId, SpuId};
 
 use crate::FluvioConfig;
 use crate::metadata::objects::{ListResponse, ListRequest};
@@ -91,8 +95,8 @@ impl FluvioAdmin {
     #[instrument]
     pub async fn connect() -> Result<Self> {
         let config_file = ConfigFile::load_default_or_new()?;
-        let cluster_config = config_file.config().current_cluster()?;
-        Self::connect_with_config(cluster_config).await
+        let cluster_config = config_file.config().current_cluster_config()?;
+        Self::connect_with_config(&cluster_config).await
     }
 
     /// Creates a new admin connection using custom configurations
@@ -116,8 +120,9 @@ impl FluvioAdmin {
     #[instrument(skip(config))]
     pub async fn connect_with_config(config: &FluvioConfig) -> Result<Self> {
         let connector = DomainConnector::try_from(config.tls.clone())?;
-        let client_config =
+        let mut client_config =
             ClientConfig::new(&config.endpoint, connector, config.use_spu_local_address);
+        client_config.set_credentials(config.credentials.clone().map(Into::into));
         let inner_client = client_config.connect().await?;
         debug!(addr = %inner_client.config().addr(), "connected to cluster");
 
@@ -201,6 +206,52 @@ impl FluvioAdmin {
         Ok(())
     }
 
+    /// Update existing object in place
+    /// changes which can be applied depend on spec
+    #[instrument(skip(self, key, action))]
+    pub async fn update<S, K>(&self, key: K, action: S::UpdateAction) -> Result<()>
+    where
+        S: UpdatableAdminSpec + Sync + Send,
+        K: Into<S::UpdateKey>,
+    {
+        let update_request: UpdateRequest<S> = UpdateRequest::new(key.into(), action);
+        debug!("sending update request: {:#?}", update_request);
+
+        self.send_receive_admin::<ObjectApiUpdateRequest, _>(update_request)
+            .await?
+            .as_result()?;
+        Ok(())
+    }
+
+    /// Move replicas of partition to target SPUs, first SPU becomes leader.
+    /// Replicas are moved in background once target replicas have caught up with leader
+    #[instrument(skip(self, topic))]
+    pub async fn reassign_partition(
+        &self,
+        topic: impl Into<String>,
+        partition: PartitionId,
+        replicas: Vec<SpuId>,
+    ) -> Result<()> {
+        let request = ReassignPartitionRequest {
+            topic: topic.into(),
+            partition,
+            replicas,
+        };
+        debug!("sending reassign partition request: {:#?}", request);
+        self.socket.send_receive(request).await?.as_result()?;
+        Ok(())
+    }
+
+    /// Move all replicas hosted by SPU to other online SPUs, so SPU can be removed
+    #[instrument(skip(self))]
+    pub async fn drain_spu(&self, spu: SpuId) -> Result<()> {
+        self.socket
+            .send_receive(DrainSpuRequest { spu })
+            .await?
+            .as_result()?;
+        Ok(())
+    }
+
     /// return all instance of this spec
     #[instrument(skip(self))]
     pub async fn all<S>(&self) -> Result<Vec<Metadata<S>>>
@@ -5,7 +5,10 @@
 //!
 use serde::{Serialize, Deserialize};
 
-use crate::{config::TlsPolicy, FluvioError};
+use crate::{
+    config::{ProfileCredentials, TlsPolicy},
+    FluvioError,
+};
 
 use super::ConfigFile;
 
@@ -33,14 +36,18 @@ pub struct FluvioConfig {
     /// It is purely to override client id when creating ClientConfig
     #[serde(skip)]
     pub client_id: Option<String>,
+
+    /// Credentials of profile used to connect to this cluster.
+    /// This is not part of cluster and doesn't persist.
+    #[serde(skip)]
+    pub credentials: Option<ProfileCredentials>,
 }
 
 impl FluvioConfig {
     /// get current cluster config from default profile
     pub fn load() -> Result<Self, FluvioError> {
         let config_file = ConfigFile::load_default_or_new()?;
-        let cluster_config = config_file.config().current_cluster()?;
-        Ok(cluster_config.to_owned())
+        config_file.config().current_cluster_config()
     }
 
     /// Create a new cluster configuration with no TLS.
@@ -50,6 +57,7 @@ impl FluvioConfig {
             use_spu_local_address: false,
             tls: TlsPolicy::Disabled,
             client_id: None,
+            credentials: None,
         }
     }
 
@@ -58,16 +66,21 @@ impl FluvioConfig {
         self.tls = tls.into();
         self
     }
+
+    /// Add credentials used to authenticate with this cluster.
+    pub fn with_credentials(mut self, credentials: Option<ProfileCredentials>) -> Self {
+        self.credentials = credentials;
+        self
+    }
 }
 
 impl TryFrom<FluvioConfig> for fluvio_socket::ClientConfig {
     type Error = std::io::Error;
     fn try_from(config: FluvioConfig) -> Result<Self, Self::Error> {
         let connector = fluvio_future::net::DomainConnector::try_from(config.tls.clone())?;
-        Ok(Self::new(
-            &config.endpoint,
-            connector,
-            config.use_spu_local_address,
-        ))
+        let mut client_config =
+            Self::new(&config.endpoint, connector, config.use_spu_local_address);
+        client_config.set_credentials(config.credentials.map(Into::into));
+        Ok(client_config)
     }
 }
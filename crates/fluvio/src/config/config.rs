@@ -401,6 +401,25 @@ impl Config {
             .and_then(|profile| self.cluster.get(&profile.cluster))
     }
 
+    /// Returns the FluvioConfig of current profile with profile credentials.
+    pub fn current_cluster_config(&self) -> Result<FluvioConfig, FluvioError> {
+        let profile = self.current_profile()?;
+        let cluster = self.current_cluster()?;
+        Ok(cluster
+            .clone()
+            .with_credentials(profile.credentials.clone()))
+    }
+
+    /// Returns the FluvioConfig of named profile with profile credentials.
+    pub fn cluster_config_with_profile(&self, profile_name: &str) -> Option<FluvioConfig> {
+        let profile = self.profile.get(profile_name)?;
+        self.cluster.get(&profile.cluster).map(|cluster| {
+            cluster
+                .clone()
+                .with_credentials(profile.credentials.clone())
+        })
+    }
+
     /// Returns a reference to the named FluvioConfig.
     pub fn cluster(&self, cluster_name: &str) -> Option<&FluvioConfig> {
         self.cluster.get(cluster_name)
@@ -436,6 +455,9 @@ pub struct Profile {
     pub cluster: String,
     pub topic: Option<String>,
     pub partition: Option<i32>,
+    /// credentials used to authenticate with cluster instead of client certificate
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<ProfileCredentials>,
 }
 
 impl Profile {
@@ -449,6 +471,37 @@ impl Profile {
     pub fn set_cluster(&mut self, cluster: String) {
         self.cluster = cluster;
     }
+
+    pub fn set_credentials(&mut self, credentials: Option<ProfileCredentials>) {
+        self.credentials = credentials;
+    }
+}
+
+/// Credentials stored in profile
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum ProfileCredentials {
+    /// username and password, verified with SCRAM-SHA-256
+    Password { username: String, password: String },
+    /// token signed by cluster
+    Token { token: String },
+}
+
+impl std::fmt::Debug for ProfileCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        fluvio_socket::sasl::Credentials::from(self.clone()).fmt(f)
+    }
+}
+
+impl From<ProfileCredentials> for fluvio_socket::sasl::Credentials {
+    fn from(credentials: ProfileCredentials) -> Self {
+        match credentials {
+            ProfileCredentials::Password { username, password } => {
+                Self::Password { username, password }
+            }
+            ProfileCredentials::Token { token } => Self::Token(token),
+        }
+    }
 }
 
 #[derive(Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
@@ -540,6 +593,43 @@ pub mod test {
             .expect("save should succeed");
     }
 
+    #[test]
+    fn test_profile_credentials() {
+        let mut config = Config::new_with_local_cluster("localhost:9003".to_owned());
+        config
+            .profile_mut(LOCAL_PROFILE)
+            .unwrap()
+            .set_credentials(Some(ProfileCredentials::Token {
+                token: "abc.def".to_owned(),
+            }));
+        let cfg_path = temp_dir().join("credentials.toml");
+        config
+            .save_to(cfg_path.clone())
+            .expect("save should succeed");
+
+        let update_conf_file =
+            ConfigFile::load(Some(cfg_path.to_string_lossy().to_string())).expect("parse failed");
+        let cluster = update_conf_file
+            .config()
+            .current_cluster_config()
+            .expect("cluster");
+        assert_eq!(
+            cluster.credentials,
+            Some(ProfileCredentials::Token {
+                token: "abc.def".to_owned()
+            })
+        );
+        // credentials are not part of cluster
+        assert_eq!(
+            update_conf_file
+                .config()
+                .current_cluster()
+                .expect("cluster")
+                .credentials,
+            None
+        );
+    }
+
     #[test]
     fn test_set_tls() {
         let mut conf_file = ConfigFile::load(Some("test-data/profiles/config.toml".to_owned()))
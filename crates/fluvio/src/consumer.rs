@@ -11,10 +11,13 @@ use futures_util::FutureExt;
 use fluvio_types::PartitionId;
 use fluvio_types::defaults::{FLUVIO_CLIENT_MAX_FETCH_BYTES, FLUVIO_MAX_SIZE_TOPIC_NAME};
 use fluvio_types::event::offsets::OffsetPublisher;
+use fluvio_future::timer::sleep;
 use fluvio_spu_schema::server::stream_fetch::{
     DefaultStreamFetchRequest, DefaultStreamFetchResponse, CHAIN_SMARTMODULE_API,
+    SMARTMODULE_CHECKPOINT_API,
 };
 use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::fetch::AbortedTransactionFilter;
 use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::link::ErrorCode;
 use fluvio_protocol::record::Batch;
@@ -22,7 +25,9 @@ use fluvio_protocol::record::Batch;
 use crate::{FluvioError};
 use crate::metrics::ClientMetrics;
 use crate::offset::{Offset, fetch_offsets};
+use crate::producer::RetryPolicy;
 use crate::spu::{SpuDirectory, SpuPool};
+use crate::throttle::Throttle;
 use derive_builder::Builder;
 
 pub use fluvio_protocol::record::ConsumerRecord as Record;
@@ -242,6 +247,7 @@ where
         impl Stream<Item = Result<Batch, ErrorCode>>,
         fluvio_protocol::record::Offset,
     )> {
+        let check_crc = !config.disable_crc_check;
         let (stream, start_offset) = self.request_stream(offset, config).await?;
         let metrics = self.metrics.clone();
         let flattened =
@@ -256,27 +262,34 @@ where
                 // This way the consumer always gets to read all records that were properly
                 // processed before hitting an error, so that the error does not obscure those records.
 
+                // transaction markers and batches of aborted transactions are not delivered
+                let mut transaction_filter = AbortedTransactionFilter::default();
+                transaction_filter.add(response.partition.aborted.unwrap_or_default());
+
                 let inner_metrics = metrics.clone();
-                let batches =
-                    response
-                        .partition
-                        .records
-                        .batches
-                        .into_iter()
-                        .map(move |raw_batch| {
-                            inner_metrics
-                                .consumer()
-                                .add_records(raw_batch.records_len() as u64);
-                            inner_metrics
-                                .consumer()
-                                .add_bytes(raw_batch.batch_len() as u64);
-
-                            let batch: Result<Batch, _> = raw_batch.try_into();
-                            match batch {
-                                Ok(batch) => Ok(batch),
-                                Err(err) => Err(ErrorCode::Other(err.to_string())),
-                            }
-                        });
+                let batches = response
+                    .partition
+                    .records
+                    .batches
+                    .into_iter()
+                    .filter(move |raw_batch| transaction_filter.retain(raw_batch))
+                    .map(move |raw_batch| {
+                        inner_metrics
+                            .consumer()
+                            .add_records(raw_batch.records_len() as u64);
+                        inner_metrics
+                            .consumer()
+                            .add_bytes(raw_batch.batch_len() as u64);
+
+                        if check_crc && !raw_batch.validate_crc() {
+                            return Err(ErrorCode::CorruptBatch);
+                        }
+                        let batch: Result<Batch, _> = raw_batch.try_into();
+                        match batch {
+                            Ok(batch) => Ok(batch),
+                            Err(err) => Err(ErrorCode::Other(err.to_string())),
+                        }
+                    });
                 let error = {
                     let code = response.partition.error_code;
                     match code {
@@ -304,19 +317,69 @@ where
     ) -> Result<(
         impl Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>>,
         fluvio_protocol::record::Offset,
+    )> {
+        let replica = ReplicaKey::new(&self.topic, self.partition);
+        // leader is resolved before connecting so change during connecting is not missed
+        let leader = self.pool.replica_leader(&replica).await?;
+        let (stream, start_absolute_offset, record_count) =
+            self.connect_stream(&replica, offset, &config).await?;
+
+        let stream = match config.retry_policy {
+            Some(policy) if !config.disable_continuous => {
+                let failover = failover::Failover::new(
+                    PartitionConsumer::new(
+                        self.topic.clone(),
+                        self.partition,
+                        self.pool.clone(),
+                        self.metrics.clone(),
+                    ),
+                    config.clone(),
+                    policy,
+                    replica,
+                    leader,
+                    stream,
+                    start_absolute_offset,
+                );
+                Either::Left(Box::pin(failover.into_stream()))
+            }
+            _ => Either::Right(StreamExt::map(stream, |item| {
+                item.map_err(|e| {
+                    error!(?e, "error in stream");
+                    ErrorCode::Other(e.to_string())
+                })
+            })),
+        };
+
+        let stream = if config.disable_continuous {
+            Either::Left(TakeRecords::new(stream, record_count))
+        } else {
+            Either::Right(stream)
+        };
+
+        Ok((stream, start_absolute_offset))
+    }
+
+    /// Creates stream of responses from current leader of partition.
+    /// Returns stream, start offset and number of records available at start.
+    #[instrument(skip(self, config))]
+    async fn connect_stream(
+        &self,
+        replica: &ReplicaKey,
+        offset: Offset,
+        config: &ConsumerConfig,
+    ) -> Result<(
+        failover::ResponseStream,
+        fluvio_protocol::record::Offset,
+        i64,
     )> {
         use fluvio_future::task::spawn;
         use futures_util::stream::empty;
 
-        let replica = ReplicaKey::new(&self.topic, self.partition);
-        let mut serial_socket = self.pool.create_serial_socket(&replica).await?;
-        let offsets = fetch_offsets(&mut serial_socket, &replica).await?;
+        let mut serial_socket = self.pool.create_serial_socket(replica).await?;
+        let offsets = fetch_offsets(&mut serial_socket, replica, offset.timestamp()).await?;
 
-        let start_absolute_offset = offset.resolve(&offsets).await?;
+        let mut start_absolute_offset = offset.resolve(&offsets).await?;
         let end_absolute_offset = offsets.last_stable_offset;
-        let record_count = end_absolute_offset - start_absolute_offset;
-
-        debug!(start_absolute_offset, end_absolute_offset, record_count);
 
         let stream_request = DefaultStreamFetchRequest::builder()
             .topic(self.topic.to_owned())
@@ -324,7 +387,8 @@ where
             .fetch_offset(start_absolute_offset)
             .isolation(config.isolation)
             .max_bytes(config.max_bytes)
-            .smartmodules(config.smartmodule)
+            .smartmodules(config.smartmodule.clone())
+            .checkpoint(config.checkpoint.clone())
             .build()?;
 
         let stream_fetch_version = serial_socket
@@ -335,14 +399,40 @@ where
         if stream_fetch_version < CHAIN_SMARTMODULE_API {
             warn!("SPU does not support SmartModule chaining. SmartModules will not be applied to the stream");
         }
+        if config.checkpoint.is_some() && stream_fetch_version < SMARTMODULE_CHECKPOINT_API {
+            warn!("SPU does not support SmartModule checkpoints. State will not be restored");
+        }
 
         let mut stream = self
             .pool
-            .create_stream_with_version(&replica, stream_request, stream_fetch_version)
+            .create_stream_with_version(replica, stream_request, stream_fetch_version)
             .await?;
 
+        // SPU resuming stream from checkpoint tells where stream starts in first response
+        let first_response = if config.checkpoint.is_some()
+            && !config.smartmodule.is_empty()
+            && stream_fetch_version >= SMARTMODULE_CHECKPOINT_API
+        {
+            let response = stream.next().await;
+            if let Some(Ok(response)) = &response {
+                if let Some(offset) = response.partition.next_offset_for_fetch() {
+                    start_absolute_offset = offset;
+                }
+            }
+            Some(response)
+        } else {
+            None
+        };
+        let record_count = (end_absolute_offset - start_absolute_offset).max(0);
+
+        debug!(start_absolute_offset, end_absolute_offset, record_count);
+
         let ft_stream = async move {
-            if let Some(Ok(raw_response)) = stream.next().await {
+            let first_response = match first_response {
+                Some(response) => response,
+                None => stream.next().await,
+            };
+            if let Some(Ok(raw_response)) = first_response {
                 let response: DefaultStreamFetchResponse = raw_response;
 
                 let stream_id = response.stream_id;
@@ -357,6 +447,12 @@ where
                 let publisher = OffsetPublisher::shared(0);
                 let mut listener = publisher.change_listener();
 
+                // SPU sends more records only after offsets are acknowledged, so consumer
+                // exceeding its quota holds acknowledgement back for throttle time
+                let throttle = Throttle::default();
+                throttle.set(response.throttle_time_ms);
+                let ack_throttle = throttle.clone();
+
                 // update stream with received offsets
                 spawn(async move {
                     use fluvio_spu_schema::server::update_offset::{UpdateOffsetsRequest, OffsetUpdate};
@@ -368,6 +464,13 @@ where
                             info!("fetch last is end, terminating");
                             break;
                         } else {
+                            if let Some(wait) = ack_throttle.remaining() {
+                                debug!(
+                                    throttle_ms = wait.as_millis() as u64,
+                                    stream_id, "consumer throttled by SPU"
+                                );
+                                sleep(wait).await;
+                            }
                             debug!(
                                 offset = fetch_last_value,
                                 session_id = stream_id,
@@ -399,16 +502,13 @@ where
                 let response_publisher = publisher.clone();
                 let update_stream = StreamExt::map(stream, move |item| {
                     item.map(|response| {
+                        throttle.set(response.throttle_time_ms);
                         if let Some(last_offset) = response.partition.next_offset_for_fetch() {
                             debug!(last_offset, stream_id, "received last offset from spu");
                             response_publisher.update(last_offset);
                         }
                         response
                     })
-                    .map_err(|e| {
-                        error!(?e, "error in stream");
-                        ErrorCode::Other(e.to_string())
-                    })
                 });
                 Either::Left(
                     iter(vec![Ok(response)])
@@ -420,13 +520,11 @@ where
             }
         };
 
-        let stream = if config.disable_continuous {
-            TakeRecords::new(ft_stream.flatten_stream().boxed(), record_count).boxed()
-        } else {
-            ft_stream.flatten_stream().boxed()
-        };
-
-        Ok((stream, start_absolute_offset))
+        Ok((
+            ft_stream.flatten_stream().boxed(),
+            start_absolute_offset,
+            record_count,
+        ))
     }
 }
 
@@ -497,14 +595,14 @@ mod publish_stream {
     use std::sync::Arc;
     use std::task::{Poll, Context};
 
-    use pin_project::pin_project;
+    use pin_project::{pin_project, pinned_drop};
     use futures_util::ready;
 
     use super::Stream;
     use super::OffsetPublisher;
 
-    // signal offset when stream is done
-    #[pin_project]
+    // signal offset when stream is done or dropped
+    #[pin_project(PinnedDrop)]
     pub struct EndPublishSt<St> {
         #[pin]
         stream: St,
@@ -534,6 +632,169 @@ mod publish_stream {
             self.stream.size_hint()
         }
     }
+
+    // stream replaced on failover may be dropped before it is done
+    #[pinned_drop]
+    impl<St> PinnedDrop for EndPublishSt<St> {
+        fn drop(self: Pin<&mut Self>) {
+            self.publisher.update(-1);
+        }
+    }
+}
+
+mod failover {
+
+    use futures_util::future::{select, Either};
+    use futures_util::stream::{unfold, BoxStream, Stream, StreamExt};
+    use tracing::{debug, warn, instrument};
+
+    use fluvio_future::retry::{retry, RetryExt};
+    use fluvio_protocol::link::ErrorCode;
+    use fluvio_protocol::record::ReplicaKey;
+    use fluvio_socket::SocketError;
+    use fluvio_spu_schema::server::stream_fetch::DefaultStreamFetchResponse;
+    use fluvio_types::SpuId;
+
+    use crate::offset::Offset;
+    use crate::producer::RetryPolicy;
+    use crate::spu::SpuDirectory;
+
+    use super::{ConsumerConfig, PartitionConsumer};
+
+    pub(super) type ResponseStream =
+        BoxStream<'static, Result<DefaultStreamFetchResponse, SocketError>>;
+
+    enum Event {
+        Response(Option<Result<DefaultStreamFetchResponse, SocketError>>),
+        LeaderChanged(SpuId),
+    }
+
+    /// Follows leader of partition.
+    /// When leader changes or connection to it is lost, stream is re-created
+    /// on current leader starting from offset after last delivered record.
+    pub(super) struct Failover<P> {
+        consumer: PartitionConsumer<P>,
+        config: ConsumerConfig,
+        policy: RetryPolicy,
+        replica: ReplicaKey,
+        leader: SpuId,
+        /// none after reconnecting has failed
+        stream: Option<ResponseStream>,
+        next_offset: i64,
+    }
+
+    impl<P> Failover<P>
+    where
+        P: SpuDirectory,
+    {
+        pub(super) fn new(
+            consumer: PartitionConsumer<P>,
+            config: ConsumerConfig,
+            policy: RetryPolicy,
+            replica: ReplicaKey,
+            leader: SpuId,
+            stream: ResponseStream,
+            start_offset: i64,
+        ) -> Self {
+            Self {
+                consumer,
+                config,
+                policy,
+                replica,
+                leader,
+                stream: Some(stream),
+                next_offset: start_offset,
+            }
+        }
+
+        pub(super) fn into_stream(
+            self,
+        ) -> impl Stream<Item = Result<DefaultStreamFetchResponse, ErrorCode>> {
+            unfold(self, |failover| failover.next_response())
+        }
+
+        async fn next_response(
+            mut self,
+        ) -> Option<(Result<DefaultStreamFetchResponse, ErrorCode>, Self)> {
+            loop {
+                let stream = self.stream.as_mut()?;
+                let leader_change = self
+                    .consumer
+                    .pool
+                    .wait_for_leader_change(&self.replica, self.leader);
+                let event = match select(stream.next(), leader_change).await {
+                    Either::Left((response, _)) => Event::Response(response),
+                    Either::Right((Ok(leader), _)) => Event::LeaderChanged(leader),
+                    Either::Right((Err(err), next)) => {
+                        warn!(%err, "unable to watch partition leader");
+                        Event::Response(next.await)
+                    }
+                };
+
+                match event {
+                    Event::Response(Some(Ok(mut response))) => {
+                        if matches!(
+                            response.partition.error_code,
+                            ErrorCode::NotLeaderForPartition | ErrorCode::PartitionNotLeader
+                        ) {
+                            debug!(replica = %self.replica, "spu is no longer leader");
+                        } else {
+                            // records before resume point were delivered by previous leader
+                            let next_offset = self.next_offset;
+                            let batches = &mut response.partition.records.batches;
+                            batches.retain(|batch| batch.get_last_offset() >= next_offset);
+                            for batch in batches.iter_mut() {
+                                if let Err(err) = batch.skip_records_before(next_offset) {
+                                    warn!(%err, next_offset, "unable to skip delivered records");
+                                    return Some((Err(ErrorCode::CompressionError), self));
+                                }
+                            }
+                            if let Some(offset) = response.partition.next_offset_for_fetch() {
+                                self.next_offset = self.next_offset.max(offset);
+                            }
+                            return Some((Ok(response), self));
+                        }
+                    }
+                    Event::Response(Some(Err(err))) => warn!(%err, "stream interrupted"),
+                    Event::Response(None) => debug!("stream ended"),
+                    Event::LeaderChanged(leader) => {
+                        debug!(replica = %self.replica, leader, "partition leader changed");
+                    }
+                }
+
+                if let Err(err) = self.reconnect().await {
+                    self.stream = None;
+                    return Some((Err(err), self));
+                }
+            }
+        }
+
+        /// connect to current leader within retry policy
+        #[instrument(skip(self), fields(replica = %self.replica, next_offset = self.next_offset))]
+        async fn reconnect(&mut self) -> Result<(), ErrorCode> {
+            let consumer = &self.consumer;
+            let replica = &self.replica;
+            let config = &self.config;
+            let next_offset = self.next_offset;
+
+            let connect = retry(self.policy.iter(), || async move {
+                let leader = consumer.pool.replica_leader(replica).await?;
+                let offset = Offset::absolute(next_offset)?;
+                let (stream, _, _) = consumer.connect_stream(replica, offset, config).await?;
+                debug!(leader, "reconnected to leader");
+                Ok::<_, anyhow::Error>((leader, stream))
+            });
+
+            let (leader, stream) = connect
+                .timeout(self.policy.timeout)
+                .await
+                .map_err(|err| ErrorCode::Other(format!("failover timed out: {err}")))?
+                .map_err(|err| ErrorCode::Other(format!("failover failed: {err}")))?;
+            self.leader = leader;
+            self.stream = Some(stream);
+            Ok(())
+        }
+    }
 }
 
 /// MAX FETCH BYTES
@@ -569,6 +830,18 @@ pub struct ConsumerConfig {
     pub(crate) isolation: Isolation,
     #[builder(default)]
     pub(crate) smartmodule: Vec<SmartModuleInvocation>,
+    /// Retries used to reconnect when partition leader changes or connection to it is lost.
+    /// With `None`, stream ends instead.
+    #[builder(default = "Some(RetryPolicy::default())")]
+    pub(crate) retry_policy: Option<RetryPolicy>,
+    /// Name under which SPU checkpoints state of aggregate SmartModules.
+    /// Stream started again with same name restores the state and continues
+    /// from offset of checkpoint instead of requested offset.
+    #[builder(default, setter(into, strip_option))]
+    pub(crate) checkpoint: Option<String>,
+    /// Skip verifying CRC of received batches against their content.
+    #[builder(default)]
+    pub(crate) disable_crc_check: bool,
 }
 
 impl ConsumerConfig {
@@ -599,12 +872,13 @@ impl PartitionSelectionStrategy {
         let pairs = match self {
             PartitionSelectionStrategy::All(topic) => {
                 let topics = spu_pool.metadata.topics();
-                let topic_spec = topics
+                let topic_metadata = topics
                     .lookup_by_key(topic)
                     .await?
-                    .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?
-                    .spec;
-                let partition_count = topic_spec.partitions();
+                    .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?;
+                let partition_count = topic_metadata
+                    .status
+                    .available_partitions(topic_metadata.spec.partitions());
                 (0..(partition_count as PartitionId))
                     .map(|partition| (topic.clone(), partition))
                     .collect::<Vec<_>>()
@@ -757,4 +1031,16 @@ mod tests {
     fn test_consumer_config_default() {
         let _config = ConsumerConfig::builder().build().unwrap();
     }
+
+    #[test]
+    fn test_consumer_config_retry_policy() {
+        let config = ConsumerConfig::builder().build().unwrap();
+        assert_eq!(config.retry_policy, Some(RetryPolicy::default()));
+
+        let config = ConsumerConfig::builder()
+            .retry_policy(None)
+            .build()
+            .unwrap();
+        assert!(config.retry_policy.is_none());
+    }
 }
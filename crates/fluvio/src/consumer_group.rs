@@ -0,0 +1,329 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use tracing::{debug, instrument, warn};
+use async_channel::Sender;
+use futures_util::stream::{Stream, StreamExt, pending, select, select_all};
+use futures_util::future::join_all;
+use derive_builder::Builder;
+
+use fluvio_future::task::spawn;
+use fluvio_future::timer::sleep;
+use fluvio_protocol::link::ErrorCode;
+use fluvio_protocol::record::ConsumerRecord as Record;
+use fluvio_sc_schema::consumer_group::{
+    CommitOffsetsRequest, ConsumerOffset, FetchCommittedOffsetsRequest, HeartbeatRequest,
+    JoinGroupRequest, LeaveGroupRequest,
+};
+use fluvio_socket::VersionedSerialSocket;
+use fluvio_types::PartitionId;
+
+use crate::{FluvioError, Offset, PartitionConsumer, ConsumerConfig};
+use crate::metrics::ClientMetrics;
+use crate::spu::SpuPool;
+
+/// Configures membership of [`GroupConsumer`] in consumer group
+#[derive(Debug, Builder, Clone)]
+#[builder(build_fn(private, name = "build_impl"))]
+pub struct ConsumerGroupConfig {
+    #[builder(setter(into))]
+    pub(crate) group: String,
+    #[builder(setter(into))]
+    pub(crate) topic: String,
+    /// member is removed from group if SC doesn't receive heartbeat within this time
+    #[builder(default = "Duration::from_secs(30)")]
+    pub(crate) session_timeout: Duration,
+    /// interval between heartbeats, must be shorter than session timeout
+    #[builder(default = "Duration::from_secs(3)")]
+    pub(crate) heartbeat_interval: Duration,
+    #[builder(default = "ConsumerConfig::builder().build().expect(\"default config\")")]
+    pub(crate) consumer: ConsumerConfig,
+}
+
+impl ConsumerGroupConfig {
+    pub fn builder() -> ConsumerGroupConfigBuilder {
+        ConsumerGroupConfigBuilder::default()
+    }
+}
+
+impl ConsumerGroupConfigBuilder {
+    pub fn build(&self) -> Result<ConsumerGroupConfig> {
+        let config = self.build_impl().map_err(|e| {
+            FluvioError::ConsumerConfig(format!("Missing required config option: {e}"))
+        })?;
+        Ok(config)
+    }
+}
+
+/// Member of consumer group.
+///
+/// Partitions of topic are divided among members of group by SC. Stream of member only
+/// contains records of partitions assigned to it, starting at offsets committed by group.
+/// Assignment changes when members join or leave. Stream ends when that happens and
+/// [`GroupConsumer::stream`] must be called again to consume new assignment.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fluvio::{Fluvio, Offset, ConsumerGroupConfig};
+/// # mod futures {
+/// #     pub use futures_util::stream::StreamExt;
+/// # }
+/// # async fn example(fluvio: &Fluvio) -> anyhow::Result<()> {
+/// use futures::StreamExt;
+/// let config = ConsumerGroupConfig::builder()
+///     .group("my-group")
+///     .topic("my-topic")
+///     .build()?;
+/// let mut consumer = fluvio.group_consumer(config).await?;
+/// loop {
+///     let mut stream = consumer.stream(Offset::beginning()).await?;
+///     while let Some(Ok(record)) = stream.next().await {
+///         println!("{}", record.get_value().as_utf8_lossy_string());
+///         consumer.commit(&record).await?;
+///     }
+/// }
+/// # }
+/// ```
+pub struct GroupConsumer {
+    config: ConsumerGroupConfig,
+    socket: Arc<VersionedSerialSocket>,
+    pool: Arc<SpuPool>,
+    metrics: Arc<ClientMetrics>,
+    member_id: String,
+    generation: i32,
+    partitions: Vec<PartitionId>,
+}
+
+impl GroupConsumer {
+    pub(crate) async fn join(
+        config: ConsumerGroupConfig,
+        socket: VersionedSerialSocket,
+        pool: Arc<SpuPool>,
+        metrics: Arc<ClientMetrics>,
+    ) -> Result<Self> {
+        let mut consumer = Self {
+            config,
+            socket: Arc::new(socket),
+            pool,
+            metrics,
+            member_id: String::new(),
+            generation: 0,
+            partitions: vec![],
+        };
+        consumer.rejoin().await?;
+        Ok(consumer)
+    }
+
+    /// id assigned to this member by SC
+    pub fn member_id(&self) -> &str {
+        &self.member_id
+    }
+
+    /// generation of current assignment
+    pub fn generation(&self) -> i32 {
+        self.generation
+    }
+
+    /// partitions assigned to this member
+    pub fn partitions(&self) -> &[PartitionId] {
+        &self.partitions
+    }
+
+    async fn rejoin(&mut self) -> Result<()> {
+        let request = JoinGroupRequest {
+            group: self.config.group.clone(),
+            topic: self.config.topic.clone(),
+            member_id: (!self.member_id.is_empty()).then(|| self.member_id.clone()),
+            session_timeout_ms: self.config.session_timeout.as_millis() as u32,
+        };
+        let response = self.socket.send_receive(request).await?;
+        if response.error_code.is_error() {
+            return Err(anyhow!(
+                "failed to join group {}: {}",
+                self.config.group,
+                response.error_code
+            ));
+        }
+        debug!(
+            member_id = %response.member_id,
+            generation = response.generation,
+            partitions = ?response.partitions,
+            "joined group"
+        );
+        self.member_id = response.member_id;
+        self.generation = response.generation;
+        self.partitions = response.partitions;
+        Ok(())
+    }
+
+    /// refresh assignment, rejoin if SC no longer knows this member
+    async fn refresh(&mut self) -> Result<()> {
+        let response = self
+            .socket
+            .send_receive(HeartbeatRequest {
+                group: self.config.group.clone(),
+                member_id: self.member_id.clone(),
+            })
+            .await?;
+        match response.error_code {
+            ErrorCode::None => {
+                self.generation = response.generation;
+                self.partitions = response.partitions;
+                Ok(())
+            }
+            ErrorCode::ConsumerGroupMemberNotFound | ErrorCode::ConsumerGroupNotFound => {
+                self.rejoin().await
+            }
+            error_code => Err(anyhow!("heartbeat failed: {error_code}")),
+        }
+    }
+
+    /// Stream records of assigned partitions.
+    ///
+    /// Each partition starts at committed offset of group or at `default_offset` if
+    /// nothing has been committed yet. Stream ends when assignment changes, member with no
+    /// partitions assigned gets stream which stays pending until then.
+    #[instrument(skip(self, default_offset))]
+    pub async fn stream(
+        &mut self,
+        default_offset: Offset,
+    ) -> Result<impl Stream<Item = Result<Record, ErrorCode>>> {
+        self.refresh().await?;
+
+        let committed = self
+            .socket
+            .send_receive(FetchCommittedOffsetsRequest {
+                group: self.config.group.clone(),
+            })
+            .await?;
+        if committed.error_code.is_error() {
+            return Err(anyhow!(
+                "failed to fetch committed offsets: {}",
+                committed.error_code
+            ));
+        }
+
+        let consumers = self
+            .partitions
+            .iter()
+            .map(|partition| {
+                let offset = match committed.offset(*partition) {
+                    Some(offset) => Offset::absolute(offset)?,
+                    None => default_offset.clone(),
+                };
+                let consumer = PartitionConsumer::new(
+                    self.config.topic.clone(),
+                    *partition,
+                    self.pool.clone(),
+                    self.metrics.clone(),
+                );
+                Ok((consumer, offset))
+            })
+            .collect::<Result<Vec<_>, FluvioError>>()?;
+
+        let streams = join_all(consumers.iter().map(|(consumer, offset)| {
+            consumer.stream_with_config(offset.clone(), self.config.consumer.clone())
+        }))
+        .await
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?;
+
+        // heartbeats are sent by own task, so they keep member alive while records are processed
+        let (rebalanced, rebalance) = async_channel::bounded(1);
+        spawn(heartbeat_until_rebalance(
+            self.socket.clone(),
+            self.config.group.clone(),
+            self.member_id.clone(),
+            self.generation,
+            self.config.heartbeat_interval,
+            rebalanced,
+        ));
+        let rebalance = async move {
+            let _ = rebalance.recv().await;
+        };
+        Ok(select(select_all(streams), pending()).take_until(Box::pin(rebalance)))
+    }
+
+    /// Commit record as consumed, group resumes after it
+    pub async fn commit(&self, record: &Record) -> Result<()> {
+        self.commit_offsets(vec![(record.partition, record.offset + 1)])
+            .await
+    }
+
+    /// Commit offsets of next records to be consumed
+    pub async fn commit_offsets(&self, offsets: Vec<(PartitionId, i64)>) -> Result<()> {
+        let request = CommitOffsetsRequest {
+            group: self.config.group.clone(),
+            member_id: self.member_id.clone(),
+            generation: self.generation,
+            offsets: offsets
+                .into_iter()
+                .map(|(partition, offset)| ConsumerOffset { partition, offset })
+                .collect(),
+        };
+        let response = self.socket.send_receive(request).await?;
+        if response.error_code.is_error() {
+            return Err(anyhow!("failed to commit offsets: {}", response.error_code));
+        }
+        Ok(())
+    }
+
+    /// Leave group, partitions of this member are assigned to remaining members
+    pub async fn leave(self) -> Result<()> {
+        let response = self
+            .socket
+            .send_receive(LeaveGroupRequest {
+                group: self.config.group.clone(),
+                member_id: self.member_id.clone(),
+            })
+            .await?;
+        if response.error_code.is_error() {
+            return Err(anyhow!("failed to leave group: {}", response.error_code));
+        }
+        Ok(())
+    }
+}
+
+/// send heartbeats until generation of group changes or membership is lost, then signal
+/// rebalance. Task ends early if stream was dropped.
+async fn heartbeat_until_rebalance(
+    socket: Arc<VersionedSerialSocket>,
+    group: String,
+    member_id: String,
+    generation: i32,
+    interval: Duration,
+    rebalanced: Sender<()>,
+) {
+    loop {
+        sleep(interval).await;
+        if rebalanced.is_closed() {
+            debug!("group stream dropped, stopping heartbeat");
+            return;
+        }
+        match socket
+            .send_receive(HeartbeatRequest {
+                group: group.clone(),
+                member_id: member_id.clone(),
+            })
+            .await
+        {
+            Ok(response) if response.error_code.is_ok() && response.generation == generation => {}
+            Ok(response) => {
+                debug!(
+                    generation = response.generation,
+                    error = ?response.error_code,
+                    "group rebalanced"
+                );
+                let _ = rebalanced.try_send(());
+                return;
+            }
+            Err(err) => {
+                warn!(%err, "heartbeat failed");
+                let _ = rebalanced.try_send(());
+                return;
+            }
+        }
+    }
+}
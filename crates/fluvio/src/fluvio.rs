@@ -6,6 +6,8 @@ use tokio::sync::OnceCell;
 use anyhow::{anyhow, Result};
 
 use fluvio_sc_schema::objects::ObjectApiWatchRequest;
+use fluvio_sc_schema::producer::InitProducerIdRequest;
+use fluvio_sc_schema::consumer_group::JoinGroupRequest;
 use fluvio_types::PartitionId;
 use fluvio_socket::{
     ClientConfig, Versions, VersionedSerialSocket, SharedMultiplexerSocket, MultiplexerSocket,
@@ -21,8 +23,9 @@ use crate::FluvioError;
 use crate::FluvioConfig;
 use crate::consumer::MultiplePartitionConsumer;
 use crate::consumer::PartitionSelectionStrategy;
+use crate::consumer_group::{ConsumerGroupConfig, GroupConsumer};
 use crate::metrics::ClientMetrics;
-use crate::producer::TopicProducerConfig;
+use crate::producer::{TopicProducerConfig, ProducerIdentity, Transaction};
 use crate::spu::SpuPool;
 use crate::sync::MetadataStores;
 
@@ -91,6 +94,7 @@ impl Fluvio {
         if let Some(client_id) = &config.client_id {
             client_config.set_client_id(client_id.to_owned());
         }
+        client_config.set_credentials(config.credentials.clone().map(Into::into));
         let inner_client = client_config.connect().await?;
         debug!("connected to cluster");
 
@@ -173,7 +177,7 @@ impl Fluvio {
     pub async fn topic_producer_with_config<S: Into<String>>(
         &self,
         topic: S,
-        config: TopicProducerConfig,
+        mut config: TopicProducerConfig,
     ) -> Result<TopicProducer> {
         let topic = topic.into();
         debug!(topic = &*topic, "Creating producer");
@@ -183,9 +187,34 @@ impl Fluvio {
             return Err(FluvioError::TopicNotFound(topic).into());
         }
 
+        if config.idempotent {
+            config.producer_identity = Some(self.init_producer_id().await?);
+        }
+
         TopicProducer::new(topic, spu_pool, config, self.metric.clone()).await
     }
 
+    /// Begins transaction, records sent in it to any topic and partition are
+    /// committed or aborted together
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use fluvio::{Fluvio, RecordKey};
+    /// # async fn do_produce_in_transaction(fluvio: &Fluvio) -> anyhow::Result<()> {
+    /// let mut transaction = fluvio.begin_transaction().await?;
+    /// transaction.send("my-topic", RecordKey::NULL, "Hello, Fluvio!").await?;
+    /// transaction.commit().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn begin_transaction(&self) -> Result<Transaction> {
+        let spu_pool = self.spu_pool().await?;
+        let identity = self.init_producer_id().await?;
+        debug!(producer_id = identity.producer_id, "transaction started");
+        Ok(Transaction::new(identity, spu_pool, self.metric.clone()))
+    }
+
     /// Creates a new `PartitionConsumer` for the given topic and partition
     ///
     /// If you have a topic with multiple partitions, then in order to receive
@@ -238,6 +267,37 @@ impl Fluvio {
         ))
     }
 
+    /// Joins consumer group and creates [`GroupConsumer`] for partitions assigned to it
+    ///
+    /// Partitions of topic are divided among members of group. Offsets committed by
+    /// members are stored in cluster, so group resumes where it left off.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use fluvio::{Fluvio, ConsumerGroupConfig};
+    /// # async fn do_join_group(fluvio: &Fluvio) -> anyhow::Result<()> {
+    /// let config = ConsumerGroupConfig::builder()
+    ///     .group("my-group")
+    ///     .topic("my-topic")
+    ///     .build()?;
+    /// let consumer = fluvio.group_consumer(config).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn group_consumer(&self, config: ConsumerGroupConfig) -> Result<GroupConsumer> {
+        if self.versions.lookup_version::<JoinGroupRequest>().is_none() {
+            return Err(anyhow!("cluster does not support consumer groups"));
+        }
+        GroupConsumer::join(
+            config,
+            self.create_serial_client().await,
+            self.spu_pool().await?,
+            self.metric.clone(),
+        )
+        .await
+    }
+
     /// Provides an interface for managing a Fluvio cluster
     ///
     /// # Example
@@ -265,6 +325,35 @@ impl Fluvio {
         self.versions.platform_version()
     }
 
+    /// request new producer id from SC for idempotent producer
+    async fn init_producer_id(&self) -> Result<ProducerIdentity> {
+        if self
+            .versions
+            .lookup_version::<InitProducerIdRequest>()
+            .is_none()
+        {
+            return Err(anyhow!("cluster does not support idempotent producer"));
+        }
+
+        let response = self
+            .create_serial_client()
+            .await
+            .send_receive(InitProducerIdRequest::default())
+            .await?;
+        if response.error_code.is_error() {
+            return Err(anyhow!(
+                "failed to get producer id: {}",
+                response.error_code
+            ));
+        }
+        debug!(producer_id = response.producer_id, "producer id assigned");
+
+        Ok(ProducerIdentity {
+            producer_id: response.producer_id,
+            producer_epoch: response.producer_epoch,
+        })
+    }
+
     /// create serial connection
     async fn create_serial_client(&self) -> VersionedSerialSocket {
         VersionedSerialSocket::new(
@@ -89,9 +89,11 @@ mod error;
 mod admin;
 mod fluvio;
 pub mod consumer;
+mod consumer_group;
 mod producer;
 mod offset;
 mod sync;
+mod throttle;
 pub mod spu;
 pub mod metrics;
 pub mod config;
@@ -102,8 +104,8 @@ pub use error::FluvioError;
 pub use config::FluvioConfig;
 pub use producer::{
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducer, RecordKey, ProduceOutput,
-    FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy, RetryStrategy,
-    Partitioner, PartitionerConfig, ProducerError,
+    ProduceRecord, FutureRecordMetadata, RecordMetadata, DeliverySemantic, RetryPolicy,
+    RetryStrategy, Partitioner, PartitionerConfig, ProducerError, Transaction,
 };
 #[cfg(feature = "smartengine")]
 pub use producer::{SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData};
@@ -115,6 +117,7 @@ pub use consumer::{
     SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind, SmartModuleContextData,
     SmartModuleExtraParams,
 };
+pub use consumer_group::{GroupConsumer, ConsumerGroupConfig, ConsumerGroupConfigBuilder};
 pub use offset::Offset;
 
 pub use crate::admin::FluvioAdmin;
@@ -266,6 +269,14 @@ pub mod metadata {
         pub use fluvio_sc_schema::tableformat::*;
     }
 
+    pub mod transform {
+        pub use fluvio_sc_schema::transform::*;
+    }
+
+    pub mod quota {
+        pub use fluvio_sc_schema::quota::*;
+    }
+
     pub mod core {
         pub use fluvio_sc_schema::core::*;
     }
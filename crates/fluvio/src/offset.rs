@@ -3,7 +3,8 @@ use std::io::ErrorKind;
 
 use tracing::{debug, trace};
 use fluvio_protocol::record::ReplicaKey;
-use fluvio_spu_schema::server::fetch_offset::FetchOffsetsRequest;
+use fluvio_spu_schema::server::fetch_offset::{FetchOffsetsRequest, FETCH_OFFSET_TIMESTAMP_API};
+use fluvio_types::Timestamp;
 use fluvio_spu_schema::server::fetch_offset::FetchOffsetPartitionResponse;
 
 use crate::FluvioError;
@@ -14,6 +15,7 @@ pub(crate) enum OffsetInner {
     Absolute(i64),
     FromBeginning(i64),
     FromEnd(i64),
+    Timestamp(Timestamp),
 }
 
 impl OffsetInner {
@@ -28,6 +30,10 @@ impl OffsetInner {
                 let resolved = offsets.last_stable_offset - offset;
                 resolved.clamp(offsets.start_offset, offsets.last_stable_offset)
             }
+            Self::Timestamp(_) => offsets
+                .timestamp_offset
+                .unwrap_or(offsets.last_stable_offset)
+                .clamp(offsets.start_offset, offsets.last_stable_offset),
         }
     }
 }
@@ -271,6 +277,33 @@ impl Offset {
         }
     }
 
+    /// Creates an offset pointing to the first records produced at or after the timestamp
+    ///
+    /// Timestamp is in milliseconds since the Unix epoch. Offset resolves to the first batch
+    /// containing such records, so some records of that batch may be older than the timestamp.
+    /// If all records are older, offset points to the end of the log.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::Offset;
+    /// // Creates an offset pointing to records produced since 2023-01-01T00:00:00Z
+    /// let offset: Offset = Offset::from_timestamp(1_672_531_200_000);
+    /// ```
+    pub fn from_timestamp(timestamp: Timestamp) -> Offset {
+        Self {
+            inner: OffsetInner::Timestamp(timestamp),
+        }
+    }
+
+    /// timestamp to be resolved by SPU
+    pub(crate) fn timestamp(&self) -> Option<Timestamp> {
+        match self.inner {
+            OffsetInner::Timestamp(timestamp) => Some(timestamp),
+            _ => None,
+        }
+    }
+
     /// Converts this offset into an absolute offset
     ///
     /// If this offset is relative from the beginning (i.e. it was created
@@ -303,15 +336,33 @@ impl Offset {
 pub(crate) async fn fetch_offsets(
     client: &mut VersionedSerialSocket,
     replica: &ReplicaKey,
+    timestamp: Option<Timestamp>,
 ) -> Result<FetchOffsetPartitionResponse, FluvioError> {
     debug!("fetching offset for replica: {}", replica);
 
-    let response = client
-        .send_receive(FetchOffsetsRequest::new(
-            replica.topic.to_owned(),
-            replica.partition,
-        ))
-        .await?;
+    let request = match timestamp {
+        Some(timestamp) => {
+            let supported = client
+                .versions()
+                .lookup_version::<FetchOffsetsRequest>()
+                .map(|version| version >= FETCH_OFFSET_TIMESTAMP_API)
+                .unwrap_or(false);
+            if !supported {
+                return Err(IoError::new(
+                    ErrorKind::Unsupported,
+                    "SPU does not support seeking by timestamp",
+                )
+                .into());
+            }
+            FetchOffsetsRequest::new_with_timestamp(
+                replica.topic.to_owned(),
+                replica.partition,
+                timestamp,
+            )
+        }
+        None => FetchOffsetsRequest::new(replica.topic.to_owned(), replica.partition),
+    };
+    let response = client.send_receive(request).await?;
 
     trace!(
         "receive fetch response replica: {}, {:#?}",
@@ -343,6 +394,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -357,6 +409,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(3);
@@ -371,6 +424,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -385,6 +439,7 @@ mod tests {
             partition_index: 0,
             start_offset: 5,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromBeginning(15);
@@ -399,6 +454,7 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(3);
@@ -413,6 +469,7 @@ mod tests {
             partition_index: 0,
             start_offset: 6,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(6);
@@ -427,10 +484,32 @@ mod tests {
             partition_index: 0,
             start_offset: 0,
             last_stable_offset: 10,
+            ..Default::default()
         };
 
         let offset_inner = OffsetInner::FromEnd(100);
         let absolute = offset_inner.resolve(&offsets);
         assert_eq!(absolute, 0);
     }
+
+    #[test]
+    fn test_offset_timestamp() {
+        let mut offsets = FetchOffsetPartitionResponse {
+            error_code: Default::default(),
+            partition_index: 0,
+            start_offset: 2,
+            last_stable_offset: 10,
+            timestamp_offset: Some(5),
+        };
+
+        let offset_inner = OffsetInner::Timestamp(1_000);
+        assert_eq!(offset_inner.resolve(&offsets), 5);
+
+        // records have been removed by retention
+        offsets.timestamp_offset = Some(0);
+        assert_eq!(offset_inner.resolve(&offsets), 2);
+
+        offsets.timestamp_offset = None;
+        assert_eq!(offset_inner.resolve(&offsets), 10);
+    }
 }
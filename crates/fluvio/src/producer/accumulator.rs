@@ -1,7 +1,7 @@
 use std::collections::VecDeque;
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
 use std::task::{Context, Poll};
 use std::time::Duration;
 
@@ -55,7 +55,7 @@ impl BatchesDeque {
 pub(crate) struct RecordAccumulator {
     batch_size: usize,
     queue_size: usize,
-    batches: Arc<Vec<BatchHandler>>,
+    batches: RwLock<Vec<BatchHandler>>,
     compression: Compression,
 }
 
@@ -71,7 +71,7 @@ impl RecordAccumulator {
             batches.push((BatchEvents::shared(), BatchesDeque::shared()));
         }
         Self {
-            batches: Arc::new(batches),
+            batches: RwLock::new(batches),
             batch_size,
             compression,
             queue_size,
@@ -86,7 +86,10 @@ impl RecordAccumulator {
     ) -> Result<PushRecord, ProducerError> {
         let (batch_events, batches_lock) = self
             .batches
+            .read()
+            .expect("batches lock poisoned")
             .get(partition_id as usize)
+            .cloned()
             .ok_or(ProducerError::PartitionNotFound(partition_id))?;
 
         let mut batches = batches_lock.batches.lock().await;
@@ -140,8 +143,28 @@ impl RecordAccumulator {
         }
     }
 
-    pub(crate) fn batches(&self) -> Arc<Vec<BatchHandler>> {
-        self.batches.clone()
+    pub(crate) fn batches(&self) -> Vec<BatchHandler> {
+        self.batches.read().expect("batches lock poisoned").clone()
+    }
+
+    pub(crate) fn partition_count(&self) -> PartitionCount {
+        self.batches.read().expect("batches lock poisoned").len() as PartitionCount
+    }
+
+    /// Add batch queues for partitions added to topic.
+    /// Returns queues of new partitions only, with their partition ids.
+    pub(crate) fn add_partitions(
+        &self,
+        partition_n: PartitionCount,
+    ) -> Vec<(PartitionId, BatchHandler)> {
+        let mut batches = self.batches.write().expect("batches lock poisoned");
+        let mut added = vec![];
+        for partition_id in batches.len() as PartitionId..partition_n {
+            let handler = (BatchEvents::shared(), BatchesDeque::shared());
+            batches.push(handler.clone());
+            added.push((partition_id, handler));
+        }
+        added
     }
 }
 
@@ -410,6 +433,29 @@ mod test {
         );
     }
 
+    #[fluvio_future::test]
+    async fn test_record_accumulator_add_partitions() {
+        let accumulator = RecordAccumulator::new(1024, 10, 2, Compression::None);
+        assert_eq!(accumulator.partition_count(), 2);
+        assert!(accumulator
+            .push_record(Record::from(("key", "value")), 3)
+            .await
+            .is_err());
+
+        let added = accumulator.add_partitions(4);
+        assert_eq!(
+            added.iter().map(|(id, _)| *id).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        assert_eq!(accumulator.partition_count(), 4);
+        assert!(accumulator.add_partitions(3).is_empty());
+
+        accumulator
+            .push_record(Record::from(("key", "value")), 3)
+            .await
+            .expect("failed push");
+    }
+
     #[fluvio_future::test]
     async fn test_produce_partition_response_future_ready() {
         //given
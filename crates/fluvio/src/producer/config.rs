@@ -111,6 +111,26 @@ pub struct TopicProducerConfig {
 
     #[builder(default)]
     pub(crate) smartmodules: Vec<SmartModuleInvocation>,
+
+    /// Enable idempotent producer. Producer gets assigned producer id from SC and numbers
+    /// its batches per partition, so SPU can detect duplicate batches sent by retries.
+    #[builder(default)]
+    pub(crate) idempotent: bool,
+
+    /// Identity assigned by SC when producer is idempotent
+    #[builder(setter(skip))]
+    pub(crate) producer_identity: Option<ProducerIdentity>,
+
+    /// Batches belong to transaction, set for producers created by [`crate::Transaction`]
+    #[builder(setter(skip))]
+    pub(crate) transactional: bool,
+}
+
+/// Producer id and epoch assigned to idempotent producer
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) struct ProducerIdentity {
+    pub(crate) producer_id: i64,
+    pub(crate) producer_epoch: i16,
 }
 
 impl Default for TopicProducerConfig {
@@ -128,6 +148,9 @@ impl Default for TopicProducerConfig {
             stats_collect: default_stats_collect(),
             delivery_semantic: default_delivery(),
             smartmodules: vec![],
+            idempotent: false,
+            producer_identity: None,
+            transactional: false,
         }
     }
 }
@@ -1,7 +1,10 @@
 use chrono::Utc;
 
 use fluvio_protocol::{
-    record::{RawRecords, Batch, Offset, MemoryRecords, BATCH_HEADER_SIZE, ProducerBatchHeader},
+    record::{
+        RawRecords, Batch, Offset, MemoryRecords, BATCH_HEADER_SIZE, ProducerBatchHeader,
+        RECORD_HEADERS_VERSION,
+    },
     Encoder,
 };
 use fluvio_types::Timestamp;
@@ -44,7 +47,7 @@ impl MemoryBatch {
         let timestamp_delta = self.elapsed();
         record.get_mut_header().set_timestamp_delta(timestamp_delta);
 
-        let record_size = record.write_size(0);
+        let record_size = record.write_size(RECORD_HEADERS_VERSION);
 
         if self.estimated_size() + record_size > self.write_limit {
             self.is_full = true;
@@ -99,8 +102,9 @@ impl MemoryBatch {
 
 impl From<MemoryBatch> for Batch<MemoryRecords> {
     fn from(p_batch: MemoryBatch) -> Self {
-        let mut batch =
-            Self::new_with_len((BATCH_HEADER_SIZE + p_batch.records.write_size(0)) as i32);
+        let mut batch = Self::new_with_len(
+            (BATCH_HEADER_SIZE + p_batch.records.write_size(RECORD_HEADERS_VERSION)) as i32,
+        );
 
         let compression = p_batch.compression();
         let records = p_batch.records;
@@ -1,6 +1,6 @@
 use std::sync::Arc;
 
-use tracing::instrument;
+use tracing::{debug, instrument};
 use async_lock::RwLock;
 use anyhow::Result;
 
@@ -8,7 +8,7 @@ use fluvio_protocol::record::ReplicaKey;
 use fluvio_protocol::record::Record;
 use fluvio_compression::Compression;
 use fluvio_sc_schema::topic::CompressionAlgorithm;
-use fluvio_types::PartitionId;
+use fluvio_types::{PartitionCount, PartitionId};
 use fluvio_types::event::StickyEvent;
 
 mod accumulator;
@@ -19,10 +19,12 @@ mod record;
 mod partitioning;
 mod partition_producer;
 mod memory_batch;
+mod transaction;
 
 pub mod event;
 
 pub use fluvio_protocol::record::{RecordKey, RecordData};
+pub use fluvio_protocol::record::Record as ProduceRecord;
 
 use crate::FluvioError;
 use crate::metrics::ClientMetrics;
@@ -37,14 +39,26 @@ pub use self::config::{
     TopicProducerConfigBuilder, TopicProducerConfig, TopicProducerConfigBuilderError,
     DeliverySemantic, RetryPolicy, RetryStrategy,
 };
+pub(crate) use self::config::ProducerIdentity;
 pub use self::error::ProducerError;
 use self::event::EventHandler;
 pub use self::output::ProduceOutput;
 use self::partition_producer::PartitionProducer;
 pub use self::record::{FutureRecordMetadata, RecordMetadata};
+pub use self::transaction::Transaction;
+use self::transaction::TransactionCoordinator;
 
 /// Pool of producers for a given topic. There is a producer per partition
 struct ProducerPool {
+    config: Arc<TopicProducerConfig>,
+    topic: String,
+    spu_pool: Arc<SpuPool>,
+    client_metric: Arc<ClientMetrics>,
+    partitions: std::sync::RwLock<PartitionProducers>,
+}
+
+#[derive(Default)]
+struct PartitionProducers {
     flush_events: Vec<(Arc<EventHandler>, Arc<EventHandler>)>,
     end_events: Vec<Arc<StickyEvent>>,
     errors: Vec<Arc<RwLock<Option<ProducerError>>>>,
@@ -55,45 +69,33 @@ impl ProducerPool {
         config: Arc<TopicProducerConfig>,
         topic: String,
         spu_pool: Arc<SpuPool>,
-        batches: Arc<Vec<BatchHandler>>,
+        batches: Vec<BatchHandler>,
         client_metric: Arc<ClientMetrics>,
     ) -> Self {
-        let mut end_events = vec![];
-        let mut flush_events = vec![];
-        let mut errors = vec![];
-        for (partition_id, (batch_events, batch_list)) in batches.iter().enumerate() {
-            let end_event = StickyEvent::shared();
-            let flush_event = (EventHandler::shared(), EventHandler::shared());
-            let replica = ReplicaKey::new(topic.clone(), partition_id as PartitionId);
-            let error = Arc::new(RwLock::new(None));
-
-            PartitionProducer::start(
-                config.clone(),
-                replica,
-                spu_pool.clone(),
-                batch_list.clone(),
-                batch_events.clone(),
-                error.clone(),
-                end_event.clone(),
-                flush_event.clone(),
-                client_metric.clone(),
-            );
-            errors.push(error);
-            end_events.push(end_event);
-            flush_events.push(flush_event);
-        }
-        Self {
-            end_events,
-            flush_events,
-            errors,
+        let pool = Self {
+            config,
+            topic,
+            spu_pool,
+            client_metric,
+            partitions: Default::default(),
+        };
+        {
+            let mut partitions = pool
+                .partitions
+                .write()
+                .expect("producer pool lock poisoned");
+            for (partition_id, batch) in batches.into_iter().enumerate() {
+                pool.start_partition(&mut partitions, partition_id as PartitionId, batch);
+            }
         }
+        pool
     }
 
     fn shared(
         config: Arc<TopicProducerConfig>,
         topic: String,
         spu_pool: Arc<SpuPool>,
-        batches: Arc<Vec<BatchHandler>>,
+        batches: Vec<BatchHandler>,
         client_metric: Arc<ClientMetrics>,
     ) -> Arc<Self> {
         Arc::new(ProducerPool::new(
@@ -105,9 +107,52 @@ impl ProducerPool {
         ))
     }
 
+    /// start producers for partitions added to topic after producer was created
+    fn add_partitions(&self, accumulator: &RecordAccumulator, partition_count: PartitionCount) {
+        let mut partitions = self
+            .partitions
+            .write()
+            .expect("producer pool lock poisoned");
+        for (partition_id, batch) in accumulator.add_partitions(partition_count) {
+            debug!(topic = %self.topic, partition_id, "starting producer for new partition");
+            self.start_partition(&mut partitions, partition_id, batch);
+        }
+    }
+
+    fn start_partition(
+        &self,
+        partitions: &mut PartitionProducers,
+        partition_id: PartitionId,
+        (batch_events, batch_list): BatchHandler,
+    ) {
+        let end_event = StickyEvent::shared();
+        let flush_event = (EventHandler::shared(), EventHandler::shared());
+        let replica = ReplicaKey::new(self.topic.clone(), partition_id);
+        let error = Arc::new(RwLock::new(None));
+
+        PartitionProducer::start(
+            self.config.clone(),
+            replica,
+            self.spu_pool.clone(),
+            batch_list,
+            batch_events,
+            error.clone(),
+            end_event.clone(),
+            flush_event.clone(),
+            self.client_metric.clone(),
+        );
+        partitions.errors.push(error);
+        partitions.end_events.push(end_event);
+        partitions.flush_events.push(flush_event);
+    }
+
     async fn flush_all_batches(&self) -> Result<()> {
+        let (flush_events, errors) = {
+            let partitions = self.partitions.read().expect("producer pool lock poisoned");
+            (partitions.flush_events.clone(), partitions.errors.clone())
+        };
         for ((manual_flush_notifier, batch_flushed_event), error) in
-            self.flush_events.iter().zip(self.errors.iter())
+            flush_events.iter().zip(errors.iter())
         {
             let listener = batch_flushed_event.listen();
             manual_flush_notifier.notify().await;
@@ -124,20 +169,35 @@ impl ProducerPool {
     }
 
     async fn last_error(&self, partition_id: PartitionId) -> Option<ProducerError> {
-        let error = self.errors[partition_id as usize].read().await;
+        let error = self
+            .partitions
+            .read()
+            .expect("producer pool lock poisoned")
+            .errors
+            .get(partition_id as usize)
+            .cloned()?;
+        let error = error.read().await;
         error.clone()
     }
 
     async fn clear_errors(&self) {
-        for error in self.errors.iter() {
+        let errors = self
+            .partitions
+            .read()
+            .expect("producer pool lock poisoned")
+            .errors
+            .clone();
+        for error in errors.iter() {
             let mut error_handle = error.write().await;
             *error_handle = None;
         }
     }
 
     fn end(&self) {
-        for event in &self.end_events {
-            event.notify();
+        if let Ok(partitions) = self.partitions.read() {
+            for event in &partitions.end_events {
+                event.notify();
+            }
         }
     }
 }
@@ -168,6 +228,8 @@ struct InnerTopicProducer {
     spu_pool: Arc<SpuPool>,
     record_accumulator: RecordAccumulator,
     producer_pool: Arc<ProducerPool>,
+    /// transaction which records belong to, it tracks partitions written in it
+    transaction: Option<Arc<TransactionCoordinator>>,
 }
 
 impl InnerTopicProducer {
@@ -180,12 +242,15 @@ impl InnerTopicProducer {
     async fn push_record(self: Arc<Self>, record: Record) -> Result<PushRecord> {
         let topics = self.spu_pool.metadata.topics();
 
-        let topic_spec = topics
+        let topic = topics
             .lookup_by_key(&self.topic)
             .await?
-            .ok_or_else(|| FluvioError::TopicNotFound(self.topic.to_string()))?
-            .spec;
-        let partition_count = topic_spec.partitions();
+            .ok_or_else(|| FluvioError::TopicNotFound(self.topic.to_string()))?;
+        let partition_count = topic.status.available_partitions(topic.spec.partitions());
+        if partition_count > self.record_accumulator.partition_count() {
+            self.producer_pool
+                .add_partitions(&self.record_accumulator, partition_count);
+        }
         let partition_config = PartitionerConfig { partition_count };
 
         let key = record.key.as_ref().map(|k| k.as_ref());
@@ -199,6 +264,12 @@ impl InnerTopicProducer {
             return Err(error.into());
         }
 
+        if let Some(transaction) = &self.transaction {
+            transaction
+                .add_partition(ReplicaKey::new(self.topic.clone(), partition))
+                .await?;
+        }
+
         let push_record = self
             .record_accumulator
             .push_record(record, partition)
@@ -313,15 +384,28 @@ impl TopicProducer {
         spu_pool: Arc<SpuPool>,
         config: TopicProducerConfig,
         metrics: Arc<ClientMetrics>,
+    ) -> Result<Self> {
+        Self::new_in_transaction(topic, spu_pool, config, metrics, None).await
+    }
+
+    /// create producer whose records are written in transaction
+    pub(crate) async fn new_in_transaction(
+        topic: String,
+        spu_pool: Arc<SpuPool>,
+        config: TopicProducerConfig,
+        metrics: Arc<ClientMetrics>,
+        transaction: Option<Arc<TransactionCoordinator>>,
     ) -> Result<Self> {
         let config = Arc::new(config);
         let topics = spu_pool.metadata.topics();
-        let topic_spec = topics
+        let topic_metadata = topics
             .lookup_by_key(&topic)
             .await?
-            .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?
-            .spec;
-        let partition_count = topic_spec.partitions();
+            .ok_or_else(|| FluvioError::TopicNotFound(topic.to_string()))?;
+        let partition_count = topic_metadata
+            .status
+            .available_partitions(topic_metadata.spec.partitions());
+        let topic_spec = topic_metadata.spec;
 
         let compression =
             match topic_spec.get_compression_type() {
@@ -380,6 +464,7 @@ impl TopicProducer {
                 spu_pool,
                 producer_pool,
                 record_accumulator,
+                transaction,
             }),
             #[cfg(feature = "smartengine")]
             sm_chain: Default::default(),
@@ -433,7 +518,29 @@ impl TopicProducer {
         let record_key = key.into();
         let record_value = value.into();
         let record = Record::from((record_key, record_value));
+        self.send_record(record).await
+    }
 
+    /// Sends a record to this producer's Topic.
+    ///
+    /// Unlike `send`, this allows record headers to be attached to the record.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use fluvio::{TopicProducer, ProduceRecord};
+    /// # async fn example(producer: &TopicProducer) -> anyhow::Result<()> {
+    /// let record = ProduceRecord::new_key_value("Key", "Value")
+    ///     .with_header("content-type", "text/plain");
+    /// producer.send_record(record).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[instrument(
+        skip(self, record),
+        fields(topic = %self.inner.topic),
+    )]
+    pub async fn send_record(&self, record: ProduceRecord) -> Result<ProduceOutput> {
         cfg_if::cfg_if! {
             if #[cfg(feature = "smartengine")] {
                 let mut entries = vec![record];
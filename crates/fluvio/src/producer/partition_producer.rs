@@ -1,11 +1,15 @@
 use std::sync::Arc;
+use std::sync::atomic::{AtomicI32, Ordering};
 
 use async_lock::{RwLock};
 use tracing::{debug, info, instrument, error, trace};
 
 use fluvio_protocol::record::ReplicaKey;
-use fluvio_protocol::record::{RawRecords, Batch};
-use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest};
+use fluvio_protocol::record::{RawRecords, Batch, RECORD_HEADERS_VERSION};
+use fluvio_spu_schema::produce::{
+    DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest,
+    PRODUCE_RECORD_HEADERS_API_VERSION,
+};
 use fluvio_future::timer::sleep;
 use fluvio_types::SpuId;
 use fluvio_types::event::StickyEvent;
@@ -16,6 +20,7 @@ use crate::producer::accumulator::ProducePartitionResponseFuture;
 use crate::producer::config::DeliverySemantic;
 use fluvio_socket::VersionedSerialSocket;
 use crate::spu::SpuPool;
+use crate::throttle::Throttle;
 use crate::TopicProducerConfig;
 
 use super::ProducerError;
@@ -31,6 +36,10 @@ pub(crate) struct PartitionProducer {
     batch_events: Arc<BatchEvents>,
     last_error: Arc<RwLock<Option<ProducerError>>>,
     metrics: Arc<ClientMetrics>,
+    /// sequence of next batch when producer is idempotent
+    next_sequence: AtomicI32,
+    /// timestamp in millis until which batches are held back when SPU throttles producer exceeding its quota
+    throttle: Throttle,
 }
 
 impl PartitionProducer {
@@ -51,6 +60,8 @@ impl PartitionProducer {
             batch_events,
             last_error,
             metrics,
+            next_sequence: AtomicI32::new(0),
+            throttle: Throttle::default(),
         }
     }
 
@@ -179,6 +190,14 @@ impl PartitionProducer {
     /// Flush all the batches that are full or have reached the linger time.
     /// If force is set to true, flush all batches regardless of linger time.
     pub(crate) async fn flush(&self, force: bool) -> Result<()> {
+        if let Some(wait) = self.throttle.remaining() {
+            debug!(
+                wait_ms = wait.as_millis() as u64,
+                "producer throttled by SPU"
+            );
+            sleep(wait).await;
+        }
+
         let leader = self.current_leader().await?;
 
         let spu_socket = self
@@ -205,6 +224,14 @@ impl PartitionProducer {
             }
         }
 
+        // SPU before record headers can't skip them, so they are dropped for it
+        let record_version = match spu_socket.lookup_version::<DefaultProduceRequest>() {
+            Some(version) if version >= PRODUCE_RECORD_HEADERS_API_VERSION => {
+                RECORD_HEADERS_VERSION
+            }
+            _ => 0,
+        };
+
         // Send each batch and notify base offset
         let mut request = DefaultProduceRequest::default();
 
@@ -221,9 +248,18 @@ impl PartitionProducer {
                 ..Default::default()
             };
             let notify = p_batch.notify.clone();
-            let batch = p_batch.batch();
+            let mut batch = p_batch.batch();
+            if let Some(identity) = &self.config.producer_identity {
+                let header = batch.get_mut_header();
+                header.producer_id = identity.producer_id;
+                header.producer_epoch = identity.producer_epoch;
+                header.first_sequence = self
+                    .next_sequence
+                    .fetch_add(batch.records_len() as i32, Ordering::SeqCst);
+                header.set_transactional(self.config.transactional);
+            }
 
-            let raw_batch: Batch<RawRecords> = batch.try_into()?;
+            let raw_batch: Batch<RawRecords> = batch.try_into_raw(record_version)?;
 
             let producer_metrics = self.metrics.producer_client();
             producer_metrics.add_records(raw_batch.records_len() as u64);
@@ -264,7 +300,15 @@ impl PartitionProducer {
             DeliverySemantic::AtMostOnce => {
                 use futures_util::FutureExt;
                 let async_response = socket.send_async(request).await?;
-                let shared = FutureExt::map(async_response, Arc::new).boxed().shared();
+                let throttle = self.throttle.clone();
+                let shared = FutureExt::map(async_response, move |response| {
+                    if let Ok(response) = &response {
+                        throttle.set(response.throttle_time_ms);
+                    }
+                    Arc::new(response)
+                })
+                .boxed()
+                .shared();
                 (0..partition_count)
                     .map(|index| ProducePartitionResponseFuture::from(shared.clone(), index))
                     .collect()
@@ -276,6 +320,7 @@ impl PartitionProducer {
                     .timeout(policy.timeout)
                     .await
                     .map_err(|timeout_err| FluvioError::Producer(timeout_err.into()))??;
+                self.throttle.set(produce_response.throttle_time_ms);
 
                 let mut futures = Vec::with_capacity(partition_count);
                 for topic in produce_response.responses.into_iter() {
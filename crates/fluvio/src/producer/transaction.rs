@@ -0,0 +1,285 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_lock::Mutex;
+use tracing::{debug, instrument, warn};
+
+use fluvio_protocol::record::{Batch, ControlRecordType, RawRecords, ReplicaKey};
+use fluvio_spu_schema::Isolation;
+use fluvio_spu_schema::produce::{DefaultPartitionRequest, DefaultTopicRequest, DefaultProduceRequest};
+
+use crate::FluvioError;
+use crate::metrics::ClientMetrics;
+use crate::spu::SpuPool;
+
+use super::{
+    ProduceOutput, ProduceRecord, ProducerError, ProducerIdentity, RecordData, RecordKey,
+    TopicProducer, TopicProducerConfig,
+};
+
+/// Records produced to one or more topics and partitions which become visible
+/// to consumers with [`crate::Isolation::ReadCommitted`] all at once when transaction is committed.
+///
+/// Create transaction with [`crate::Fluvio::begin_transaction()`]. Records of aborted transaction
+/// are skipped by consumers. First partition written in transaction is its coordinator: commit or
+/// abort is decided by marker written there and SPU writes the same marker to every other
+/// partition, so transaction never ends up committed in some partitions and aborted in others.
+/// Transaction which is neither committed nor aborted, for example because producer crashed,
+/// is aborted by coordinator once transaction timeout passes.
+///
+/// # Example
+///
+/// ```no_run
+/// # use fluvio::{Fluvio, RecordKey};
+/// # async fn example(fluvio: &Fluvio) -> anyhow::Result<()> {
+/// let mut transaction = fluvio.begin_transaction().await?;
+/// transaction.send("orders", RecordKey::NULL, "order").await?;
+/// transaction.send("payments", RecordKey::NULL, "payment").await?;
+/// transaction.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Transaction {
+    identity: ProducerIdentity,
+    spu_pool: Arc<SpuPool>,
+    metrics: Arc<ClientMetrics>,
+    producers: BTreeMap<String, TopicProducer>,
+    coordinator: Arc<TransactionCoordinator>,
+}
+
+impl Transaction {
+    pub(crate) fn new(
+        identity: ProducerIdentity,
+        spu_pool: Arc<SpuPool>,
+        metrics: Arc<ClientMetrics>,
+    ) -> Self {
+        let coordinator = Arc::new(TransactionCoordinator::new(
+            identity,
+            spu_pool.clone(),
+            TopicProducerConfig::default().timeout,
+        ));
+        Self {
+            identity,
+            spu_pool,
+            metrics,
+            producers: BTreeMap::new(),
+            coordinator,
+        }
+    }
+
+    /// Use custom config for records sent to topic in this transaction.
+    /// Must be called before first record is sent to topic, default config is used otherwise.
+    pub async fn add_topic_with_config<S: Into<String>>(
+        &mut self,
+        topic: S,
+        config: TopicProducerConfig,
+    ) -> Result<()> {
+        let topic = topic.into();
+        if self.producers.contains_key(&topic) {
+            return Err(anyhow::anyhow!(
+                "topic '{topic}' already has producer in transaction"
+            ));
+        }
+        let producer = self.create_producer(topic.clone(), config).await?;
+        self.producers.insert(topic, producer);
+        Ok(())
+    }
+
+    /// Sends a key/value record to topic as part of this transaction
+    pub async fn send<S, K, V>(&mut self, topic: S, key: K, value: V) -> Result<ProduceOutput>
+    where
+        S: Into<String>,
+        K: Into<RecordKey>,
+        V: Into<RecordData>,
+    {
+        let record = ProduceRecord::from((key.into(), value.into()));
+        self.send_record(topic, record).await
+    }
+
+    /// Sends a record to topic as part of this transaction
+    pub async fn send_record<S: Into<String>>(
+        &mut self,
+        topic: S,
+        record: ProduceRecord,
+    ) -> Result<ProduceOutput> {
+        let topic = topic.into();
+        if !self.producers.contains_key(&topic) {
+            self.add_topic_with_config(topic.clone(), TopicProducerConfig::default())
+                .await?;
+        }
+        let producer = self
+            .producers
+            .get(&topic)
+            .expect("producer added for topic");
+        producer.send_record(record).await
+    }
+
+    /// Flush all records of transaction and make them visible to consumers.
+    /// Commit fails if transaction has been aborted by SPU because it timed out.
+    /// Once commit is written to coordinator partition, transaction is committed
+    /// in all partitions even if producer stops before marking them.
+    #[instrument(skip(self), fields(producer_id = self.identity.producer_id))]
+    pub async fn commit(self) -> Result<()> {
+        for producer in self.producers.values() {
+            producer.flush().await?;
+        }
+        self.coordinator.end(ControlRecordType::Commit).await?;
+        debug!("transaction committed");
+        Ok(())
+    }
+
+    /// Abort transaction, its records are skipped by consumers
+    #[instrument(skip(self), fields(producer_id = self.identity.producer_id))]
+    pub async fn abort(self) -> Result<()> {
+        // records are flushed anyway, so none is written after abort marker
+        for producer in self.producers.values() {
+            if let Err(err) = producer.flush().await {
+                warn!(%err, "failed to flush records of aborted transaction");
+            }
+        }
+        self.coordinator.end(ControlRecordType::Abort).await?;
+        debug!("transaction aborted");
+        Ok(())
+    }
+
+    async fn create_producer(
+        &self,
+        topic: String,
+        mut config: TopicProducerConfig,
+    ) -> Result<TopicProducer> {
+        config.producer_identity = Some(self.identity);
+        config.transactional = true;
+        TopicProducer::new_in_transaction(
+            topic,
+            self.spu_pool.clone(),
+            config,
+            self.metrics.clone(),
+            Some(self.coordinator.clone()),
+        )
+        .await
+    }
+}
+
+/// Partitions written in transaction and its coordinator partition
+#[derive(Debug, Default)]
+struct TransactionPartitions {
+    coordinator: Option<ReplicaKey>,
+    partitions: BTreeSet<ReplicaKey>,
+}
+
+/// Registers partitions of transaction with its coordinator and writes its decision
+pub(crate) struct TransactionCoordinator {
+    identity: ProducerIdentity,
+    spu_pool: Arc<SpuPool>,
+    timeout: Duration,
+    state: Mutex<TransactionPartitions>,
+}
+
+impl TransactionCoordinator {
+    fn new(identity: ProducerIdentity, spu_pool: Arc<SpuPool>, timeout: Duration) -> Self {
+        Self {
+            identity,
+            spu_pool,
+            timeout,
+            state: Mutex::new(TransactionPartitions::default()),
+        }
+    }
+
+    /// add partition to transaction before its first record is sent, so
+    /// coordinator knows about it even if producer stops before ending transaction
+    pub(crate) async fn add_partition(&self, replica: ReplicaKey) -> Result<()> {
+        let mut state = self.state.lock().await;
+        if state.partitions.contains(&replica) {
+            return Ok(());
+        }
+        let coordinator = state
+            .coordinator
+            .get_or_insert_with(|| replica.clone())
+            .clone();
+        let batch = Batch::add_partitions(
+            self.identity.producer_id,
+            self.identity.producer_epoch,
+            vec![replica.clone()],
+        );
+        self.write_control(&coordinator, batch).await?;
+        debug!(%replica, %coordinator, "partition added to transaction");
+        state.partitions.insert(replica);
+        Ok(())
+    }
+
+    /// write decision to coordinator, then to other partitions. SPU of coordinator writes
+    /// decision to partitions which producer fails to mark, so errors there are not returned
+    async fn end(&self, record_type: ControlRecordType) -> Result<()> {
+        let state = std::mem::take(&mut *self.state.lock().await);
+        let coordinator = match state.coordinator {
+            Some(coordinator) => coordinator,
+            None => return Ok(()),
+        };
+        let decision = || {
+            Batch::control(
+                self.identity.producer_id,
+                self.identity.producer_epoch,
+                record_type,
+            )
+        };
+        self.write_control(&coordinator, decision()).await?;
+        debug!(%coordinator, ?record_type, "transaction decision written");
+
+        for replica in state.partitions.iter().filter(|p| **p != coordinator) {
+            if let Err(err) = self.write_control(replica, decision()).await {
+                warn!(%replica, %err, "transaction marker left to coordinator");
+            }
+        }
+        Ok(())
+    }
+
+    /// write control batch to leader of partition
+    async fn write_control(&self, replica: &ReplicaKey, batch: Batch) -> Result<()> {
+        let leader = self
+            .spu_pool
+            .metadata
+            .partitions()
+            .lookup_by_key(replica)
+            .await?
+            .ok_or_else(|| {
+                FluvioError::PartitionNotFound(replica.topic.clone(), replica.partition)
+            })?
+            .spec
+            .leader;
+        let socket = self
+            .spu_pool
+            .create_serial_socket_from_leader(leader)
+            .await?;
+
+        let batch: Batch<RawRecords> = batch.try_into()?;
+        let mut partition_request = DefaultPartitionRequest {
+            partition_index: replica.partition,
+            ..Default::default()
+        };
+        partition_request.records.batches.push(batch);
+        let request = DefaultProduceRequest {
+            isolation: Isolation::ReadCommitted,
+            timeout: self.timeout,
+            topics: vec![DefaultTopicRequest {
+                name: replica.topic.clone(),
+                partitions: vec![partition_request],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let response = socket.send_receive(request).await?;
+        for partition_response in response
+            .responses
+            .iter()
+            .flat_map(|topic| topic.partitions.iter())
+        {
+            if partition_response.error_code.is_error() {
+                return Err(ProducerError::from(partition_response.error_code.clone()).into());
+            }
+        }
+        Ok(())
+    }
+}
@@ -41,6 +41,16 @@ pub trait SpuDirectory {
     ) -> Result<AsyncResponse<R>, FluvioError>
     where
         R: Sync + Send;
+
+    /// current leader of replica
+    async fn replica_leader(&self, replica: &ReplicaKey) -> Result<SpuId, FluvioError>;
+
+    /// wait until leader of replica is no longer `leader`, returns new leader
+    async fn wait_for_leader_change(
+        &self,
+        replica: &ReplicaKey,
+        leader: SpuId,
+    ) -> Result<SpuId, FluvioError>;
 }
 
 /// Stream Socket to SPU
@@ -196,17 +206,7 @@ impl SpuDirectory for SpuPool {
         &self,
         replica: &ReplicaKey,
     ) -> Result<VersionedSerialSocket, FluvioError> {
-        let partition_search = self.metadata.partitions().lookup_by_key(replica).await?;
-        let partition = if let Some(partition) = partition_search {
-            partition
-        } else {
-            return Err(FluvioError::PartitionNotFound(
-                replica.topic.to_string(),
-                replica.partition,
-            ));
-        };
-
-        let leader_id = partition.spec.leader;
+        let leader_id = self.replica_leader(replica).await?;
         let socket = self.create_serial_socket_from_leader(leader_id).await?;
         Ok(socket)
     }
@@ -221,26 +221,19 @@ impl SpuDirectory for SpuPool {
     where
         R: Sync + Send,
     {
-        let partition_search = self.metadata.partitions().lookup_by_key(replica).await?;
-
-        let partition = if let Some(partition) = partition_search {
-            partition
-        } else {
-            return Err(FluvioError::PartitionNotFound(
-                replica.topic.to_owned(),
-                replica.partition,
-            ));
-        };
-
-        let leader_id = partition.spec.leader;
+        let leader_id = self.replica_leader(replica).await?;
 
         // check if already have existing leader or create new connection to leader
         let mut client_lock = self.spu_clients.lock().await;
 
         if let Some(spu_socket) = client_lock.get_mut(&leader_id) {
-            return spu_socket
-                .create_stream_with_version(request, version)
-                .await;
+            if !spu_socket.is_stale() {
+                return spu_socket
+                    .create_stream_with_version(request, version)
+                    .await;
+            } else {
+                client_lock.remove(&leader_id);
+            }
         }
 
         let mut spu_socket = self.connect_to_leader(leader_id).await?;
@@ -251,4 +244,33 @@ impl SpuDirectory for SpuPool {
 
         Ok(stream)
     }
+    async fn replica_leader(&self, replica: &ReplicaKey) -> Result<SpuId, FluvioError> {
+        let partition_search = self.metadata.partitions().lookup_by_key(replica).await?;
+        match partition_search {
+            Some(partition) => Ok(partition.spec.leader),
+            None => Err(FluvioError::PartitionNotFound(
+                replica.topic.to_owned(),
+                replica.partition,
+            )),
+        }
+    }
+
+    #[instrument(skip(self, replica))]
+    async fn wait_for_leader_change(
+        &self,
+        replica: &ReplicaKey,
+        leader: SpuId,
+    ) -> Result<SpuId, FluvioError> {
+        let mut listener = self.metadata.partitions().store().change_listener();
+        loop {
+            // sync before look up so no change is missed
+            listener.load_last();
+            let current = self.replica_leader(replica).await?;
+            if current != leader {
+                debug!(%replica, leader, current, "leader changed");
+                return Ok(current);
+            }
+            listener.listen().await;
+        }
+    }
 }
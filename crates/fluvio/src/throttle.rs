@@ -0,0 +1,28 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::Duration;
+
+use chrono::Utc;
+
+/// Timestamp in millis until which client holds back requests because SPU throttles it
+/// for exceeding its quota. Shared by task reading responses and task sending requests.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Throttle {
+    until: Arc<AtomicI64>,
+}
+
+impl Throttle {
+    /// throttle time is counted from receiving response
+    pub(crate) fn set(&self, throttle_time_ms: i32) {
+        if throttle_time_ms > 0 {
+            let until = Utc::now().timestamp_millis() + throttle_time_ms as i64;
+            self.until.fetch_max(until, Ordering::SeqCst);
+        }
+    }
+
+    /// time left until throttle ends
+    pub(crate) fn remaining(&self) -> Option<Duration> {
+        let remaining = self.until.load(Ordering::SeqCst) - Utc::now().timestamp_millis();
+        (remaining > 0).then(|| Duration::from_millis(remaining as u64))
+    }
+}